@@ -16,18 +16,28 @@ use actix_web::{
 use futures::{Stream, StreamExt};
 use http::StatusCode;
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
+    leptos_server::{
+        csrf_error, server_fn_by_path, server_fn_limits, use_csrf_policy,
+        AuthRequest, CsrfCheck, Payload, ServerFnLimits, CSRF_HEADER,
+    },
     server_fn::Encoding,
     ssr::render_to_stream_with_prefix_undisposed_with_context_and_block_replacement,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
+use leptos_integration_utils::{
+    build_async_response, check_server_fn_request, html_parts_with_shell,
+    provide_csrf_token, provide_request_context, request_span, run_server_fn,
+    server_fn_not_found,
+};
+pub use leptos_integration_utils::{
+    session, write_precache_manifest, RequestContext,
+};
 use leptos_meta::*;
 use leptos_router::*;
 use parking_lot::RwLock;
 use regex::Regex;
 use std::{fmt::Display, future::Future, sync::Arc};
-use tracing::instrument;
+use tracing::{instrument, Instrument};
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
 /// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
 #[derive(Debug, Clone, Default)]
@@ -164,6 +174,11 @@ pub fn handle_server_fns() -> Route {
 /// This version allows you to pass in a closure that adds additional route data to the
 /// context, allowing you to pass in info about the route or user from Actix, or other info.
 ///
+/// The body is read after the rate limit and the authorization hook have been
+/// checked. Unless a [max_body_size](leptos_server::ServerFnLimits::max_body_size)
+/// is set, it may be at most 256 KiB long, as with Actix's default
+/// [PayloadConfig](actix_web::web::PayloadConfig).
+///
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
@@ -173,8 +188,11 @@ pub fn handle_server_fns_with_context(
     additional_context: impl Fn(leptos::Scope) + 'static + Clone + Send,
 ) -> Route {
     web::to(
-        move |req: HttpRequest, params: web::Path<String>, body: web::Bytes| {
+        move |req: HttpRequest,
+              params: web::Path<String>,
+              payload: web::Payload| {
             let additional_context = additional_context.clone();
+            let span = tracing::info_span!("server fn", path = %req.path());
             async move {
                 let additional_context = additional_context.clone();
 
@@ -185,6 +203,44 @@ pub fn handle_server_fns_with_context(
                     .and_then(|value| value.to_str().ok());
 
                 if let Some(server_fn) = server_fn_by_path(path.as_str()) {
+                    // the rate limit and the authorization hook are checked
+                    // before the body is read, so that a request they reject
+                    // can't make the server read a large body
+                    let mut limits = server_fn_limits();
+                    if limits.body_size_limit().is_none() {
+                        limits = limits.max_body_size(DEFAULT_BODY_SIZE_LIMIT);
+                    }
+                    let client_ip =
+                        req.peer_addr().map(|addr| addr.ip().to_string());
+                    let checked = async {
+                        check_server_fn_request(
+                            &limits,
+                            client_ip.as_deref(),
+                            AuthRequest {
+                                fn_name: path.clone(),
+                                encoding: server_fn.encoding.clone(),
+                                method: req.method().to_string(),
+                                uri: req.uri().to_string(),
+                                headers: req
+                                    .headers()
+                                    .iter()
+                                    .filter_map(|(name, value)| {
+                                        Some((
+                                            name.as_str().to_string(),
+                                            value.to_str().ok()?.to_string(),
+                                        ))
+                                    })
+                                    .collect(),
+                            },
+                        )
+                        .await?;
+                        read_body(payload, &req, &limits).await
+                    }
+                    .await;
+                    let (body, checked) = match checked {
+                        Ok(body) => (body, Ok(())),
+                        Err(e) => (Bytes::new(), Err(e)),
+                    };
                     let body: &[u8] = &body;
 
                     let runtime = create_runtime();
@@ -197,6 +253,26 @@ pub fn handle_server_fns_with_context(
                     // provide HttpRequest as context in server scope
                     provide_context(cx, req.clone());
                     provide_context(cx, res_options.clone());
+                    session::provide_session(
+                        cx,
+                        req.headers()
+                            .get(header::COOKIE)
+                            .and_then(|value| value.to_str().ok()),
+                        {
+                            let res_options = res_options.clone();
+                            move |cookie| {
+                                if let Ok(cookie) =
+                                    header::HeaderValue::from_str(&cookie)
+                                {
+                                    res_options.append_header(
+                                        header::SET_COOKIE,
+                                        cookie,
+                                    );
+                                }
+                            }
+                        },
+                    );
+                    provide_request_context(cx);
 
                     let query = req.query_string().as_bytes();
 
@@ -204,7 +280,32 @@ pub fn handle_server_fns_with_context(
                         Encoding::Url | Encoding::Cbor => body,
                         Encoding::GetJSON | Encoding::GetCBOR => query,
                     };
-                    let res = match (server_fn.trait_obj)(cx, data).await {
+                    // a request that fails the CSRF check never reaches the
+                    // server function
+                    let policy = use_csrf_policy(cx);
+                    let allowed = policy.allows(&CsrfCheck {
+                        encoding: &server_fn.encoding,
+                        cookies: req
+                            .headers()
+                            .get(header::COOKIE)
+                            .and_then(|value| value.to_str().ok()),
+                        header: req
+                            .headers()
+                            .get(CSRF_HEADER)
+                            .and_then(|value| value.to_str().ok()),
+                        body,
+                    });
+                    let checked = checked.and_then(|()| {
+                        if allowed {
+                            Ok(())
+                        } else {
+                            Err(csrf_error())
+                        }
+                    });
+                    let result =
+                        run_server_fn(cx, &server_fn, data, &limits, checked)
+                            .await;
+                    let res = match result {
                         Ok(serialized) => {
                             let res_options =
                                 use_context::<ResponseOptions>(cx).unwrap();
@@ -212,10 +313,13 @@ pub fn handle_server_fns_with_context(
                             let mut res: HttpResponseBuilder;
                             let mut res_parts = res_options.0.write();
 
+                            // a file is always sent, so that it can be
+                            // downloaded by following a link
                             if accept_header == Some("application/json")
                                 || accept_header
                                     == Some("application/x-www-form-urlencoded")
                                 || accept_header == Some("application/cbor")
+                                || matches!(serialized, Payload::File(_))
                             {
                                 res = HttpResponse::Ok();
                             }
@@ -261,27 +365,46 @@ pub fn handle_server_fns_with_context(
                                     res.content_type("application/json");
                                     res.body(data)
                                 }
+                                Payload::File(file) => {
+                                    res.content_type(file.mime.as_str())
+                                        .insert_header((
+                                            "Content-Disposition",
+                                            file.content_disposition(),
+                                        ));
+                                    match file.body {
+                                        FileBody::Bytes(bytes) => {
+                                            res.body(bytes)
+                                        }
+                                        FileBody::Stream(stream) => res
+                                            .streaming(stream.map(|chunk| {
+                                                chunk.map(Bytes::from)
+                                            })),
+                                    }
+                                }
                             }
                         }
-                        Err(e) => HttpResponse::InternalServerError().body(
-                            serde_json::to_string(&e)
-                                .unwrap_or_else(|_| e.to_string()),
-                        ),
+                        Err(e) => {
+                            let mut res = HttpResponse::build(
+                                StatusCode::from_u16(e.status()).unwrap_or(
+                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                ),
+                            );
+                            res.body(
+                                serde_json::to_string(&e)
+                                    .unwrap_or_else(|_| e.to_string()),
+                            )
+                        }
                     };
                     // clean up the scope
                     disposer.dispose();
                     runtime.dispose();
                     res
                 } else {
-                    HttpResponse::BadRequest().body(format!(
-                        "Could not find a server function at the route {:?}. \
-                         \n\nIt's likely that you need to call \
-                         ServerFn::register() on the server function type, \
-                         somewhere in your `main` function.",
-                        req.path()
-                    ))
+                    HttpResponse::BadRequest()
+                        .body(server_fn_not_found(&format!("{:?}", req.path())))
                 }
             }
+            .instrument(span)
         },
     )
 }
@@ -559,6 +682,7 @@ where
         let app_fn = app_fn.clone();
         let additional_context = additional_context.clone();
         let res_options = ResponseOptions::default();
+        let span = request_span(req.method().as_str(), req.path());
 
         async move {
             let app = {
@@ -579,6 +703,7 @@ where
             )
             .await
         }
+        .instrument(span)
     };
     match method {
         Method::Get => web::get().to(handler),
@@ -616,6 +741,7 @@ where
         let app_fn = app_fn.clone();
         let additional_context = additional_context.clone();
         let res_options = ResponseOptions::default();
+        let span = request_span(req.method().as_str(), req.path());
 
         async move {
             let app = {
@@ -630,6 +756,7 @@ where
             stream_app_in_order(&options, app, res_options, additional_context)
                 .await
         }
+        .instrument(span)
     };
     match method {
         Method::Get => web::get().to(handler),
@@ -668,6 +795,7 @@ where
         let app_fn = app_fn.clone();
         let additional_context = additional_context.clone();
         let res_options = ResponseOptions::default();
+        let span = request_span(req.method().as_str(), req.path());
 
         async move {
             let app = {
@@ -687,6 +815,7 @@ where
             )
             .await
         }
+        .instrument(span)
     };
     match method {
         Method::Get => web::get().to(handler),
@@ -773,6 +902,7 @@ where
         let app_fn = app_fn.clone();
         let data_fn = data_fn.clone();
         let res_options = ResponseOptions::default();
+        let span = request_span(req.method().as_str(), req.path());
 
         async move {
             let data = match data_fn(req.clone()).await {
@@ -792,8 +922,37 @@ where
 
             stream_app(&options, app, res_options, |_cx| {}, false).await
         }
+        .instrument(span)
     })
 }
+
+// The limit of Actix's default `PayloadConfig`, which applies to server
+// function bodies unless another limit is set.
+const DEFAULT_BODY_SIZE_LIMIT: usize = 256 * 1024;
+
+// Reads the body of a request to a server function, failing without reading
+// any further once it is longer than the [ServerFnLimits] allow.
+async fn read_body(
+    mut payload: web::Payload,
+    req: &HttpRequest,
+    limits: &ServerFnLimits,
+) -> Result<Bytes, ServerFnError> {
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        limits.check_body_size(content_length)?;
+    }
+    let mut bytes = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| ServerFnError::Request(e.to_string()))?;
+        limits.check_body_size(bytes.len() + chunk.len())?;
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.freeze())
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn provide_contexts(
     cx: leptos::Scope,
@@ -805,9 +964,41 @@ fn provide_contexts(
     let integration = ServerIntegration { path };
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
+    let cookies = req
+        .headers()
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok());
+    if let Some(set_cookie) = provide_csrf_token(cx, cookies)
+        .and_then(|value| header::HeaderValue::from_str(&value).ok())
+    {
+        res_options.append_header(header::SET_COOKIE, set_cookie);
+    }
+    session::provide_session(
+        cx,
+        req.headers()
+            .get(header::COOKIE)
+            .and_then(|value| value.to_str().ok()),
+        {
+            let res_options = res_options.clone();
+            move |cookie| {
+                if let Ok(cookie) = header::HeaderValue::from_str(&cookie) {
+                    res_options.append_header(header::SET_COOKIE, cookie);
+                }
+            }
+        },
+    );
+    provide_shell_error_handler(cx, {
+        let res_options = res_options.clone();
+        move |_| {
+            if res_options.0.read().status.is_none() {
+                res_options.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    });
     provide_context(cx, res_options);
     provide_context(cx, req.clone());
     provide_server_redirect(cx, move |path| redirect(cx, path));
+    provide_request_context(cx);
 }
 
 fn leptos_corrected_path(req: &HttpRequest) -> String {
@@ -867,11 +1058,12 @@ async fn build_stream_response(
     scope: ScopeId,
 ) -> HttpResponse {
     let cx = leptos::Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>(cx).as_ref());
+    let mut stream = Box::pin(stream);
+    let (head, first_chunk, tail) =
+        html_parts_with_shell(options, &mut stream, cx).await;
 
     let mut stream = Box::pin(
-        futures::stream::once(async move { head.clone() })
+        futures::stream::iter([head, first_chunk])
             .chain(stream)
             .chain(futures::stream::once(async move {
                 runtime.dispose();
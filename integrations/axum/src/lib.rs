@@ -6,7 +6,7 @@
 //! directory in the Leptos repository.
 
 use axum::{
-    body::{Body, Bytes, Full, StreamBody},
+    body::{Body, Bytes, StreamBody},
     extract::{Path, RawQuery},
     http::{
         header::{HeaderName, HeaderValue},
@@ -23,21 +23,29 @@ use http::{
     header, method::Method, request::Parts, uri::Uri, version::Version,
     Response,
 };
-use hyper::body;
+use hyper::body::{self, HttpBody};
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
+    leptos_server::{
+        csrf_error, server_fn_by_path, server_fn_limits, use_csrf_policy,
+        AuthRequest, CsrfCheck, Payload, ServerFnLimits, CSRF_HEADER,
+    },
     server_fn::Encoding,
     ssr::*,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
+use leptos_integration_utils::{
+    build_async_response, check_server_fn_request, html_parts_with_shell,
+    provide_csrf_token, provide_request_context, request_span, run_server_fn,
+    server_fn_not_found,
+};
+pub use leptos_integration_utils::{
+    session, write_precache_manifest, RequestContext,
+};
 use leptos_meta::{generate_head_metadata_separated, MetaContext};
 use leptos_router::*;
-use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
-use std::{io, pin::Pin, sync::Arc, thread::available_parallelism};
+use std::{io, pin::Pin, sync::Arc};
 use tokio::task::LocalSet;
-use tokio_util::task::LocalPoolHandle;
 use tracing::Instrument;
 /// A struct to hold the parts of the incoming Request. Since `http::Request` isn't cloneable, we're forced
 /// to construct this for Leptos to use in Axum
@@ -300,6 +308,54 @@ pub async fn handle_server_fns_with_context(
     handle_server_fns_inner(fn_name, headers, query, additional_context, req)
         .await
 }
+
+/// The headers of a request that can be read as text, with lowercase names.
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
+
+/// Warns, once, that the rate limit can't be applied because the client's
+/// address is unknown.
+fn warn_missing_connect_info() {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    WARNED.call_once(|| {
+        tracing::warn!(
+            "server functions have a rate limit, but the client's address is \
+             unknown, so every client is limited together or rejected. Serve \
+             the app with \
+             `into_make_service_with_connect_info::<SocketAddr>()` to limit \
+             each client separately."
+        );
+    });
+}
+
+/// Reads the body of a request to a server function, failing without reading
+/// any further once it is longer than the [ServerFnLimits] allow.
+async fn read_body(
+    mut body: Body,
+    headers: &HeaderMap,
+    limits: &ServerFnLimits,
+) -> Result<Bytes, ServerFnError> {
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        limits.check_body_size(content_length)?;
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| ServerFnError::Request(e.to_string()))?;
+        limits.check_body_size(bytes.len() + chunk.len())?;
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.into())
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 async fn handle_server_fns_inner(
     fn_name: String,
@@ -315,12 +371,46 @@ async fn handle_server_fns_inner(
         .unwrap_or(fn_name);
 
     let (tx, rx) = futures::channel::oneshot::channel();
-    let pool_handle = get_leptos_pool();
-    pool_handle.spawn_pinned(move || {
+    let span = tracing::info_span!("server fn", name = %fn_name);
+    spawn_pinned(move || {
         async move {
             let res = if let Some(server_fn) =
                 server_fn_by_path(fn_name.as_str())
             {
+                let client_ip = req
+                    .extensions()
+                    .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+                    .map(|info| info.0.ip().to_string());
+                // the rate limit and the authorization hook are checked
+                // before the body is read, so that a request they reject
+                // can't make the server read a large body
+                let limits = server_fn_limits();
+                if client_ip.is_none() && limits.has_rate_limit() {
+                    warn_missing_connect_info();
+                }
+                let (parts, body) = req.into_parts();
+                let checked = async {
+                    check_server_fn_request(
+                        &limits,
+                        client_ip.as_deref(),
+                        AuthRequest {
+                            fn_name: fn_name.clone(),
+                            encoding: server_fn.encoding.clone(),
+                            method: parts.method.to_string(),
+                            uri: parts.uri.to_string(),
+                            headers: header_pairs(&headers),
+                        },
+                    )
+                    .await?;
+                    read_body(body, &headers, &limits).await
+                }
+                .await;
+                let (body, checked) = match checked {
+                    Ok(body) => (body, Ok(())),
+                    Err(e) => (Bytes::new(), Err(e)),
+                };
+                let req = Request::from_parts(parts, Body::from(body));
+
                 let runtime = create_runtime();
                 let (cx, disposer) = raw_scope_and_disposer(runtime);
 
@@ -331,14 +421,53 @@ async fn handle_server_fns_inner(
                 provide_context(cx, req_parts.clone());
                 provide_context(cx, leptos_req);
                 // Add this so that we can set headers and status of the response
-                provide_context(cx, ResponseOptions::default());
+                let res_options = ResponseOptions::default();
+                provide_context(cx, res_options.clone());
+                session::provide_session(
+                    cx,
+                    headers
+                        .get(header::COOKIE)
+                        .and_then(|value| value.to_str().ok()),
+                    {
+                        let res_options = res_options.clone();
+                        move |cookie| {
+                            if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+                                res_options
+                                    .append_header(header::SET_COOKIE, cookie);
+                            }
+                        }
+                    },
+                );
+                provide_request_context(cx);
 
                 let query: &Bytes = &query.unwrap_or("".to_string()).into();
                 let data = match &server_fn.encoding {
                     Encoding::Url | Encoding::Cbor => &req_parts.body,
                     Encoding::GetJSON | Encoding::GetCBOR => query,
                 };
-                let res = match (server_fn.trait_obj)(cx, data).await {
+                // a request that fails the CSRF check never reaches the
+                // server function
+                let policy = use_csrf_policy(cx);
+                let allowed = policy.allows(&CsrfCheck {
+                    encoding: &server_fn.encoding,
+                    cookies: headers
+                        .get(header::COOKIE)
+                        .and_then(|value| value.to_str().ok()),
+                    header: headers
+                        .get(CSRF_HEADER)
+                        .and_then(|value| value.to_str().ok()),
+                    body: &req_parts.body,
+                });
+                let checked = checked.and_then(|()| {
+                    if allowed {
+                        Ok(())
+                    } else {
+                        Err(csrf_error())
+                    }
+                });
+                let result =
+                    run_server_fn(cx, &server_fn, data, &limits, checked).await;
+                let res = match result {
                     Ok(serialized) => {
                         // If ResponseOptions are set, add the headers and status to the request
                         let res_options = use_context::<ResponseOptions>(cx);
@@ -358,10 +487,13 @@ async fn handle_server_fns_inner(
                             res_options_inner.headers.clone(),
                         );
 
+                        // a file is always sent, so that it can be
+                        // downloaded by following a link
                         if accept_header == Some("application/json")
                             || accept_header
                                 == Some("application/x-www-form-urlencoded")
                             || accept_header == Some("application/cbor")
+                            || matches!(serialized, Payload::File(_))
                         {
                             res = res.status(StatusCode::OK);
                         }
@@ -389,43 +521,55 @@ async fn handle_server_fns_inner(
                         match serialized {
                             Payload::Binary(data) => res
                                 .header("Content-Type", "application/cbor")
-                                .body(Full::from(data)),
+                                .body(Body::from(data)),
                             Payload::Url(data) => res
                                 .header(
                                     "Content-Type",
                                     "application/x-www-form-urlencoded",
                                 )
-                                .body(Full::from(data)),
+                                .body(Body::from(data)),
                             Payload::Json(data) => res
                                 .header("Content-Type", "application/json")
-                                .body(Full::from(data)),
+                                .body(Body::from(data)),
+                            Payload::File(file) => {
+                                let res = res
+                                    .header("Content-Type", file.mime.as_str())
+                                    .header(
+                                        "Content-Disposition",
+                                        file.content_disposition(),
+                                    );
+                                match file.body {
+                                    FileBody::Bytes(bytes) => {
+                                        res.body(Body::from(bytes))
+                                    }
+                                    FileBody::Stream(stream) => {
+                                        res.body(Body::wrap_stream(stream))
+                                    }
+                                }
+                            }
                         }
                     }
-                    Err(e) => Response::builder()
-                        .status(StatusCode::INTERNAL_SERVER_ERROR)
-                        .body(Full::from(
+                    Err(e) => {
+                        Response::builder().status(e.status()).body(Body::from(
                             serde_json::to_string(&e)
                                 .unwrap_or_else(|_| e.to_string()),
-                        )),
+                        ))
+                    }
                 };
                 // clean up the scope
                 disposer.dispose();
                 runtime.dispose();
                 res
             } else {
-                Response::builder().status(StatusCode::BAD_REQUEST).body(
-                    Full::from(format!(
-                        "Could not find a server function at the route \
-                         {fn_name}. \n\nIt's likely that you need to call \
-                         ServerFn::register() on the server function type, \
-                         somewhere in your `main` function."
-                    )),
-                )
+                Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from(server_fn_not_found(&fn_name)))
             }
             .expect("could not build Response");
 
             _ = tx.send(res);
         }
+        .instrument(span)
     });
 
     rx.await.unwrap()
@@ -679,11 +823,11 @@ where
             let default_res_options = ResponseOptions::default();
             let res_options2 = default_res_options.clone();
             let res_options3 = default_res_options.clone();
-            let local_pool = get_leptos_pool();
             let (tx, rx) = futures::channel::mpsc::channel(8);
 
-            let current_span = tracing::Span::current();
-            local_pool.spawn_pinned(move || async move {
+            let current_span =
+                request_span(req.method().as_str(), req.uri().path());
+            spawn_pinned(move || async move {
                 let app = {
                     // Need to get the path and query string of the Request
                     // For reasons that escape me, if the incoming URI protocol is https, it provides the absolute URI
@@ -753,11 +897,12 @@ async fn forward_stream(
     mut tx: Sender<String>,
 ) {
     let cx = Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>(cx).as_ref());
+    let mut shell = Box::pin(bundle);
+    let (head, first_chunk, tail) =
+        html_parts_with_shell(options, &mut shell, cx).await;
 
     _ = tx.send(head).await;
-    let mut shell = Box::pin(bundle);
+    _ = tx.send(first_chunk).await;
     while let Some(fragment) = shell.next().await {
         _ = tx.send(fragment).await;
     }
@@ -840,9 +985,9 @@ where
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::mpsc::channel(8);
-                let local_pool = get_leptos_pool();
-                let current_span = tracing::Span::current();
-                local_pool.spawn_pinned(|| async move {
+                let current_span =
+                    request_span(req.method().as_str(), req.uri().path());
+                spawn_pinned(|| async move {
                     let app = {
                         let full_path = full_path.clone();
                         let (req, req_parts) = generate_request_and_parts(req).await;
@@ -879,10 +1024,43 @@ fn provide_contexts<B: 'static + std::fmt::Debug + std::default::Default>(
     let integration = ServerIntegration { path };
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
+    let cookies = req_parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok());
+    if let Some(set_cookie) = provide_csrf_token(cx, cookies)
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        default_res_options.append_header(header::SET_COOKIE, set_cookie);
+    }
+    session::provide_session(
+        cx,
+        req_parts
+            .headers
+            .get(header::COOKIE)
+            .and_then(|value| value.to_str().ok()),
+        {
+            let res_options = default_res_options.clone();
+            move |cookie| {
+                if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+                    res_options.append_header(header::SET_COOKIE, cookie);
+                }
+            }
+        },
+    );
     provide_context(cx, req_parts);
     provide_context(cx, leptos_req);
+    provide_shell_error_handler(cx, {
+        let res_options = default_res_options.clone();
+        move |_| {
+            if res_options.0.read().status.is_none() {
+                res_options.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    });
     provide_context(cx, default_res_options);
     provide_server_redirect(cx, move |path| redirect(cx, path));
+    provide_request_context(cx);
 }
 
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
@@ -1010,8 +1188,9 @@ where
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::oneshot::channel();
-                let local_pool = get_leptos_pool();
-                local_pool.spawn_pinned(move || {
+                let current_span =
+                    request_span(req.method().as_str(), req.uri().path());
+                spawn_pinned(move || {
                     async move {
                         let app = {
                             let full_path = full_path.clone();
@@ -1043,7 +1222,7 @@ where
                         *writable = new_res_parts;
 
                         _ = tx.send(html);
-                    }
+                    }.instrument(current_span)
                 });
 
                 let html = rx.await.expect("to complete HTML rendering");
@@ -1303,14 +1482,66 @@ impl LeptosRoutes for axum::Router {
         router
     }
 }
-#[tracing::instrument(level = "trace", fields(error), skip_all)]
-fn get_leptos_pool() -> LocalPoolHandle {
-    static LOCAL_POOL: OnceCell<LocalPoolHandle> = OnceCell::new();
-    LOCAL_POOL
-        .get_or_init(|| {
-            tokio_util::task::LocalPoolHandle::new(
-                available_parallelism().map(Into::into).unwrap_or(1),
-            )
-        })
-        .clone()
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ServerFnLimits {
+        ServerFnLimits::default().max_body_size(8)
+    }
+
+    fn content_length(len: usize) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_LENGTH, len.into());
+        headers
+    }
+
+    fn chunked(chunks: &[&'static str]) -> Body {
+        Body::wrap_stream(futures::stream::iter(
+            chunks
+                .iter()
+                .map(|chunk| Ok::<_, io::Error>(chunk.as_bytes())),
+        ))
+    }
+
+    #[tokio::test]
+    async fn reads_body_within_limit() {
+        let body =
+            read_body(chunked(&["abc", "def"]), &HeaderMap::new(), &limits())
+                .await
+                .unwrap();
+        assert_eq!(&body[..], b"abcdef");
+    }
+
+    #[tokio::test]
+    async fn rejects_long_content_length_without_reading() {
+        // the body itself is short, so only the header can reject it
+        let result =
+            read_body(Body::from("abc"), &content_length(100), &limits()).await;
+        assert!(matches!(result, Err(ServerFnError::PayloadTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn stops_reading_long_body() {
+        let result = read_body(
+            chunked(&["abcdef", "ghijkl", "mnopqr"]),
+            &HeaderMap::new(),
+            &limits(),
+        )
+        .await;
+        assert!(matches!(result, Err(ServerFnError::PayloadTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn reads_any_body_without_limit() {
+        let body = read_body(
+            chunked(&["abcdef", "ghijkl"]),
+            &content_length(12),
+            &ServerFnLimits::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(body.len(), 12);
+    }
 }
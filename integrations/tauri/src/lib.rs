@@ -0,0 +1,204 @@
+#![forbid(unsafe_code)]
+
+//! Provides functions to run Leptos in a [Tauri](https://tauri.app/) app,
+//! with the server functions running in the Rust process of the app rather
+//! than on a web server.
+//!
+//! The app's frontend is built for the client as usual. Instead of sending
+//! HTTP requests, it sends its server function calls over Tauri's IPC
+//! channel with the [TauriIpcClient](leptos::server_fn::client::TauriIpcClient),
+//! and routes with the [MemoryIntegration](https://docs.rs/leptos_router/latest/leptos_router/struct.MemoryIntegration.html),
+//! as the custom protocol Tauri serves the page with can't load any URL
+//! other than the first one:
+//!
+//! ```ignore
+//! // the frontend, built with the `csr` feature
+//! fn main() {
+//!     set_http_client(TauriIpcClient::default());
+//!     mount_to_body(|cx| {
+//!         provide_context(
+//!             cx,
+//!             RouterIntegrationContext::new(MemoryIntegration::new("/")),
+//!         );
+//!         view! { cx, <App/> }
+//!     })
+//! }
+//! ```
+//!
+//! The Tauri backend, built with the `ssr` feature, registers the server
+//! functions and answers the calls in a command:
+//!
+//! ```ignore
+//! #[tauri::command]
+//! async fn leptos_server_fn(request: HttpRequest) -> HttpResponse {
+//!     leptos_tauri::handle_server_fn(request).await
+//! }
+//!
+//! fn main() {
+//!     AddTodo::register().unwrap();
+//!     tauri::Builder::default()
+//!         .invoke_handler(tauri::generate_handler![leptos_server_fn])
+//!         .run(tauri::generate_context!())
+//!         .unwrap();
+//! }
+//! ```
+
+use leptos::{
+    leptos_server::{
+        server_fn_by_path, server_fn_limits, AuthRequest, Payload,
+    },
+    server_fn::{
+        client::{HttpMethod, HttpRequest, HttpResponse},
+        Encoding,
+    },
+    *,
+};
+use leptos_integration_utils::{
+    check_server_fn_request, run_server_fn, server_fn_not_found,
+};
+use tracing::Instrument;
+
+/// Runs the server function called by a request sent with the
+/// [TauriIpcClient](leptos::server_fn::client::TauriIpcClient), and returns
+/// its response.
+///
+/// The server function can read the [HttpRequest] with [use_context]. The
+/// [authorization hook](leptos::leptos_server::set_server_fn_auth) and the
+/// [limits](leptos::leptos_server::set_server_fn_limits) on the body size and
+/// running time apply as they do on a web server. As the calls come from the
+/// app's own webview, there is no CSRF check.
+pub async fn handle_server_fn(request: HttpRequest) -> HttpResponse {
+    handle_server_fn_with_context(request, |_| {}).await
+}
+
+/// Like [handle_server_fn], but calls `additional_context` with the scope of
+/// the server function before it runs, e.g., to provide a database pool or
+/// the state of the Tauri app with [provide_context].
+pub async fn handle_server_fn_with_context(
+    request: HttpRequest,
+    additional_context: impl Fn(Scope) + Clone + Send + 'static,
+) -> HttpResponse {
+    let (path, query) = request
+        .url
+        .split_once('?')
+        .map(|(path, query)| (path.to_string(), query.to_string()))
+        .unwrap_or_else(|| (request.url.clone(), String::new()));
+    // the prefix of the server function is not part of its name
+    let fn_name = path.rsplit('/').next().unwrap_or_default().to_string();
+    let span = tracing::info_span!("server fn", name = %fn_name);
+
+    spawn_pinned(move || {
+        async move {
+            let Some(server_fn) = server_fn_by_path(&fn_name) else {
+                return error_response(400, server_fn_not_found(&fn_name));
+            };
+
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            additional_context(cx);
+            provide_context(cx, request.clone());
+
+            let body = request.body.clone().unwrap_or_default();
+            let data = match &server_fn.encoding {
+                Encoding::Url | Encoding::Cbor => body.as_slice(),
+                Encoding::GetJSON | Encoding::GetCBOR => query.as_bytes(),
+            };
+            let limits = server_fn_limits();
+            // the calls come from the app itself, so there is no client IP
+            // to apply the rate limit to
+            let checked = async {
+                limits.check_body_size(data.len())?;
+                check_server_fn_request(
+                    &limits,
+                    None,
+                    AuthRequest {
+                        fn_name: fn_name.clone(),
+                        encoding: server_fn.encoding.clone(),
+                        method: match request.method {
+                            HttpMethod::Get => "GET",
+                            HttpMethod::Post => "POST",
+                        }
+                        .to_string(),
+                        uri: request.url.clone(),
+                        headers: request
+                            .headers
+                            .iter()
+                            .map(|(name, value)| {
+                                (name.to_ascii_lowercase(), value.clone())
+                            })
+                            .collect(),
+                    },
+                )
+                .await
+            }
+            .await;
+            let result =
+                run_server_fn(cx, &server_fn, data, &limits, checked).await;
+
+            let response = match result {
+                Ok(payload) => payload_response(payload).await,
+                Err(e) => error_response(
+                    e.status(),
+                    serde_json::to_string(&e).unwrap_or_else(|_| e.to_string()),
+                ),
+            };
+            disposer.dispose();
+            runtime.dispose();
+            response
+        }
+        .instrument(span)
+    })
+    .await
+    .unwrap_or_else(|e| error_response(500, e.to_string()))
+}
+
+/// Builds the response for the result of a server function. The IPC channel
+/// can't stream a response, so a streamed file is read before it is sent.
+async fn payload_response(payload: Payload) -> HttpResponse {
+    let (content_type, headers, body) = match payload {
+        Payload::Binary(data) => ("application/cbor".into(), vec![], data),
+        Payload::Url(data) => (
+            "application/x-www-form-urlencoded".into(),
+            vec![],
+            data.into_bytes(),
+        ),
+        Payload::Json(data) => {
+            ("application/json".into(), vec![], data.into_bytes())
+        }
+        Payload::File(file) => {
+            let disposition = file.content_disposition();
+            let mime = file.mime.clone();
+            match file.into_bytes().await {
+                Ok(bytes) => (
+                    mime,
+                    vec![("content-disposition".into(), disposition)],
+                    bytes,
+                ),
+                Err(e) => {
+                    return error_response(
+                        e.status(),
+                        serde_json::to_string(&e)
+                            .unwrap_or_else(|_| e.to_string()),
+                    )
+                }
+            }
+        }
+    };
+    let mut response = HttpResponse {
+        status: 200,
+        status_text: "OK".into(),
+        headers: vec![("content-type".into(), content_type)],
+        body,
+    };
+    response.headers.extend(headers);
+    response
+}
+
+fn error_response(status: u16, body: String) -> HttpResponse {
+    HttpResponse {
+        status,
+        status_text: String::new(),
+        headers: Vec::new(),
+        body: body.into_bytes(),
+    }
+}
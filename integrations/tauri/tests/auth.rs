@@ -0,0 +1,66 @@
+use leptos::{
+    leptos_server::{set_server_fn_auth, AuthRequest},
+    server_fn::{client::HttpRequest, Encoding},
+    Scope, ServerFnError,
+};
+use serde::{Deserialize, Serialize};
+use std::{future::Future, pin::Pin};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Secret {}
+
+impl leptos::server_fn::ServerFn<Scope> for Secret {
+    type Output = String;
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "secret"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::Url
+    }
+
+    fn call_fn(
+        self,
+        _cx: Scope,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>> {
+        Box::pin(async { Ok("42".to_string()) })
+    }
+}
+
+async fn check_token(req: AuthRequest) -> Result<(), ServerFnError> {
+    match req.header("authorization") {
+        Some("secret") => Ok(()),
+        Some(_) => Err(ServerFnError::Forbidden("invalid token".into())),
+        None => Err(ServerFnError::Unauthorized("log in first".into())),
+    }
+}
+
+// the hook is global, so the cases are checked in one test
+#[tokio::test]
+async fn auth_errors_are_sent_as_401_and_403() {
+    _ = <Secret as leptos::ServerFn>::register();
+    set_server_fn_auth(check_token);
+
+    let response =
+        leptos_tauri::handle_server_fn(HttpRequest::post("/api/secret", ""))
+            .await;
+    assert_eq!(response.status, 401);
+
+    let response = leptos_tauri::handle_server_fn(
+        HttpRequest::post("/api/secret", "").header("authorization", "guess"),
+    )
+    .await;
+    assert_eq!(response.status, 403);
+
+    let response = leptos_tauri::handle_server_fn(
+        HttpRequest::post("/api/secret", "").header("authorization", "secret"),
+    )
+    .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"\"42\"");
+}
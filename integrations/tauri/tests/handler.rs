@@ -0,0 +1,135 @@
+use leptos::{
+    provide_context,
+    server_fn::{
+        client::{HttpRequest, HttpResponse},
+        Encoding,
+    },
+    use_context, Scope, ServerFnError,
+};
+use serde::{Deserialize, Serialize};
+use std::{future::Future, pin::Pin};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct AddOne {
+    n: i32,
+}
+
+impl leptos::server_fn::ServerFn<Scope> for AddOne {
+    type Output = i32;
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "add_one"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::Url
+    }
+
+    fn call_fn(
+        self,
+        _cx: Scope,
+    ) -> Pin<Box<dyn Future<Output = Result<i32, ServerFnError>>>> {
+        Box::pin(async move { Ok(self.n + 1) })
+    }
+}
+
+#[derive(Clone)]
+struct Greeting(&'static str);
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Greet {}
+
+impl leptos::server_fn::ServerFn<Scope> for Greet {
+    type Output = String;
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "greet"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::GetJSON
+    }
+
+    fn call_fn(
+        self,
+        cx: Scope,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>> {
+        let greeting = use_context::<Greeting>(cx);
+        let request = use_context::<HttpRequest>(cx);
+        Box::pin(async move {
+            let name = request
+                .and_then(|request| {
+                    request
+                        .headers
+                        .into_iter()
+                        .find(|(name, _)| name == "x-name")
+                        .map(|(_, value)| value)
+                })
+                .unwrap_or_default();
+            Ok(format!("{} {name}", greeting.map_or("", |g| g.0)))
+        })
+    }
+}
+
+fn register() {
+    // each test registers them, and only the first one succeeds
+    _ = <AddOne as leptos::ServerFn>::register();
+    _ = <Greet as leptos::ServerFn>::register();
+}
+
+fn body(response: &HttpResponse) -> &str {
+    std::str::from_utf8(&response.body).unwrap()
+}
+
+#[tokio::test]
+async fn runs_server_fn() {
+    register();
+    let response = leptos_tauri::handle_server_fn(HttpRequest::post(
+        "/api/add_one",
+        "n=41",
+    ))
+    .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(body(&response), "42");
+}
+
+#[tokio::test]
+async fn provides_request_and_context() {
+    register();
+    let response = leptos_tauri::handle_server_fn_with_context(
+        HttpRequest::get("/api/greet").header("x-name", "Ferris"),
+        |cx| provide_context(cx, Greeting("Hello,")),
+    )
+    .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(body(&response), "\"Hello, Ferris\"");
+}
+
+#[tokio::test]
+async fn invalid_arguments_are_an_error() {
+    register();
+    let response = leptos_tauri::handle_server_fn(HttpRequest::post(
+        "/api/add_one",
+        "n=forty-one",
+    ))
+    .await;
+    assert_eq!(response.status, 500);
+    assert!(body(&response).contains("Deserialization"));
+}
+
+#[tokio::test]
+async fn unknown_server_fn_is_a_bad_request() {
+    let response =
+        leptos_tauri::handle_server_fn(HttpRequest::post("/api/missing", ""))
+            .await;
+    assert_eq!(response.status, 400);
+    assert!(body(&response).contains("ServerFn::register()"));
+}
@@ -0,0 +1,50 @@
+use leptos::{
+    leptos_server::{authorize, AuthRequest, Payload, ServerFnLimits},
+    server_fn::ServerFunction,
+    Scope, ServerFnError,
+};
+
+/// The message sent back for a request to a path at which no server function
+/// is registered.
+pub fn server_fn_not_found(fn_name: &str) -> String {
+    format!(
+        "Could not find a server function at the route {fn_name}. \n\nIt's \
+         likely that you need to call ServerFn::register() on the server \
+         function type, somewhere in your `main` function."
+    )
+}
+
+/// Checks a request to a server function against the
+/// [rate limit](ServerFnLimits::rate_limit) for `client_ip` and the
+/// [authorization hook](leptos::leptos_server::set_server_fn_auth). Call it
+/// before reading the body, so that a request that is rejected can't make
+/// the server read a large body.
+pub async fn check_server_fn_request(
+    limits: &ServerFnLimits,
+    client_ip: Option<&str>,
+    request: AuthRequest,
+) -> Result<(), ServerFnError> {
+    limits.check_rate_limit(client_ip)?;
+    authorize(request).await
+}
+
+/// Runs a server function with the arguments in `data`, or returns the error
+/// in `checked` without running it if the request failed a check. The server
+/// function fails with a [timeout error](ServerFnLimits::timeout_error) if it
+/// runs for longer than the [timeout](ServerFnLimits::timeout).
+pub async fn run_server_fn(
+    cx: Scope,
+    server_fn: &ServerFunction<Scope>,
+    data: &[u8],
+    limits: &ServerFnLimits,
+    checked: Result<(), ServerFnError>,
+) -> Result<Payload, ServerFnError> {
+    checked?;
+    let call = (server_fn.trait_obj)(cx, data);
+    match limits.timeout_duration() {
+        None => call.await,
+        Some(timeout) => tokio::time::timeout(timeout, call)
+            .await
+            .unwrap_or_else(|_| Err(limits.timeout_error())),
+    }
+}
@@ -1,10 +1,30 @@
 use futures::{Stream, StreamExt};
-use leptos::{use_context, RuntimeId, ScopeId};
-use leptos_config::LeptosOptions;
+use leptos::{
+    provide_context,
+    server_fn::csrf::{cookie_value, CSRF_COOKIE},
+    use_context, use_csrf_policy, CsrfToken, RuntimeId, Scope, ScopeId,
+};
+use leptos_config::{AssetHashes, LeptosOptions};
 use leptos_meta::MetaContext;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 extern crate tracing;
 
+mod dispatch;
+mod request_context;
+pub mod session;
+
+pub use dispatch::*;
+pub use request_context::*;
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn autoreload(options: &LeptosOptions) -> String {
     let site_ip = &options.site_addr.ip().to_string();
@@ -42,6 +62,68 @@ fn autoreload(options: &LeptosOptions) -> String {
         false => "".to_string(),
     }
 }
+
+// The hashes of each hash file are read once, as the files do not change
+// while the server is running: cargo-leptos restarts it after every build.
+// A file that can't be read is tried again on the next request.
+fn asset_hashes(options: &LeptosOptions) -> AssetHashes {
+    static HASHES: Lazy<Mutex<HashMap<PathBuf, AssetHashes>>> =
+        Lazy::new(Default::default);
+
+    if !options.hash_files {
+        return AssetHashes::default();
+    }
+    let path = Path::new(&options.site_root).join(&options.hash_file);
+    let mut hashes = HASHES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cached) = hashes.get(&path) {
+        return cached.clone();
+    }
+    match options.asset_hashes() {
+        Ok(read) => hashes.entry(path).or_insert(read).clone(),
+        Err(e) => {
+            tracing::error!("{e}, serving the files without hashes");
+            AssetHashes::default()
+        }
+    }
+}
+
+// The app can't know the hashed name of its stylesheet at compile time, so a
+// `<Stylesheet/>` for the unhashed file in `head` is pointed at the hashed
+// one. If there is none, a link to the hashed file is returned instead.
+fn hashed_stylesheet(options: &LeptosOptions, head: &mut String) -> String {
+    let hashes = asset_hashes(options);
+    if hashes.css.is_none() {
+        return String::new();
+    }
+    let pkg_path = &options.site_pkg_dir;
+    let unhashed = format!(r#"href="/{pkg_path}/{}.css""#, options.output_name);
+    let hashed = format!(
+        r#"href="/{pkg_path}/{}""#,
+        hashes.hashed(&options.output_name, "css")
+    );
+    if head.contains(&unhashed) {
+        *head = head.replace(&unhashed, &hashed);
+        String::new()
+    } else {
+        format!(r#"<link rel="stylesheet" {hashed}>"#)
+    }
+}
+
+/// A span for rendering the page for a single request, which is the parent of
+/// the spans for everything that happens while rendering it, like resources
+/// loading. The span is only enabled with the `tracing` feature.
+pub fn request_span(method: &str, path: &str) -> tracing::Span {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::info_span!("leptos render", method, path)
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        _ = (method, path);
+        tracing::Span::none()
+    }
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 pub fn html_parts(
     options: &LeptosOptions,
@@ -58,6 +140,11 @@ pub fn html_parts(
         wasm_output_name.push_str("_bg");
     }
 
+    let hashes = asset_hashes(options);
+    let js_file = hashes.hashed(output_name, "js");
+    let wasm_file = hashes.hashed(&wasm_output_name, "wasm");
+    let stylesheet = hashed_stylesheet(options, &mut String::new());
+
     let leptos_autoreload = autoreload(options);
 
     let html_metadata =
@@ -68,9 +155,10 @@ pub fn html_parts(
                 <head>
                     <meta charset="utf-8"/>
                     <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                    <link rel="modulepreload" href="/{pkg_path}/{output_name}.js">
-                    <link rel="preload" href="/{pkg_path}/{wasm_output_name}.wasm" as="fetch" type="application/wasm" crossorigin="">
-                    <script type="module">import init, {{ hydrate }} from '/{pkg_path}/{output_name}.js'; init('/{pkg_path}/{wasm_output_name}.wasm').then(hydrate);</script>
+                    {stylesheet}
+                    <link rel="modulepreload" href="/{pkg_path}/{js_file}">
+                    <link rel="preload" href="/{pkg_path}/{wasm_file}" as="fetch" type="application/wasm" crossorigin="">
+                    <script type="module">import init, {{ hydrate }} from '/{pkg_path}/{js_file}'; init('/{pkg_path}/{wasm_file}').then(hydrate);</script>
                     {leptos_autoreload}
                     "#
     );
@@ -94,14 +182,19 @@ pub fn html_parts_separated(
         wasm_output_name.push_str("_bg");
     }
 
+    let hashes = asset_hashes(options);
+    let js_file = hashes.hashed(output_name, "js");
+    let wasm_file = hashes.hashed(&wasm_output_name, "wasm");
+
     let leptos_autoreload = autoreload(options);
 
     let html_metadata =
         meta.and_then(|mc| mc.html.as_string()).unwrap_or_default();
-    let head = meta
+    let mut head = meta
         .as_ref()
         .map(|meta| meta.dehydrate())
         .unwrap_or_default();
+    let stylesheet = hashed_stylesheet(options, &mut head);
     let head = format!(
         r#"<!DOCTYPE html>
             <html{html_metadata}>
@@ -109,9 +202,10 @@ pub fn html_parts_separated(
                     <meta charset="utf-8"/>
                     <meta name="viewport" content="width=device-width, initial-scale=1"/>
                     {head}
-                    <link rel="modulepreload" href="/{pkg_path}/{output_name}.js">
-                    <link rel="preload" href="/{pkg_path}/{wasm_output_name}.wasm" as="fetch" type="application/wasm" crossorigin="">
-                    <script type="module">import init, {{ hydrate }} from '/{pkg_path}/{output_name}.js'; init('/{pkg_path}/{wasm_output_name}.wasm').then(hydrate);</script>
+                    {stylesheet}
+                    <link rel="modulepreload" href="/{pkg_path}/{js_file}">
+                    <link rel="preload" href="/{pkg_path}/{wasm_file}" as="fetch" type="application/wasm" crossorigin="">
+                    <script type="module">import init, {{ hydrate }} from '/{pkg_path}/{js_file}'; init('/{pkg_path}/{wasm_file}').then(hydrate);</script>
                     {leptos_autoreload}
                     "#
     );
@@ -119,6 +213,132 @@ pub fn html_parts_separated(
     (head, tail)
 }
 
+/// Waits for the first chunk of an HTML stream of the app, and then returns it
+/// along with the parts of the document around it, as [html_parts_separated]
+/// does.
+///
+/// The first chunk is only rendered once every resource created with
+/// [create_blocking_resource](leptos::create_blocking_resource) has loaded, so
+/// the `<head>` includes metadata like a `<Title/>` that is set from their
+/// data, and a status code or headers they set are in the `ResponseOptions`
+/// before the response is sent.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub async fn html_parts_with_shell(
+    options: &LeptosOptions,
+    stream: &mut (impl Stream<Item = String> + Unpin),
+    cx: Scope,
+) -> (String, String, &'static str) {
+    let shell = stream.next().await.unwrap_or_default();
+    let (head, tail) =
+        html_parts_separated(options, use_context::<MetaContext>(cx).as_ref());
+    (head, shell, tail)
+}
+
+/// The URLs of the assets the client needs to start the app: the JS glue
+/// code, the WASM binary, and the stylesheet built by `cargo-leptos`. A
+/// service worker can add these to its cache when it is installed, so that
+/// the app keeps working offline.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn precache_manifest(options: &LeptosOptions) -> Vec<String> {
+    let pkg_path = &options.site_pkg_dir;
+    let output_name = &options.output_name;
+
+    // see html_parts_separated
+    let mut wasm_output_name = output_name.clone();
+    if std::env::var("LEPTOS_OUTPUT_NAME").is_err() {
+        wasm_output_name.push_str("_bg");
+    }
+
+    let hashes = asset_hashes(options);
+    vec![
+        format!("/{pkg_path}/{}", hashes.hashed(output_name, "js")),
+        format!("/{pkg_path}/{}", hashes.hashed(&wasm_output_name, "wasm")),
+        format!("/{pkg_path}/{}", hashes.hashed(output_name, "css")),
+    ]
+}
+
+/// The [precache_manifest] as a JSON array of URLs, to be served to a
+/// service worker.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn precache_manifest_json(options: &LeptosOptions) -> String {
+    serde_json::to_string(&precache_manifest(options))
+        .expect("could not serialize precache manifest")
+}
+
+/// Writes the [precache_manifest_json] to `precache-manifest.json` in the
+/// `site_pkg_dir`, and returns its path. It is then served with the rest of
+/// the app's assets, at `/{site_pkg_dir}/precache-manifest.json`, for a
+/// service worker to fetch when it is installed, to know which files to
+/// cache so that the app keeps working offline.
+///
+/// Call this in your `main` function, before the server starts.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn write_precache_manifest(
+    options: &LeptosOptions,
+) -> std::io::Result<PathBuf> {
+    let path = Path::new(&options.site_root)
+        .join(&options.site_pkg_dir)
+        .join("precache-manifest.json");
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, precache_manifest_json(options))?;
+    Ok(path)
+}
+
+static CSRF_COOKIE_SECURE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the CSRF cookie is only sent over HTTPS, like
+/// [SessionConfig::secure](session::SessionConfig::secure) does for the
+/// session cookie. This should be `true` in production.
+pub fn set_csrf_cookie_secure(secure: bool) {
+    CSRF_COOKIE_SECURE.store(secure, Ordering::Relaxed);
+}
+
+/// Returns the CSRF token of the browser that sent a request with the given
+/// `Cookie` header. If it doesn't have one yet, a new token is created, and
+/// the `Set-Cookie` header that gives it to the browser is returned with it.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn csrf_token(cookies: Option<&str>) -> (CsrfToken, Option<String>) {
+    let existing = cookies
+        .and_then(|cookies| cookie_value(cookies, CSRF_COOKIE))
+        .filter(|token| {
+            !token.is_empty() && token.bytes().all(|b| b.is_ascii_hexdigit())
+        });
+    match existing {
+        Some(token) => (CsrfToken(token.to_string()), None),
+        None => {
+            let token = uuid::Uuid::new_v4().simple().to_string();
+            let secure = if CSRF_COOKIE_SECURE.load(Ordering::Relaxed) {
+                "; Secure"
+            } else {
+                ""
+            };
+            // the cookie is read by the client, so it can't be `HttpOnly`
+            let set_cookie =
+                format!("{CSRF_COOKIE}={token}; Path=/; SameSite=Lax{secure}");
+            (CsrfToken(token), Some(set_cookie))
+        }
+    }
+}
+
+/// Gives a CSRF token to the browser that sent a request with the given
+/// `Cookie` header, if the [CsrfPolicy](leptos::CsrfPolicy) for the request is
+/// enabled. The token is provided as context, and the `Set-Cookie` header
+/// that gives it to the browser is returned if it doesn't have it yet.
+///
+/// The policy is read with [use_csrf_policy], so call this after the
+/// `additional_context` of the integration has run.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn provide_csrf_token(cx: Scope, cookies: Option<&str>) -> Option<String> {
+    if !use_csrf_policy(cx).is_enabled() {
+        return None;
+    }
+    let (token, set_cookie) = csrf_token(cookies);
+    provide_context(cx, token);
+    set_cookie
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 pub async fn build_async_response(
     stream: impl Stream<Item = String> + 'static,
@@ -0,0 +1,100 @@
+use leptos::{provide_context, use_context, Scope};
+use std::sync::Arc;
+
+type Provider = Arc<dyn Fn(Scope) + Send + Sync>;
+
+/// A typed list of values to provide as context for every request, both
+/// while rendering the app and while running server functions, so that
+/// components, resources, and server functions can get them with
+/// [use_context].
+///
+/// Pass [RequestContext::provider] as the `additional_context` of an
+/// integration. The values are provided after the integration's own context
+/// (like the request and [ResponseOptions](https://docs.rs/leptos_axum/latest/leptos_axum/struct.ResponseOptions.html)),
+/// so the functions given to [RequestContext::with] can read the request.
+///
+/// ```rust,ignore
+/// let context = RequestContext::new()
+///     // the same pool for every request
+///     .value(pool.clone())
+///     // worked out again for each request
+///     .with(|cx| Locale::from_request(&use_context::<RequestParts>(cx).unwrap()));
+///
+/// let provider = context.provider();
+/// let app = Router::new()
+///     .route(
+///         "/api/*fn_name",
+///         post({
+///             let provider = provider.clone();
+///             move |path, headers, query, req| {
+///                 handle_server_fns_with_context(
+///                     path, headers, query, provider.clone(), req,
+///                 )
+///             }
+///         }),
+///     )
+///     .leptos_routes_with_context(options, routes, provider, |cx| {
+///         view! { cx, <App/> }
+///     });
+/// ```
+#[derive(Clone, Default)]
+pub struct RequestContext {
+    providers: Vec<Provider>,
+}
+
+impl std::fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+impl RequestContext {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provides a clone of `value` for every request, e.g., a database pool.
+    pub fn value<T>(mut self, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.providers
+            .push(Arc::new(move |cx| provide_context(cx, value.clone())));
+        self
+    }
+
+    /// Provides the value returned by `f`, which is called again for every
+    /// request, e.g., to find the user or the locale of the request.
+    pub fn with<T>(
+        mut self,
+        f: impl Fn(Scope) -> T + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Clone + 'static,
+    {
+        self.providers
+            .push(Arc::new(move |cx| provide_context(cx, f(cx))));
+        self
+    }
+
+    /// Returns a function to pass as the `additional_context` of an
+    /// integration.
+    pub fn provider(&self) -> impl Fn(Scope) + Clone + Send + Sync + 'static {
+        let context = self.clone();
+        move |cx| provide_context(cx, context.clone())
+    }
+}
+
+/// Provides the values of the [RequestContext] that was passed to the
+/// integration, if there is one. The integrations call this once they have
+/// provided their own context for a request.
+pub fn provide_request_context(cx: Scope) {
+    if let Some(context) = use_context::<RequestContext>(cx) {
+        for provider in &context.providers {
+            provider(cx);
+        }
+    }
+}
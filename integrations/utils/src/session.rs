@@ -0,0 +1,460 @@
+//! Cookie-backed sessions, shared by server-side rendering and server
+//! functions.
+//!
+//! Call [set_session_config] in your `main` function to turn sessions on.
+//! Each request then gets a [Session] in its context, whether it renders a
+//! page or calls a server function, and the browser is identified by a
+//! session cookie.
+//!
+//! ```rust,ignore
+//! // in main
+//! set_session_config(SessionConfig::new(MemoryStore::new()));
+//!
+//! #[server(Login, "/api")]
+//! async fn login(cx: Scope, user: String) -> Result<(), ServerFnError> {
+//!     let session = use_context::<Session>(cx).expect("sessions are on");
+//!     // a new ID after logging in prevents session fixation
+//!     session.renew().await?;
+//!     session.insert("user", &user).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Changes are written to the store straight away. The session cookie is set
+//! whenever the session is saved, so that it expires along with the session,
+//! and when it is destroyed. This must happen before the response headers are
+//! sent: in a server function, or before the first chunk of a streamed page.
+
+use futures::Future;
+use leptos::{provide_context, Scope, ServerFnError};
+use once_cell::sync::OnceCell;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// The values stored in a session, encoded as JSON.
+pub type SessionData = HashMap<String, String>;
+
+/// The future returned by the methods of a [SessionStore].
+pub type SessionFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, SessionError>> + Send + 'a>>;
+
+/// Errors that can occur when reading or writing a [Session].
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum SessionError {
+    /// The [SessionStore] failed.
+    #[error("session store error: {0}")]
+    Store(String),
+    /// A value could not be encoded or decoded.
+    #[error("session serialization error: {0}")]
+    Serialization(String),
+}
+
+impl From<SessionError> for ServerFnError {
+    fn from(e: SessionError) -> Self {
+        ServerFnError::ServerError(e.to_string())
+    }
+}
+
+/// Somewhere to keep the data of each session, keyed by session ID.
+pub trait SessionStore: Send + Sync + 'static {
+    /// Loads the session with the given ID, or `None` if there is no such
+    /// session or it has expired.
+    fn load<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> SessionFuture<'a, Option<SessionData>>;
+
+    /// Saves the session with the given ID, which expires after `ttl`.
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        data: &'a SessionData,
+        ttl: Duration,
+    ) -> SessionFuture<'a, ()>;
+
+    /// Deletes the session with the given ID.
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()>;
+}
+
+/// A [SessionStore] that keeps sessions in memory. Sessions are lost when
+/// the server restarts, and aren't shared between servers.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore(Arc<Mutex<HashMap<String, (SessionData, Instant)>>>);
+
+impl MemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for MemoryStore {
+    fn load<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> SessionFuture<'a, Option<SessionData>> {
+        let mut sessions = lock(&self.0);
+        let data = match sessions.get(id) {
+            Some((data, expires)) if *expires > Instant::now() => {
+                Some(data.clone())
+            }
+            Some(_) => {
+                sessions.remove(id);
+                None
+            }
+            None => None,
+        };
+        Box::pin(async move { Ok(data) })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        data: &'a SessionData,
+        ttl: Duration,
+    ) -> SessionFuture<'a, ()> {
+        let now = Instant::now();
+        let mut sessions = lock(&self.0);
+        sessions.retain(|_, (_, expires)| *expires > now);
+        sessions.insert(id.to_string(), (data.clone(), now + ttl));
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()> {
+        lock(&self.0).remove(id);
+        Box::pin(async move { Ok(()) })
+    }
+}
+
+/// A [SessionStore] that keeps sessions in [Redis](https://redis.io/), so
+/// that they survive restarts and are shared between servers.
+#[cfg(feature = "redis")]
+#[derive(Clone, Debug)]
+pub struct RedisStore {
+    client: redis::Client,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisStore {
+    /// Creates a store that uses the given client. Sessions are stored
+    /// under keys starting with `session:`.
+    pub fn new(client: redis::Client) -> Self {
+        Self {
+            client,
+            prefix: "session:".to_string(),
+        }
+    }
+
+    /// Sets the prefix of the keys that sessions are stored under.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    async fn connection(&self) -> Result<redis::aio::Connection, SessionError> {
+        self.client
+            .get_async_connection()
+            .await
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+}
+
+#[cfg(feature = "redis")]
+impl SessionStore for RedisStore {
+    fn load<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> SessionFuture<'a, Option<SessionData>> {
+        use redis::AsyncCommands;
+
+        Box::pin(async move {
+            let mut connection = self.connection().await?;
+            let json: Option<String> = connection
+                .get(format!("{}{id}", self.prefix))
+                .await
+                .map_err(|e| SessionError::Store(e.to_string()))?;
+            json.map(|json| serde_json::from_str(&json))
+                .transpose()
+                .map_err(|e| SessionError::Serialization(e.to_string()))
+        })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        data: &'a SessionData,
+        ttl: Duration,
+    ) -> SessionFuture<'a, ()> {
+        use redis::AsyncCommands;
+
+        Box::pin(async move {
+            let json = serde_json::to_string(data)
+                .map_err(|e| SessionError::Serialization(e.to_string()))?;
+            let mut connection = self.connection().await?;
+            connection
+                .set_ex(
+                    format!("{}{id}", self.prefix),
+                    json,
+                    ttl.as_secs() as usize,
+                )
+                .await
+                .map_err(|e| SessionError::Store(e.to_string()))
+        })
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()> {
+        use redis::AsyncCommands;
+
+        Box::pin(async move {
+            let mut connection = self.connection().await?;
+            connection
+                .del(format!("{}{id}", self.prefix))
+                .await
+                .map_err(|e| SessionError::Store(e.to_string()))
+        })
+    }
+}
+
+/// How sessions are stored and identified.
+#[derive(Clone)]
+pub struct SessionConfig {
+    store: Arc<dyn SessionStore>,
+    cookie_name: String,
+    ttl: Duration,
+    secure: bool,
+}
+
+impl SessionConfig {
+    /// Keeps sessions in the given store, for 7 days after they were last
+    /// changed, identified by the `leptos_session` cookie.
+    pub fn new(store: impl SessionStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            cookie_name: "leptos_session".to_string(),
+            ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            secure: false,
+        }
+    }
+
+    /// Sets the name of the session cookie.
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Sets how long a session is kept after it was last changed.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets whether the session cookie is only sent over HTTPS. This should
+    /// be `true` in production.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    fn cookie(&self, id: &str, max_age: Duration) -> String {
+        let secure = if self.secure { "; Secure" } else { "" };
+        format!(
+            "{}={id}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{secure}",
+            self.cookie_name,
+            max_age.as_secs()
+        )
+    }
+}
+
+static SESSION_CONFIG: OnceCell<SessionConfig> = OnceCell::new();
+
+/// Turns sessions on. Call this once in your `main` function, before the
+/// server starts; later calls are ignored.
+pub fn set_session_config(config: SessionConfig) {
+    if SESSION_CONFIG.set(config).is_err() {
+        tracing::warn!("the session config can only be set once");
+    }
+}
+
+/// Provides a [Session] for the current request as context, if sessions are
+/// turned on. The integrations call this for every request; `set_cookie` is
+/// called with the value of a `Set-Cookie` header to add to the response.
+pub fn provide_session(
+    cx: Scope,
+    cookies: Option<&str>,
+    set_cookie: impl Fn(String) + Send + Sync + 'static,
+) {
+    let Some(config) = SESSION_CONFIG.get() else {
+        return;
+    };
+    let id = cookies
+        .and_then(|cookies| {
+            cookies.split(';').find_map(|cookie| {
+                let (name, value) = cookie.trim().split_once('=')?;
+                (name == config.cookie_name).then_some(value)
+            })
+        })
+        .filter(|id| {
+            id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+        .map(String::from);
+    provide_context(
+        cx,
+        Session {
+            config: config.clone(),
+            state: Arc::new(Mutex::new(SessionState { id, data: None })),
+            set_cookie: Arc::new(set_cookie),
+        },
+    );
+}
+
+struct SessionState {
+    id: Option<String>,
+    // `None` until it is loaded from the store
+    data: Option<SessionData>,
+}
+
+/// The session of the browser that made the current request. Get it with
+/// `use_context::<Session>(cx)` in a component or server function, once
+/// sessions are turned on with [set_session_config].
+///
+/// Values are encoded as JSON. The session is loaded from the store when it
+/// is first read, and is only created when a value is first inserted. If the
+/// browser sends the ID of a session that doesn't
+/// exist, for example because it has expired, the new session gets a new
+/// ID rather than that one.
+#[derive(Clone)]
+pub struct Session {
+    config: SessionConfig,
+    state: Arc<Mutex<SessionState>>,
+    set_cookie: Arc<dyn Fn(String) + Send + Sync>,
+}
+
+impl std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session").field("id", &self.id()).finish()
+    }
+}
+
+impl Session {
+    /// The ID of the session, or `None` if there is no session yet.
+    pub fn id(&self) -> Option<String> {
+        lock(&self.state).id.clone()
+    }
+
+    async fn data(&self) -> Result<SessionData, SessionError> {
+        let id = {
+            let state = lock(&self.state);
+            if let Some(data) = &state.data {
+                return Ok(data.clone());
+            }
+            state.id.clone()
+        };
+        let data = match id {
+            Some(id) => match self.config.store.load(&id).await? {
+                Some(data) => data,
+                // an ID that isn't in the store may have been chosen by
+                // someone else, so a new one is created when it is saved
+                None => {
+                    let mut state = lock(&self.state);
+                    if state.id.as_ref() == Some(&id) {
+                        state.id = None;
+                    }
+                    SessionData::new()
+                }
+            },
+            None => SessionData::new(),
+        };
+        lock(&self.state).data = Some(data.clone());
+        Ok(data)
+    }
+
+    async fn save(&self, data: SessionData) -> Result<(), SessionError> {
+        let id = {
+            let mut state = lock(&self.state);
+            state.data = Some(data.clone());
+            state.id.get_or_insert_with(new_id).clone()
+        };
+        // the cookie is set again every time, so that it lasts as long as the
+        // session does now that its TTL has started again
+        (self.set_cookie)(self.config.cookie(&id, self.config.ttl));
+        self.config.store.save(&id, &data, self.config.ttl).await
+    }
+
+    /// Returns the value with the given key, or `None` if it is not set.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, SessionError> {
+        self.data()
+            .await?
+            .get(key)
+            .map(|json| serde_json::from_str(json))
+            .transpose()
+            .map_err(|e| SessionError::Serialization(e.to_string()))
+    }
+
+    /// Sets the value with the given key, creating the session if there
+    /// isn't one yet.
+    pub async fn insert<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), SessionError> {
+        let json = serde_json::to_string(value)
+            .map_err(|e| SessionError::Serialization(e.to_string()))?;
+        let mut data = self.data().await?;
+        data.insert(key.to_string(), json);
+        self.save(data).await
+    }
+
+    /// Removes the value with the given key.
+    pub async fn remove(&self, key: &str) -> Result<(), SessionError> {
+        let mut data = self.data().await?;
+        if data.remove(key).is_some() {
+            self.save(data).await?;
+        }
+        Ok(())
+    }
+
+    /// Moves the session to a new ID, keeping its values. Do this when a user
+    /// logs in, so that an ID set by someone else before then is useless.
+    pub async fn renew(&self) -> Result<(), SessionError> {
+        let data = self.data().await?;
+        let old_id = lock(&self.state).id.take();
+        if let Some(old_id) = old_id {
+            self.config.store.delete(&old_id).await?;
+        }
+        self.save(data).await
+    }
+
+    /// Deletes the session and its values, and removes the session cookie.
+    /// Do this when a user logs out.
+    pub async fn destroy(&self) -> Result<(), SessionError> {
+        let old_id = {
+            let mut state = lock(&self.state);
+            state.data = Some(SessionData::new());
+            state.id.take()
+        };
+        if let Some(old_id) = old_id {
+            (self.set_cookie)(self.config.cookie("", Duration::ZERO));
+            self.config.store.delete(&old_id).await?;
+        }
+        Ok(())
+    }
+}
+
+// a panic elsewhere can't leave the sessions half-updated, since each change
+// is a single insert or removal, so a poisoned lock is still used
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
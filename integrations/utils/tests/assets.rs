@@ -0,0 +1,84 @@
+use leptos::{create_runtime, run_scope, use_context, view, IntoView};
+use leptos_config::LeptosOptions;
+use leptos_integration_utils::{
+    html_parts_separated, precache_manifest, write_precache_manifest,
+};
+use leptos_meta::{provide_meta_context, MetaContext, Stylesheet};
+use std::path::PathBuf;
+
+fn site_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir()
+        .join(format!("leptos-assets-{}-{name}", std::process::id()));
+    _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+fn options(root: &PathBuf) -> LeptosOptions {
+    LeptosOptions::builder()
+        .output_name("app")
+        .site_root(root.to_string_lossy())
+        .hash_files(true)
+        .build()
+}
+
+#[test]
+fn hashes_are_read_for_each_hash_file() {
+    let first = site_root("first");
+    std::fs::write(first.join("hash.txt"), "css: aaa\n").unwrap();
+    let second = site_root("second");
+    std::fs::write(second.join("hash.txt"), "css: bbb\n").unwrap();
+
+    let (head, _) = html_parts_separated(&options(&first), None);
+    assert!(head.contains(r#"href="/pkg/app.aaa.css""#));
+    let (head, _) = html_parts_separated(&options(&second), None);
+    assert!(head.contains(r#"href="/pkg/app.bbb.css""#));
+}
+
+#[test]
+fn missing_hash_file_is_read_again() {
+    let root = site_root("missing");
+    let (head, _) = html_parts_separated(&options(&root), None);
+    assert!(!head.contains("app.ccc.css"));
+
+    std::fs::write(root.join("hash.txt"), "css: ccc\n").unwrap();
+    let (head, _) = html_parts_separated(&options(&root), None);
+    assert!(head.contains(r#"href="/pkg/app.ccc.css""#));
+}
+
+#[test]
+fn stylesheet_of_app_is_hashed_once() {
+    let root = site_root("stylesheet");
+    std::fs::write(root.join("hash.txt"), "css: ddd\n").unwrap();
+
+    run_scope(create_runtime(), move |cx| {
+        provide_meta_context(cx);
+        _ = view! { cx, <Stylesheet id="leptos" href="/pkg/app.css"/> }
+            .into_view(cx);
+        let meta = use_context::<MetaContext>(cx);
+
+        let (head, _) = html_parts_separated(&options(&root), meta.as_ref());
+        assert_eq!(head.matches("rel=\"stylesheet\"").count(), 1);
+        assert!(head.contains(r#"href="/pkg/app.ddd.css""#));
+        assert!(!head.contains(r#"href="/pkg/app.css""#));
+    });
+}
+
+#[test]
+fn precache_manifest_is_written_as_json() {
+    let root = site_root("manifest");
+    std::fs::write(root.join("hash.txt"), "js: eee\nwasm: fff\n").unwrap();
+    // Rust's debug escaping of the control character is not valid JSON
+    let options = LeptosOptions::builder()
+        .output_name("app\u{1b}")
+        .site_root(root.to_string_lossy())
+        .hash_files(true)
+        .build();
+
+    let path = write_precache_manifest(&options).unwrap();
+    assert_eq!(path, root.join("pkg").join("precache-manifest.json"));
+    let urls: Vec<String> =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(urls, precache_manifest(&options));
+    assert!(urls[0].ends_with(".eee.js"));
+}
@@ -0,0 +1,61 @@
+use leptos::{
+    create_runtime, provide_context, run_scope,
+    server_fn::{csrf::CsrfCheck, Encoding},
+    use_context, use_csrf_policy, CsrfPolicy, CsrfToken,
+};
+use leptos_integration_utils::{provide_csrf_token, set_csrf_cookie_secure};
+
+#[test]
+fn token_is_issued_when_policy_is_provided_as_context() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, CsrfPolicy::DoubleSubmitCookie);
+
+        let set_cookie = provide_csrf_token(cx, None).unwrap();
+        let token = use_context::<CsrfToken>(cx).unwrap();
+        assert!(set_cookie.starts_with(&format!("leptos_csrf={}", token.0)));
+
+        // the same policy checks the requests that send the token back
+        let cookies = format!("leptos_csrf={}", token.0);
+        assert!(use_csrf_policy(cx).allows(&CsrfCheck {
+            encoding: &Encoding::Cbor,
+            cookies: Some(&cookies),
+            header: Some(&token.0),
+            body: b"",
+        }));
+    });
+}
+
+#[test]
+fn existing_token_is_kept() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, CsrfPolicy::DoubleSubmitCookie);
+
+        let set_cookie = provide_csrf_token(cx, Some("leptos_csrf=0a1b2c"));
+        assert_eq!(set_cookie, None);
+        assert_eq!(
+            use_context::<CsrfToken>(cx),
+            Some(CsrfToken("0a1b2c".to_string()))
+        );
+    });
+}
+
+#[test]
+fn no_token_without_policy() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, CsrfPolicy::Disabled);
+
+        assert_eq!(provide_csrf_token(cx, None), None);
+        assert_eq!(use_context::<CsrfToken>(cx), None);
+    });
+}
+
+#[test]
+fn cookie_can_be_limited_to_https() {
+    set_csrf_cookie_secure(true);
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, CsrfPolicy::DoubleSubmitCookie);
+
+        let set_cookie = provide_csrf_token(cx, None).unwrap();
+        assert!(set_cookie.ends_with("; SameSite=Lax; Secure"));
+    });
+}
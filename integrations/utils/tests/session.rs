@@ -0,0 +1,150 @@
+use futures::executor::block_on;
+use leptos::{create_runtime, run_scope, use_context};
+use leptos_integration_utils::session::{
+    provide_session, set_session_config, MemoryStore, Session, SessionConfig,
+    SessionStore,
+};
+use std::{
+    sync::{Arc, Mutex, Once},
+    thread,
+    time::Duration,
+};
+
+static CONFIG: Once = Once::new();
+
+/// Runs `f` with the session of a request with the given cookies, returning
+/// what it returns and the `Set-Cookie` headers it caused.
+fn with_session<T>(
+    cookies: Option<&str>,
+    f: impl FnOnce(Session) -> T,
+) -> (T, Vec<String>) {
+    CONFIG.call_once(|| {
+        set_session_config(
+            SessionConfig::new(MemoryStore::new()).ttl(Duration::from_secs(60)),
+        )
+    });
+    let set_cookies = Arc::new(Mutex::new(Vec::new()));
+    let runtime = create_runtime();
+    let session = run_scope(runtime, {
+        let cookies = cookies.map(String::from);
+        let set_cookies = Arc::clone(&set_cookies);
+        move |cx| {
+            provide_session(cx, cookies.as_deref(), move |cookie| {
+                set_cookies.lock().unwrap().push(cookie)
+            });
+            use_context::<Session>(cx).expect("sessions are on")
+        }
+    });
+    let value = f(session);
+    runtime.dispose();
+    let set_cookies = set_cookies.lock().unwrap().clone();
+    (value, set_cookies)
+}
+
+fn session_cookie(id: &str) -> String {
+    format!("leptos_session={id}")
+}
+
+#[test]
+fn session_is_created_on_insert() {
+    let (id, set_cookies) = with_session(None, |session| {
+        assert_eq!(session.id(), None);
+        block_on(session.insert("user", &"ada")).unwrap();
+        session.id().unwrap()
+    });
+    assert_eq!(set_cookies.len(), 1);
+    assert!(set_cookies[0].starts_with(&session_cookie(&id)));
+    assert!(set_cookies[0].contains("Max-Age=60"));
+
+    let (user, _) = with_session(Some(&session_cookie(&id)), |session| {
+        block_on(session.get::<String>("user")).unwrap()
+    });
+    assert_eq!(user.as_deref(), Some("ada"));
+}
+
+#[test]
+fn cookie_is_refreshed_when_session_is_saved() {
+    let (id, _) = with_session(None, |session| {
+        block_on(session.insert("count", &1)).unwrap();
+        session.id().unwrap()
+    });
+
+    let (_, set_cookies) =
+        with_session(Some(&session_cookie(&id)), |session| {
+            block_on(session.insert("count", &2)).unwrap();
+        });
+    assert_eq!(set_cookies.len(), 1);
+    assert!(set_cookies[0].starts_with(&session_cookie(&id)));
+    assert!(set_cookies[0].contains("Max-Age=60"));
+
+    // reading doesn't change the session, so the cookie is left alone
+    let (count, set_cookies) =
+        with_session(Some(&session_cookie(&id)), |session| {
+            block_on(session.get::<u32>("count")).unwrap()
+        });
+    assert_eq!(count, Some(2));
+    assert!(set_cookies.is_empty());
+}
+
+#[test]
+fn unknown_id_is_replaced() {
+    let unknown = "0123456789abcdef0123456789abcdef";
+    let (id, _) = with_session(Some(&session_cookie(unknown)), |session| {
+        assert_eq!(block_on(session.get::<String>("user")).unwrap(), None);
+        block_on(session.insert("user", &"ada")).unwrap();
+        session.id().unwrap()
+    });
+    assert_ne!(id, unknown);
+}
+
+#[test]
+fn renew_moves_values_to_new_id() {
+    let (old_id, _) = with_session(None, |session| {
+        block_on(session.insert("user", &"ada")).unwrap();
+        session.id().unwrap()
+    });
+
+    let (new_id, _) = with_session(Some(&session_cookie(&old_id)), |session| {
+        block_on(session.renew()).unwrap();
+        session.id().unwrap()
+    });
+    assert_ne!(new_id, old_id);
+
+    let (old_user, _) =
+        with_session(Some(&session_cookie(&old_id)), |session| {
+            block_on(session.get::<String>("user")).unwrap()
+        });
+    assert_eq!(old_user, None);
+    let (new_user, _) =
+        with_session(Some(&session_cookie(&new_id)), |session| {
+            block_on(session.get::<String>("user")).unwrap()
+        });
+    assert_eq!(new_user.as_deref(), Some("ada"));
+}
+
+#[test]
+fn destroy_removes_cookie() {
+    let (id, _) = with_session(None, |session| {
+        block_on(session.insert("user", &"ada")).unwrap();
+        session.id().unwrap()
+    });
+
+    let (_, set_cookies) =
+        with_session(Some(&session_cookie(&id)), |session| {
+            block_on(session.destroy()).unwrap();
+            assert_eq!(session.id(), None);
+        });
+    assert_eq!(set_cookies.len(), 1);
+    assert!(set_cookies[0].starts_with("leptos_session=;"));
+    assert!(set_cookies[0].contains("Max-Age=0"));
+}
+
+#[test]
+fn memory_store_expires_sessions() {
+    let store = MemoryStore::new();
+    let data = [("user".to_string(), "\"ada\"".to_string())].into();
+    block_on(store.save("a", &data, Duration::from_millis(20))).unwrap();
+    assert_eq!(block_on(store.load("a")).unwrap(), Some(data));
+    thread::sleep(Duration::from_millis(30));
+    assert_eq!(block_on(store.load("a")).unwrap(), None);
+}
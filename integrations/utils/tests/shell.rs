@@ -0,0 +1,124 @@
+use futures::Stream;
+use leptos::*;
+use leptos_config::LeptosOptions;
+use leptos_integration_utils::html_parts_with_shell;
+use leptos_meta::*;
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+type HtmlStream = Pin<Box<dyn Stream<Item = String>>>;
+
+// stands in for the `ResponseOptions` provided by an integration
+#[derive(Clone, Default)]
+struct Status(Rc<Cell<Option<u16>>>);
+
+#[component]
+fn Post(cx: Scope) -> impl IntoView {
+    provide_meta_context(cx);
+    let status = use_context::<Status>(cx).unwrap();
+    let post =
+        create_blocking_resource(cx, || (), |_| async { None::<String> });
+    let comments = create_resource(
+        cx,
+        || (),
+        |_| async {
+            let (tx, rx) = futures::channel::oneshot::channel();
+            let mut tx = Some(tx);
+            TestExecutor::set_timer(
+                Box::new(move || {
+                    if let Some(tx) = tx.take() {
+                        _ = tx.send(());
+                    }
+                }),
+                Duration::from_secs(10),
+                None,
+            );
+            _ = rx.await;
+            "first comment".to_string()
+        },
+    );
+
+    view! { cx,
+        <Suspense fallback=|| "loading post">
+            {move || post.read(cx).map(|post| {
+                let title = post.unwrap_or_else(|| {
+                    status.0.set(Some(404));
+                    "Post not found".to_string()
+                });
+                view! { cx,
+                    <Title text=title.clone()/>
+                    <h1>{title}</h1>
+                }
+            })}
+        </Suspense>
+        <Suspense fallback=|| "loading comments">
+            {move || comments.read(cx)}
+        </Suspense>
+    }
+}
+
+// Renders the app and reads the parts of the document from the stream as an
+// integration does, and returns them with the status that was set by then.
+fn first_parts(in_order: bool) -> (String, String, Option<u16>) {
+    let executor = TestExecutor::new();
+    let status = Status::default();
+    let view = |cx: Scope| view! { cx, <Post/> }.into_view(cx);
+    let prefix = |cx: Scope| Cow::from(generate_head_metadata_separated(cx).1);
+    let context = {
+        let status = status.clone();
+        move |cx: Scope| provide_context(cx, status)
+    };
+    let (stream, runtime, scope): (HtmlStream, _, _) = if in_order {
+        let (stream, runtime, scope) =
+            ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+                view, prefix, context,
+            );
+        (Box::pin(stream), runtime, scope)
+    } else {
+        let (stream, runtime, scope) =
+            ssr::render_to_stream_with_prefix_undisposed_with_context(
+                view, prefix, context,
+            );
+        (Box::pin(stream), runtime, scope)
+    };
+
+    let parts = Rc::new(RefCell::new(None));
+    spawn_local({
+        let parts = Rc::clone(&parts);
+        async move {
+            let options = LeptosOptions::builder().output_name("app").build();
+            let cx = Scope { runtime, id: scope };
+            let mut stream = stream;
+            let (head, shell, _) =
+                html_parts_with_shell(&options, &mut stream, cx).await;
+            *parts.borrow_mut() = Some((head, shell, status.0.get()));
+        }
+    });
+    executor.run_until_stalled();
+    runtime.dispose();
+
+    parts.take().expect("the shell to be rendered")
+}
+
+#[test]
+fn blocking_resources_set_head_and_status_before_the_shell_is_sent() {
+    let (head, shell, status) = first_parts(false);
+    assert!(head.contains("<title>Post not found</title>"));
+    assert_eq!(status, Some(404));
+    assert!(shell.contains("Post not found</h1>"));
+    // other resources keep streaming
+    assert!(shell.contains("loading comments"));
+    assert!(!shell.contains("first comment"));
+}
+
+#[test]
+fn blocking_resources_set_head_and_status_before_in_order_shell_is_sent() {
+    let (head, _, status) = first_parts(true);
+    assert!(head.contains("<title>Post not found</title>"));
+    assert_eq!(status, Some(404));
+}
@@ -11,14 +11,24 @@ use futures::{
     Future, SinkExt, Stream, StreamExt,
 };
 use http::{header, method::Method, uri::Uri, version::Version, StatusCode};
-use hyper::body;
+use hyper::body::{self, HttpBody};
 use leptos::{
-    leptos_server::{server_fn_by_path, Payload},
+    leptos_server::{
+        csrf_error, server_fn_by_path, server_fn_limits, use_csrf_policy,
+        AuthRequest, CsrfCheck, Payload, ServerFnLimits, CSRF_HEADER,
+    },
     server_fn::Encoding,
     ssr::*,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
+use leptos_integration_utils::{
+    build_async_response, check_server_fn_request, html_parts_with_shell,
+    provide_csrf_token, provide_request_context, request_span, run_server_fn,
+    server_fn_not_found,
+};
+pub use leptos_integration_utils::{
+    session, write_precache_manifest, RequestContext,
+};
 use leptos_meta::{generate_head_metadata_separated, MetaContext};
 use leptos_router::*;
 use parking_lot::RwLock;
@@ -180,16 +190,51 @@ pub async fn handle_server_fns_with_context(
     handle_server_fns_inner(req, additional_context).await
 }
 
+/// The headers of a request that can be read as text, with lowercase names.
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
+
+/// Reads the body of a request to a server function, failing without reading
+/// any further once it is longer than the [ServerFnLimits] allow.
+async fn read_body(
+    mut body: Body,
+    headers: &HeaderMap,
+    limits: &ServerFnLimits,
+) -> Result<Bytes, ServerFnError> {
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        limits.check_body_size(content_length)?;
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| ServerFnError::Request(e.to_string()))?;
+        limits.check_body_size(bytes.len() + chunk.len())?;
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.into())
+}
+
 async fn handle_server_fns_inner(
     req: Request,
     additional_context: impl Fn(leptos::Scope) + Clone + Send + 'static,
 ) -> Result<Response> {
     let fn_name = req.params::<String>()?;
     let headers = req.headers().clone();
+    let client_ip = req.remote_addr().map(|addr| addr.ip().to_string());
     let query = req.query_string().unwrap_or("").to_owned().into();
     let (tx, rx) = futures::channel::oneshot::channel();
+    let span = tracing::info_span!("server fn", name = %fn_name);
     spawn_blocking({
         move || {
+            let _span = span.entered();
             tokio::runtime::Runtime::new()
                 .expect("couldn't spawn runtime")
                 .block_on({
@@ -197,17 +242,71 @@ async fn handle_server_fns_inner(
                         let res = if let Some(server_fn) =
                             server_fn_by_path(fn_name.as_str())
                         {
+                            // the rate limit and the authorization hook are
+                            // checked before the body is read, so that a
+                            // request they reject can't make the server read a
+                            // large body
+                            let limits = server_fn_limits();
+                            let (parts, body) = req.into_parts();
+                            let checked = async {
+                                check_server_fn_request(
+                                    &limits,
+                                    client_ip.as_deref(),
+                                    AuthRequest {
+                                        fn_name: fn_name.clone(),
+                                        encoding: server_fn.encoding.clone(),
+                                        method: parts.method.to_string(),
+                                        uri: parts.uri.to_string(),
+                                        headers: header_pairs(&headers),
+                                    },
+                                )
+                                .await?;
+                                read_body(body, &headers, &limits).await
+                            }
+                            .await;
+                            let (body, checked) = match checked {
+                                Ok(body) => (body, Ok(())),
+                                Err(e) => (Bytes::new(), Err(e)),
+                            };
+
                             let runtime = create_runtime();
                             let (cx, disposer) =
                                 raw_scope_and_disposer(runtime);
 
                             additional_context(cx);
 
-                            let req_parts = generate_request_parts(req).await;
+                            let req_parts = RequestParts {
+                                method: parts.method,
+                                uri: parts.uri,
+                                headers: parts.headers,
+                                version: parts.version,
+                                body,
+                            };
                             // Add this so we can get details about the Request
                             provide_context(cx, req_parts.clone());
                             // Add this so that we can set headers and status of the response
-                            provide_context(cx, ResponseOptions::default());
+                            let res_options = ResponseOptions::default();
+                            provide_context(cx, res_options.clone());
+                            session::provide_session(
+                                cx,
+                                headers
+                                    .get(header::COOKIE)
+                                    .and_then(|value| value.to_str().ok()),
+                                {
+                                    let res_options = res_options.clone();
+                                    move |cookie| {
+                                        if let Ok(cookie) =
+                                            HeaderValue::from_str(&cookie)
+                                        {
+                                            res_options.append_header(
+                                                header::SET_COOKIE,
+                                                cookie,
+                                            );
+                                        }
+                                    }
+                                },
+                            );
+                            provide_request_context(cx);
 
                             let data = match &server_fn.encoding {
                                 Encoding::Url | Encoding::Cbor => {
@@ -216,9 +315,31 @@ async fn handle_server_fns_inner(
                                 Encoding::GetJSON | Encoding::GetCBOR => &query,
                             };
 
-                            let res = match (server_fn.trait_obj)(cx, data)
-                                .await
-                            {
+                            // a request that fails the CSRF check never reaches the
+                            // server function
+                            let policy = use_csrf_policy(cx);
+                            let allowed = policy.allows(&CsrfCheck {
+                                encoding: &server_fn.encoding,
+                                cookies: headers
+                                    .get(header::COOKIE)
+                                    .and_then(|value| value.to_str().ok()),
+                                header: headers
+                                    .get(CSRF_HEADER)
+                                    .and_then(|value| value.to_str().ok()),
+                                body: &req_parts.body,
+                            });
+                            let checked = checked.and_then(|()| {
+                                if allowed {
+                                    Ok(())
+                                } else {
+                                    Err(csrf_error())
+                                }
+                            });
+                            let result = run_server_fn(
+                                cx, &server_fn, data, &limits, checked,
+                            )
+                            .await;
+                            let res = match result {
                                 Ok(serialized) => {
                                     // If ResponseOptions are set, add the headers and status to the request
                                     let res_options =
@@ -246,6 +367,8 @@ async fn handle_server_fns_inner(
                                         header_ref.extend(res_headers.drain());
                                     };
 
+                                    // a file is always sent, so that it can be
+                                    // downloaded by following a link
                                     if accept_header == Some("application/json")
                                         || accept_header
                                             == Some(
@@ -254,6 +377,10 @@ async fn handle_server_fns_inner(
                                             )
                                         || accept_header
                                             == Some("application/cbor")
+                                        || matches!(
+                                            serialized,
+                                            Payload::File(_)
+                                        )
                                     {
                                         res = res.status(StatusCode::OK);
                                     }
@@ -295,10 +422,30 @@ async fn handle_server_fns_inner(
                                                 "application/json",
                                             )
                                             .body(Body::from(data)),
+                                        Payload::File(file) => {
+                                            let res = res
+                                                .header(
+                                                    header::CONTENT_TYPE,
+                                                    file.mime.as_str(),
+                                                )
+                                                .header(
+                                                    header::CONTENT_DISPOSITION,
+                                                    file.content_disposition(),
+                                                );
+                                            match file.body {
+                                                FileBody::Bytes(bytes) => {
+                                                    res.body(Body::from(bytes))
+                                                }
+                                                FileBody::Stream(stream) => res
+                                                    .body(Body::wrap_stream(
+                                                        stream,
+                                                    )),
+                                            }
+                                        }
                                     }
                                 }
                                 Err(e) => Response::builder()
-                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                    .status(e.status())
                                     .body(Body::from(
                                         serde_json::to_string(&e)
                                             .unwrap_or_else(|_| e.to_string()),
@@ -311,13 +458,7 @@ async fn handle_server_fns_inner(
                         } else {
                             Response::builder()
                                 .status(StatusCode::BAD_REQUEST)
-                                .body(Body::from(format!(
-                                    "Could not find a server function at the \
-                                     route {fn_name}. \n\nIt's likely that \
-                                     you need to call ServerFn::register() on \
-                                     the server function type, somewhere in \
-                                     your `main` function."
-                                )))
+                                .body(Body::from(server_fn_not_found(&fn_name)))
                         }
                         .expect("could not build Response");
 
@@ -568,10 +709,13 @@ where
 
                 let (tx, rx) = futures::channel::mpsc::channel(8);
 
+                let span =
+                    request_span(req.method().as_str(), req.uri().path());
                 spawn_blocking({
                     let app_fn = app_fn.clone();
                     let add_context = add_context.clone();
                     move || {
+                        let _span = span.entered();
                         tokio::runtime::Runtime::new()
                             .expect("couldn't spawn runtime")
                             .block_on({
@@ -649,11 +793,12 @@ async fn forward_stream(
     mut tx: Sender<String>,
 ) {
     let cx = Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>(cx).as_ref());
+    let mut shell = Box::pin(bundle);
+    let (head, first_chunk, tail) =
+        html_parts_with_shell(options, &mut shell, cx).await;
 
     _ = tx.send(head).await;
-    let mut shell = Box::pin(bundle);
+    _ = tx.send(first_chunk).await;
     while let Some(fragment) = shell.next().await {
         _ = tx.send(fragment).await;
     }
@@ -733,10 +878,13 @@ where
 
                 let (tx, rx) = futures::channel::mpsc::channel(8);
 
+                let span =
+                    request_span(req.method().as_str(), req.uri().path());
                 spawn_blocking({
                     let app_fn = app_fn.clone();
                     let add_context = add_context.clone();
                     move || {
+                        let _span = span.entered();
                         tokio::runtime::Runtime::new()
                             .expect("couldn't spawn runtime")
                             .block_on({
@@ -784,9 +932,42 @@ fn provide_contexts(
     let integration = ServerIntegration { path };
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
+    let cookies = req_parts
+        .headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok());
+    if let Some(set_cookie) = provide_csrf_token(cx, cookies)
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        default_res_options.append_header(header::SET_COOKIE, set_cookie);
+    }
+    session::provide_session(
+        cx,
+        req_parts
+            .headers
+            .get(header::COOKIE)
+            .and_then(|value| value.to_str().ok()),
+        {
+            let res_options = default_res_options.clone();
+            move |cookie| {
+                if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+                    res_options.append_header(header::SET_COOKIE, cookie);
+                }
+            }
+        },
+    );
     provide_context(cx, req_parts);
+    provide_shell_error_handler(cx, {
+        let res_options = default_res_options.clone();
+        move |_| {
+            if res_options.0.read().status.is_none() {
+                res_options.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    });
     provide_context(cx, default_res_options);
     provide_server_redirect(cx, move |path| redirect(cx, path));
+    provide_request_context(cx);
 }
 
 /// Returns a Viz [Handler](viz::Handler) that listens for a `GET` request and tries
@@ -918,10 +1099,13 @@ where
 
                 let (tx, rx) = futures::channel::oneshot::channel();
 
+                let span =
+                    request_span(req.method().as_str(), req.uri().path());
                 spawn_blocking({
                     let app_fn = app_fn.clone();
                     let add_context = add_context.clone();
                     move || {
+                        let _span = span.entered();
                         tokio::runtime::Runtime::new()
                             .expect("couldn't spawn runtime")
                             .block_on({
@@ -0,0 +1,183 @@
+use leptos::component;
+use leptos_dom::{
+    ev,
+    helpers::{request_animation_frame, set_timeout},
+    html, Fragment, IntoView, View,
+};
+use leptos_reactive::{
+    create_effect, create_memo, create_rw_signal, signal_prelude::*,
+    store_value, Scope, ScopeDisposer, StoredValue,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// Like [`Show`](crate::Show), but animates its children in and out with CSS
+/// transitions, in the same way as Vue’s `<Transition>` or Headless UI’s
+/// `<Transition>`.
+///
+/// The children are rendered inside a `<div>`. When `when` becomes `true`,
+/// the children are mounted with the `enter_from` class, which is replaced by
+/// `enter_to` on the next frame, so that the CSS transition between them runs.
+/// When `when` becomes `false`, the `leave_from` class is replaced by
+/// `leave_to` in the same way, and the children are only unmounted once the
+/// transition has ended.
+///
+/// The end of a transition is detected with the `transitionend` event. If the
+/// classes might not cause a transition (for example, if the user prefers
+/// reduced motion), set `duration_ms` so that the transition also ends after
+/// that time.
+///
+/// No animation runs for the initial value of `when`, so server-rendered HTML
+/// hydrates without flickering.
+///
+/// ```rust
+/// # use leptos_reactive::*;
+/// # use leptos_macro::*;
+/// # use leptos_dom::*; use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (open, set_open) = create_signal(cx, false);
+///
+/// view! { cx,
+///   <button on:click=move |_| set_open.update(|n| *n = !*n)>"Toggle"</button>
+///   <AnimatedShow
+///     when=move || open()
+///     enter_from="opacity-0"
+///     enter_to="opacity-100 transition-opacity"
+///     leave_from="opacity-100"
+///     leave_to="opacity-0 transition-opacity"
+///     duration_ms=300
+///   >
+///     <p>"Hello!"</p>
+///   </AnimatedShow>
+/// }
+/// # });
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn AnimatedShow<W>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The components AnimatedShow wraps
+    children: Box<dyn Fn(Scope) -> Fragment>,
+    /// A closure that returns whether the children should be shown
+    when: W,
+    /// The class the children are mounted with when they start entering
+    #[prop(optional, into)]
+    enter_from: String,
+    /// The class that replaces `enter_from` on the next frame
+    #[prop(optional, into)]
+    enter_to: String,
+    /// The class set when the children start leaving
+    #[prop(optional, into)]
+    leave_from: String,
+    /// The class that replaces `leave_from` on the next frame
+    #[prop(optional, into)]
+    leave_to: String,
+    /// Ends a transition after this many milliseconds, even if no
+    /// `transitionend` event has fired
+    #[prop(optional)]
+    duration_ms: Option<u64>,
+) -> impl IntoView
+where
+    W: Fn() -> bool + 'static,
+{
+    let when = create_memo(cx, move |_| when());
+    let mounted = create_rw_signal(cx, when.get_untracked());
+    let class = create_rw_signal(cx, String::new());
+    let phase = store_value(cx, Phase::Idle);
+    // increases every time `when` changes, so that the callbacks of an
+    // interrupted transition do nothing
+    let generation = store_value(cx, 0_usize);
+
+    let finish = move |for_generation: usize| {
+        if generation.get_value() != for_generation {
+            return;
+        }
+        match phase.get_value() {
+            Phase::Idle => {}
+            Phase::Entering => class.set(String::new()),
+            Phase::Leaving => {
+                class.set(String::new());
+                mounted.set(false);
+            }
+        }
+        phase.set_value(Phase::Idle);
+    };
+
+    create_effect(cx, move |prev: Option<bool>| {
+        let show = when.get();
+        if prev.is_none() {
+            return show;
+        }
+
+        generation.update_value(|n| *n += 1);
+        let current = generation.get_value();
+        let (from, to) = if show {
+            phase.set_value(Phase::Entering);
+            (enter_from.clone(), enter_to.clone())
+        } else {
+            phase.set_value(Phase::Leaving);
+            (leave_from.clone(), leave_to.clone())
+        };
+        class.set(from);
+        if !mounted.get_untracked() {
+            mounted.set(true);
+        }
+        next_frame(generation, current, move || class.set(to));
+
+        if let Some(ms) = duration_ms {
+            set_timeout(move || finish(current), Duration::from_millis(ms));
+        }
+        show
+    });
+
+    let prev_disposer = Rc::new(RefCell::new(None::<ScopeDisposer>));
+
+    move || {
+        if let Some(disposer) = prev_disposer.take() {
+            disposer.dispose();
+        }
+        if !mounted.get() {
+            return None::<View>;
+        }
+        let (view, disposer) = cx.run_child_scope(|cx| {
+            html::div(cx)
+                .attr("class", move || class.get())
+                .on(ev::transitionend, move |ev| {
+                    // ignore transitions of the children themselves
+                    if ev.target() == ev.current_target() {
+                        finish(generation.get_value());
+                    }
+                })
+                .child(children(cx))
+                .into_view(cx)
+        });
+        *prev_disposer.borrow_mut() = Some(disposer);
+        Some(view)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Phase {
+    Idle,
+    Entering,
+    Leaving,
+}
+
+/// Runs `f` once the browser has painted the current frame, so that a class
+/// set before it is applied before the one set by `f`.
+fn next_frame(
+    generation: StoredValue<usize>,
+    for_generation: usize,
+    f: impl FnOnce() + 'static,
+) {
+    request_animation_frame(move || {
+        request_animation_frame(move || {
+            if generation.get_value() == for_generation {
+                f();
+            }
+        })
+    });
+}
@@ -0,0 +1,121 @@
+use crate::AttributeValue;
+use leptos::component;
+use leptos_dom::{
+    create_node_ref, debug_warn,
+    helpers::{self, on_animation_frame},
+    html, window, CanvasContext, CanvasContextKind, IntoAttribute, IntoView,
+    NodeRef,
+};
+use leptos_reactive::{store_value, Scope};
+use std::{rc::Rc, time::Duration};
+
+type OnInit = Rc<dyn Fn(&CanvasContext)>;
+type OnFrame = Rc<dyn Fn(&CanvasContext, Duration)>;
+type OnResize = Rc<dyn Fn(&CanvasContext, f64, f64)>;
+
+/// A `<canvas>` element with a 2D or WebGL rendering context, and hooks that
+/// run the drawing code at the right times:
+/// - `on_init` once the canvas is mounted and its context has been created,
+///   e.g., to compile shaders.
+/// - `on_resize` with the size of the canvas in CSS pixels, once it is
+///   mounted and each time its size changes. The drawing buffer is resized
+///   to match before it is called, taking the pixel density of the screen
+///   into account.
+/// - `on_frame` on every animation frame, with the time since the previous
+///   frame.
+///
+/// Each hook receives the [CanvasContext]. They only run in the browser, and
+/// stop when the component is unmounted. To draw when a signal changes,
+/// rather than on every frame, use an effect with the `node_ref`.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use std::rc::Rc;
+/// # run_scope(create_runtime(), |cx| {
+/// let (x, set_x) = create_signal(cx, 0.0);
+///
+/// view! { cx,
+///   <Canvas
+///     class="full-width"
+///     on_frame=Rc::new(move |ctx: &CanvasContext, dt| {
+///         let Some(ctx) = ctx.as_2d() else { return };
+///         set_x.update(|x| *x = (*x + dt.as_secs_f64() * 50.0) % 300.0);
+///         ctx.clear_rect(0.0, 0.0, 300.0, 150.0);
+///         ctx.fill_rect(x.get_untracked(), 50.0, 20.0, 20.0);
+///     })
+///   />
+/// }
+/// # });
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn Canvas(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The kind of rendering context to create, 2D by default
+    #[prop(optional)]
+    context: CanvasContextKind,
+    /// Called once the canvas is mounted, with its rendering context
+    #[prop(optional)]
+    on_init: Option<OnInit>,
+    /// Called on every animation frame, with the time since the previous one
+    #[prop(optional)]
+    on_frame: Option<OnFrame>,
+    /// Called with the width and height of the canvas in CSS pixels, once it
+    /// is mounted and whenever its size changes
+    #[prop(optional)]
+    on_resize: Option<OnResize>,
+    /// A [`NodeRef`] in which the `<canvas>` element should be stored
+    #[prop(optional)]
+    node_ref: Option<NodeRef<html::Canvas>>,
+    /// Sets the `class` attribute of the `<canvas>`
+    #[prop(optional, into)]
+    class: Option<AttributeValue>,
+) -> impl IntoView {
+    let node_ref = node_ref.unwrap_or_else(|| create_node_ref(cx));
+    let rendering_context = store_value(cx, None::<CanvasContext>);
+
+    node_ref.on_load(cx, move |canvas| {
+        let Some(ctx) = CanvasContext::new(&canvas, context) else {
+            debug_warn!(
+                "<Canvas/> could not create a `{}` rendering context",
+                context.as_str()
+            );
+            return;
+        };
+        if let Some(on_init) = &on_init {
+            on_init(&ctx);
+        }
+        rendering_context.set_value(Some(ctx));
+    });
+
+    // the prop shadows the helper
+    helpers::on_resize(cx, node_ref, move |width, height| {
+        if let Some(canvas) = node_ref.get_untracked() {
+            let scale = window().device_pixel_ratio();
+            canvas.set_width((width * scale).round() as u32);
+            canvas.set_height((height * scale).round() as u32);
+        }
+        rendering_context.with_value(|ctx| {
+            if let (Some(ctx), Some(on_resize)) = (ctx, &on_resize) {
+                on_resize(ctx, width, height);
+            }
+        });
+    });
+
+    if let Some(on_frame) = on_frame {
+        on_animation_frame(cx, move |dt| {
+            rendering_context.with_value(|ctx| {
+                if let Some(ctx) = ctx {
+                    on_frame(ctx, dt);
+                }
+            });
+        });
+    }
+
+    let class = class.map(|class| class.into_attribute_boxed(cx));
+    html::canvas(cx).attr("class", class).node_ref(node_ref)
+}
@@ -2,8 +2,10 @@ use crate::Children;
 use leptos_dom::{Errors, IntoView};
 use leptos_macro::{component, view};
 use leptos_reactive::{
-    create_rw_signal, provide_context, signal_prelude::*, RwSignal, Scope,
+    create_rw_signal, provide_context, signal_prelude::*, use_context,
+    RwSignal, Scope,
 };
+use std::rc::Rc;
 
 /// When you render a `Result<_, _>` in your view, in the `Err` case it will
 /// render nothing, and search up through the view tree for an `<ErrorBoundary/>`.
@@ -41,8 +43,17 @@ where
     IV: IntoView,
 {
     let errors: RwSignal<Errors> = create_rw_signal(cx, Errors::default());
+    let fallback = Rc::new(fallback);
 
     provide_context(cx, errors);
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    provide_context(
+        cx,
+        ErrorBoundaryFallback(Rc::new({
+            let fallback = Rc::clone(&fallback);
+            move |cx, errors| fallback(cx, errors).into_view(cx)
+        })),
+    );
 
     // Run children so that they render and execute resources
     let children = children(cx).into_view(cx);
@@ -52,6 +63,12 @@ where
         if errors_empty.get() {
             children.clone().into_view(cx)
         } else {
+            // only provided on the server, where this runs once, as the
+            // initial shell is rendered
+            if let Some(handler) = use_context::<ShellErrorHandler>(cx) {
+                errors.with_untracked(|errors| (handler.f)(errors));
+            }
+
             view! { cx,
                 <>
                     {fallback(cx, errors)}
@@ -62,3 +79,67 @@ where
         }
     }
 }
+
+/// The fallback of the nearest [ErrorBoundary], so that a `<Suspense/>`
+/// streamed from the server can show it if its children return an error.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[derive(Clone)]
+pub(crate) struct ErrorBoundaryFallback(
+    pub(crate) Rc<dyn Fn(Scope, RwSignal<Errors>) -> leptos_dom::View>,
+);
+
+/// If the nearest [ErrorBoundary] has caught any errors, shows its fallback
+/// in place of `child`, which is kept hidden, just as the boundary does.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+pub(crate) fn with_error_fallback(
+    cx: Scope,
+    child: leptos_dom::View,
+) -> leptos_dom::View {
+    match (
+        use_context::<RwSignal<Errors>>(cx),
+        use_context::<ErrorBoundaryFallback>(cx),
+    ) {
+        (Some(errors), Some(fallback))
+            if !errors.with_untracked(Errors::is_empty) =>
+        {
+            view! { cx,
+                <>
+                    {(fallback.0)(cx, errors)}
+                    <leptos-error-boundary style="display: none">{child}</leptos-error-boundary>
+                </>
+            }
+            .into_view(cx)
+        }
+        _ => child,
+    }
+}
+
+/// Wrapping type for a function provided as context to be notified when an
+/// [ErrorBoundary] shows its fallback while the initial HTML shell is being
+/// rendered on the server. See [provide_shell_error_handler].
+#[derive(Clone)]
+pub struct ShellErrorHandler {
+    f: Rc<dyn Fn(&Errors)>,
+}
+
+impl std::fmt::Debug for ShellErrorHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShellErrorHandler").finish()
+    }
+}
+
+/// Provides a function that is called when an [ErrorBoundary] shows its
+/// fallback while the initial HTML shell is being rendered on the server.
+/// Server integrations use this to set a `500` status code, as the response
+/// has not been sent yet.
+pub fn provide_shell_error_handler(
+    cx: Scope,
+    handler: impl Fn(&Errors) + 'static,
+) {
+    provide_context(
+        cx,
+        ShellErrorHandler {
+            f: Rc::new(handler),
+        },
+    )
+}
@@ -0,0 +1,30 @@
+use leptos_dom::{FlushMarker, IntoView};
+use leptos_macro::component;
+use leptos_reactive::Scope;
+
+/// When rendering an in-order HTML stream, sends all the HTML rendered before
+/// this point down to the client right away, rather than buffering it until
+/// the next `<Suspense/>` or the end of the page.
+///
+/// This renders nothing, and has no effect in any other rendering mode. See
+/// [StreamOptions](crate::ssr::StreamOptions) for other ways to control how
+/// the stream is split into chunks.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///   <header>"My App"</header>
+///   // the header is sent before the rest of the page has been rendered
+///   <Flush/>
+///   <main>"..."</main>
+/// }
+/// # });
+/// ```
+#[component]
+pub fn Flush(
+    /// The scope the component is running in.
+    cx: Scope,
+) -> impl IntoView {
+    FlushMarker.into_view(cx)
+}
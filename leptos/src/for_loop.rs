@@ -1,7 +1,9 @@
 use leptos_dom::IntoView;
 use leptos_macro::component;
-use leptos_reactive::Scope;
-use std::hash::Hash;
+use leptos_reactive::{
+    create_rw_signal, on_cleanup, signal_prelude::*, RwSignal, Scope, Signal,
+};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
 
 /// Iterates over children and displays them, keyed by the `key` function given.
 ///
@@ -66,3 +68,222 @@ where
 {
     leptos_dom::Each::new(each, key, view).into_view(cx)
 }
+
+/// Iterates over children and displays them, keyed by the `key` function
+/// given, like [`For`], but passes each row a [`Signal`] of its item.
+///
+/// With [`For`], a row is only rendered once for each key: if an item in
+/// `each` changes but keeps its key, the row keeps showing the old value.
+/// With `ForEach`, the row's signal is updated instead, so only the parts of
+/// that row that read it are updated, and no other row is touched. This lets
+/// you keep plain data in the list, rather than a signal for every field.
+///
+/// ```
+/// # use leptos::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// struct Todo {
+///     id: usize,
+///     title: String,
+///     done: bool,
+/// }
+///
+/// #[component]
+/// fn Todos(cx: Scope) -> impl IntoView {
+///     let (todos, set_todos) = create_signal::<Vec<Todo>>(cx, vec![]);
+///
+///     let toggle = move |id: usize| {
+///         set_todos.update(|todos| {
+///             if let Some(todo) = todos.iter_mut().find(|todo| todo.id == id)
+///             {
+///                 todo.done = !todo.done;
+///             }
+///         })
+///     };
+///
+///     view! {
+///       cx,
+///       <ul>
+///         <ForEach
+///           each=todos
+///           key=|todo| todo.id
+///           // only the toggled row is updated
+///           view=move |cx, todo: Signal<Todo>| {
+///             view! {
+///               cx,
+///               <li on:click=move |_| toggle(todo.with(|todo| todo.id))>
+///                 {move || todo.with(|todo| todo.title.clone())}
+///                 {move || todo.with(|todo| todo.done).then_some(" ✓")}
+///               </li>
+///             }
+///           }
+///         />
+///       </ul>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component(transparent)]
+pub fn ForEach<IF, I, T, EF, N, KF, K>(
+    cx: Scope,
+    /// Items over which the component should iterate.
+    each: IF,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// The view that will be displayed for each item.
+    view: EF,
+) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(Scope, Signal<T>) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    keyed_rows(each, key, move |cx, _, item| view(cx, item)).into_view(cx)
+}
+
+/// Iterates over children and displays them, keyed by the `key` function
+/// given, like [`ForEach`], but also passes each row a [`Signal`] of its
+/// current position in `each`, counting from `0`.
+///
+/// When the items are reordered, the rows are moved rather than rendered
+/// again, and their index signals are updated, so row numbers and styles that
+/// alternate between rows stay correct.
+///
+/// ```
+/// # use leptos::*;
+///
+/// #[component]
+/// fn Leaderboard(cx: Scope) -> impl IntoView {
+///     let (players, set_players) = create_signal::<Vec<String>>(cx, vec![]);
+///
+///     view! {
+///       cx,
+///       <ol>
+///         <ForEnumerate
+///           each=players
+///           key=|name| name.clone()
+///           view=move |cx, index: Signal<usize>, name: Signal<String>| {
+///             view! {
+///               cx,
+///               <li class:odd=move || index.get() % 2 == 1>
+///                 {move || index.get() + 1} ". " {name}
+///               </li>
+///             }
+///           }
+///         />
+///       </ol>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component(transparent)]
+pub fn ForEnumerate<IF, I, T, EF, N, KF, K>(
+    cx: Scope,
+    /// Items over which the component should iterate.
+    each: IF,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// The view that will be displayed for each item, given its index.
+    view: EF,
+) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(Scope, Signal<usize>, Signal<T>) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    keyed_rows(each, key, view).into_view(cx)
+}
+
+/// The signals passed to one row of [`ForEach`] or [`ForEnumerate`].
+struct Row<T: 'static> {
+    index: RwSignal<usize>,
+    item: RwSignal<T>,
+}
+
+impl<T: 'static> Clone for Row<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Row<T> {}
+
+/// Keys the items like [`For`], creating a signal for the index and the item
+/// of each row, which are updated in place when they change.
+fn keyed_rows<IF, I, T, EF, N, KF, K>(
+    each: IF,
+    key: KF,
+    view: EF,
+) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(Scope, Signal<usize>, Signal<T>) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    let key = Rc::new(key);
+    let rows = Rc::new(RefCell::new(HashMap::<K, Row<T>>::new()));
+
+    let items = {
+        let key = Rc::clone(&key);
+        let rows = Rc::clone(&rows);
+        move || {
+            let items = each().into_iter().enumerate().collect::<Vec<_>>();
+            // update the rows that are already displayed in place
+            for (index, item) in &items {
+                let row = rows.borrow().get(&key(item)).copied();
+                if let Some(row) = row {
+                    if row.index.get_untracked() != *index {
+                        row.index.set(*index);
+                    }
+                    if row.item.with_untracked(|value| value != item) {
+                        row.item.set(item.clone());
+                    }
+                }
+            }
+            items
+        }
+    };
+
+    let row_key = Rc::clone(&key);
+    leptos_dom::Each::new(
+        items,
+        move |(_, item)| key(item),
+        move |cx, (index, item)| {
+            let key = row_key(&item);
+            let row = Row {
+                index: create_rw_signal(cx, index),
+                item: create_rw_signal(cx, item),
+            };
+            rows.borrow_mut().insert(key.clone(), row);
+            on_cleanup(cx, {
+                let rows = Rc::clone(&rows);
+                move || {
+                    let mut rows = rows.borrow_mut();
+                    // a new row may already have been added for the same key
+                    if rows.get(&key).map(|r| r.item) == Some(row.item) {
+                        rows.remove(&key);
+                    }
+                }
+            });
+            view(cx, row.index.into(), row.item.into())
+        },
+    )
+}
@@ -0,0 +1,46 @@
+use leptos::component;
+use leptos_dom::{html, IntoView};
+use leptos_reactive::Scope;
+use leptos_server::{FormState, ServerFn, Validate};
+
+/// Shows the error for one field of a [FormState] in a `<p role="alert">`, once
+/// the field has been touched or the user has tried to submit the form.
+/// Renders nothing while the field is valid.
+///
+/// Use [FORM_ERROR](leptos_server::FORM_ERROR) as the `field` to show errors
+/// that do not belong to one field, including errors returned by the server
+/// function.
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn FieldError<T>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The form the field belongs to
+    form: FormState<T>,
+    /// The name of the field
+    #[prop(into)]
+    field: String,
+    /// The class of the `<p>`. Defaults to `field-error`.
+    #[prop(optional, into)]
+    class: Option<String>,
+) -> impl IntoView
+where
+    T: ServerFn + Validate + Clone + 'static,
+{
+    let class = class.unwrap_or_else(|| "field-error".to_string());
+
+    move || {
+        if !form.is_touched(&field) {
+            return None;
+        }
+        form.error(&field).map(|error| {
+            html::p(cx)
+                .attr("class", class.clone())
+                .attr("role", "alert")
+                .child(error)
+        })
+    }
+}
@@ -92,6 +92,22 @@
 //!   from the server to the client.
 //! - `miniserde` In SSR/hydrate mode, uses [miniserde](https://docs.rs/miniserde/latest/miniserde/) to serialize resources and send them
 //!   from the server to the client.
+//! - `tracing` Wraps the creation of every component in a [tracing](https://docs.rs/tracing/latest/tracing/)
+//!   span named after it. Whether or not this is enabled, effects and resource fetches have spans in
+//!   debug builds and on the server, as do server functions. With the `tracing` feature of the server
+//!   integrations, each request they render has a span too, so a slow page can be traced to the
+//!   resources that held it up.
+//! - `undelegated-events` Attaches every event listener directly to its element, instead of
+//!   delegating bubbling events to a single listener on the `window`. This leaves the event
+//!   delegation code out of the binary, at the cost of one listener per element.
+//! - `markdown` Adds the [Markdown] component, which renders Markdown to sanitized HTML using
+//!   [pulldown-cmark](https://docs.rs/pulldown-cmark/latest/pulldown_cmark/).
+//!
+//! The code that hydrates server-rendered HTML is only included with the `hydrate` feature, so
+//! a client-side rendered app built with `csr` alone does not pay for it. For the smallest
+//! possible `csr` binary, combine this with `undelegated-events`, build with `opt-level = "z"`,
+//! `lto = true`, `codegen-units = 1`, and `panic = "abort"` in your release profile, and run
+//! `wasm-opt -Oz` on the output. Components you do not use are never included.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in.
@@ -143,6 +159,10 @@
 
 mod additional_attributes;
 pub use additional_attributes::*;
+mod animated_show;
+pub use animated_show::*;
+mod canvas;
+pub use canvas::*;
 pub use leptos_config::{self, get_configuration, LeptosOptions};
 #[cfg(not(all(
     target_arch = "wasm32",
@@ -150,46 +170,78 @@ pub use leptos_config::{self, get_configuration, LeptosOptions};
 )))]
 /// Utilities for server-side rendering HTML.
 pub mod ssr {
-    pub use leptos_dom::{ssr::*, ssr_in_order::*};
+    pub use leptos_dom::{
+        ssr::*, ssr_in_order::*, ssr_snapshot::*, ssr_static::*,
+    };
 }
 #[allow(deprecated)]
 pub use leptos_dom::{
-    self, create_node_ref, debug_warn, document, error, ev,
+    self, component_stack, connect_devtools, create_broadcast_signal,
+    create_idb_resource, create_node_ref, create_persistent_signal,
+    create_persistent_signal_with_options, debug_warn, document, error, ev,
+    format_component_stack,
     helpers::{
-        event_target, event_target_checked, event_target_value,
+        event_target, event_target_checked, event_target_value, get_drag_data,
+        on_animation_frame, on_resize, on_visible, register_service_worker,
         request_animation_frame, request_animation_frame_with_handle,
-        request_idle_callback, request_idle_callback_with_handle, set_interval,
-        set_interval_with_handle, set_timeout, set_timeout_with_handle,
-        window_event_listener, window_event_listener_untyped,
-        window_event_listener_with_precast,
+        request_idle_callback, request_idle_callback_with_handle,
+        set_drag_data, set_interval, set_interval_with_handle, set_timeout,
+        set_timeout_with_handle, start_download, use_droppable,
+        use_online_status, window_event_listener,
+        window_event_listener_untyped, window_event_listener_with_precast,
+        Droppable,
     },
-    html, log, math, mount_to, mount_to_body, svg, warn, window, Attribute,
-    Class, CollectView, Errors, Fragment, HtmlElement, IntoAttribute,
-    IntoClass, IntoProperty, IntoStyle, IntoView, NodeRef, Property, View,
+    html, hydrate_fragment, init_client, log, math, mount_to, mount_to_body,
+    mount_to_with_runtime, run_worker, svg, use_clipboard, use_fullscreen,
+    use_hotkeys, use_hotkeys_with_options, use_web_share, warn, window,
+    Attribute, CanvasContext, CanvasContextKind, Class, ClientOptions,
+    CollectView, DynComponent, ErrorKey, Errors, FlushMarker, Fragment, Hotkey,
+    HotkeyOptions, HotkeyParseError, HtmlElement, IdbError, IdbStore,
+    IdbUpgrade, IntoAnyView, IntoAttribute, IntoClass, IntoProperty, IntoStyle,
+    IntoView, LogLevel, MountHandle, NodeRef, PanicHook,
+    PersistentSignalOptions, Property, ShareData, StorageKind, UseClipboard,
+    UseFullscreen, UseWebShare, View, WorkerError, WorkerFn, WorkerHandle,
 };
 pub use leptos_macro::*;
 pub use leptos_reactive::*;
 pub use leptos_server::{
-    self, create_action, create_multi_action, create_server_action,
-    create_server_multi_action, Action, MultiAction, ServerFn, ServerFnError,
+    self, create_action, create_form, create_form_with_values,
+    create_multi_action, create_server_action, create_server_action_with_retry,
+    create_server_multi_action, set_csrf_policy, use_csrf_policy,
+    use_csrf_token, Action, CsrfPolicy, CsrfToken, FieldErrors, FileBody,
+    FileResponse, FormField, FormState, MultiAction, ServerFn, ServerFnError,
+    Validate, CSRF_FIELD, FORM_ERROR,
 };
+// used by the worker macro
+#[doc(hidden)]
+pub use serde;
 pub use server_fn::{self, ServerFn as _};
 pub use typed_builder;
 mod error_boundary;
 pub use error_boundary::*;
+mod flush;
+pub use flush::*;
 mod for_loop;
+mod form;
+#[cfg(feature = "markdown")]
+mod markdown;
 mod show;
 pub use for_loop::*;
+pub use form::*;
+#[cfg(feature = "markdown")]
+pub use markdown::*;
 pub use show::*;
 mod suspense;
 pub use suspense::*;
 mod text_prop;
 mod transition;
+mod worker;
 pub use text_prop::TextProp;
 #[cfg(any(debug_assertions, feature = "ssr"))]
 #[doc(hidden)]
 pub use tracing;
 pub use transition::*;
+pub use worker::*;
 extern crate self as leptos;
 
 /// The most common type for the `children` property on components,
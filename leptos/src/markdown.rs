@@ -0,0 +1,102 @@
+use crate::AttributeValue;
+use leptos::component;
+use leptos_dom::{html, IntoAttribute, IntoView};
+use leptos_reactive::{create_memo, MaybeSignal, Scope, SignalGet, SignalWith};
+use pulldown_cmark::{html::push_html, CowStr, Event, Options, Parser, Tag};
+
+/// Renders Markdown to HTML, as the [Markdown] component does.
+///
+/// The output is safe to insert into the page: raw HTML in the Markdown is
+/// escaped and shown as text, and links or images with `javascript:`,
+/// `vbscript:` or (non-image) `data:` URLs have them replaced by `#`. Tables,
+/// strikethrough, footnotes and task lists are enabled.
+///
+/// ```rust
+/// # use leptos::*;
+/// assert_eq!(
+///     render_markdown("*Hi* <script>alert(1)</script>"),
+///     "<p><em>Hi</em> &lt;script&gt;alert(1)&lt;/script&gt;</p>\n"
+/// );
+/// ```
+pub fn render_markdown(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(html) => Event::Text(html),
+        Event::Start(Tag::Link(kind, url, title)) => {
+            Event::Start(Tag::Link(kind, sanitize_url(url, false), title))
+        }
+        Event::Start(Tag::Image(kind, url, title)) => {
+            Event::Start(Tag::Image(kind, sanitize_url(url, true), title))
+        }
+        event => event,
+    });
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    push_html(&mut html, events);
+    html
+}
+
+fn sanitize_url(url: CowStr<'_>, image: bool) -> CowStr<'_> {
+    let scheme = url.trim_start().to_ascii_lowercase();
+    let unsafe_url = scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || (scheme.starts_with("data:")
+            && !(image && scheme.starts_with("data:image/")));
+    if unsafe_url {
+        CowStr::Borrowed("#")
+    } else {
+        url
+    }
+}
+
+/// Renders Markdown into a `<div>`, using [render_markdown].
+///
+/// The HTML is rendered on the server. The rendered Markdown has no
+/// hydration markers, so the browser can replace it with the same HTML while
+/// hydrating without any mismatch. When `src` is a signal, the content is
+/// rendered again whenever it changes.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (text, set_text) = create_signal(cx, "# Notes".to_string());
+///
+/// view! { cx,
+///   <textarea on:input=move |ev| set_text.set(event_target_value(&ev))/>
+///   <Markdown class="preview" src=text/>
+/// }
+/// # });
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all)
+)]
+#[component]
+pub fn Markdown(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The Markdown to render
+    #[prop(into)]
+    src: MaybeSignal<String>,
+    /// Sets the `class` attribute of the `<div>`
+    #[prop(optional, into)]
+    class: Option<AttributeValue>,
+) -> impl IntoView {
+    let html = create_memo(cx, move |_| src.with(|src| render_markdown(src)));
+    let class = class.map(|class| class.into_attribute_boxed(cx));
+
+    let div = html::div(cx).attr("class", class);
+    // the server renders the HTML as the children of the `<div>`, while the
+    // browser sets it as a property, so that it can be updated
+    #[cfg(not(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    )))]
+    let div = div.inner_html(html.get());
+    div.prop("innerHTML", move || html.get())
+}
@@ -1,4 +1,6 @@
 use cfg_if::cfg_if;
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use leptos_dom::{helpers::keep_exiting_views, View};
 use leptos_dom::{DynChild, Fragment, HydrationCtx, IntoView};
 use leptos_macro::component;
 #[cfg(any(feature = "csr", feature = "hydrate"))]
@@ -6,7 +8,7 @@ use leptos_reactive::ScopeDisposer;
 use leptos_reactive::{provide_context, Scope, SuspenseContext};
 #[cfg(any(feature = "csr", feature = "hydrate"))]
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 /// If any [Resources](leptos_reactive::Resource) are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
@@ -16,6 +18,11 @@ use std::rc::Rc;
 /// those resources are read under the suspense), so you cannot assume that resources have
 /// `Some` value in `children`.
 ///
+/// When streaming HTML from the server, `timeout_ms` limits how long the server waits for
+/// the resources. If it elapses, the server sends the `fallback` instead, so one slow
+/// resource can't hold up the page; the resources' data is still sent once it's ready, and
+/// the `children` are then rendered on the client.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -60,6 +67,20 @@ pub fn Suspense<F, E>(
     cx: Scope,
     /// Returns a fallback UI that will be shown while `async` [Resources](leptos_reactive::Resource) are still loading.
     fallback: F,
+    /// How long the server should wait for `async` [Resources](leptos_reactive::Resource) to
+    /// load before streaming the `fallback` instead.
+    #[prop(optional)]
+    timeout_ms: Option<u64>,
+    /// If set, when the `fallback` and the children replace each other, a copy
+    /// of the outgoing view stays in the DOM for this many milliseconds, with the
+    /// `exit_class`, so that it can be animated out. See
+    /// [`keep_exiting_views`](leptos_dom::helpers::keep_exiting_views).
+    #[prop(optional)]
+    exit_duration_ms: Option<u64>,
+    /// The class added to the outgoing view while it is exiting. Defaults to
+    /// `"exiting"`.
+    #[prop(optional, into)]
+    exit_class: Option<String>,
     /// Children will be displayed once all `async` [Resources](leptos_reactive::Resource) have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
 ) -> impl IntoView
@@ -68,99 +89,133 @@ where
     E: IntoView,
 {
     let context = SuspenseContext::new(cx);
+    context.set_timeout(timeout_ms.map(Duration::from_millis));
 
     // provide this SuspenseContext to any resources below it
     provide_context(cx, context);
 
     let orig_child = Rc::new(children);
+    let fallback = Rc::new(fallback);
 
     let current_id = HydrationCtx::next_component();
     #[cfg(any(feature = "csr", feature = "hydrate"))]
     let prev_disposer = Rc::new(RefCell::new(None::<ScopeDisposer>));
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let prev_view = Rc::new(RefCell::new(None::<View>));
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    {
+        _ = exit_duration_ms;
+        _ = exit_class;
+    }
 
-    let child = DynChild::new({
-        #[cfg(not(any(feature = "csr", feature = "hydrate")))]
-        let current_id = current_id.clone();
-        move || {
-            cfg_if! {
-                if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-                    if let Some(disposer) = prev_disposer.take() {
-                        disposer.dispose();
-                    }
-                    let (view, disposer) =
-                        cx.run_child_scope(|cx| if context.ready() {
-                        Fragment::lazy(Box::new(|| vec![orig_child(cx).into_view(cx)])).into_view(cx)
-                    } else {
-                        Fragment::lazy(Box::new(|| vec![fallback().into_view(cx)])).into_view(cx)
-                    });
-                    *prev_disposer.borrow_mut() = Some(disposer);
-                    view
-
-                } else {
-                    use leptos_reactive::signal_prelude::*;
+    // everything after the `<Suspense/>` is numbered from its position, not
+    // from the nodes within it
+    let child = HydrationCtx::scoped(|| {
+        DynChild::new({
+            #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+            let current_id = current_id.clone();
+            move || {
+                cfg_if! {
+                    if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                        if let (Some(ms), Some(prev)) =
+                            (exit_duration_ms, prev_view.take())
+                        {
+                            keep_exiting_views(
+                                &[prev],
+                                exit_class.as_deref().unwrap_or("exiting"),
+                                Duration::from_millis(ms),
+                            );
+                        }
+                        if let Some(disposer) = prev_disposer.take() {
+                            disposer.dispose();
+                        }
+                        let (view, disposer) =
+                            cx.run_child_scope(|cx| if context.ready() {
+                            Fragment::lazy(Box::new(|| vec![orig_child(cx).into_view(cx)])).into_view(cx)
+                        } else {
+                            Fragment::lazy(Box::new(|| vec![fallback().into_view(cx)])).into_view(cx)
+                        });
+                        *prev_disposer.borrow_mut() = Some(disposer);
+                        if exit_duration_ms.is_some() {
+                            *prev_view.borrow_mut() = Some(view.clone());
+                        }
+                        view
 
-                    // run the child; we'll probably throw this away, but it will register resource reads
-                    let child = orig_child(cx).into_view(cx);
-                    let after_original_child = HydrationCtx::id();
+                    } else {
+                        use crate::error_boundary::with_error_fallback;
+                        use leptos_reactive::signal_prelude::*;
 
-                    let initial = {
-                        // no resources were read under this, so just return the child
-                        if context.pending_resources.get() == 0 {
-                            child
-                        }
-                        // show the fallback, but also prepare to stream HTML
-                        else {
-                            let orig_child = Rc::clone(&orig_child);
+                        // run the child; we'll probably throw this away, but it will register resource reads
+                        // like in the browser, the children get their own scope, so that context they provide
+                        // doesn't leak out to the siblings of the <Suspense/>
+                        let (child, _) = cx.run_child_scope(|cx| orig_child(cx).into_view(cx));
 
-                            cx.register_suspense(
-                                context,
-                                &current_id.to_string(),
-                                // out-of-order streaming
-                                {
-                                    let current_id = current_id.clone();
-                                    let orig_child = Rc::clone(&orig_child);
-                                    move || {
-                                        HydrationCtx::continue_from(current_id.clone());
-                                        Fragment::lazy(Box::new(move || {
-                                            vec![DynChild::new(move || orig_child(cx)).into_view(cx)]
-                                        }))
-                                        .into_view(cx)
-                                        .render_to_string(cx)
-                                        .to_string()
-                                    }
-                                },
-                                // in-order streaming
-                                {
-                                    let current_id = current_id.clone();
-                                    move || {
-                                        HydrationCtx::continue_from(current_id.clone());
-                                        Fragment::lazy(Box::new(move || {
-                                            vec![DynChild::new(move || orig_child(cx)).into_view(cx)]
-                                        }))
-                                        .into_view(cx)
-                                        .into_stream_chunks(cx)
-                                    }
-                                },
-                            );
+                        // the fallback is replaced when the resources
+                        // resolve, so the keys after it must not depend on it
+                        HydrationCtx::with_key(HydrationCtx::id(), || {
+                            // no resources were read under this, so just return the child
+                            if context.pending_resources.get() == 0 {
+                                child
+                            }
+                            // show the fallback, but also prepare to stream HTML
+                            else {
+                                let orig_child = Rc::clone(&orig_child);
 
-                            // return the fallback for now, wrapped in fragment identifier
-                            fallback().into_view(cx)
-                        }
-                    };
+                                cx.register_suspense(
+                                    context,
+                                    &current_id.to_string(),
+                                    // out-of-order streaming
+                                    {
+                                        let current_id = current_id.clone();
+                                        let orig_child = Rc::clone(&orig_child);
+                                        let fallback = Rc::clone(&fallback);
+                                        move || {
+                                            HydrationCtx::continue_from(current_id.clone());
+                                            if context.timed_out() {
+                                                return fallback().into_view(cx).render_to_string(cx).to_string();
+                                            }
+                                            let view = Fragment::lazy(Box::new(move || {
+                                                vec![DynChild::new(move || cx.run_child_scope(|cx| orig_child(cx)).0).into_view(cx)]
+                                            }))
+                                            .into_view(cx);
+                                            with_error_fallback(cx, view)
+                                                .render_to_string(cx)
+                                                .to_string()
+                                        }
+                                    },
+                                    // in-order streaming
+                                    {
+                                        let current_id = current_id.clone();
+                                        let fallback = Rc::clone(&fallback);
+                                        move || {
+                                            HydrationCtx::continue_from(current_id.clone());
+                                            if context.timed_out() {
+                                                return fallback().into_view(cx).into_stream_chunks(cx);
+                                            }
+                                            let view = Fragment::lazy(Box::new(move || {
+                                                vec![DynChild::new(move || cx.run_child_scope(|cx| orig_child(cx)).0).into_view(cx)]
+                                            }))
+                                            .into_view(cx);
+                                            with_error_fallback(cx, view)
+                                                .into_stream_chunks(cx)
+                                        }
+                                    },
+                                );
 
-                    HydrationCtx::continue_from(after_original_child);
-                    initial
+                                // return the fallback for now, wrapped in fragment identifier
+                                fallback().into_view(cx)
+                            }
+                        })
+                    }
                 }
             }
-        }
-    })
-    .into_view(cx);
+        })
+        .into_view(cx)
+    });
     let core_component = match child {
         leptos_dom::View::CoreComponent(repr) => repr,
         _ => unreachable!(),
     };
 
-    HydrationCtx::continue_from(current_id.clone());
-
     leptos_dom::View::Suspense(current_id, core_component)
 }
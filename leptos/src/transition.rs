@@ -1,9 +1,10 @@
-use leptos_dom::{Fragment, IntoView, View};
+use leptos_dom::{helpers::keep_exiting_views, Fragment, IntoView, View};
 use leptos_macro::component;
 use leptos_reactive::{use_context, Scope, SignalSetter, SuspenseContext};
 use std::{
     cell::{Cell, RefCell},
     rc::Rc,
+    time::Duration,
 };
 
 /// If any [Resource](leptos_reactive::Resource)s are read in the `children` of this
@@ -71,6 +72,16 @@ pub fn Transition<F, E>(
     /// or not pending (`false`).
     #[prop(optional)]
     set_pending: Option<SignalSetter<bool>>,
+    /// If set, when new children replace the previous ones, a copy of the
+    /// previous children stays in the DOM for this many milliseconds, with the
+    /// `exit_class`, so that it can be animated out (e.g., to cross-fade between
+    /// pages). See [`keep_exiting_views`](leptos_dom::helpers::keep_exiting_views).
+    #[prop(optional)]
+    exit_duration_ms: Option<u64>,
+    /// The class added to the previous children while they are exiting.
+    /// Defaults to `"exiting"`.
+    #[prop(optional, into)]
+    exit_class: Option<String>,
     /// Will be displayed once all resources have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
 ) -> impl IntoView
@@ -117,6 +128,21 @@ where
                 let suspense_context = use_context::<SuspenseContext>(cx)
                     .expect("there to be a SuspenseContext");
 
+                // if the new children read resources that are still loading,
+                // the previous children are shown again as the fallback, and
+                // only exit once the children run again with the resources
+                // loaded
+                if let (Some(ms), Some(prev), true) = (
+                    exit_duration_ms,
+                    &*prev_children.borrow(),
+                    suspense_context.all_resolved(),
+                ) {
+                    keep_exiting_views(
+                        prev,
+                        exit_class.as_deref().unwrap_or("exiting"),
+                        Duration::from_millis(ms),
+                    );
+                }
                 if cfg!(feature = "hydrate") || !first_run.get() {
                     *prev_children.borrow_mut() = Some(frag.nodes.clone());
                 }
@@ -0,0 +1,44 @@
+use leptos_dom::{WorkerError, WorkerFn, WorkerHandle};
+use leptos_reactive::Scope;
+use leptos_server::{create_action, Action};
+
+/// Creates an [Action] that runs a [worker function](macro@crate::worker) in
+/// the given worker each time it is dispatched, so that the work doesn't
+/// block the main thread. Its value is the output of the function, or the
+/// [WorkerError] if it could not be run.
+///
+/// ```ignore
+/// # use leptos::*;
+/// #[worker(CountPrimes)]
+/// pub async fn count_primes(below: u32) -> usize {
+///     (2..below).filter(|n| (2..*n).all(|d| n % d != 0)).count()
+/// }
+///
+/// #[component]
+/// fn Primes(cx: Scope, worker: WorkerHandle) -> impl IntoView {
+///     let count = create_worker_action::<CountPrimes>(cx, worker);
+///
+///     view! { cx,
+///       <button on:click=move |_| count.dispatch(CountPrimes { below: 100_000 })>
+///         "Count primes"
+///       </button>
+///       <p>
+///         {move || match count.value().get() {
+///             Some(Ok(n)) => format!("{n} primes"),
+///             Some(Err(e)) => e.to_string(),
+///             None if count.pending().get() => "Counting...".into(),
+///             None => String::new(),
+///         }}
+///       </p>
+///     }
+/// }
+/// ```
+pub fn create_worker_action<F>(
+    cx: Scope,
+    worker: WorkerHandle,
+) -> Action<F, Result<F::Output, WorkerError>>
+where
+    F: WorkerFn,
+{
+    create_action(cx, move |args: &F| worker.call(args.clone()))
+}
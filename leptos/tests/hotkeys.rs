@@ -0,0 +1,62 @@
+use leptos::Hotkey;
+
+fn parse(s: &str) -> Hotkey {
+    s.parse().unwrap()
+}
+
+#[test]
+fn hotkeys_are_displayed_in_a_canonical_form() {
+    assert_eq!(parse("k").to_string(), "k");
+    assert_eq!(parse("Ctrl+K").to_string(), "ctrl+k");
+    assert_eq!(
+        parse("shift + alt + ArrowUp").to_string(),
+        "alt+shift+arrowup"
+    );
+    assert_eq!(parse("cmd+option+k").to_string(), "alt+meta+k");
+    assert_eq!(parse("ctrl+space").to_string(), "ctrl+space");
+    assert_eq!(parse("ctrl+plus").to_string(), "ctrl+plus");
+}
+
+#[test]
+fn shorthands_are_the_same_as_key_names() {
+    assert_eq!(parse("esc"), parse("Escape"));
+    assert_eq!(parse("up"), parse("ArrowUp"));
+    assert_eq!(parse("shift+left"), parse("shift+ArrowLeft"));
+    assert_eq!(parse("del"), parse("Delete"));
+    assert_eq!(parse("control+k"), parse("ctrl+k"));
+    assert_eq!(parse("command+k"), parse("meta+k"));
+}
+
+#[test]
+fn hotkeys_survive_a_round_trip() {
+    for s in [
+        "ctrl+k",
+        "alt+shift+meta+f1",
+        "shift+space",
+        "ctrl+plus",
+        "/",
+    ] {
+        assert_eq!(parse(&parse(s).to_string()), parse(s));
+    }
+}
+
+#[test]
+fn mod_is_ctrl_outside_apple_devices() {
+    assert_eq!(parse("mod+k"), parse("ctrl+k"));
+}
+
+#[test]
+fn invalid_hotkeys_are_rejected() {
+    for (s, reason) in [
+        ("", "has no key"),
+        ("ctrl+", "has no key"),
+        ("hyper+k", "has an unknown modifier `hyper`"),
+        ("ctrl+Ctrl+k", "repeats `Ctrl`"),
+    ] {
+        let error = s.parse::<Hotkey>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("invalid hotkey: `{s}` {reason}")
+        );
+    }
+}
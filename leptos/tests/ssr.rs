@@ -198,3 +198,869 @@ fn ssr_option() {
         );
     });
 }
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_script_string_escaping() {
+    use leptos::ssr::escape_script_string;
+
+    assert_eq!(
+        escape_script_string("</script><script>alert(1)</script>"),
+        r#""\u003c/script\u003e\u003cscript\u003ealert(1)\u003c/script\u003e""#
+    );
+    assert_eq!(
+        escape_script_string("<!-- a & b -->"),
+        r#""\u003c!-- a \u0026 b --\u003e""#
+    );
+    assert_eq!(
+        escape_script_string("a\u{2028}b\u{2029}c"),
+        r#""a\u2028b\u2029c""#
+    );
+    assert_eq!(
+        escape_script_string(r#"say "hi" \</SCRIPT>"#),
+        r#""say \"hi\" \\\u003c/SCRIPT\u003e""#
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_rendered_parts_inject() {
+    use leptos::ssr::RenderedParts;
+
+    let parts = RenderedParts {
+        head: "<title>Hi</title>".to_string(),
+        body: "<p>Hello</p>".to_string(),
+        scripts: "<script>var a = 1;</script>".to_string(),
+    };
+
+    assert_eq!(
+        parts.inject(
+            "<html><head><!--leptos-head--></head><body><!--leptos-body--><!\
+             --leptos-other--><!--leptos-scripts--></body></html>"
+        ),
+        "<html><head><title>Hi</title></head><body><p>Hello</p><!\
+         --leptos-other--><script>var a = 1;</script></body></html>"
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_fragment_hydration_ids() {
+    use leptos::*;
+
+    let html = leptos::ssr::render_fragment_to_string("frag", |cx| {
+        view! { cx, <div><span>"Hello"</span></div> }
+    });
+
+    assert!(html.contains("<div id=\"_frag-1\">"));
+    assert!(html.contains("<span id=\"_frag-2\">"));
+    assert!(!html.contains("_0-"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_shell_error_handler() {
+    use leptos::*;
+    use std::{cell::Cell, rc::Rc};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let caught = Rc::new(Cell::new(0));
+        provide_shell_error_handler(cx, {
+            let caught = Rc::clone(&caught);
+            move |errors| caught.set(errors.iter().count())
+        });
+
+        let rendered = view! { cx,
+            <ErrorBoundary fallback=|cx, _| view! { cx, <p>"Error!"</p> }>
+                {"NaN".parse::<i32>()}
+            </ErrorBoundary>
+        };
+
+        let html = rendered.into_view(cx).render_to_string(cx);
+        assert!(html.contains("Error!</p>"));
+        assert_eq!(caught.get(), 1);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_snapshot_normalization() {
+    use leptos::{ssr::*, *};
+
+    let html = render_to_snapshot(|cx| {
+        view! { cx,
+            <ul>
+                <li title="first" class="item">"A"</li>
+                <li class="item">"B"</li>
+            </ul>
+        }
+    });
+    assert_eq!(
+        html,
+        "<ul id=\"_h0\"><li class=\"item\" id=\"_h1\" \
+         title=\"first\">A</li><li class=\"item\" id=\"_h2\">B</li></ul>"
+    );
+    assert_eq!(normalize_html(&html), html);
+
+    let diff = diff_html("<p>A</p>", "<p>B</p>").unwrap();
+    assert_eq!(diff, "  <p>\n- A\n+ B\n  </p>\n");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_snapshot_keeps_ids_that_are_not_hydration_ids() {
+    use leptos::ssr::*;
+
+    assert_eq!(
+        normalize_html(
+            "<div id=\"_0-1-k3-2\"><p id=\"_tab-2\"></p><p id=\"_0-1\"></p><p \
+             id=\"_0-\"></p></div>"
+        ),
+        "<div id=\"_h0\"><p id=\"_tab-2\"></p><p id=\"_h1\"></p><p \
+         id=\"_0-\"></p></div>"
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_component_stack() {
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[component]
+    fn Inner(cx: Scope, stack: Rc<RefCell<String>>) -> impl IntoView {
+        *stack.borrow_mut() = format_component_stack();
+        view! { cx, <p>"Inner"</p> }
+    }
+
+    #[component]
+    fn Outer(cx: Scope, stack: Rc<RefCell<String>>) -> impl IntoView {
+        view! { cx, <div><Inner stack=stack/></div> }
+    }
+
+    let stack = Rc::new(RefCell::new(String::new()));
+    _ = create_scope(create_runtime(), {
+        let stack = Rc::clone(&stack);
+        move |cx| {
+            _ = view! { cx, <Outer stack=stack/> }.into_view(cx);
+        }
+    });
+
+    // the stack is only kept in debug builds
+    if cfg!(debug_assertions) {
+        assert_eq!(*stack.borrow(), "in `<Inner/>` in `<Outer/>`");
+    }
+    assert!(format_component_stack().is_empty());
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_derive_into_view() {
+    use leptos::*;
+    use std::fmt;
+
+    #[derive(IntoView)]
+    enum Status {
+        Active,
+        Custom(#[into_view] &'static str),
+    }
+
+    impl fmt::Display for Status {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Status::Active => write!(f, "Active"),
+                Status::Custom(_) => write!(f, "Custom"),
+            }
+        }
+    }
+
+    #[derive(IntoView)]
+    #[into_view(with = dollars)]
+    struct Money(i64);
+
+    fn dollars(money: Money, _cx: Scope) -> String {
+        format!("${}.{:02}", money.0 / 100, money.0 % 100)
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        assert_eq!(Status::Active.into_view(cx).render_to_string(cx), "Active");
+        assert_eq!(
+            Status::Custom("custom").into_view(cx).render_to_string(cx),
+            "custom"
+        );
+        assert_eq!(Money(1250).into_view(cx).render_to_string(cx), "$12.50");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_std_types_into_view() {
+    use leptos::*;
+    use std::{collections::BTreeMap, rc::Rc, sync::Arc, time::Duration};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let name: Arc<str> = Arc::from("Arc");
+        assert_eq!(name.into_view(cx).render_to_string(cx), "Arc");
+        let name: Rc<str> = Rc::from("Rc");
+        assert_eq!(name.into_view(cx).render_to_string(cx), "Rc");
+        assert_eq!(
+            Duration::from_millis(1500)
+                .into_view(cx)
+                .render_to_string(cx),
+            "1.5s"
+        );
+
+        let list = ["apple", "banana"].into_view(cx).render_to_string(cx);
+        assert!(list.find("apple").unwrap() < list.find("banana").unwrap());
+
+        let map = BTreeMap::from([(2, "banana"), (1, "apple")]);
+        let values = map.into_values().into_view(cx).render_to_string(cx);
+        assert!(values.find("apple").unwrap() < values.find("banana").unwrap());
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_keyed_fragment_keeps_hydration_ids() {
+    use leptos::{leptos_dom::HydrationCtx, *};
+
+    // the ID of the node after a keyed fragment, and of the fragment itself
+    fn render(cx: Scope, show: bool) -> (String, String) {
+        HydrationCtx::reset_id();
+        let fragment = Fragment::with_key("maybe", || {
+            vec![show.then(|| view! { cx, <p>"Shown"</p> }).into_view(cx)]
+        });
+        (HydrationCtx::id().to_string(), fragment.id().to_string())
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let shown = render(cx, true);
+        let hidden = render(cx, false);
+        assert_eq!(shown, hidden);
+        assert_eq!(shown, ("0-1".to_string(), "0-kmaybe-0".to_string()));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_scoped_hydration_ids() {
+    use leptos::{leptos_dom::HydrationCtx, *};
+
+    _ = create_scope(create_runtime(), |cx| {
+        HydrationCtx::reset_id();
+        let before = HydrationCtx::peek();
+        let inner = HydrationCtx::scoped(|| {
+            view! { cx, <div><p>"Rendered twice"</p></div> }
+                .into_view(cx)
+                .render_to_string(cx)
+        });
+        assert!(inner.contains("_0-1"));
+        assert_eq!(HydrationCtx::peek(), before);
+
+        let root = HydrationCtx::fragment_root("island");
+        let id = HydrationCtx::with_key(root, HydrationCtx::id);
+        assert_eq!(id.to_string(), "island-1");
+        assert_eq!(HydrationCtx::peek(), before);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_children_keep_their_context() {
+    use leptos::*;
+
+    #[derive(Clone)]
+    struct Marker(&'static str);
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx,
+            <div>
+                <Suspense fallback=|| ()>
+                    {provide_context(cx, Marker("inside"));}
+                    {use_context::<Marker>(cx).map(|marker| marker.0)}
+                </Suspense>
+                {use_context::<Marker>(cx)
+                    .map(|marker| marker.0)
+                    .unwrap_or("outside")}
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("inside"));
+        assert!(rendered.contains("outside"));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_for_each_renders_every_item() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (items, _) = create_signal(cx, vec![(1, "one"), (2, "two")]);
+        let rendered = view! {
+            cx,
+            <ul>
+                <ForEach
+                    each=items
+                    key=|item| item.0
+                    view=move |cx, item: Signal<(i32, &'static str)>| {
+                        view! { cx, <li>{move || item.with(|item| item.1)}</li> }
+                    }
+                />
+            </ul>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("one"));
+        assert!(rendered.contains("two"));
+        assert!(rendered.find("one") < rendered.find("two"));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_for_enumerate_numbers_items() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (items, _) = create_signal(cx, vec!["one", "two"]);
+        let rendered = view! {
+            cx,
+            <ul>
+                <ForEnumerate
+                    each=items
+                    key=|item| *item
+                    view=move |cx, index: Signal<usize>, item: Signal<&'static str>| {
+                        view! { cx, <li>{move || format!("{}:{}", index.get(), item.get())}</li> }
+                    }
+                />
+            </ul>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("0:one"));
+        assert!(rendered.contains("1:two"));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_web_apis_are_unsupported_and_do_nothing() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let clipboard = use_clipboard(cx);
+        let share = use_web_share(cx);
+        let video = create_node_ref::<html::Video>(cx);
+        let fullscreen = use_fullscreen(cx, video);
+
+        // the same value the browser starts with, so that hydration matches
+        let rendered = view! { cx,
+            <button disabled=move || !clipboard.is_supported.get()>"Copy"</button>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+        assert!(rendered.contains("disabled"));
+
+        assert!(!share.is_supported.get_untracked());
+        assert!(!fullscreen.is_supported.get_untracked());
+
+        clipboard.copy("text");
+        share.share(&ShareData::default());
+        fullscreen.toggle();
+        assert_eq!(clipboard.copied.get_untracked(), None);
+        assert!(!clipboard.pending.get_untracked());
+        assert!(!share.pending.get_untracked());
+        assert!(!fullscreen.is_fullscreen.get_untracked());
+        assert_eq!(fullscreen.error.get_untracked(), None);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_canvas_renders_element_without_running_hooks() {
+    use leptos::*;
+    use std::rc::Rc;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! {
+            cx,
+            <Canvas
+                class="scene"
+                context=CanvasContextKind::WebGl
+                on_init=Rc::new(|_: &CanvasContext| panic!("ran on the server"))
+                on_frame=Rc::new(|_: &CanvasContext, _| panic!("ran on the server"))
+            />
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("<canvas"));
+        assert!(rendered.contains("class=\"scene\""));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_email_inlines_styles_and_reports_scripts() {
+    use leptos::{ssr::*, *};
+
+    let html = render_to_static(StaticTarget::Email, |cx| {
+        view! { cx,
+            <style>"p { color: gray } a:hover { color: red }"</style>
+            <p style="margin: 0">"Welcome!"</p>
+        }
+    })
+    .unwrap();
+    assert!(html.contains(r#"style="color: gray; margin: 0;""#));
+    assert!(html.contains("<style>a:hover{color: red}</style>"));
+    assert!(!html.contains("leptos-hk"));
+
+    let error = render_to_static(StaticTarget::Amp, |cx| {
+        view! { cx,
+            <script>"alert(1)"</script>
+            <a href="javascript:void(0)" on:click=|_| ()>"Click"</a>
+        }
+    })
+    .unwrap_err();
+    assert_eq!(error.diagnostics.len(), 3);
+}
+
+#[cfg(all(
+    feature = "markdown",
+    not(any(feature = "csr", feature = "hydrate"))
+))]
+#[test]
+fn ssr_markdown_renders_sanitized_html() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! {
+            cx,
+            <Markdown
+                class="post"
+                src="# Title\n\n<img src=x onerror=alert(1)> [link](javascript:alert(1))"
+            />
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("class=\"post\""));
+        assert!(rendered.contains("<h1>Title</h1>"));
+        assert!(rendered.contains("&lt;img"));
+        assert!(rendered.contains("<a href=\"#\">link</a>"));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_errors_keep_insertion_order() {
+    use leptos::*;
+    use std::collections::HashMap;
+
+    let mut errors = Errors::default();
+    errors.insert("b".into(), "second");
+    errors.insert("a".into(), "first");
+    errors.insert("b".into(), "replaced");
+    let messages = errors
+        .iter()
+        .map(|(_, error)| error.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(messages, ["replaced", "first"]);
+
+    let fields = HashMap::from([
+        ("password".to_string(), "Too short.".to_string()),
+        ("email".to_string(), "Missing.".to_string()),
+    ]);
+    let errors = Errors::from(fields);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors.iter().next().unwrap().0, &ErrorKey::from("email"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_renders_type_erased_views_and_dyn_components() {
+    use leptos::*;
+
+    #[component(props(derive(Clone)))]
+    fn Heading(cx: Scope, text: String) -> impl IntoView {
+        view! { cx, <h2>{text}</h2> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let heading = DynComponent::new(
+            Heading,
+            HeadingProps::builder().text("Welcome".to_string()).build(),
+        );
+        let blocks: Vec<Box<dyn IntoAnyView>> = vec![
+            heading.clone().into_any(),
+            "between".into_any(),
+            heading.into_any(),
+        ];
+
+        let rendered = view! { cx, <main>{blocks}</main> }
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert_eq!(rendered.matches("Welcome").count(), 2);
+        assert!(rendered.contains("between"));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_email_inlines_rules_by_specificity() {
+    use leptos::{ssr::*, *};
+
+    let html = render_to_static(StaticTarget::Email, |cx| {
+        view! { cx,
+            <style>"a.button { color: white } a { color: blue }"</style>
+            <a class="button" href="/confirm">"Confirm"</a>
+        }
+    })
+    .unwrap();
+    assert!(html.contains(r#"style="color: blue; color: white;""#));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_amp_keeps_boilerplate_and_styles_head() {
+    use leptos::{ssr::*, *};
+
+    let html = render_to_static(StaticTarget::Amp, |cx| {
+        view! { cx,
+            <head>
+                <style amp-boilerplate="">"body { visibility: hidden }"</style>
+            </head>
+            <body>
+                <style>"p { color: gray }"</style>
+                <p>"Welcome!"</p>
+            </body>
+        }
+    })
+    .unwrap();
+    assert!(html.contains(
+        "<style amp-boilerplate=\"\">body { visibility: hidden }</style>"
+    ));
+    let custom = html.find("<style amp-custom>p { color: gray }</style>");
+    let head_end = html.find("</head>");
+    assert!(custom.is_some() && head_end.is_some());
+    assert!(custom < head_end);
+    assert_eq!(html.matches("<style").count(), 2);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_idb_store_is_unavailable() {
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    let executor = TestExecutor::new();
+    let store = IdbStore::new("my-app", 1, "drafts");
+    let results = Rc::new(RefCell::new(Vec::new()));
+    spawn_local({
+        let results = Rc::clone(&results);
+        async move {
+            let mut results = results.borrow_mut();
+            results.push(store.put("post-1", &"Hello".to_string()).await);
+            results.push(store.get::<String>("post-1").await.map(|_| ()));
+            results.push(store.delete("post-1").await);
+            results.push(store.clear().await);
+            results.push(store.keys().await.map(|_| ()));
+        }
+    });
+    executor.run_until_stalled();
+
+    let results = results.borrow();
+    assert_eq!(results.len(), 5);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(IdbError::Unavailable))));
+}
+
+// Renders the view as an in-order stream with the given options, and collects
+// its chunks once every resource has loaded.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn in_order_chunks(
+    options: leptos::ssr::StreamOptions,
+    view: impl FnOnce(leptos::Scope) -> leptos::View + 'static,
+) -> Vec<String> {
+    use futures::StreamExt;
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    let executor = TestExecutor::new();
+    let (stream, runtime, _) =
+        ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+            view,
+            |_| "".into(),
+            move |cx| provide_context(cx, options),
+        );
+    let chunks = Rc::new(RefCell::new(Vec::new()));
+    spawn_local({
+        let chunks = Rc::clone(&chunks);
+        async move {
+            let mut stream = Box::pin(stream);
+            while let Some(chunk) = stream.next().await {
+                chunks.borrow_mut().push(chunk);
+            }
+        }
+    });
+    executor.run_until_stalled();
+    runtime.dispose();
+
+    chunks.take()
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_in_order_flush_sends_html_rendered_so_far() {
+    use leptos::{ssr::StreamOptions, *};
+
+    let chunks = in_order_chunks(StreamOptions::default(), |cx| {
+        let data = create_resource(cx, || (), |_| async { "data".to_string() });
+        view! { cx,
+            <p>"before"</p>
+            <Suspense fallback=|| ()>{move || data.read(cx)}</Suspense>
+            <p>"middle"</p>
+            <Flush/>
+            <p>"after"</p>
+        }
+        .into_view(cx)
+    });
+
+    let chunk_with = |text: &str| {
+        chunks
+            .iter()
+            .position(|chunk| chunk.contains(text))
+            .unwrap_or_else(|| panic!("no chunk contains {text:?}"))
+    };
+    // sent at the `<Suspense/>`, and then at the `<Flush/>`
+    assert!(chunk_with("before") < chunk_with("data"));
+    assert_eq!(chunk_with("data"), chunk_with("middle"));
+    assert!(chunk_with("middle") < chunk_with("after"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_in_order_min_chunk_size_joins_small_chunks() {
+    use leptos::{ssr::StreamOptions, *};
+
+    let view = |cx: Scope| {
+        let data = create_resource(cx, || (), |_| async { "data".to_string() });
+        view! { cx,
+            <p>"before"</p>
+            <Suspense fallback=|| ()>{move || data.read(cx)}</Suspense>
+            <p>"after"</p>
+        }
+        .into_view(cx)
+    };
+    let chunk_with = |chunks: &[String], text: &str| {
+        chunks
+            .iter()
+            .position(|chunk| chunk.contains(text))
+            .unwrap()
+    };
+
+    let chunks = in_order_chunks(StreamOptions::default(), view);
+    assert!(chunk_with(&chunks, "before") < chunk_with(&chunks, "data"));
+
+    let chunks = in_order_chunks(
+        StreamOptions {
+            min_chunk_size: 64 * 1024,
+            ..Default::default()
+        },
+        view,
+    );
+    assert_eq!(chunk_with(&chunks, "before"), chunk_with(&chunks, "data"));
+    assert_eq!(chunk_with(&chunks, "data"), chunk_with(&chunks, "after"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_streams_pause_until_reader_catches_up() {
+    use futures::StreamExt;
+    use leptos::{ssr::StreamOptions, *};
+
+    let options = StreamOptions {
+        buffer_size: 1,
+        ..Default::default()
+    };
+    let view = |cx: Scope| {
+        let data = create_resource(cx, || (), |_| async { "data".to_string() });
+        view! { cx,
+            <Suspense fallback=|| ()>{move || data.read(cx)}</Suspense>
+            <p>"one"</p>
+            <Flush/>
+            <p>"two"</p>
+            <Flush/>
+            <p>"three"</p>
+        }
+        .into_view(cx)
+    };
+
+    let executor = TestExecutor::new();
+    let (in_order, runtime, _) =
+        ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+            view,
+            |_| "".into(),
+            move |cx| provide_context(cx, options),
+        );
+    let (out_of_order, other_runtime, _) =
+        ssr::render_to_stream_with_prefix_undisposed_with_context(
+            view,
+            |_| "".into(),
+            move |cx| provide_context(cx, options),
+        );
+
+    // nothing is read, so both are waiting for room in the channel
+    executor.run_until_stalled();
+    assert_eq!(executor.pending_tasks(), 2);
+
+    spawn_local(async move {
+        assert!(in_order
+            .collect::<Vec<_>>()
+            .await
+            .concat()
+            .contains("three"));
+        assert!(out_of_order
+            .collect::<Vec<_>>()
+            .await
+            .concat()
+            .contains("data"));
+    });
+    executor.run_until_stalled();
+    assert_eq!(executor.pending_tasks(), 0);
+
+    runtime.dispose();
+    other_runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_timeout_streams_fallback_for_slow_resource() {
+    use futures::StreamExt;
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    let executor = TestExecutor::new();
+    let (stream, runtime, _) = ssr::render_to_stream_with_prefix_undisposed(
+        |cx| {
+            let data = create_resource(
+                cx,
+                || (),
+                |_| async {
+                    let (tx, rx) = futures::channel::oneshot::channel();
+                    let mut tx = Some(tx);
+                    TestExecutor::set_timer(
+                        Box::new(move || {
+                            if let Some(tx) = tx.take() {
+                                _ = tx.send(());
+                            }
+                        }),
+                        Duration::from_secs(10),
+                        None,
+                    );
+                    _ = rx.await;
+                    "slow server fn".to_string()
+                },
+            );
+            view! { cx,
+                <Suspense fallback=|| "loading" timeout_ms=100>
+                    {move || data.read(cx)}
+                </Suspense>
+            }
+            .into_view(cx)
+        },
+        |_| "".into(),
+    );
+    let html = Rc::new(RefCell::new(String::new()));
+    spawn_local({
+        let html = Rc::clone(&html);
+        async move {
+            let mut stream = Box::pin(stream);
+            while let Some(chunk) = stream.next().await {
+                html.borrow_mut().push_str(&chunk);
+            }
+        }
+    });
+
+    executor.run_until_stalled();
+    assert!(!html.borrow().contains("<template"));
+
+    // the fallback replaces the suspended HTML once the timeout elapses
+    executor.advance_time(Duration::from_millis(100));
+    {
+        let html = html.borrow();
+        let (_, fragment) = html.split_once("<template").unwrap();
+        assert!(fragment.contains("loading"));
+        assert!(!html.contains("slow server fn"));
+    }
+
+    // and the data is still sent once it has loaded
+    executor.advance_time(Duration::from_secs(10));
+    assert!(html.borrow().contains("slow server fn"));
+    assert_eq!(executor.pending_tasks(), 0);
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_binary_resource_payload_is_sent_by_both_streams() {
+    use futures::StreamExt;
+    use leptos::{ssr::ResourcePayload, *};
+    use std::{cell::RefCell, rc::Rc};
+
+    let view = |cx: Scope| {
+        let data = create_resource(cx, || (), |_| async { "data".to_string() });
+        view! { cx,
+            <Suspense fallback=|| ()>{move || data.read(cx)}</Suspense>
+        }
+        .into_view(cx)
+    };
+    let context = |cx: Scope| provide_context(cx, ResourcePayload::Binary);
+
+    let executor = TestExecutor::new();
+    let (in_order, runtime, _) =
+        ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+            view,
+            |_| "".into(),
+            context,
+        );
+    let (out_of_order, other_runtime, _) =
+        ssr::render_to_stream_with_prefix_undisposed_with_context(
+            view,
+            |_| "".into(),
+            context,
+        );
+    let pages = Rc::new(RefCell::new(Vec::new()));
+    spawn_local({
+        let pages = Rc::clone(&pages);
+        async move {
+            let in_order = in_order.collect::<Vec<_>>().await.concat();
+            let out_of_order = out_of_order.collect::<Vec<_>>().await.concat();
+            pages.borrow_mut().extend([in_order, out_of_order]);
+        }
+    });
+    executor.run_until_stalled();
+    runtime.dispose();
+    other_runtime.dispose();
+
+    let pages = pages.take();
+    assert_eq!(pages.len(), 2);
+    for html in pages {
+        assert!(!html.contains("__LEPTOS_RESOURCE_RESOLVERS.get("));
+        let (_, payload) = html
+            .split_once(&format!(r#"id="{RESOURCE_PAYLOAD_ID}">"#))
+            .unwrap();
+        let (payload, _) = payload.split_once("</script>").unwrap();
+        let resources = decode_resource_payload(payload).unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].1, r#""data""#);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{net::AddrParseError, num::ParseIntError};
+use std::{net::AddrParseError, num::ParseIntError, str::ParseBoolError};
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
@@ -13,6 +13,8 @@ pub enum LeptosConfigError {
     ConfigError(String),
     #[error("Config Error: {0}")]
     EnvVarError(String),
+    #[error("Could not read hash file {0}")]
+    HashFileError(String),
 }
 impl From<config::ConfigError> for LeptosConfigError {
     fn from(e: config::ConfigError) -> Self {
@@ -26,6 +28,12 @@ impl From<ParseIntError> for LeptosConfigError {
     }
 }
 
+impl From<ParseBoolError> for LeptosConfigError {
+    fn from(e: ParseBoolError) -> Self {
+        Self::ConfigError(e.to_string())
+    }
+}
+
 impl From<AddrParseError> for LeptosConfigError {
     fn from(e: AddrParseError) -> Self {
         Self::ConfigError(e.to_string())
@@ -49,6 +49,24 @@ pub struct LeptosOptions {
     /// Defaults to `3001`
     #[builder(default = 3001)]
     pub reload_port: u32,
+    /// Whether the JS, WASM, and CSS files have a content hash in their names,
+    /// like `app.1a2b3c.js`, so that they can be served with immutable cache
+    /// headers. The hashes are read from [hash_file](Self::hash_file). Should
+    /// match `hash-files` in cargo-leptos (if using).
+    /// Defaults to `false`
+    #[builder(default = false)]
+    #[serde(default)]
+    pub hash_files: bool,
+    /// The path of the file with the content hashes of the JS, WASM, and CSS
+    /// files, relative to `site_root`. See [AssetHashes].
+    /// Defaults to `hash.txt`
+    #[builder(setter(into), default = default_hash_file())]
+    #[serde(default = "default_hash_file")]
+    pub hash_file: String,
+}
+
+fn default_hash_file() -> String {
+    "hash.txt".to_string()
 }
 
 impl LeptosOptions {
@@ -77,8 +95,75 @@ impl LeptosOptions {
                 .parse()?,
             reload_port: env_w_default("LEPTOS_RELOAD_PORT", "3001")?
                 .parse()?,
+            hash_files: env_w_default("LEPTOS_HASH_FILES", "false")?.parse()?,
+            hash_file: env_w_default("LEPTOS_HASH_FILE", "hash.txt")?,
         })
     }
+
+    /// Reads the content hashes of the JS, WASM, and CSS files from the
+    /// [hash_file](Self::hash_file). Returns the default (no hashes) if
+    /// [hash_files](Self::hash_files) is `false`.
+    pub fn asset_hashes(&self) -> Result<AssetHashes, LeptosConfigError> {
+        if !self.hash_files {
+            return Ok(AssetHashes::default());
+        }
+        let path = Path::new(&self.site_root).join(&self.hash_file);
+        let text = fs::read_to_string(&path).map_err(|e| {
+            LeptosConfigError::HashFileError(format!("{}: {e}", path.display()))
+        })?;
+        Ok(AssetHashes::parse(&text))
+    }
+}
+
+/// The content hashes of the files generated for the client, read from the
+/// build manifest written by cargo-leptos with `hash-files = true`. Each line
+/// of the manifest has the form `js: <hash>`, `wasm: <hash>`, or
+/// `css: <hash>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetHashes {
+    /// The hash of the JS file generated by wasm-bindgen.
+    pub js: Option<String>,
+    /// The hash of the WASM file.
+    pub wasm: Option<String>,
+    /// The hash of the CSS file.
+    pub css: Option<String>,
+}
+
+impl AssetHashes {
+    /// Parses the contents of a hash file. Unknown or malformed lines are
+    /// ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut hashes = Self::default();
+        for line in text.lines() {
+            let Some((kind, hash)) = line.split_once(':') else {
+                continue;
+            };
+            let hash = Some(hash.trim().to_string());
+            match kind.trim() {
+                "js" => hashes.js = hash,
+                "wasm" => hashes.wasm = hash,
+                "css" => hashes.css = hash,
+                _ => {}
+            }
+        }
+        hashes
+    }
+
+    /// Inserts the hash for the given file extension (`js`, `wasm`, or `css`)
+    /// before the extension of `file_name`, like `app.1a2b3c.js`. Returns
+    /// `file_name` unchanged if there is no such hash.
+    pub fn hashed(&self, file_name: &str, extension: &str) -> String {
+        let hash = match extension {
+            "js" => &self.js,
+            "wasm" => &self.wasm,
+            "css" => &self.css,
+            _ => &None,
+        };
+        match hash {
+            Some(hash) => format!("{file_name}.{hash}.{extension}"),
+            None => format!("{file_name}.{extension}"),
+        }
+    }
 }
 
 fn env_w_default(
@@ -1,4 +1,4 @@
-use crate::{env_w_default, from_str, Env, LeptosOptions};
+use crate::{env_w_default, from_str, AssetHashes, Env, LeptosOptions};
 use std::{net::SocketAddr, str::FromStr};
 
 #[test]
@@ -49,3 +49,16 @@ fn try_from_env_test() {
     );
     assert_eq!(config.reload_port, 8080);
 }
+
+#[test]
+fn asset_hashes() {
+    let hashes = AssetHashes::parse("js: 1a2b\nwasm:3c4d \n\ncss: 5e6f\nfoo\n");
+    assert_eq!(hashes.js.as_deref(), Some("1a2b"));
+    assert_eq!(hashes.wasm.as_deref(), Some("3c4d"));
+    assert_eq!(hashes.css.as_deref(), Some("5e6f"));
+    assert_eq!(hashes.hashed("app", "js"), "app.1a2b.js");
+    assert_eq!(hashes.hashed("app_bg", "wasm"), "app_bg.3c4d.wasm");
+
+    let hashes = AssetHashes::default();
+    assert_eq!(hashes.hashed("app", "css"), "app.css");
+}
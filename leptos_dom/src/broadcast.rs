@@ -0,0 +1,196 @@
+use leptos_reactive::{
+    create_signal, ReadSignal, Scope, Serializable, WriteSignal,
+};
+
+/// Creates a signal whose value is kept the same in every tab of the same
+/// site that creates a broadcast signal with the same channel name, using a
+/// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel).
+/// This is useful for state like whether the user is logged in, which
+/// should change in every tab at once.
+///
+/// The value is encoded with the same [Serializable] implementation as
+/// resources. When two tabs set it at about the same time, the last change
+/// wins in every tab. The signal starts as `T::default()`, and a new tab
+/// asks the others for their value, so it catches up shortly afterwards.
+///
+/// Unlike [create_persistent_signal](crate::create_persistent_signal), the
+/// value is not saved: it is lost once every tab is closed. On the server,
+/// this is an ordinary signal.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn LogoutButton(cx: Scope) -> impl IntoView {
+///     let (logged_in, set_logged_in) =
+///         create_broadcast_signal::<bool>(cx, "logged-in");
+///
+///     view! { cx,
+///       <button
+///         disabled=move || !logged_in.get()
+///         on:click=move |_| set_logged_in.set(false)
+///       >
+///         "Log out everywhere"
+///       </button>
+///     }
+/// }
+/// ```
+pub fn create_broadcast_signal<T>(
+    cx: Scope,
+    channel: &str,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serializable + Default + 'static,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        browser::create(cx, channel)
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = channel;
+        create_signal(cx, T::default())
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+    use leptos_reactive::{
+        create_render_effect, on_cleanup, SignalSet, SignalWith,
+    };
+    use std::{cell::RefCell, rc::Rc};
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+    // Orders changes across tabs: the later time wins, and the tab ID breaks
+    // ties.
+    type Version = (f64, u32);
+
+    // Messages are either `?`, asking the other tabs for their value, or
+    // `=<time>:<tab>:<value>`.
+    const REQUEST: &str = "?";
+
+    struct State {
+        version: Version,
+        // the text of the current value, so that a value received from
+        // another tab is not sent back
+        text: Option<String>,
+    }
+
+    pub(super) fn create<T>(
+        cx: Scope,
+        name: &str,
+    ) -> (ReadSignal<T>, WriteSignal<T>)
+    where
+        T: Serializable + Default + 'static,
+    {
+        let (value, set_value) = create_signal(cx, T::default());
+
+        let channel = match web_sys::BroadcastChannel::new(name) {
+            Ok(channel) => channel,
+            Err(e) => {
+                crate::debug_warn!(
+                    "could not open broadcast channel `{name}`: {e:?}"
+                );
+                return (value, set_value);
+            }
+        };
+
+        let tab = (js_sys::Math::random() * u32::MAX as f64) as u32;
+        let state = Rc::new(RefCell::new(State {
+            version: (0.0, tab),
+            text: None,
+        }));
+        let send = {
+            let channel = channel.clone();
+            move |version: Version, text: &str| {
+                let message = format!("={}:{}:{text}", version.0, version.1);
+                _ = channel.post_message(&JsValue::from_str(&message));
+            }
+        };
+
+        create_render_effect(cx, {
+            let state = Rc::clone(&state);
+            let send = send.clone();
+            let name = name.to_string();
+            move |prev: Option<()>| {
+                let text = value.with(|value| value.ser());
+                // the default value is not worth sending
+                if prev.is_none() {
+                    return;
+                }
+                let text = match text {
+                    Ok(text) => text,
+                    Err(e) => {
+                        crate::debug_warn!(
+                            "could not encode broadcast signal `{name}`: {e}"
+                        );
+                        return;
+                    }
+                };
+                let mut state = state.borrow_mut();
+                if state.text.as_deref() == Some(text.as_str()) {
+                    return;
+                }
+                // always newer than any change seen so far, even if the
+                // clocks of the tabs differ
+                let time = js_sys::Date::now().max(state.version.0 + 1.0);
+                state.version = (time, tab);
+                send(state.version, &text);
+                state.text = Some(text);
+            }
+        });
+
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new({
+            let state = Rc::clone(&state);
+            let name = name.to_string();
+            move |ev: web_sys::MessageEvent| {
+                let Some(message) = ev.data().as_string() else {
+                    return;
+                };
+                if message == REQUEST {
+                    let state = state.borrow();
+                    if let Some(text) = &state.text {
+                        send(state.version, text);
+                    }
+                    return;
+                }
+                let Some((version, text)) = parse(&message) else {
+                    return;
+                };
+                {
+                    let mut state = state.borrow_mut();
+                    if version <= state.version {
+                        return;
+                    }
+                    state.version = version;
+                    state.text = Some(text.to_string());
+                }
+                match T::de(text) {
+                    Ok(value) => {
+                        _ = set_value.try_set(value);
+                    }
+                    Err(e) => crate::debug_warn!(
+                        "could not decode broadcast signal `{name}`: {e}"
+                    ),
+                }
+            }
+        });
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        _ = channel.post_message(&JsValue::from_str(REQUEST));
+
+        on_cleanup(cx, move || {
+            channel.set_onmessage(None);
+            channel.close();
+            drop(on_message);
+        });
+
+        (value, set_value)
+    }
+
+    fn parse(message: &str) -> Option<(Version, &str)> {
+        let message = message.strip_prefix('=')?;
+        let (time, rest) = message.split_once(':')?;
+        let (tab, text) = rest.split_once(':')?;
+        Some(((time.parse().ok()?, tab.parse().ok()?), text))
+    }
+}
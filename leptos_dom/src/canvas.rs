@@ -0,0 +1,78 @@
+use wasm_bindgen::JsCast;
+
+/// The kind of rendering context a `<Canvas/>` creates for its `<canvas>`
+/// element.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CanvasContextKind {
+    /// A [`CanvasRenderingContext2D`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D).
+    #[default]
+    TwoD,
+    /// A [`WebGLRenderingContext`](https://developer.mozilla.org/en-US/docs/Web/API/WebGLRenderingContext).
+    WebGl,
+    /// A [`WebGL2RenderingContext`](https://developer.mozilla.org/en-US/docs/Web/API/WebGL2RenderingContext).
+    WebGl2,
+}
+
+impl CanvasContextKind {
+    /// The name of the context, as passed to
+    /// [`getContext()`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLCanvasElement/getContext).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TwoD => "2d",
+            Self::WebGl => "webgl",
+            Self::WebGl2 => "webgl2",
+        }
+    }
+}
+
+/// The rendering context of a `<canvas>` element.
+#[derive(Clone, Debug)]
+pub enum CanvasContext {
+    /// A 2D context.
+    TwoD(web_sys::CanvasRenderingContext2d),
+    /// A WebGL context.
+    WebGl(web_sys::WebGlRenderingContext),
+    /// A WebGL 2 context.
+    WebGl2(web_sys::WebGl2RenderingContext),
+}
+
+impl CanvasContext {
+    /// Gets a rendering context of the given kind for the canvas, or `None`
+    /// if the browser doesn't support it, or the canvas already has a context
+    /// of another kind.
+    pub fn new(
+        canvas: &web_sys::HtmlCanvasElement,
+        kind: CanvasContextKind,
+    ) -> Option<Self> {
+        let context = canvas.get_context(kind.as_str()).ok().flatten()?;
+        Some(match kind {
+            CanvasContextKind::TwoD => Self::TwoD(context.dyn_into().ok()?),
+            CanvasContextKind::WebGl => Self::WebGl(context.dyn_into().ok()?),
+            CanvasContextKind::WebGl2 => Self::WebGl2(context.dyn_into().ok()?),
+        })
+    }
+
+    /// The 2D context, if this is one.
+    pub fn as_2d(&self) -> Option<&web_sys::CanvasRenderingContext2d> {
+        match self {
+            Self::TwoD(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The WebGL context, if this is one.
+    pub fn as_webgl(&self) -> Option<&web_sys::WebGlRenderingContext> {
+        match self {
+            Self::WebGl(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The WebGL 2 context, if this is one.
+    pub fn as_webgl2(&self) -> Option<&web_sys::WebGl2RenderingContext> {
+        match self {
+            Self::WebGl2(context) => Some(context),
+            _ => None,
+        }
+    }
+}
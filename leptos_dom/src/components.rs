@@ -1,6 +1,7 @@
 mod dyn_child;
 mod each;
 mod errors;
+mod flush;
 mod fragment;
 mod unit;
 
@@ -13,6 +14,7 @@ use crate::{mount_child, prepare_to_move, MountKind, Mountable};
 pub use dyn_child::*;
 pub use each::*;
 pub use errors::*;
+pub use flush::*;
 pub use fragment::*;
 use leptos_reactive::Scope;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -262,14 +264,97 @@ where
             children_fn,
         } = self;
 
+        #[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+        let _measure = ComponentMeasure::start(&name, &id);
+
+        #[cfg(debug_assertions)]
+        let stack_entry =
+            leptos_reactive::ComponentStackEntry::push(name.clone());
+
         let mut repr = ComponentRepr::new_with_id(name, id);
 
         // disposed automatically when the parent scope is disposed
         let (child, _) = cx
             .run_child_scope(|cx| cx.untrack(|| children_fn(cx).into_view(cx)));
 
+        #[cfg(debug_assertions)]
+        drop(stack_entry);
+
         repr.children.push(child);
 
         repr.into_view(cx)
     }
 }
+
+/// The names of the components that are being created on the current thread,
+/// from the innermost to the outermost. While an effect, memo or event
+/// handler runs after the view has been built, these are the components that
+/// created it. This is always empty in release builds.
+pub fn component_stack() -> Vec<Cow<'static, str>> {
+    leptos_reactive::current_component_stack()
+}
+
+/// Formats the [component_stack] like ``in `<TodoItem/>` in `<TodoList/>` in
+/// `<App/>` ``, or returns an empty string if it is empty.
+pub fn format_component_stack() -> String {
+    component_stack()
+        .iter()
+        .map(|name| format!("in `<{name}/>`"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Appends the [component_stack] to a message, on a new line. Used by
+/// [debug_warn!](crate::debug_warn).
+#[doc(hidden)]
+pub fn with_component_stack(mut message: String) -> String {
+    let stack = format_component_stack();
+    if !stack.is_empty() {
+        message.push_str("\n    ");
+        message.push_str(&stack);
+    }
+    message
+}
+
+/// Measures how long a component takes to render or hydrate, so that it shows
+/// up in the Performance tab of the browser's DevTools. The measure ends when
+/// this is dropped.
+#[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+struct ComponentMeasure {
+    performance: Option<web_sys::Performance>,
+    mark: String,
+    measure: String,
+}
+
+#[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+impl ComponentMeasure {
+    fn start(name: &str, id: &HydrationKey) -> Self {
+        let performance = crate::window().performance();
+        // the hydration key keeps the marks of nested components with the
+        // same name apart
+        let mark = format!("leptos:{name}:{id}");
+        let phase = if HydrationCtx::is_hydrating() {
+            "hydrate"
+        } else {
+            "render"
+        };
+        if let Some(performance) = &performance {
+            _ = performance.mark(&mark);
+        }
+        Self {
+            performance,
+            mark,
+            measure: format!("<{name}/> ({phase})"),
+        }
+    }
+}
+
+#[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+impl Drop for ComponentMeasure {
+    fn drop(&mut self) {
+        if let Some(performance) = &self.performance {
+            _ = performance.measure_with_start_mark(&self.measure, &self.mark);
+            performance.clear_marks_with_mark_name(&self.mark);
+        }
+    }
+}
@@ -8,7 +8,7 @@ use std::{borrow::Cow, cell::RefCell, fmt, ops::Deref, rc::Rc};
 cfg_if! {
   if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
     use crate::{mount_child, prepare_to_move, unmount_child, MountKind, Mountable};
-    use leptos_reactive::{create_effect, ScopeDisposer};
+    use leptos_reactive::{create_render_effect, ScopeDisposer};
     use wasm_bindgen::JsCast;
   }
 }
@@ -183,7 +183,7 @@ where
             let span = tracing::Span::current();
 
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            create_effect(
+            create_render_effect(
                 cx,
                 move |prev_run: Option<(
                     Option<web_sys::Node>,
@@ -192,6 +192,18 @@ where
                     #[cfg(debug_assertions)]
                     let _guard = span.enter();
 
+                    // in strict mode, render the child once and throw it
+                    // away, picking up the hydration IDs where they were
+                    #[cfg(debug_assertions)]
+                    if leptos_reactive::is_strict_mode()
+                        && !HydrationCtx::is_hydrating()
+                    {
+                        let (_, disposer) = HydrationCtx::scoped(|| {
+                            cx.run_child_scope(|cx| child_fn().into_view(cx))
+                        });
+                        disposer.dispose();
+                    }
+
                     let (new_child, disposer) =
                         cx.run_child_scope(|cx| child_fn().into_view(cx));
 
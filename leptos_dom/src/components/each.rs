@@ -4,7 +4,7 @@ cfg_if! {
   if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
     use crate::{mount_child, prepare_to_move, MountKind, Mountable, RANGE};
     use once_cell::unsync::OnceCell;
-    use leptos_reactive::create_effect;
+    use leptos_reactive::create_render_effect;
     use rustc_hash::FxHasher;
     use std::hash::BuildHasherDefault;
     use wasm_bindgen::JsCast;
@@ -372,7 +372,7 @@ where
 
         cfg_if::cfg_if! {
           if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-            create_effect(cx, move |prev_hash_run: Option<HashRun<FxIndexSet<K>>>| {
+            create_render_effect(cx, move |prev_hash_run: Option<HashRun<FxIndexSet<K>>>| {
               let mut children_borrow = children.borrow_mut();
 
               #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -1,12 +1,39 @@
 use crate::{HydrationCtx, IntoView};
 use cfg_if::cfg_if;
+use indexmap::IndexMap;
 use leptos_reactive::{signal_prelude::*, use_context, RwSignal};
 use std::{borrow::Cow, collections::HashMap, error::Error, sync::Arc};
 
 /// A struct to hold all the possible errors that could be provided by child Views
+///
+/// Errors are kept in the order they were first inserted, so iterating over
+/// them in a view gives a stable order. Anything that can be converted into a
+/// `Box<dyn Error + Send + Sync>` can be inserted, including `anyhow::Error`
+/// and `String`.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let errors = create_rw_signal(cx, Errors::default());
+/// errors.update(|errors| {
+///     errors.insert("email".into(), "Enter an email address.");
+///     errors.insert("name".into(), "Enter your name.");
+/// });
+///
+/// view! { cx,
+///   <ul>
+///     <For
+///       each=move || errors.get()
+///       key=|(key, _)| key.clone()
+///       view=move |cx, (_, error)| view! { cx, <li>{error.to_string()}</li> }
+///     />
+///   </ul>
+/// }
+/// # });
+/// ```
 #[derive(Debug, Clone, Default)]
 #[repr(transparent)]
-pub struct Errors(HashMap<ErrorKey, Arc<dyn Error + Send + Sync>>);
+pub struct Errors(IndexMap<ErrorKey, Arc<dyn Error + Send + Sync>>);
 
 /// A unique key for an error that occurs at a particular location in the user interface.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
@@ -33,13 +60,55 @@ impl IntoIterator for Errors {
     }
 }
 
+impl<'a> IntoIterator for &'a Errors {
+    type Item = (&'a ErrorKey, &'a Arc<dyn Error + Send + Sync>);
+    type IntoIter = Iter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, E> FromIterator<(K, E)> for Errors
+where
+    K: Into<ErrorKey>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, E)>>(iter: I) -> Self {
+        let mut errors = Errors::default();
+        errors.extend(iter);
+        errors
+    }
+}
+
+impl<K, E> Extend<(K, E)> for Errors
+where
+    K: Into<ErrorKey>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn extend<I: IntoIterator<Item = (K, E)>>(&mut self, iter: I) {
+        for (key, error) in iter {
+            self.insert(key.into(), error);
+        }
+    }
+}
+
+/// Converts validation errors with one message per field, like the
+/// `FieldErrors` of a form, keyed by field name. As the map has no order of
+/// its own, the fields are sorted by name.
+impl From<HashMap<String, String>> for Errors {
+    fn from(field_errors: HashMap<String, String>) -> Self {
+        let mut field_errors = field_errors.into_iter().collect::<Vec<_>>();
+        field_errors.sort_by(|(a, _), (b, _)| a.cmp(b));
+        field_errors.into_iter().collect()
+    }
+}
+
 /// An owning iterator over all the errors contained in the [Errors] struct.
 #[repr(transparent)]
 pub struct IntoIter(
-    std::collections::hash_map::IntoIter<
-        ErrorKey,
-        Arc<dyn Error + Send + Sync>,
-    >,
+    indexmap::map::IntoIter<ErrorKey, Arc<dyn Error + Send + Sync>>,
 );
 
 impl Iterator for IntoIter {
@@ -56,11 +125,7 @@ impl Iterator for IntoIter {
 /// An iterator over all the errors contained in the [Errors] struct.
 #[repr(transparent)]
 pub struct Iter<'a>(
-    std::collections::hash_map::Iter<
-        'a,
-        ErrorKey,
-        Arc<dyn Error + Send + Sync>,
-    >,
+    indexmap::map::Iter<'a, ErrorKey, Arc<dyn Error + Send + Sync>>,
 );
 
 impl<'a> Iterator for Iter<'a> {
@@ -77,7 +142,7 @@ impl<'a> Iterator for Iter<'a> {
 impl<T, E> IntoView for Result<T, E>
 where
     T: IntoView + 'static,
-    E: Error + Send + Sync + 'static,
+    E: Into<Box<dyn Error + Send + Sync>> + 'static,
 {
     fn into_view(self, cx: leptos_reactive::Scope) -> crate::View {
         let id = ErrorKey(HydrationCtx::peek().previous.into());
@@ -86,7 +151,7 @@ where
             Ok(stuff) => {
                 if let Some(errors) = errors {
                     errors.update(|errors| {
-                        errors.0.remove(&id);
+                        errors.remove(&id);
                     });
                 }
                 stuff.into_view(cx)
@@ -140,20 +205,28 @@ impl Errors {
         self.0.is_empty()
     }
 
-    /// Add an error to Errors that will be processed by `<ErrorBoundary/>`
+    /// The number of errors.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Add an error to Errors that will be processed by `<ErrorBoundary/>`.
+    /// If there is already an error with this key, it is replaced, and keeps
+    /// its place in the order.
     pub fn insert<E>(&mut self, key: ErrorKey, error: E)
     where
-        E: Error + Send + Sync + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
     {
-        self.0.insert(key, Arc::new(error));
+        self.0.insert(key, Arc::from(error.into()));
     }
 
     /// Add an error with the default key for errors outside the reactive system
     pub fn insert_with_default_key<E>(&mut self, error: E)
     where
-        E: Error + Send + Sync + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
     {
-        self.0.insert(Default::default(), Arc::new(error));
+        self.insert(Default::default(), error);
     }
 
     /// Remove an error to Errors that will be processed by `<ErrorBoundary/>`
@@ -161,10 +234,28 @@ impl Errors {
         &mut self,
         key: &ErrorKey,
     ) -> Option<Arc<dyn Error + Send + Sync>> {
-        self.0.remove(key)
+        self.0.shift_remove(key)
+    }
+
+    /// Removes all the errors.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// The error with the given key, if there is one.
+    #[inline(always)]
+    pub fn get(&self, key: &ErrorKey) -> Option<&Arc<dyn Error + Send + Sync>> {
+        self.0.get(key)
+    }
+
+    /// Returns `true` if there is an error with the given key.
+    #[inline(always)]
+    pub fn contains_key(&self, key: &ErrorKey) -> bool {
+        self.0.contains_key(key)
     }
 
-    /// An iterator over all the errors, in arbitrary order.
+    /// An iterator over all the errors, in the order they were inserted.
     #[inline(always)]
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.0.iter())
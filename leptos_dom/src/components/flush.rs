@@ -0,0 +1,34 @@
+use crate::{ComponentRepr, IntoView, View};
+use leptos_reactive::Scope;
+
+/// The name given to the component rendered by [`FlushMarker`], which is
+/// recognized when rendering an in-order HTML stream.
+const FLUSH_MARKER_NAME: &str = "leptos-flush";
+
+/// Marks a point in an in-order HTML stream at which all the HTML rendered
+/// so far should be sent to the client, instead of being buffered until the
+/// next `<Suspense/>` boundary.
+///
+/// This renders nothing, and has no effect outside of in-order streaming.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlushMarker;
+
+impl IntoView for FlushMarker {
+    fn into_view(self, cx: Scope) -> View {
+        ComponentRepr::new(FLUSH_MARKER_NAME).into_view(cx)
+    }
+}
+
+impl ComponentRepr {
+    /// Whether this component was rendered by a [`FlushMarker`].
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    pub(crate) fn is_flush_marker(&self) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(any(debug_assertions, feature = "ssr"))] {
+                self.name == FLUSH_MARKER_NAME
+            } else {
+                false
+            }
+        }
+    }
+}
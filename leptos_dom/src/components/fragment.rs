@@ -84,6 +84,38 @@ impl Fragment {
         }
     }
 
+    /// Creates a new [`Fragment`] whose hydration IDs are derived from `key`,
+    /// rather than from its position in the view.
+    ///
+    /// Normally, every node takes the next hydration ID, so a structure that
+    /// renders a different number of nodes on the server and on the client
+    /// (for example, an `Option` that is `Some` on one and `None` on the
+    /// other) shifts the IDs of everything rendered after it, and hydration
+    /// fails. The nodes of a keyed fragment are numbered from its key instead,
+    /// and it does not advance the IDs of the nodes around it, so a mismatch
+    /// stays inside the fragment.
+    ///
+    /// `key` must be unique among the keyed fragments of the same component,
+    /// and should only contain characters that are valid in an HTML `id`.
+    ///
+    /// ```
+    /// # use leptos::*;
+    /// # fn user_name() -> Option<String> { None }
+    /// # let runtime = create_runtime();
+    /// # _ = create_scope(runtime, |cx| {
+    /// let greeting = Fragment::with_key("greeting", || {
+    ///     vec![user_name()
+    ///         .map(|name| view! { cx, <p>"Hello, " {name}</p> })
+    ///         .into_view(cx)]
+    /// });
+    /// # });
+    /// ```
+    pub fn with_key(key: &str, nodes: impl FnOnce() -> Vec<View>) -> Self {
+        let id = HydrationCtx::keyed(key);
+        let nodes = HydrationCtx::with_key(id.clone(), nodes);
+        Self::new_with_id(id, nodes)
+    }
+
     /// Gives access to the [View] children contained within the fragment.
     #[inline(always)]
     pub fn as_children(&self) -> &[View] {
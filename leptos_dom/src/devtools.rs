@@ -0,0 +1,113 @@
+use leptos_reactive::Scope;
+
+/// Streams the reactive graph of the current runtime to a DevTools server,
+/// such as a browser extension, over a
+/// [`WebSocket`](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket)
+/// at `url`.
+///
+/// Every message is JSON. Once the socket opens, it is sent
+/// `{"type": "graph", "graph": ...}` with a
+/// [`GraphSnapshot`](leptos_reactive::GraphSnapshot) of the whole graph, and
+/// then `{"type": "update", "node": ...}` with a
+/// [`NodeSnapshot`](leptos_reactive::NodeSnapshot) each time a named signal,
+/// memo or effect changes. Sending the text `snapshot` to the app asks for a
+/// new snapshot of the whole graph.
+///
+/// The socket is closed when the scope is disposed. On the server, this does
+/// nothing.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     if cfg!(debug_assertions) {
+///         connect_devtools(cx, "ws://localhost:3030");
+///     }
+///
+///     let (count, set_count) = create_signal_named(cx, "count", 0);
+///     view! { cx,
+///       <button on:click=move |_| set_count.update(|n| *n += 1)>
+///         {count}
+///       </button>
+///     }
+/// }
+/// ```
+pub fn connect_devtools(cx: Scope, url: &str) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        browser::connect(cx, url)
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (cx, url);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+    use leptos_reactive::on_cleanup;
+    use serde_json::json;
+    use std::rc::Rc;
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    // The only message the DevTools can send, to ask for the whole graph.
+    const REQUEST: &str = "snapshot";
+
+    pub(super) fn connect(cx: Scope, url: &str) {
+        let socket = match web_sys::WebSocket::new(url) {
+            Ok(socket) => Rc::new(socket),
+            Err(e) => {
+                crate::debug_warn!(
+                    "could not connect to DevTools at `{url}`: {e:?}"
+                );
+                return;
+            }
+        };
+
+        let send_graph = {
+            let socket = Rc::clone(&socket);
+            let runtime = cx.runtime;
+            move || {
+                let graph = runtime.graph_snapshot();
+                send(&socket, json!({ "type": "graph", "graph": graph }));
+            }
+        };
+
+        let on_open = Closure::<dyn Fn()>::new(send_graph.clone());
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new(
+            move |ev: web_sys::MessageEvent| {
+                if ev.data().as_string().as_deref() == Some(REQUEST) {
+                    send_graph();
+                }
+            },
+        );
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        let on_update = cx.runtime.on_node_update({
+            let socket = Rc::clone(&socket);
+            move |node| {
+                send(&socket, json!({ "type": "update", "node": node }));
+            }
+        });
+
+        on_cleanup(cx, move || {
+            cx.runtime.remove_node_update(on_update);
+            socket.set_onopen(None);
+            socket.set_onmessage(None);
+            _ = socket.close();
+            drop(on_open);
+            drop(on_message);
+        });
+    }
+
+    // Updates that happen before the socket opens, or after it closes, are
+    // dropped: the graph sent when it opens already includes them.
+    fn send(socket: &web_sys::WebSocket, message: serde_json::Value) {
+        if socket.ready_state() == web_sys::WebSocket::OPEN {
+            _ = socket.send_with_str(&message.to_string());
+        }
+    }
+}
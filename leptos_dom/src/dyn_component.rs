@@ -0,0 +1,126 @@
+use crate::{IntoView, View};
+use leptos_reactive::Scope;
+use std::{fmt, rc::Rc};
+
+/// A view whose type has been erased, so that views of different types can be
+/// stored together and rendered later, e.g., in a `Vec<Box<dyn IntoAnyView>>`.
+///
+/// `Box<dyn IntoView>` can't be rendered, as [IntoView::into_view] takes
+/// `self` by value; this trait takes a `Box<Self>` instead. Every type that
+/// implements [IntoView] implements it, and can be boxed with
+/// [IntoView::into_any].
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let blocks: Vec<Box<dyn IntoAnyView>> = vec![
+///     "Some text".into_any(),
+///     Box::new(view! { cx, <h1>"Title"</h1> }),
+///     (move || 42).into_any(),
+/// ];
+///
+/// view! { cx, <article>{blocks}</article> }
+/// # ;
+/// # });
+/// ```
+pub trait IntoAnyView {
+    /// Converts the boxed value into a [View].
+    fn into_any_view(self: Box<Self>, cx: Scope) -> View;
+}
+
+impl<T: IntoView> IntoAnyView for T {
+    #[inline(always)]
+    fn into_any_view(self: Box<Self>, cx: Scope) -> View {
+        (*self).into_view(cx)
+    }
+}
+
+impl IntoView for Box<dyn IntoAnyView> {
+    #[inline(always)]
+    fn into_view(self, cx: Scope) -> View {
+        self.into_any_view(cx)
+    }
+}
+
+/// A component and its props, stored so that it can be rendered later, and
+/// as many times as needed. Components of different types can be stored
+/// together, e.g., in a table of plugins, or a layout loaded from a CMS.
+///
+/// The props are cloned each time the component is rendered. The props
+/// generated by `#[component]` can derive [Clone] with
+/// `#[component(props(derive(Clone)))]`.
+///
+/// ```
+/// # use leptos::*;
+/// # use std::collections::HashMap;
+/// #[component(props(derive(Clone)))]
+/// fn Heading(cx: Scope, text: String) -> impl IntoView {
+///     view! { cx, <h2>{text}</h2> }
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// let mut blocks: HashMap<&str, DynComponent> = HashMap::new();
+/// blocks.insert(
+///     "heading",
+///     DynComponent::new(
+///         Heading,
+///         HeadingProps::builder().text("Welcome".to_string()).build(),
+///     ),
+/// );
+/// blocks.insert("divider", DynComponent::from_fn(|cx| view! { cx, <hr/> }));
+///
+/// let layout = ["heading", "divider"];
+/// view! { cx,
+///   <main>
+///     {layout.iter().filter_map(|name| blocks.get(name).cloned()).collect_view(cx)}
+///   </main>
+/// }
+/// # ;
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct DynComponent(Rc<dyn Fn(Scope) -> View>);
+
+impl DynComponent {
+    /// Stores a component function, like one created with `#[component]`,
+    /// and the props to render it with.
+    pub fn new<P, V>(
+        component: impl Fn(Scope, P) -> V + 'static,
+        props: P,
+    ) -> Self
+    where
+        P: Clone + 'static,
+        V: IntoView,
+    {
+        Self(Rc::new(move |cx| {
+            component(cx, props.clone()).into_view(cx)
+        }))
+    }
+
+    /// Stores a function that renders a view.
+    pub fn from_fn<V>(f: impl Fn(Scope) -> V + 'static) -> Self
+    where
+        V: IntoView,
+    {
+        Self(Rc::new(move |cx| f(cx).into_view(cx)))
+    }
+
+    /// Renders the component.
+    #[inline(always)]
+    pub fn render(&self, cx: Scope) -> View {
+        (self.0)(cx)
+    }
+}
+
+impl fmt::Debug for DynComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynComponent").finish()
+    }
+}
+
+impl IntoView for DynComponent {
+    #[inline(always)]
+    fn into_view(self, cx: Scope) -> View {
+        self.render(cx)
+    }
+}
@@ -1,12 +1,22 @@
 pub mod typed;
 
-use std::{borrow::Cow, cell::RefCell, collections::HashSet};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
-use wasm_bindgen::{
-    convert::FromWasmAbi, intern, prelude::Closure, JsCast, JsValue,
-    UnwrapThrowExt,
-};
+use leptos_reactive::ComponentStack;
+#[cfg(all(target_arch = "wasm32", feature = "web", debug_assertions))]
+use leptos_reactive::{with_error_source, ErrorSource};
+use std::borrow::Cow;
+#[cfg(not(feature = "undelegated-events"))]
+use std::{cell::RefCell, collections::HashSet};
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use wasm_bindgen::{convert::FromWasmAbi, intern, prelude::Closure, JsCast};
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "web",
+    not(feature = "undelegated-events")
+))]
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
 
+#[cfg(not(feature = "undelegated-events"))]
 thread_local! {
     pub(crate) static GLOBAL_EVENTS: RefCell<HashSet<Cow<'static, str>>> = RefCell::new(HashSet::new());
 }
@@ -44,28 +54,39 @@ pub fn add_event_helper<E: crate::ev::EventDescriptor + 'static>(
 
 /// Adds an event listener to the target DOM element using implicit event delegation.
 #[doc(hidden)]
-#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "web",
+    not(feature = "undelegated-events")
+))]
 pub fn add_event_listener<E>(
     target: &web_sys::Element,
     key: Cow<'static, str>,
     event_name: Cow<'static, str>,
-    #[cfg(debug_assertions)] mut cb: Box<dyn FnMut(E)>,
-    #[cfg(not(debug_assertions))] cb: Box<dyn FnMut(E)>,
+    mut cb: Box<dyn FnMut(E)>,
     options: &Option<web_sys::AddEventListenerOptions>,
 ) where
     E: FromWasmAbi + 'static,
 {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = Box::new(move |e| {
-          leptos_reactive::SpecialNonReactiveZone::enter();
-          let _guard = span.enter();
-          cb(e);
-          leptos_reactive::SpecialNonReactiveZone::exit();
-        });
-      }
-    }
+    #[cfg(debug_assertions)]
+    let span = ::tracing::Span::current();
+    #[cfg(debug_assertions)]
+    let source = ErrorSource::EventHandler {
+        event: event_name.clone(),
+    };
+    let stack = ComponentStack::capture();
+    let cb = Box::new(move |e| {
+        #[cfg(debug_assertions)]
+        leptos_reactive::SpecialNonReactiveZone::enter();
+        #[cfg(debug_assertions)]
+        let _guard = span.enter();
+        #[cfg(debug_assertions)]
+        with_error_source(source.clone(), || stack.run(|| cb(e)));
+        #[cfg(not(debug_assertions))]
+        stack.run(|| cb(e));
+        #[cfg(debug_assertions)]
+        leptos_reactive::SpecialNonReactiveZone::exit();
+    });
 
     let cb = Closure::wrap(cb as Box<dyn FnMut(E)>).into_js_value();
     let key = intern(&key);
@@ -73,28 +94,57 @@ pub fn add_event_listener<E>(
     add_delegated_event_listener(&key, event_name, options);
 }
 
+/// Adds an event listener directly to the target DOM element. With the
+/// `undelegated-events` feature, this is used for every event, so that the
+/// code for event delegation can be left out of the binary.
+#[doc(hidden)]
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "web",
+    feature = "undelegated-events"
+))]
+#[inline(always)]
+pub fn add_event_listener<E>(
+    target: &web_sys::Element,
+    _key: Cow<'static, str>,
+    event_name: Cow<'static, str>,
+    cb: Box<dyn FnMut(E)>,
+    options: &Option<web_sys::AddEventListenerOptions>,
+) where
+    E: FromWasmAbi + 'static,
+{
+    add_event_listener_undelegated(target, &event_name, cb, options)
+}
+
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn add_event_listener_undelegated<E>(
     target: &web_sys::Element,
     event_name: &str,
-    #[cfg(debug_assertions)] mut cb: Box<dyn FnMut(E)>,
-    #[cfg(not(debug_assertions))] cb: Box<dyn FnMut(E)>,
+    mut cb: Box<dyn FnMut(E)>,
     options: &Option<web_sys::AddEventListenerOptions>,
 ) where
     E: FromWasmAbi + 'static,
 {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = Box::new(move |e| {
-          leptos_reactive::SpecialNonReactiveZone::enter();
-          let _guard = span.enter();
-          cb(e);
-          leptos_reactive::SpecialNonReactiveZone::exit();
-        });
-      }
-    }
+    #[cfg(debug_assertions)]
+    let span = ::tracing::Span::current();
+    #[cfg(debug_assertions)]
+    let source = ErrorSource::EventHandler {
+        event: Cow::Owned(event_name.to_string()),
+    };
+    let stack = ComponentStack::capture();
+    let cb = Box::new(move |e| {
+        #[cfg(debug_assertions)]
+        leptos_reactive::SpecialNonReactiveZone::enter();
+        #[cfg(debug_assertions)]
+        let _guard = span.enter();
+        #[cfg(debug_assertions)]
+        with_error_source(source.clone(), || stack.run(|| cb(e)));
+        #[cfg(not(debug_assertions))]
+        stack.run(|| cb(e));
+        #[cfg(debug_assertions)]
+        leptos_reactive::SpecialNonReactiveZone::exit();
+    });
 
     let event_name = intern(event_name);
     let cb = Closure::wrap(cb as Box<dyn FnMut(E)>).into_js_value();
@@ -112,7 +162,11 @@ pub(crate) fn add_event_listener_undelegated<E>(
 }
 
 // cf eventHandler in ryansolid/dom-expressions
-#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "web",
+    not(feature = "undelegated-events")
+))]
 pub(crate) fn add_delegated_event_listener(
     key: &str,
     event_name: Cow<'static, str>,
@@ -1,8 +1,13 @@
 //! A variety of DOM utility functions.
 
-use crate::{events::typed as ev, is_server, window};
-use leptos_reactive::{on_cleanup, Scope};
-use std::time::Duration;
+use crate::{
+    events::typed as ev, html::ElementDescriptor, is_server, window, NodeRef,
+};
+use leptos_reactive::{
+    create_render_effect, create_signal, on_cleanup, ReadSignal, Scope,
+    Serializable, SignalSet,
+};
+use std::{cell::Cell, rc::Rc, time::Duration};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
 /// Sets a property on a DOM element.
@@ -189,6 +194,10 @@ impl TimeoutHandle {
     /// Cancels the timeout to which this refers.
     /// See [`clearTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/clearTimeout)
     pub fn clear(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if leptos_reactive::TestExecutor::clear_timer(self.0) {
+            return;
+        }
         window().clear_timeout_with_handle(self.0);
     }
 }
@@ -226,6 +235,21 @@ pub fn set_timeout_with_handle(
       }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    if leptos_reactive::TestExecutor::is_installed() {
+        let mut cb = Some(cb);
+        let id = leptos_reactive::TestExecutor::set_timer(
+            Box::new(move || {
+                if let Some(cb) = cb.take() {
+                    cb();
+                }
+            }),
+            duration,
+            None,
+        );
+        return Ok(TimeoutHandle(id));
+    }
+
     #[inline(never)]
     fn st(cb: JsValue, duration: Duration) -> Result<TimeoutHandle, JsValue> {
         window()
@@ -321,6 +345,10 @@ impl IntervalHandle {
     /// Cancels the repeating event to which this refers.
     /// See [`clearInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/clearInterval)
     pub fn clear(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if leptos_reactive::TestExecutor::clear_timer(self.0) {
+            return;
+        }
         window().clear_interval_with_handle(self.0);
     }
 }
@@ -384,6 +412,16 @@ pub fn set_interval_with_handle(
       }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    if leptos_reactive::TestExecutor::is_installed() {
+        let id = leptos_reactive::TestExecutor::set_timer(
+            Box::new(cb),
+            duration,
+            Some(duration),
+        );
+        return Ok(IntervalHandle(id));
+    }
+
     #[inline(never)]
     fn si(
         cb: Box<dyn Fn()>,
@@ -474,6 +512,510 @@ pub fn ssr_event_listener<E: crate::ev::EventDescriptor + 'static>(
     event: E,
     event_handler: impl FnMut(E::EventType) + 'static,
 ) {
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    crate::ssr_static::report_event_listener(&event);
     _ = event;
     _ = event_handler;
 }
+
+/// Registers the [service worker](https://developer.mozilla.org/en-US/docs/Web/API/Service_Worker_API)
+/// at the given script URL, which can cache the app's assets so that it keeps
+/// working offline. Does nothing on the server, or if the browser does not
+/// support service workers; failures are logged to the console.
+///
+/// The server integrations can write a precache manifest listing the app's
+/// JS, WASM, and CSS files (see `write_precache_manifest`), for the service
+/// worker to cache when it is installed.
+pub fn register_service_worker(script_url: &str) {
+    if is_server() {
+        return;
+    }
+
+    let container = window().navigator().service_worker();
+    // `navigator.serviceWorker` is undefined outside of secure contexts
+    if container.is_undefined() {
+        crate::debug_warn!(
+            "could not register service worker at {script_url}: service \
+             workers are not available"
+        );
+        return;
+    }
+
+    let promise = container.register(script_url);
+    let script_url = script_url.to_string();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = wasm_bindgen_futures::JsFuture::from(promise).await {
+            crate::error!(
+                "could not register service worker at {script_url}: {e:?}"
+            );
+        }
+    });
+}
+
+/// Returns a signal that is `true` while the browser is online, and `false`
+/// while it is offline, following the `online` and `offline` events on the
+/// `Window`. Always `true` on the server.
+///
+/// It starts out `true` in the browser too, so that a hydrated view matches
+/// the server-rendered HTML, and reads `navigator.onLine` in the next
+/// animation frame, once the view has been mounted.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn OfflineBanner(cx: Scope) -> impl IntoView {
+///     let online = use_online_status(cx);
+///
+///     view! { cx,
+///       <Show when=move || !online.get() fallback=|_| ()>
+///         <p>"You are offline. Changes will be saved when you reconnect."</p>
+///       </Show>
+///     }
+/// }
+/// ```
+pub fn use_online_status(cx: Scope) -> leptos_reactive::ReadSignal<bool> {
+    let (online, set_online) = leptos_reactive::create_signal(cx, true);
+
+    if !is_server() {
+        request_animation_frame(move || {
+            _ = set_online.try_set(window().navigator().on_line());
+        });
+        let on_online = Closure::<dyn Fn()>::new(move || {
+            _ = set_online.try_set(true);
+        });
+        let on_offline = Closure::<dyn Fn()>::new(move || {
+            _ = set_online.try_set(false);
+        });
+        let window = window();
+        _ = window.add_event_listener_with_callback(
+            "online",
+            on_online.as_ref().unchecked_ref(),
+        );
+        _ = window.add_event_listener_with_callback(
+            "offline",
+            on_offline.as_ref().unchecked_ref(),
+        );
+        on_cleanup(cx, move || {
+            _ = window.remove_event_listener_with_callback(
+                "online",
+                on_online.as_ref().unchecked_ref(),
+            );
+            _ = window.remove_event_listener_with_callback(
+                "offline",
+                on_offline.as_ref().unchecked_ref(),
+            );
+        });
+    }
+
+    online
+}
+
+/// Downloads the file at the given URL by navigating to it, rather than
+/// fetching it, so that the browser saves it the same way it would when a
+/// link to it is clicked. The current page stays open, as long as the server
+/// responds with `Content-Disposition: attachment`, as it does for server
+/// functions that return a `FileResponse`. Does nothing on the server.
+///
+/// To let the file be downloaded before (or without) the WASM loading, use the
+/// same URL as the `href` of a link instead.
+///
+/// ```rust,ignore
+/// let url = ExportTodos { user: 1 }.download_url()?;
+/// view! { cx,
+///   <button on:click=move |_| start_download(&url)>"Export"</button>
+/// }
+/// ```
+pub fn start_download(url: &str) {
+    if is_server() {
+        return;
+    }
+
+    if let Err(e) = location().set_href(url) {
+        crate::error!("could not download {url}: {e:?}");
+    }
+}
+
+/// Leaves a copy of the elements in `views` in the DOM, just before them,
+/// with the given `class` added, and removes the copies after `duration`.
+/// Call this right before the `views` are replaced, so that the outgoing
+/// content can be animated out (for example, cross-fading between pages) with
+/// CSS like `.exiting { position: absolute; animation: fade-out 300ms; }`.
+///
+/// The copies are not interactive: they have no event listeners and are not
+/// updated by any signals. Does nothing on the server.
+pub fn keep_exiting_views(
+    views: &[crate::View],
+    class: &str,
+    duration: Duration,
+) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use crate::Mountable;
+
+        let mut copies = Vec::new();
+        for view in views {
+            if matches!(view, crate::View::Transparent(_)) {
+                continue;
+            }
+            let first = view.get_opening_node();
+            let last = view.get_closing_node();
+            let parent = match first.parent_node() {
+                Some(parent) => parent,
+                None => continue,
+            };
+
+            let mut node = Some(first.clone());
+            while let Some(current) = node {
+                if current.dyn_ref::<web_sys::Element>().is_some() {
+                    if let Ok(copy) = current.clone_node_with_deep(true) {
+                        let copy = copy.unchecked_into::<web_sys::Element>();
+                        _ = copy.class_list().add_1(class);
+                        _ = parent.insert_before(&copy, Some(&first));
+                        copies.push(copy);
+                    }
+                }
+                if current == last {
+                    break;
+                }
+                node = current.next_sibling();
+            }
+        }
+
+        if !copies.is_empty() {
+            set_timeout(
+                move || {
+                    for copy in copies {
+                        copy.remove();
+                    }
+                },
+                duration,
+            );
+        }
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = views;
+        _ = class;
+        _ = duration;
+    }
+}
+
+/// The type under which [set_drag_data] stores its payload in the
+/// [`DataTransfer`](web_sys::DataTransfer).
+const DRAG_DATA_TYPE: &str = "application/x-leptos";
+
+/// Stores `data` in the [`DataTransfer`](web_sys::DataTransfer) of a drag
+/// event, so that it can be read with [get_drag_data] (or by
+/// [use_droppable]) where it is dropped. Call this in an `on:dragstart`
+/// handler.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Card(cx: Scope, id: usize) -> impl IntoView {
+///     view! { cx,
+///       <li draggable="true" on:dragstart=move |ev| set_drag_data(&ev, &id)>
+///         {id}
+///       </li>
+///     }
+/// }
+/// ```
+pub fn set_drag_data<T: Serializable>(ev: &web_sys::DragEvent, data: &T) {
+    match (ev.data_transfer(), data.ser()) {
+        (Some(transfer), Ok(data)) => {
+            _ = transfer.set_data(DRAG_DATA_TYPE, &data);
+        }
+        (_, Err(e)) => crate::error!("could not serialize drag data: {e}"),
+        _ => {}
+    }
+}
+
+/// Reads the data stored with [set_drag_data] from a drag event, usually in
+/// an `on:drop` handler. Returns `None` if the event carries no data of this
+/// kind, e.g., because a file or some text was dropped instead.
+pub fn get_drag_data<T: Serializable>(ev: &web_sys::DragEvent) -> Option<T> {
+    let data = ev.data_transfer()?.get_data(DRAG_DATA_TYPE).ok()?;
+    if data.is_empty() {
+        return None;
+    }
+    T::de(&data).ok()
+}
+
+/// The state of a drop target created with [use_droppable].
+#[derive(Debug, Clone, Copy)]
+pub struct Droppable {
+    /// `true` while something is being dragged over the element.
+    pub is_over: ReadSignal<bool>,
+}
+
+/// Makes the element in `node_ref` a drop target: when data stored with
+/// [set_drag_data] is dropped on it, `on_drop` is called with that data.
+/// The returned [Droppable] tells whether something is currently dragged over
+/// the element, e.g., to highlight it. Does nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Trash(cx: Scope, set_cards: WriteSignal<Vec<usize>>) -> impl IntoView {
+///     let trash = create_node_ref::<html::Div>(cx);
+///     let droppable = use_droppable(cx, trash, move |id: usize| {
+///         set_cards.update(|cards| cards.retain(|card| *card != id))
+///     });
+///
+///     view! { cx,
+///       <div _ref=trash class:hovered=move || droppable.is_over.get()>
+///         "Drop here to delete"
+///       </div>
+///     }
+/// }
+/// ```
+pub fn use_droppable<T, El>(
+    cx: Scope,
+    node_ref: NodeRef<El>,
+    on_drop: impl Fn(T) + 'static,
+) -> Droppable
+where
+    T: Serializable + 'static,
+    El: ElementDescriptor + Clone + 'static,
+{
+    let (is_over, set_is_over) = create_signal(cx, false);
+
+    if !is_server() {
+        node_ref.on_load(cx, move |el| {
+            // `dragenter` and `dragleave` also fire when the pointer moves
+            // between the element's children, so count how deep it is
+            let depth = Rc::new(Cell::new(0_usize));
+
+            _ = el
+                .on(ev::dragenter, {
+                    let depth = Rc::clone(&depth);
+                    move |ev| {
+                        ev.prevent_default();
+                        depth.set(depth.get() + 1);
+                        _ = set_is_over.try_set(true);
+                    }
+                })
+                // the default action of `dragover` is to refuse the drop
+                .on(ev::dragover, |ev| ev.prevent_default())
+                .on(ev::dragleave, {
+                    let depth = Rc::clone(&depth);
+                    move |_| {
+                        depth.set(depth.get().saturating_sub(1));
+                        if depth.get() == 0 {
+                            _ = set_is_over.try_set(false);
+                        }
+                    }
+                })
+                .on(ev::drop, move |ev| {
+                    ev.prevent_default();
+                    depth.set(0);
+                    _ = set_is_over.try_set(false);
+                    if let Some(data) = get_drag_data::<T>(&ev) {
+                        on_drop(data);
+                    }
+                });
+        });
+    }
+
+    Droppable { is_over }
+}
+
+/// Calls `on_visible` each time the element in `node_ref` scrolls into the
+/// viewport, using an [`IntersectionObserver`](https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserver).
+/// If the element is replaced, the new one is observed instead. The observer is
+/// disconnected when `cx` is disposed. Does nothing on the server.
+///
+/// Put an element after the last item of a list to load more items when the
+/// user scrolls to the end of it:
+///
+/// ```
+/// # use leptos::*;
+/// # async fn load_posts(
+/// #     _: (),
+/// #     _: Option<usize>,
+/// # ) -> Result<Page<usize, String>, ()> {
+/// #     todo!()
+/// # }
+/// #[component]
+/// fn Feed(cx: Scope) -> impl IntoView {
+///     let posts = create_paginated_resource(cx, || (), load_posts);
+///     let end = create_node_ref::<html::Div>(cx);
+///     on_visible(cx, end, move || posts.load_next());
+///
+///     view! { cx,
+///       <ul>
+///         {move || posts.items().get().into_iter()
+///             .map(|post| view! { cx, <li>{post}</li> })
+///             .collect_view(cx)}
+///       </ul>
+///       <div _ref=end/>
+///     }
+/// }
+/// ```
+pub fn on_visible<El>(
+    cx: Scope,
+    node_ref: NodeRef<El>,
+    on_visible: impl Fn() + 'static,
+) where
+    El: ElementDescriptor + Clone + 'static,
+{
+    if !is_server() {
+        use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new(
+            move |entries: js_sys::Array| {
+                if entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<IntersectionObserverEntry>()
+                        .is_intersecting()
+                }) {
+                    on_visible();
+                }
+            },
+        );
+        let observer =
+            IntersectionObserver::new(callback.as_ref().unchecked_ref())
+                .unwrap_throw();
+
+        // the element is observed each time the node ref is loaded, so that
+        // a new element replacing it (e.g., when the list above it is
+        // rendered again) is observed instead of the old one
+        let observed = Rc::new(Cell::new(None::<web_sys::Element>));
+        create_render_effect(cx, {
+            let observer = observer.clone();
+            let observed = Rc::clone(&observed);
+            move |_| {
+                #[cfg(all(target_arch = "wasm32", feature = "web"))]
+                let el = node_ref.get().map(|el| {
+                    el.element
+                        .as_ref()
+                        .clone()
+                        .unchecked_into::<web_sys::Element>()
+                });
+                #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+                let el = node_ref.get().and(None::<web_sys::Element>);
+                if let Some(prev) = observed.take() {
+                    observer.unobserve(&prev);
+                }
+                if let Some(el) = &el {
+                    observer.observe(el);
+                }
+                observed.set(el);
+            }
+        });
+
+        on_cleanup(cx, move || {
+            observer.disconnect();
+            drop(callback);
+        });
+    }
+}
+
+/// Calls `on_frame` on every animation frame, with the time since the
+/// previous frame, using
+/// [`Window.requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame).
+/// The loop stops when `cx` is disposed. Does nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (_angle, set_angle) = create_signal(cx, 0.0);
+/// // a full turn every four seconds
+/// on_animation_frame(cx, move |dt| {
+///     set_angle.update(|angle| *angle += dt.as_secs_f64() * 90.0)
+/// });
+/// # });
+/// ```
+pub fn on_animation_frame(cx: Scope, on_frame: impl Fn(Duration) + 'static) {
+    if is_server() {
+        return;
+    }
+
+    struct FrameLoop {
+        on_frame: Box<dyn Fn(Duration)>,
+        last: Cell<f64>,
+        handle: Cell<Option<AnimationFrameRequestHandle>>,
+        stopped: Cell<bool>,
+    }
+
+    fn next_frame(frame_loop: Rc<FrameLoop>) {
+        let handle = request_animation_frame_with_handle({
+            let frame_loop = Rc::clone(&frame_loop);
+            move || {
+                if frame_loop.stopped.get() {
+                    return;
+                }
+                let now = js_sys::Date::now();
+                let dt = (now - frame_loop.last.replace(now)).max(0.0);
+                (frame_loop.on_frame)(Duration::from_secs_f64(dt / 1000.0));
+                // `on_frame` may have disposed the scope
+                if !frame_loop.stopped.get() {
+                    next_frame(frame_loop);
+                }
+            }
+        });
+        frame_loop.handle.set(handle.ok());
+    }
+
+    let frame_loop = Rc::new(FrameLoop {
+        on_frame: Box::new(on_frame),
+        last: Cell::new(js_sys::Date::now()),
+        handle: Cell::new(None),
+        stopped: Cell::new(false),
+    });
+    next_frame(Rc::clone(&frame_loop));
+    on_cleanup(cx, move || {
+        frame_loop.stopped.set(true);
+        if let Some(handle) = frame_loop.handle.take() {
+            handle.cancel();
+        }
+    });
+}
+
+/// Calls `on_resize` with the width and height of the content box of the
+/// element in `node_ref`, in CSS pixels, once it is mounted and each time its
+/// size changes, using a
+/// [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver).
+/// The observer is disconnected when `cx` is disposed. Does nothing on the
+/// server.
+pub fn on_resize<El>(
+    cx: Scope,
+    node_ref: NodeRef<El>,
+    on_resize: impl Fn(f64, f64) + 'static,
+) where
+    El: ElementDescriptor + Clone + 'static,
+{
+    if !is_server() {
+        node_ref.on_load(cx, move |el| {
+            use web_sys::{ResizeObserver, ResizeObserverEntry};
+
+            let callback = Closure::<dyn Fn(js_sys::Array)>::new(
+                move |entries: js_sys::Array| {
+                    // only the latest size matters
+                    if let Some(entry) = entries.iter().last() {
+                        let rect = entry
+                            .unchecked_into::<ResizeObserverEntry>()
+                            .content_rect();
+                        on_resize(rect.width(), rect.height());
+                    }
+                },
+            );
+            let observer =
+                ResizeObserver::new(callback.as_ref().unchecked_ref())
+                    .unwrap_throw();
+
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
+            observer.observe(el.element.as_ref());
+            #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+            {
+                _ = el;
+            }
+
+            on_cleanup(cx, move || {
+                observer.disconnect();
+                drop(callback);
+            });
+        });
+    }
+}
@@ -0,0 +1,353 @@
+use leptos_reactive::Scope;
+use std::{fmt, str::FromStr};
+
+/// A key combination, like `ctrl+k` or `shift+alt+ArrowUp`, as used by
+/// [use_hotkeys].
+///
+/// It is parsed from modifiers and one key, separated by `+`. The modifiers
+/// are `ctrl`, `alt` (or `option`), `shift`, `meta` (or `cmd`), and `mod`,
+/// which is `meta` on Apple devices and `ctrl` everywhere else. The key is
+/// compared with [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key),
+/// ignoring case; `esc`, `space`, `up`, `down`, `left`, `right`, `del` and
+/// `plus` are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: String,
+}
+
+/// The error returned when a [Hotkey] can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyParseError(String);
+
+impl fmt::Display for HotkeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hotkey: {}", self.0)
+    }
+}
+
+impl std::error::Error for HotkeyParseError {}
+
+impl FromStr for Hotkey {
+    type Err = HotkeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason: &str| HotkeyParseError(format!("`{s}` {reason}"));
+
+        let mut hotkey = Hotkey {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            key: String::new(),
+        };
+        let parts = s.split('+').map(str::trim).collect::<Vec<_>>();
+        let (key, modifiers) = parts
+            .split_last()
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| error("has no key"))?;
+
+        for modifier in modifiers {
+            let flag = match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => &mut hotkey.ctrl,
+                "alt" | "option" => &mut hotkey.alt,
+                "shift" => &mut hotkey.shift,
+                "meta" | "cmd" | "command" | "super" => &mut hotkey.meta,
+                "mod" if is_apple() => &mut hotkey.meta,
+                "mod" => &mut hotkey.ctrl,
+                _ => {
+                    return Err(error(&format!(
+                        "has an unknown modifier `{modifier}`"
+                    )))
+                }
+            };
+            if *flag {
+                return Err(error(&format!("repeats `{modifier}`")));
+            }
+            *flag = true;
+        }
+
+        let key = key.to_lowercase();
+        hotkey.key = match key.as_str() {
+            "esc" => "escape".to_string(),
+            "space" => " ".to_string(),
+            "up" | "down" | "left" | "right" => format!("arrow{key}"),
+            "del" => "delete".to_string(),
+            "plus" => "+".to_string(),
+            _ => key,
+        };
+        Ok(hotkey)
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (on, name) in [
+            (self.ctrl, "ctrl+"),
+            (self.alt, "alt+"),
+            (self.shift, "shift+"),
+            (self.meta, "meta+"),
+        ] {
+            if on {
+                f.write_str(name)?;
+            }
+        }
+        match self.key.as_str() {
+            " " => f.write_str("space"),
+            "+" => f.write_str("plus"),
+            key => f.write_str(key),
+        }
+    }
+}
+
+impl Hotkey {
+    /// Whether the keyboard event is this key combination.
+    pub fn matches(&self, ev: &web_sys::KeyboardEvent) -> bool {
+        ev.ctrl_key() == self.ctrl
+            && ev.alt_key() == self.alt
+            && ev.shift_key() == self.shift
+            && ev.meta_key() == self.meta
+            && ev.key().to_lowercase() == self.key
+    }
+}
+
+fn is_apple() -> bool {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        crate::window()
+            .navigator()
+            .platform()
+            .map(|platform| {
+                platform.starts_with("Mac") || platform.starts_with("iP")
+            })
+            .unwrap_or(false)
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        false
+    }
+}
+
+/// Options for [use_hotkeys_with_options].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HotkeyOptions {
+    /// Whether the handler also runs while the user is typing in an
+    /// `<input>`, `<textarea>`, `<select>` or editable element. Defaults to
+    /// `false`, so that a shortcut like `/` can still be typed.
+    pub in_inputs: bool,
+    /// Whether the browser's default action for the key combination (like
+    /// `ctrl+s` saving the page) is prevented. Defaults to `true`.
+    pub prevent_default: bool,
+}
+
+impl Default for HotkeyOptions {
+    fn default() -> Self {
+        Self {
+            in_inputs: false,
+            prevent_default: true,
+        }
+    }
+}
+
+/// Calls `handler` whenever the user presses one of the given key
+/// combinations, until `cx` is disposed. `keys` is a comma-separated list of
+/// [Hotkey]s, like `"ctrl+k, meta+k"`.
+///
+/// Only one handler runs for each key press: if several scopes register the
+/// same key combination, the one registered last wins, so a shortcut in a
+/// dialog takes precedence over the same shortcut in the page below it, and
+/// the page’s handler works again once the dialog is closed. Registering the
+/// same key combination twice in one scope is a mistake, and only the first
+/// registration is kept.
+///
+/// Key combinations that can’t be parsed are ignored, with a warning. Does
+/// nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Search(cx: Scope) -> impl IntoView {
+///     let (open, set_open) = create_signal(cx, false);
+///     use_hotkeys(cx, "mod+k", move |_| set_open.set(true));
+///
+///     view! { cx,
+///       <Show when=move || open.get() fallback=|_| ()>
+///         <SearchDialog on_close=move || set_open.set(false)/>
+///       </Show>
+///     }
+/// }
+///
+/// #[component]
+/// fn SearchDialog<F>(cx: Scope, on_close: F) -> impl IntoView
+/// where
+///     F: Fn() + 'static,
+/// {
+///     // only active while the dialog is open, and also while typing in it
+///     use_hotkeys_with_options(
+///         cx,
+///         "esc",
+///         HotkeyOptions {
+///             in_inputs: true,
+///             ..Default::default()
+///         },
+///         move |_| on_close(),
+///     );
+///
+///     view! { cx, <input type="search"/> }
+/// }
+/// ```
+pub fn use_hotkeys(
+    cx: Scope,
+    keys: &str,
+    handler: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+    use_hotkeys_with_options(cx, keys, HotkeyOptions::default(), handler)
+}
+
+/// Like [use_hotkeys], with the given [HotkeyOptions].
+pub fn use_hotkeys_with_options(
+    cx: Scope,
+    keys: &str,
+    options: HotkeyOptions,
+    handler: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+    let hotkeys = keys
+        .split(',')
+        .filter_map(|key| match key.parse::<Hotkey>() {
+            Ok(hotkey) => Some(hotkey),
+            Err(e) => {
+                crate::debug_warn!("{e}");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    browser::register(cx, hotkeys, options, std::rc::Rc::new(handler));
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (cx, hotkeys, options, handler);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+    use leptos_reactive::{on_cleanup, ScopeId};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    struct Binding {
+        id: usize,
+        scope: ScopeId,
+        hotkey: Hotkey,
+        options: HotkeyOptions,
+        handler: Rc<dyn Fn(web_sys::KeyboardEvent)>,
+    }
+
+    thread_local! {
+        // in order of registration, so that later bindings take precedence
+        static BINDINGS: RefCell<Vec<Binding>> = RefCell::new(Vec::new());
+        static NEXT_ID: Cell<usize> = Cell::new(0);
+        static LISTENING: Cell<bool> = Cell::new(false);
+    }
+
+    pub(super) fn register(
+        cx: Scope,
+        hotkeys: Vec<Hotkey>,
+        options: HotkeyOptions,
+        handler: Rc<dyn Fn(web_sys::KeyboardEvent)>,
+    ) {
+        listen();
+
+        let mut ids = Vec::with_capacity(hotkeys.len());
+        BINDINGS.with(|bindings| {
+            let mut bindings = bindings.borrow_mut();
+            for hotkey in hotkeys {
+                if bindings.iter().any(|binding| {
+                    binding.scope == cx.id && binding.hotkey == hotkey
+                }) {
+                    crate::debug_warn!(
+                        "the hotkey `{hotkey}` is registered twice in the \
+                         same scope; only the first handler will run"
+                    );
+                    continue;
+                }
+                let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+                ids.push(id);
+                bindings.push(Binding {
+                    id,
+                    scope: cx.id,
+                    hotkey,
+                    options,
+                    handler: Rc::clone(&handler),
+                });
+            }
+        });
+
+        on_cleanup(cx, move || {
+            BINDINGS.with(|bindings| {
+                bindings
+                    .borrow_mut()
+                    .retain(|binding| !ids.contains(&binding.id))
+            });
+        });
+    }
+
+    fn listen() {
+        if LISTENING.with(|listening| listening.replace(true)) {
+            return;
+        }
+        let on_keydown =
+            Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(on_keydown);
+        _ = crate::window().add_event_listener_with_callback(
+            "keydown",
+            on_keydown.as_ref().unchecked_ref(),
+        );
+        // the listener stays for the lifetime of the page
+        on_keydown.forget();
+    }
+
+    fn on_keydown(ev: web_sys::KeyboardEvent) {
+        // already handled, e.g., by an `on:keydown` handler
+        if ev.default_prevented() {
+            return;
+        }
+        let in_input = is_typing(&ev);
+        let found = BINDINGS.with(|bindings| {
+            bindings.borrow().iter().rev().find_map(|binding| {
+                (binding.hotkey.matches(&ev)
+                    && (binding.options.in_inputs || !in_input))
+                    .then(|| (binding.options, Rc::clone(&binding.handler)))
+            })
+        });
+        // the handler runs after the borrow ends, so that it can register or
+        // remove hotkeys itself
+        if let Some((options, handler)) = found {
+            if options.prevent_default {
+                ev.prevent_default();
+            }
+            handler(ev);
+        }
+    }
+
+    fn is_typing(ev: &web_sys::KeyboardEvent) -> bool {
+        let Some(target) = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        else {
+            return false;
+        };
+        target.is_content_editable()
+            || matches!(
+                target.tag_name().as_str(),
+                "INPUT" | "TEXTAREA" | "SELECT"
+            )
+    }
+}
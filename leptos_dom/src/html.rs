@@ -64,7 +64,7 @@ use crate::{
     ev::EventDescriptor,
     hydration::HydrationCtx,
     macro_helpers::{IntoAttribute, IntoClass, IntoProperty, IntoStyle},
-    Element, Fragment, IntoView, NodeRef, Text, View,
+    Element, IntoView, NodeRef, Text, View,
 };
 use leptos_reactive::Scope;
 use std::{borrow::Cow, fmt};
@@ -742,7 +742,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
             let class_list = self.element.as_ref().class_list();
 
-            leptos_reactive::create_effect(
+            leptos_reactive::create_render_effect(
                 self.cx,
                 move |prev_classes: Option<
                     SmallVec<[Cow<'static, str>; 4]>,
@@ -936,7 +936,12 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             let event_name = event.name();
 
             let key = event.event_delegation_key();
-            let event_handler = Box::new(event_handler);
+            // the handler runs with the element's scope as the current scope,
+            // so it can use `view!` without a `cx`
+            let cx = self.cx;
+            let event_handler = Box::new(move |ev: E::EventType| {
+                cx.with_current(|| event_handler(ev))
+            });
 
             if E::BUBBLES {
                 add_event_listener(
@@ -960,6 +965,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         {
+            crate::ssr_static::report_event_listener(&event);
             _ = event;
             _ = event_handler;
 
@@ -1044,6 +1050,66 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         }
     }
 
+    /// Adds each of the given children to this element, in order.
+    ///
+    /// This is the builder equivalent of placing several nodes
+    /// inside an element in the `view` macro.
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+    /// let items = vec!["a", "b", "c"];
+    /// html::ul(cx)
+    ///     .children(items.into_iter().map(|item| html::li(cx).child(item)));
+    /// # }
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn children<C: IntoView>(
+        self,
+        children: impl IntoIterator<Item = C>,
+    ) -> Self {
+        children
+            .into_iter()
+            .fold(self, |this, child| this.child(child))
+    }
+
+    /// Adds each of the given attributes to this element.
+    ///
+    /// This is equivalent to calling [`HtmlElement::attr`] once per item.
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+    /// html::input(cx).attrs([("type", "text"), ("name", "email")]);
+    /// # }
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn attrs<N, A>(self, attrs: impl IntoIterator<Item = (N, A)>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        A: IntoAttribute,
+    {
+        attrs
+            .into_iter()
+            .fold(self, |this, (name, attr)| this.attr(name, attr))
+    }
+
+    /// Sets each of the given styles on this element.
+    ///
+    /// This is equivalent to calling [`HtmlElement::style`] once per item.
+    #[track_caller]
+    pub fn styles<N, S>(self, styles: impl IntoIterator<Item = (N, S)>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        S: IntoStyle,
+    {
+        styles
+            .into_iter()
+            .fold(self, |this, (name, style)| this.style(name, style))
+    }
+
     /// Sets the inner HTML of this element from the provided
     /// string slice.
     ///
@@ -1116,17 +1182,6 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
     }
 }
 
-impl<El: ElementDescriptor, const N: usize> IntoView for [HtmlElement<El>; N] {
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "trace", name = "[HtmlElement; N]", skip_all)
-    )]
-    fn into_view(self, cx: Scope) -> View {
-        Fragment::new(self.into_iter().map(|el| el.into_view(cx)).collect())
-            .into_view(cx)
-    }
-}
-
 /// Creates any custom element, such as `<my-element>`.
 pub fn custom<El: ElementDescriptor>(cx: Scope, el: El) -> HtmlElement<Custom> {
     HtmlElement::new(
@@ -2,7 +2,7 @@ use cfg_if::cfg_if;
 use std::{cell::RefCell, fmt::Display};
 
 cfg_if! {
-  if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+  if #[cfg(all(target_arch = "wasm32", feature = "hydrate"))] {
     use once_cell::unsync::Lazy as LazyCell;
     use std::collections::HashMap;
     use wasm_bindgen::JsCast;
@@ -11,23 +11,10 @@ cfg_if! {
     // id "_0-0-0" is present in the DOM. If it is, we know we are hydrating from
     // the server, if not, we are starting off in CSR
     thread_local! {
-      static HYDRATION_COMMENTS: LazyCell<HashMap<String, web_sys::Comment>> = LazyCell::new(|| {
-        let document = crate::document();
-        let body = document.body().unwrap();
-        let walker = document
-          .create_tree_walker_with_what_to_show(&body, 128)
-          .unwrap();
+      static HYDRATION_COMMENTS: LazyCell<RefCell<HashMap<String, web_sys::Comment>>> = LazyCell::new(|| {
         let mut map = HashMap::new();
-        while let Ok(Some(node)) = walker.next_node() {
-          if let Some(content) = node.text_content() {
-            if let Some(hk) = content.strip_prefix("hk=") {
-              if let Some(hk) = hk.split('|').next() {
-                map.insert(hk.into(), node.unchecked_into());
-              }
-            }
-          }
-        }
-        map
+        collect_markers(&crate::document().body().unwrap(), &mut map);
+        RefCell::new(map)
       });
 
       #[cfg(debug_assertions)]
@@ -52,16 +39,37 @@ cfg_if! {
         #[cfg(debug_assertions)]
         return crate::document().get_element_by_id("_0-0-0").is_some()
           || crate::document().get_element_by_id("_0-0-0o").is_some()
-          || HYDRATION_COMMENTS.with(|comments| comments.get("_0-0-0o").is_some());
+          || HYDRATION_COMMENTS.with(|comments| comments.borrow().get("_0-0-0o").is_some());
 
         #[cfg(not(debug_assertions))]
         return crate::document().get_element_by_id("_0-0-0").is_some()
-          || HYDRATION_COMMENTS.with(|comments| comments.get("_0-0-0").is_some());
+          || HYDRATION_COMMENTS.with(|comments| comments.borrow().get("_0-0-0").is_some());
       }));
     }
 
     pub(crate) fn get_marker(id: &str) -> Option<web_sys::Comment> {
-      HYDRATION_COMMENTS.with(|comments| comments.get(id).cloned())
+      HYDRATION_COMMENTS.with(|comments| comments.borrow().get(id).cloned())
+    }
+
+    fn collect_markers(root: &web_sys::Node, map: &mut HashMap<String, web_sys::Comment>) {
+      let walker = crate::document()
+        .create_tree_walker_with_what_to_show(root, 128)
+        .unwrap();
+      while let Ok(Some(node)) = walker.next_node() {
+        if let Some(content) = node.text_content() {
+          if let Some(hk) = content.strip_prefix("hk=") {
+            if let Some(hk) = hk.split('|').next() {
+              map.insert(hk.into(), node.unchecked_into());
+            }
+          }
+        }
+      }
+    }
+  } else if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+    // without the `hydrate` feature, the app is never hydrated, so there is no
+    // need to search the page for hydration markers
+    pub(crate) fn get_marker(_id: &str) -> Option<web_sys::Comment> {
+      None
     }
   }
 }
@@ -93,6 +101,20 @@ impl Default for HydrationKey {
 thread_local!(static ID: RefCell<HydrationKey> = Default::default());
 
 /// Control and utility methods for hydration.
+///
+/// Every element, text marker and component that can be hydrated is given a
+/// [HydrationKey] when it is created. A component starts a new segment of
+/// the key, numbered from the position of the component within its parent,
+/// and the nodes within it are numbered in the order they are created. The
+/// server and the client create the same nodes in the same order, so they
+/// agree on every key.
+///
+/// Anything that creates nodes the other side might not create (a view that
+/// is rendered twice, a fallback that is replaced later, or HTML that is
+/// inserted after the page was loaded) should do so within
+/// [HydrationCtx::scoped] or [HydrationCtx::with_key], so that those nodes
+/// are numbered by their position in the tree and the keys of the nodes
+/// after them do not depend on how many were created.
 pub struct HydrationCtx;
 
 impl HydrationCtx {
@@ -134,16 +156,86 @@ impl HydrationCtx {
         ID.with(|i| *i.borrow_mut() = id);
     }
 
+    /// Runs `f`, and then continues from the current key, so that the nodes
+    /// created by `f` do not change the keys of the nodes created after it.
+    pub fn scoped<T>(f: impl FnOnce() -> T) -> T {
+        // restores the key even if `f` panics, e.g., in a test
+        struct Restore(HydrationKey);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                HydrationCtx::continue_from(std::mem::take(&mut self.0));
+            }
+        }
+
+        let _restore = Restore(HydrationCtx::peek());
+        f()
+    }
+
+    /// Runs `f` with keys numbered from `key`, and then continues from the
+    /// current key, as in [HydrationCtx::scoped].
+    pub fn with_key<T>(key: HydrationKey, f: impl FnOnce() -> T) -> T {
+        HydrationCtx::scoped(|| {
+            HydrationCtx::continue_from(key);
+            f()
+        })
+    }
+
+    /// Returns the key at which a fragment rendered with the given
+    /// `fragment_id` starts, so that its hydration IDs do not collide with
+    /// those of the rest of the page.
+    pub fn fragment_root(fragment_id: &str) -> HydrationKey {
+        HydrationKey {
+            previous: format!("{fragment_id}-"),
+            offset: 0,
+        }
+    }
+
+    /// Returns the key from which the IDs of a [`Fragment`](crate::Fragment)
+    /// created with [`Fragment::with_key`](crate::Fragment::with_key) are
+    /// numbered, within the current component.
+    pub fn keyed(key: &str) -> HydrationKey {
+        let previous = ID.with(|id| id.borrow().previous.clone());
+        // the `k` prefix keeps keys apart from the numeric offsets of
+        // unkeyed nodes
+        HydrationKey {
+            previous: format!("{previous}k{key}-"),
+            offset: 0,
+        }
+    }
+
+    /// Starts hydrating the server-rendered HTML that has been inserted into
+    /// `root` after the page was loaded.
+    #[cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+    pub(crate) fn start_hydrating(root: &web_sys::Node) {
+        HYDRATION_COMMENTS
+            .with(|comments| collect_markers(root, &mut comments.borrow_mut()));
+        IS_HYDRATING.with(|is_hydrating| {
+            *is_hydrating.borrow_mut() = LazyCell::new(|| true);
+        })
+    }
+
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
     pub(crate) fn stop_hydrating() {
+        #[cfg(feature = "hydrate")]
         IS_HYDRATING.with(|is_hydrating| {
             std::mem::take(&mut *is_hydrating.borrow_mut());
-        })
+        });
     }
 
+    /// Whether views are currently being hydrated, rather than created. This
+    /// is always `false` without the `hydrate` feature, so that the code that
+    /// hydrates views can be removed from client-side rendered apps.
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    #[inline(always)]
     pub(crate) fn is_hydrating() -> bool {
-        IS_HYDRATING.with(|is_hydrating| **is_hydrating.borrow())
+        cfg_if! {
+          if #[cfg(feature = "hydrate")] {
+            IS_HYDRATING.with(|is_hydrating| **is_hydrating.borrow())
+          } else {
+            false
+          }
+        }
     }
 
     pub(crate) fn to_string(id: &HydrationKey, closing: bool) -> String {
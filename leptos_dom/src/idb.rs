@@ -0,0 +1,485 @@
+use leptos_reactive::{
+    create_local_resource, Resource, Scope, Serializable, SerializationError,
+};
+use std::{fmt, rc::Rc};
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use {
+    futures::{
+        channel::oneshot,
+        future::{LocalBoxFuture, Shared},
+        FutureExt,
+    },
+    std::{cell::RefCell, rc::Weak},
+    wasm_bindgen::{prelude::Closure, JsCast, JsValue},
+};
+
+/// An error from an [IdbStore].
+#[derive(Debug, Clone)]
+pub enum IdbError {
+    /// IndexedDB is not available, for example on the server, or in a
+    /// private window of some browsers.
+    Unavailable,
+    /// The browser returned an error, such as when the storage quota is
+    /// exceeded.
+    Js(String),
+    /// A value could not be encoded or decoded.
+    Serialization(SerializationError),
+}
+
+impl fmt::Display for IdbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => f.write_str("IndexedDB is not available"),
+            Self::Js(e) => write!(f, "IndexedDB error: {e}"),
+            Self::Serialization(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for IdbError {}
+
+impl From<SerializationError> for IdbError {
+    fn from(e: SerializationError) -> Self {
+        Self::Serialization(e)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+impl From<JsValue> for IdbError {
+    fn from(e: JsValue) -> Self {
+        Self::Js(format!("{e:?}"))
+    }
+}
+
+/// Passed to the hook set with [IdbStore::on_upgrade] when the database is
+/// created, or opened with a higher version than before, to migrate it to the
+/// new schema.
+pub struct IdbUpgrade {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    db: web_sys::IdbDatabase,
+    old_version: u32,
+    new_version: u32,
+}
+
+impl IdbUpgrade {
+    /// The version the database had before, or `0` if it is new.
+    pub fn old_version(&self) -> u32 {
+        self.old_version
+    }
+
+    /// The version the database is being upgraded to.
+    pub fn new_version(&self) -> u32 {
+        self.new_version
+    }
+
+    /// Whether the database has an object store with the given name.
+    pub fn has_store(&self, name: &str) -> bool {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            self.db.object_store_names().contains(name)
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = name;
+            false
+        }
+    }
+
+    /// Creates an object store with the given name.
+    pub fn create_store(&self, name: &str) -> Result<(), IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            self.db.create_object_store(name)?;
+            Ok(())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = name;
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    /// Deletes the object store with the given name, and everything in it.
+    pub fn delete_store(&self, name: &str) -> Result<(), IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            self.db.delete_object_store(name)?;
+            Ok(())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = name;
+            Err(IdbError::Unavailable)
+        }
+    }
+}
+
+/// An async key-value store in the browser's
+/// [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API),
+/// which can hold much more data than `localStorage`. Values are encoded with
+/// the same [Serializable] implementation as resources.
+///
+/// Creating the store is cheap: the database is opened the first time it is
+/// used, and cloning the store shares the connection. The object store is
+/// created when the database is created or upgraded, so adding a store to an
+/// existing database needs a higher `version`.
+///
+/// When another tab opens the database with a higher version, this store
+/// closes its connection so that the upgrade can go ahead. If a connection
+/// that doesn't do that is still open, opening the database with a higher
+/// version waits until it is closed, and logs a warning.
+///
+/// On the server, every operation fails with [IdbError::Unavailable].
+///
+/// ```
+/// # use leptos::*;
+/// # async fn example() -> Result<(), IdbError> {
+/// let store = IdbStore::new("my-app", 2, "drafts").on_upgrade(|upgrade| {
+///     // version 1 stored drafts in a store with a different name
+///     if upgrade.old_version() == 1 && upgrade.has_store("posts") {
+///         _ = upgrade.delete_store("posts");
+///     }
+/// });
+///
+/// store.put("post-1", &"Hello, world!".to_string()).await?;
+/// let draft: Option<String> = store.get("post-1").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct IdbStore {
+    db_name: Rc<str>,
+    version: u32,
+    store_name: Rc<str>,
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", feature = "web")),
+        allow(dead_code)
+    )]
+    on_upgrade: Option<Rc<dyn Fn(&IdbUpgrade)>>,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    db: Rc<RefCell<Option<OpenDatabase>>>,
+}
+
+// Opening the database is shared by every operation that is waiting for it,
+// so that it is only opened once.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+type OpenDatabase =
+    Shared<LocalBoxFuture<'static, Result<web_sys::IdbDatabase, IdbError>>>;
+
+impl fmt::Debug for IdbStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdbStore")
+            .field("db_name", &self.db_name)
+            .field("version", &self.version)
+            .field("store_name", &self.store_name)
+            .finish()
+    }
+}
+
+impl IdbStore {
+    /// Creates a store for the object store `store_name` in the database
+    /// `db_name`, which is opened with the given `version`.
+    pub fn new(db_name: &str, version: u32, store_name: &str) -> Self {
+        Self {
+            db_name: db_name.into(),
+            version,
+            store_name: store_name.into(),
+            on_upgrade: None,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
+            db: Default::default(),
+        }
+    }
+
+    /// Sets a hook that is called when the database is created or its
+    /// version is increased, before this store's object store is created if
+    /// it is missing. It can create or delete other object stores.
+    pub fn on_upgrade(mut self, hook: impl Fn(&IdbUpgrade) + 'static) -> Self {
+        self.on_upgrade = Some(Rc::new(hook));
+        self
+    }
+
+    /// Reads the value with the given key, or `None` if there isn't one.
+    pub async fn get<T: Serializable>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let store = self
+                .object_store(web_sys::IdbTransactionMode::Readonly)
+                .await?;
+            let value = request(&store.get(&JsValue::from_str(key))?).await?;
+            match value.as_string() {
+                Some(text) => Ok(Some(T::de(&text)?)),
+                None => Ok(None),
+            }
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = key;
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    /// Saves a value with the given key, replacing any that was there.
+    pub async fn put<T: Serializable>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let text = value.ser()?;
+            let store = self
+                .object_store(web_sys::IdbTransactionMode::Readwrite)
+                .await?;
+            request(&store.put_with_key(
+                &JsValue::from_str(&text),
+                &JsValue::from_str(key),
+            )?)
+            .await?;
+            Ok(())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = (key, value);
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    /// Deletes the value with the given key, if any.
+    pub async fn delete(&self, key: &str) -> Result<(), IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let store = self
+                .object_store(web_sys::IdbTransactionMode::Readwrite)
+                .await?;
+            request(&store.delete(&JsValue::from_str(key))?).await?;
+            Ok(())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = key;
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    /// Deletes every value in the store.
+    pub async fn clear(&self) -> Result<(), IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let store = self
+                .object_store(web_sys::IdbTransactionMode::Readwrite)
+                .await?;
+            request(&store.clear()?).await?;
+            Ok(())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    /// Returns all the keys in the store, in order.
+    pub async fn keys(&self) -> Result<Vec<String>, IdbError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let store = self
+                .object_store(web_sys::IdbTransactionMode::Readonly)
+                .await?;
+            let keys = request(&store.get_all_keys()?).await?;
+            Ok(js_sys::Array::from(&keys)
+                .iter()
+                .filter_map(|key| key.as_string())
+                .collect())
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            Err(IdbError::Unavailable)
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    async fn object_store(
+        &self,
+        mode: web_sys::IdbTransactionMode,
+    ) -> Result<web_sys::IdbObjectStore, IdbError> {
+        let opening = self
+            .db
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                open(
+                    Rc::clone(&self.db_name),
+                    self.version,
+                    Rc::clone(&self.store_name),
+                    self.on_upgrade.clone(),
+                    Rc::downgrade(&self.db),
+                )
+                .boxed_local()
+                .shared()
+            })
+            .clone();
+        let db = match opening.await {
+            Ok(db) => db,
+            Err(e) => {
+                // the next operation tries to open it again
+                self.db.borrow_mut().take();
+                return Err(e);
+            }
+        };
+        let transaction =
+            db.transaction_with_str_and_mode(&self.store_name, mode)?;
+        Ok(transaction.object_store(&self.store_name)?)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+async fn open(
+    db_name: Rc<str>,
+    version: u32,
+    store_name: Rc<str>,
+    on_upgrade: Option<Rc<dyn Fn(&IdbUpgrade)>>,
+    slot: Weak<RefCell<Option<OpenDatabase>>>,
+) -> Result<web_sys::IdbDatabase, IdbError> {
+    let factory = crate::window()
+        .indexed_db()
+        .ok()
+        .flatten()
+        .ok_or(IdbError::Unavailable)?;
+    let open_request = factory.open_with_u32(&db_name, version)?;
+
+    let on_upgrade_needed =
+        Closure::<dyn Fn(web_sys::IdbVersionChangeEvent)>::new({
+            let open_request = open_request.clone();
+            move |ev: web_sys::IdbVersionChangeEvent| {
+                let Ok(db) = open_request.result() else {
+                    return;
+                };
+                let upgrade = IdbUpgrade {
+                    db: db.unchecked_into(),
+                    old_version: ev.old_version() as u32,
+                    new_version: ev
+                        .new_version()
+                        .map(|v| v as u32)
+                        .unwrap_or(version),
+                };
+                if let Some(hook) = &on_upgrade {
+                    hook(&upgrade);
+                }
+                if !upgrade.has_store(&store_name) {
+                    if let Err(e) = upgrade.create_store(&store_name) {
+                        crate::error!(
+                            "could not create object store `{store_name}`: {e}"
+                        );
+                    }
+                }
+            }
+        });
+    open_request
+        .set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+
+    // the request carries on once the other connections have been closed
+    let on_blocked = Closure::<dyn Fn()>::new({
+        let db_name = Rc::clone(&db_name);
+        move || {
+            crate::warn!(
+                "opening IndexedDB database `{db_name}` with version \
+                 {version} is waiting for it to be closed in other tabs"
+            )
+        }
+    });
+    open_request.set_onblocked(Some(on_blocked.as_ref().unchecked_ref()));
+
+    let db = request(&open_request).await;
+    open_request.set_onupgradeneeded(None);
+    open_request.set_onblocked(None);
+    let db: web_sys::IdbDatabase = db?.unchecked_into();
+
+    // don't block another tab that upgrades the database; the next operation
+    // opens it again
+    let on_version_change = Closure::once_into_js({
+        let db = db.clone();
+        move || {
+            db.close();
+            if let Some(slot) = slot.upgrade() {
+                slot.borrow_mut().take();
+            }
+        }
+    });
+    db.set_onversionchange(Some(on_version_change.unchecked_ref()));
+
+    Ok(db)
+}
+
+// Waits for an IndexedDB request to succeed or fail.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+async fn request(request: &web_sys::IdbRequest) -> Result<JsValue, IdbError> {
+    let (tx, rx) = oneshot::channel::<bool>();
+    let tx = Rc::new(RefCell::new(Some(tx)));
+    let callback = |succeeded| {
+        let tx = Rc::clone(&tx);
+        Closure::<dyn Fn()>::new(move || {
+            if let Some(tx) = tx.borrow_mut().take() {
+                _ = tx.send(succeeded);
+            }
+        })
+    };
+    let on_success = callback(true);
+    let on_error = callback(false);
+    request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+    request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    let succeeded = rx.await.unwrap_or(false);
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+
+    if succeeded {
+        Ok(request.result()?)
+    } else {
+        Err(match request.error() {
+            Ok(Some(e)) => IdbError::Js(e.message()),
+            _ => IdbError::Js("request failed".to_string()),
+        })
+    }
+}
+
+/// Creates a [Resource] that reads the value with the key returned by `key`
+/// from an [IdbStore], and reads it again whenever the key changes. Call
+/// [Resource::refetch] after changing the value with [IdbStore::put].
+///
+/// Like [create_local_resource], this only runs in the browser; on the
+/// server, it stays in the loading state.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Draft(cx: Scope, post_id: ReadSignal<u32>) -> impl IntoView {
+///     let store = IdbStore::new("my-app", 1, "drafts");
+///     let draft = create_idb_resource::<String>(cx, store, move || {
+///         format!("post-{}", post_id.get())
+///     });
+///
+///     view! { cx,
+///       <Suspense fallback=|| ()>
+///         {move || draft.read(cx).map(|draft| match draft {
+///             Ok(Some(draft)) => draft,
+///             Ok(None) => String::new(),
+///             Err(e) => e.to_string(),
+///         })}
+///       </Suspense>
+///     }
+/// }
+/// ```
+pub fn create_idb_resource<T>(
+    cx: Scope,
+    store: IdbStore,
+    key: impl Fn() -> String + 'static,
+) -> Resource<String, Result<Option<T>, IdbError>>
+where
+    T: Serializable + 'static,
+{
+    create_local_resource(cx, key, move |key| {
+        let store = store.clone();
+        async move { store.get(&key).await }
+    })
+}
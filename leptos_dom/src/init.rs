@@ -0,0 +1,135 @@
+use crate::{logging::set_logger, set_log_level, LogLevel};
+use std::panic::PanicInfo;
+
+/// Options that control how a client-side app reports panics and logs
+/// messages. See [init_client].
+///
+/// There is no option for the allocator: Rust only lets the global allocator
+/// be chosen at compile time, so an app that wants a different one should
+/// declare it with `#[global_allocator]` in its own crate.
+pub struct ClientOptions {
+    /// What to do when the app panics. Defaults to [PanicHook::Console].
+    /// Unless it is [PanicHook::Unchanged], panics are also reported to the
+    /// handler set with [on_error](leptos_reactive::on_error).
+    pub panic_hook: PanicHook,
+    /// The least severe messages that [log!](crate::log),
+    /// [warn!](crate::warn), and [error!](crate::error) will output.
+    /// Defaults to [LogLevel::Log], which outputs everything.
+    pub log_level: LogLevel,
+    /// Receives every message that is logged at or above the `log_level`,
+    /// instead of the browser console. This can be used to forward messages
+    /// to `tracing`, or to a remote logging service. Defaults to `None`.
+    pub logger: Option<Box<dyn Fn(LogLevel, &str)>>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            panic_hook: PanicHook::Console,
+            log_level: LogLevel::Log,
+            logger: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientOptions")
+            .field("panic_hook", &self.panic_hook)
+            .field("log_level", &self.log_level)
+            .field("logger", &self.logger.is_some())
+            .finish()
+    }
+}
+
+/// How a client-side app reports panics. See [ClientOptions].
+pub enum PanicHook {
+    /// Logs the panic message and a stack trace as an error, which goes to
+    /// the browser console unless a [logger](ClientOptions::logger) is set.
+    Console,
+    /// Leaves the panic hook as it is. In the browser, the default hook's
+    /// output is usually lost, so this is only useful if the app sets its own
+    /// hook elsewhere, which should call
+    /// [report_panic](leptos_reactive::report_panic) for panics to reach the
+    /// handler set with [on_error](leptos_reactive::on_error).
+    Unchanged,
+    /// Calls the given function.
+    Custom(Box<dyn Fn(&PanicInfo) + Send + Sync + 'static>),
+}
+
+impl std::fmt::Debug for PanicHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Console => write!(f, "Console"),
+            Self::Unchanged => write!(f, "Unchanged"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Sets up panic reporting and logging for a client-side app. This should be
+/// called once, at the start of `main` (or of the function that hydrates the
+/// app), before anything is mounted.
+///
+/// ```
+/// # use leptos::*;
+/// init_client(ClientOptions {
+///     // only show warnings and errors in production
+///     log_level: if cfg!(debug_assertions) {
+///         LogLevel::Log
+///     } else {
+///         LogLevel::Warn
+///     },
+///     ..Default::default()
+/// });
+/// ```
+pub fn init_client(options: ClientOptions) {
+    let ClientOptions {
+        panic_hook,
+        log_level,
+        logger,
+    } = options;
+
+    set_log_level(log_level);
+    set_logger(logger.map(Into::into));
+
+    // either way, also report the panic to the handler set with `on_error`
+    match panic_hook {
+        PanicHook::Console => std::panic::set_hook(Box::new(|info| {
+            leptos_reactive::report_panic(info);
+            console_hook(info);
+        })),
+        PanicHook::Unchanged => {}
+        PanicHook::Custom(hook) => {
+            std::panic::set_hook(Box::new(move |info| {
+                leptos_reactive::report_panic(info);
+                hook(info);
+            }))
+        }
+    }
+}
+
+fn console_hook(info: &PanicInfo) {
+    let mut message = info.to_string();
+
+    // the panic message has no stack trace, so take one from a JS error
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    if let Some(stack) = js_sys::Reflect::get(
+        &js_sys::Error::new(""),
+        &wasm_bindgen::JsValue::from_str("stack"),
+    )
+    .ok()
+    .and_then(|stack| stack.as_string())
+    {
+        message.push_str("\n\nStack:\n\n");
+        message.push_str(&stack);
+    }
+
+    let components = crate::format_component_stack();
+    if !components.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&components);
+    }
+
+    crate::console_error(&message);
+}
@@ -9,34 +9,53 @@
 #[cfg_attr(any(debug_assertions, feature = "ssr"), macro_use)]
 pub extern crate tracing;
 
+mod broadcast;
+mod canvas;
 mod components;
+mod devtools;
+mod dyn_component;
 mod events;
 pub mod helpers;
+mod hotkeys;
 pub mod html;
 mod hydration;
+mod idb;
+mod init;
 mod logging;
 mod macro_helpers;
 pub mod math;
 mod node_ref;
 pub mod ssr;
 pub mod ssr_in_order;
+pub mod ssr_snapshot;
+pub mod ssr_static;
+mod storage;
 pub mod svg;
 mod transparent;
+mod web_apis;
+mod worker;
+pub use broadcast::*;
+pub use canvas::*;
 use cfg_if::cfg_if;
 pub use components::*;
+pub use devtools::*;
+pub use dyn_component::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub use events::add_event_helper;
 pub use events::typed as ev;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use events::{add_event_listener, add_event_listener_undelegated};
+pub use hotkeys::*;
 pub use html::HtmlElement;
 use html::{AnyElement, ElementDescriptor};
 pub use hydration::{HydrationCtx, HydrationKey};
-use leptos_reactive::Scope;
+pub use idb::*;
+pub use init::*;
 #[cfg(feature = "stable")]
 use leptos_reactive::{
     MaybeSignal, Memo, ReadSignal, RwSignal, Signal, SignalGet,
 };
+use leptos_reactive::{RuntimeId, Scope, ScopeDisposer};
 pub use logging::*;
 pub use macro_helpers::*;
 pub use node_ref::*;
@@ -47,10 +66,13 @@ use smallvec::SmallVec;
 use std::{borrow::Cow, fmt};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use std::{cell::RefCell, rc::Rc};
+pub use storage::*;
 pub use transparent::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::JsCast;
 use wasm_bindgen::UnwrapThrowExt;
+pub use web_apis::*;
+pub use worker::*;
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 thread_local! {
@@ -64,6 +86,18 @@ thread_local! {
 pub trait IntoView {
     /// Converts the value into [`View`].
     fn into_view(self, cx: Scope) -> View;
+
+    /// Erases the type of the value, so that it can be stored alongside
+    /// views of other types. See [`IntoAnyView`].
+    ///
+    /// [`HtmlElement::into_any`] takes precedence for elements, and only
+    /// erases the type of the element; use `Box::new` for those instead.
+    fn into_any(self) -> Box<dyn IntoAnyView>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
 }
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -536,16 +570,6 @@ impl IntoView for &View {
     }
 }
 
-impl<const N: usize> IntoView for [View; N] {
-    #[cfg_attr(
-        any(debug_assertions, feature = "ssr"),
-        instrument(level = "info", name = "[Node; N]", skip_all)
-    )]
-    fn into_view(self, cx: Scope) -> View {
-        Fragment::new(self.into_iter().collect()).into_view(cx)
-    }
-}
-
 impl IntoView for &Fragment {
     fn into_view(self, cx: Scope) -> View {
         self.to_owned().into_view(cx)
@@ -845,7 +869,8 @@ where
 
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        mount_to(crate::document().body().expect("body element to exist"), f)
+        let body = crate::document().body().expect("body element to exist");
+        _ = mount_to(body, f);
       } else {
         _ = f;
         crate::warn!("`mount_to_body` should not be called outside the browser.");
@@ -853,32 +878,185 @@ where
     }
 }
 
-/// Runs the provided closure and mounts the result to the provided element.
-pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F)
+/// Runs the provided closure and mounts the result to the provided element,
+/// returning a [MountHandle] that can remove it again.
+///
+/// Each call creates an independent root, so several views can be mounted in
+/// different elements of the same page, for example to embed widgets in a
+/// page that is not rendered by Leptos.
+pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F) -> MountHandle
 where
     F: FnOnce(Scope) -> N + 'static,
     N: IntoView,
 {
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        let disposer = leptos_reactive::create_scope(
-          leptos_reactive::create_runtime(),
+        let runtime = leptos_reactive::create_runtime();
+
+        // the nodes of the view can change while it is mounted, so it is
+        // surrounded by markers that show which nodes to remove on unmount
+        let document = crate::document();
+        let start: web_sys::Node =
+            document.create_comment("leptos-mount-start").unchecked_into();
+        let end: web_sys::Node =
+            document.create_comment("leptos-mount-end").unchecked_into();
+
+        let disposer = leptos_reactive::create_scope(runtime, {
+          let start = start.clone();
+          let end = end.clone();
           move |cx| {
             let node = f(cx).into_view(cx);
 
             HydrationCtx::stop_hydrating();
 
+            parent.append_child(&start).unwrap();
             parent.append_child(&node.get_mountable_node()).unwrap();
+            parent.append_child(&end).unwrap();
 
             std::mem::forget(node);
-          },
-        );
+          }
+        });
 
-        std::mem::forget(disposer);
+        MountHandle {
+          runtime,
+          disposer,
+          start,
+          end,
+        }
       } else {
         _ = parent;
         _ = f;
         crate::warn!("`mount_to` should not be called outside the browser.");
+
+        // nothing is mounted, so no runtime is created for it
+        MountHandle {
+          runtime: RuntimeId::default(),
+        }
+      }
+    }
+}
+
+/// Runs the provided closure and mounts the result to the provided element,
+/// returning the reactive runtime that was created for it.
+///
+/// Disposing of the runtime cleans up the view's reactive system, which is
+/// useful when mounting and unmounting many views, as in tests.
+pub fn mount_to_with_runtime<F, N>(
+    parent: web_sys::HtmlElement,
+    f: F,
+) -> RuntimeId
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    mount_to(parent, f).runtime()
+}
+
+/// A view mounted by [mount_to].
+///
+/// Dropping the handle leaves the view mounted; call [MountHandle::unmount]
+/// to remove it.
+pub struct MountHandle {
+    runtime: RuntimeId,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    disposer: ScopeDisposer,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    start: web_sys::Node,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    end: web_sys::Node,
+}
+
+impl MountHandle {
+    /// The reactive runtime the view was created in. Outside the browser,
+    /// where nothing is mounted, this is not a valid runtime.
+    pub fn runtime(&self) -> RuntimeId {
+        self.runtime
+    }
+
+    /// Removes the view from the DOM, and disposes of the scope and the
+    /// runtime it was created in, running its
+    /// [`on_cleanup`](leptos_reactive::on_cleanup) callbacks and disposing its
+    /// signals, effects and resources.
+    pub fn unmount(self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let mut node = Some(self.start);
+            while let Some(current) = node {
+                node = (current != self.end)
+                    .then(|| current.next_sibling())
+                    .flatten();
+                if let Some(parent) = current.parent_node() {
+                    _ = parent.remove_child(&current);
+                }
+            }
+            self.disposer.dispose();
+        }
+        self.runtime.dispose();
+    }
+}
+
+/// Inserts server-rendered `html` into `parent` and hydrates it with the view
+/// returned by `f`, returning a disposer for the scope the view was created in.
+///
+/// The HTML should be rendered on the server by
+/// [`render_fragment_to_string`](crate::ssr::render_fragment_to_string) with the
+/// same `fragment_id`, and from the same view. This allows a server function to
+/// render part of the page, which the client then makes interactive.
+///
+/// `parent` must already be attached to the document. Any
+/// [`Resource`](leptos_reactive::Resource)s in the view will load again on the
+/// client. Without the `hydrate` feature, the HTML is ignored and the view is
+/// rendered in `parent` from scratch.
+pub fn hydrate_fragment<F, N>(
+    cx: Scope,
+    parent: &web_sys::Element,
+    fragment_id: &str,
+    html: &str,
+    f: F,
+) -> ScopeDisposer
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "hydrate"))] {
+        parent.set_inner_html(html);
+
+        HydrationCtx::start_hydrating(parent);
+
+        let root = HydrationCtx::fragment_root(fragment_id);
+        let (_, disposer) = HydrationCtx::with_key(root, || {
+            cx.run_child_scope(|cx| {
+                let node = f(cx).into_view(cx);
+                // the hydrated nodes are already in the DOM
+                std::mem::forget(node);
+            })
+        });
+
+        HydrationCtx::stop_hydrating();
+
+        disposer
+      } else if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        // without the `hydrate` feature, render the view from scratch instead
+        _ = fragment_id;
+        _ = html;
+        parent.set_inner_html("");
+
+        let (_, disposer) = cx.run_child_scope(|cx| {
+            let node = f(cx).into_view(cx);
+            parent.append_child(&node.get_mountable_node()).unwrap();
+            std::mem::forget(node);
+        });
+
+        disposer
+      } else {
+        _ = parent;
+        _ = fragment_id;
+        _ = html;
+        _ = f;
+        crate::warn!("`hydrate_fragment` should not be called outside the browser.");
+        let (_, disposer) = cx.run_child_scope(|_| ());
+        disposer
       }
     }
 }
@@ -1068,6 +1246,66 @@ where
     }
 }
 
+impl<V, const N: usize> IntoView for [V; N]
+where
+    V: IntoView,
+{
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        instrument(level = "info", name = "[V; N]", skip_all)
+    )]
+    fn into_view(self, cx: Scope) -> View {
+        self.into_iter()
+            .map(|v| v.into_view(cx))
+            .collect::<Fragment>()
+            .into_view(cx)
+    }
+}
+
+macro_rules! viewable_collection {
+  ($($collection:ty),* $(,)?) => {
+    $(
+      impl<K, V> IntoView for $collection
+      where
+        V: IntoView,
+      {
+        #[inline(always)]
+        fn into_view(self, cx: Scope) -> View {
+          self.map(|v| v.into_view(cx)).collect::<Fragment>().into_view(cx)
+        }
+      }
+    )*
+  };
+}
+
+// renders the values of a map, e.g., `map.into_values()`, without collecting
+// them into a `Vec` first
+viewable_collection![
+    std::collections::hash_map::IntoValues<K, V>,
+    std::collections::btree_map::IntoValues<K, V>,
+];
+
+impl<V> IntoView for std::collections::VecDeque<V>
+where
+    V: IntoView,
+{
+    fn into_view(self, cx: Scope) -> View {
+        self.into_iter()
+            .map(|v| v.into_view(cx))
+            .collect::<Fragment>()
+            .into_view(cx)
+    }
+}
+
+// `Duration` has no `Display` implementation, so it is rendered like its
+// `Debug` implementation, e.g., `1.5s` or `250ms`
+impl IntoView for std::time::Duration {
+    #[inline(always)]
+    fn into_view(self, _cx: Scope) -> View {
+        View::Text(Text::new(format!("{self:?}").into()))
+    }
+}
+
 macro_rules! viewable_primitive {
   ($($child_type:ty),* $(,)?) => {
     $(
@@ -1100,6 +1338,9 @@ viewable_primitive![
     char,
     bool,
     Cow<'_, str>,
+    std::rc::Rc<str>,
+    std::sync::Arc<str>,
+    Box<str>,
     std::net::IpAddr,
     std::net::SocketAddr,
     std::net::SocketAddrV4,
@@ -1131,3 +1372,23 @@ cfg_if! {
     }
   }
 }
+
+#[cfg(feature = "chrono")]
+viewable_primitive![
+    chrono::NaiveDate,
+    chrono::NaiveTime,
+    chrono::NaiveDateTime,
+    chrono::Weekday,
+];
+
+#[cfg(feature = "chrono")]
+impl<Tz> IntoView for chrono::DateTime<Tz>
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: fmt::Display,
+{
+    #[inline(always)]
+    fn into_view(self, _cx: Scope) -> View {
+        View::Text(Text::new(self.to_string().into()))
+    }
+}
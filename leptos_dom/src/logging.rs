@@ -1,7 +1,59 @@
 use crate::is_server;
 use cfg_if::cfg_if;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use wasm_bindgen::JsValue;
 
+/// How severe a logged message is. Levels are ordered from least to most
+/// severe, so that messages below a minimum level can be ignored.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Messages logged with [log!](crate::log).
+    #[default]
+    Log,
+    /// Messages logged with [warn!](crate::warn) or
+    /// [debug_warn!](crate::debug_warn).
+    Warn,
+    /// Messages logged with [error!](crate::error).
+    Error,
+    /// Used as a minimum level, ignores every message.
+    Off,
+}
+
+type Logger = Rc<dyn Fn(LogLevel, &str)>;
+
+thread_local! {
+    static LOG_LEVEL: Cell<LogLevel> = Cell::new(LogLevel::Log);
+    static LOGGER: RefCell<Option<Logger>> = RefCell::new(None);
+}
+
+/// Sets the least severe messages that will be logged on this thread. See
+/// also [init_client](crate::init_client).
+pub fn set_log_level(level: LogLevel) {
+    LOG_LEVEL.with(|log_level| log_level.set(level));
+}
+
+pub(crate) fn set_logger(logger: Option<Logger>) {
+    LOGGER.with(|current| *current.borrow_mut() = logger);
+}
+
+// Returns `true` if the message has been handled, either by being ignored or
+// by the custom logger.
+fn log_elsewhere(level: LogLevel, s: &str) -> bool {
+    if level < LOG_LEVEL.with(Cell::get) {
+        return true;
+    }
+    match LOGGER.with(|logger| logger.borrow().clone()) {
+        Some(logger) => {
+            logger(level, s);
+            true
+        }
+        None => false,
+    }
+}
+
 /// Uses `println!()`-style formatting to log something to the console (in the browser)
 /// or via `println!()` (if not in the browser).
 #[macro_export]
@@ -31,7 +83,9 @@ macro_rules! debug_warn {
         {
             #[cfg(debug_assertions)]
             {
-                $crate::warn!($($x)*)
+                $crate::console_warn(&$crate::with_component_stack(
+                    format_args!($($x)*).to_string()
+                ))
             }
             #[cfg(not(debug_assertions))]
             {
@@ -44,6 +98,9 @@ macro_rules! debug_warn {
 /// Log a string to the console (in the browser)
 /// or via `println!()` (if not in the browser).
 pub fn console_log(s: &str) {
+    if log_elsewhere(LogLevel::Log, s) {
+        return;
+    }
     if is_server() {
         println!("{s}");
     } else {
@@ -54,6 +111,9 @@ pub fn console_log(s: &str) {
 /// Log a warning to the console (in the browser)
 /// or via `println!()` (if not in the browser).
 pub fn console_warn(s: &str) {
+    if log_elsewhere(LogLevel::Warn, s) {
+        return;
+    }
     if is_server() {
         eprintln!("{s}");
     } else {
@@ -64,6 +124,9 @@ pub fn console_warn(s: &str) {
 /// Log an error to the console (in the browser)
 /// or via `println!()` (if not in the browser).
 pub fn console_error(s: &str) {
+    if log_elsewhere(LogLevel::Error, s) {
+        return;
+    }
     if is_server() {
         eprintln!("{s}");
     } else {
@@ -76,6 +139,9 @@ pub fn console_error(s: &str) {
 pub fn console_debug_warn(s: &str) {
     cfg_if! {
         if #[cfg(debug_assertions)] {
+            if log_elsewhere(LogLevel::Warn, s) {
+                return;
+            }
             if is_server() {
                 eprintln!("{s}");
             } else {
@@ -1,6 +1,6 @@
 use crate::{html::ElementDescriptor, HtmlElement};
 use leptos_reactive::{
-    create_effect, create_rw_signal, signal_prelude::*, RwSignal, Scope,
+    create_render_effect, create_rw_signal, signal_prelude::*, RwSignal, Scope,
 };
 use std::cell::Cell;
 
@@ -143,7 +143,7 @@ impl<T: ElementDescriptor + 'static> NodeRef<T> {
     {
         let f = Cell::new(Some(f));
 
-        create_effect(cx, move |_| {
+        create_render_effect(cx, move |_| {
             if let Some(node_ref) = self.get() {
                 f.take().unwrap()(node_ref);
             }
@@ -4,10 +4,11 @@
 
 use crate::{
     html::{ElementChildren, StringOrView},
+    ssr_in_order::StreamOptions,
     CoreComponent, HydrationCtx, IntoView, View,
 };
 use cfg_if::cfg_if;
-use futures::{stream::FuturesUnordered, Future, Stream, StreamExt};
+use futures::{stream::FuturesUnordered, Future, SinkExt, Stream, StreamExt};
 use itertools::Itertools;
 use leptos_reactive::*;
 use std::{borrow::Cow, pin::Pin};
@@ -47,6 +48,46 @@ where
     html.into()
 }
 
+/// Renders the given function to a static HTML string that can be hydrated on
+/// the client with [`hydrate_fragment`](crate::hydrate_fragment), after the
+/// page itself has loaded.
+///
+/// Its hydration IDs start from the given `fragment_id`, so that they do not
+/// collide with those of the page it is inserted into. Each fragment that is
+/// on the page at the same time should use a different `fragment_id`.
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::*;
+/// let html = leptos::ssr::render_fragment_to_string("comments", |cx| view! { cx,
+///   <p>"Hello, world!"</p>
+/// });
+/// assert!(html.contains("id=\"_comments-1\""));
+/// # }}
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    instrument(level = "info", skip_all,)
+)]
+pub fn render_fragment_to_string<F, N>(fragment_id: &str, f: F) -> String
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    let runtime = leptos_reactive::create_runtime();
+
+    let root = HydrationCtx::fragment_root(fragment_id);
+    let html = HydrationCtx::with_key(root, || {
+        leptos_reactive::run_scope(runtime, |cx| {
+            f(cx).into_view(cx).render_to_string(cx)
+        })
+    });
+
+    runtime.dispose();
+
+    html.into()
+}
+
 /// Renders a function to a stream of HTML strings.
 ///
 /// This renders:
@@ -214,6 +255,9 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
         }
     });
     let cx = Scope { runtime, id: scope };
+    let resource_payload =
+        use_context::<ResourcePayload>(cx).unwrap_or_default();
+    let options = use_context::<StreamOptions>(cx).unwrap_or_default();
 
     let mut blocking_fragments = FuturesUnordered::new();
     let fragments = FuturesUnordered::new();
@@ -232,7 +276,7 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
     // stream HTML for each <Suspense/> as it resolves
     let fragments = fragments_to_chunks(fragments);
     // stream data for each Resource as it resolves
-    let resources = render_serializers(serializers);
+    let resources = resource_payload.render(serializers);
 
     // HTML for the view function and script to store resources
     let stream = futures::stream::once(async move {
@@ -287,7 +331,20 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
         Default::default()
     }));
 
-    (stream, runtime, scope)
+    // render ahead of the reader by no more than `buffer_size` chunks, like
+    // the in-order stream
+    let (mut tx, rx) = futures::channel::mpsc::channel(options.buffer_size);
+    leptos_reactive::spawn_local(async move {
+        let mut stream = Box::pin(stream);
+        while let Some(chunk) = stream.next().await {
+            // if the stream has been dropped, there's no one left to send to
+            if tx.send(chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    (rx, runtime, scope)
 }
 
 #[cfg_attr(
@@ -653,10 +710,10 @@ pub(crate) fn render_serializers(
 ) -> impl Stream<Item = String> {
     serializers.map(|(id, json)| {
         let id = serde_json::to_string(&id).unwrap();
-        let json = json.replace('<', "\\u003c");
+        let json = escape_script_string(&json);
         format!(
             r#"<script>
-                  var val = {json:?};
+                  var val = {json};
                   if(__LEPTOS_RESOURCE_RESOLVERS.get({id})) {{
                       __LEPTOS_RESOURCE_RESOLVERS.get({id})(val)
                   }} else {{
@@ -667,6 +724,99 @@ pub(crate) fn render_serializers(
     })
 }
 
+/// How the data of resolved [Resource](leptos_reactive::Resource)s is sent
+/// to the client, so that it can hydrate them without loading them again.
+///
+/// This can be chosen for both out-of-order and in-order streams by providing
+/// it as context before the view is rendered, for example in the
+/// `additional_context` passed to a server integration.
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::{*, ssr::ResourcePayload};
+/// # run_scope(create_runtime(), |cx| {
+/// provide_context(cx, ResourcePayload::Binary);
+/// # });
+/// # }}
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResourcePayload {
+    /// Each resource is sent in its own `<script>` as soon as it resolves,
+    /// which hands the data to the resource even if the app has already
+    /// started to hydrate.
+    #[default]
+    Scripts,
+    /// Every resource is sent at the end of the document, in one element that
+    /// holds a base64-encoded CBOR payload (see
+    /// [encode_resource_payload](leptos_reactive::encode_resource_payload)).
+    /// This needs no script per resource and never needs to be escaped, but
+    /// the payload is only read when the app starts to hydrate, so the app
+    /// must be loaded after the document has been parsed, e.g., with
+    /// `<script type="module">`.
+    Binary,
+}
+
+impl ResourcePayload {
+    pub(crate) fn render(
+        self,
+        serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+    ) -> Pin<Box<dyn Stream<Item = String>>> {
+        match self {
+            Self::Scripts => Box::pin(render_serializers(serializers)),
+            Self::Binary => Box::pin(render_resource_payload(serializers)),
+        }
+    }
+}
+
+/// Renders every resolved resource as one
+/// [binary payload](leptos_reactive::encode_resource_payload), once all of
+/// them have resolved.
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    instrument(level = "trace", skip_all,)
+)]
+fn render_resource_payload(
+    serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+) -> impl Stream<Item = String> {
+    futures::stream::once(async move {
+        let resources = serializers.collect::<Vec<_>>().await;
+        if resources.is_empty() {
+            return String::new();
+        }
+        let payload = encode_resource_payload(
+            resources.iter().map(|(id, data)| (*id, data.as_str())),
+        );
+        // a type that isn't JavaScript keeps the browser from running it
+        format!(
+            r#"<script type="application/octet-stream" id="{RESOURCE_PAYLOAD_ID}">{payload}</script>"#
+        )
+    })
+}
+
+/// Serializes a string as a JavaScript string literal that can be safely
+/// embedded in an inline `<script>` tag.
+///
+/// In addition to the usual JSON escapes, `<`, `>`, and `&` are escaped so
+/// that the data can never close the script (`</script>`) or open an HTML
+/// comment (`<!--`), and U+2028/U+2029 are escaped because older JavaScript
+/// engines treat them as line terminators inside string literals.
+pub fn escape_script_string(value: &str) -> String {
+    let json = serde_json::to_string(value)
+        .expect("serializing a string to JSON cannot fail");
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[doc(hidden)]
 pub fn escape_attr<T>(value: &T) -> Cow<'_, str>
 where
@@ -4,19 +4,65 @@
 
 use crate::{
     html::{ElementChildren, StringOrView},
-    ssr::render_serializers,
+    ssr::ResourcePayload,
     CoreComponent, HydrationCtx, View,
 };
 use async_recursion::async_recursion;
 use cfg_if::cfg_if;
-use futures::{channel::mpsc::UnboundedSender, Stream, StreamExt};
+use futures::{channel::mpsc::Sender, SinkExt, Stream, StreamExt};
 use itertools::Itertools;
 use leptos_reactive::{
-    create_runtime, run_scope_undisposed, suspense::StreamChunk, RuntimeId,
-    Scope, ScopeId,
+    create_runtime, run_scope_undisposed, suspense::StreamChunk, use_context,
+    RuntimeId, Scope, ScopeId,
 };
 use std::{borrow::Cow, collections::VecDeque};
 
+/// Options that control how an in-order HTML stream is split into chunks. The
+/// `buffer_size` is also used by out-of-order streams.
+///
+/// These can be set by providing them as context before the view is rendered,
+/// for example in the `additional_context` of
+/// [render_to_stream_in_order_with_prefix_undisposed_with_context] or
+/// [render_to_stream_with_prefix_undisposed_with_context](crate::ssr::render_to_stream_with_prefix_undisposed_with_context).
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::{*, ssr::StreamOptions};
+/// # run_scope(create_runtime(), |cx| {
+/// // e.g., in the `additional_context` passed to a server integration
+/// provide_context(
+///     cx,
+///     StreamOptions {
+///         min_chunk_size: 1024,
+///         ..Default::default()
+///     },
+/// );
+/// # });
+/// # }}
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// HTML is only sent down the stream at a `<Suspense/>` boundary once at
+    /// least this many bytes have been buffered. An explicit flush (see
+    /// [FlushMarker](crate::FlushMarker)) and the end of the stream always
+    /// send whatever has been buffered.
+    pub min_chunk_size: usize,
+    /// The number of chunks that can be rendered ahead of the consumer of the
+    /// stream. Once this many are waiting to be read, rendering pauses until
+    /// the consumer catches up, so a slow client does not cause the whole
+    /// page to be buffered in memory.
+    pub buffer_size: usize,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            min_chunk_size: 0,
+            buffer_size: 16,
+        }
+    }
+}
+
 /// Renders a view to HTML, waiting to return until all `async` [Resource](leptos_reactive::Resource)s
 /// loaded in `<Suspense/>` elements have finished loading.
 #[tracing::instrument(level = "info", skip_all)]
@@ -31,6 +77,141 @@ pub async fn render_to_string_async(
     buf
 }
 
+/// The output of [render_to_parts], split up so that it can be injected into
+/// an HTML template that is not rendered by Leptos.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderedParts {
+    /// HTML to be inserted into the document's `<head>`, as generated by the
+    /// `head` function passed to [render_to_parts_with_context].
+    pub head: String,
+    /// The HTML for the view itself, including every resolved `<Suspense/>`.
+    pub body: String,
+    /// The `<script>` tags that pass resource data to the client so it can
+    /// hydrate. These should come after the `body` in the document.
+    pub scripts: String,
+}
+
+impl RenderedParts {
+    /// The placeholder in a template that is replaced with the `head`.
+    pub const HEAD_PLACEHOLDER: &'static str = "<!--leptos-head-->";
+    /// The placeholder in a template that is replaced with the `body`.
+    pub const BODY_PLACEHOLDER: &'static str = "<!--leptos-body-->";
+    /// The placeholder in a template that is replaced with the `scripts`.
+    pub const SCRIPTS_PLACEHOLDER: &'static str = "<!--leptos-scripts-->";
+
+    /// Replaces the `<!--leptos-head-->`, `<!--leptos-body-->`, and
+    /// `<!--leptos-scripts-->` placeholders in `template` with the rendered
+    /// parts. Placeholders that do not appear in the template are ignored.
+    pub fn inject(&self, template: &str) -> String {
+        let mut html = String::with_capacity(
+            template.len()
+                + self.head.len()
+                + self.body.len()
+                + self.scripts.len(),
+        );
+        let mut rest = template;
+        while let Some(start) = rest.find("<!--leptos-") {
+            let (before, placeholder) = rest.split_at(start);
+            html.push_str(before);
+            let part = [
+                (Self::HEAD_PLACEHOLDER, &self.head),
+                (Self::BODY_PLACEHOLDER, &self.body),
+                (Self::SCRIPTS_PLACEHOLDER, &self.scripts),
+            ]
+            .into_iter()
+            .find(|(name, _)| placeholder.starts_with(name));
+            match part {
+                Some((name, part)) => {
+                    html.push_str(part);
+                    rest = &placeholder[name.len()..];
+                }
+                None => {
+                    html.push_str("<!--leptos-");
+                    rest = &placeholder["<!--leptos-".len()..];
+                }
+            }
+        }
+        html.push_str(rest);
+        html
+    }
+}
+
+/// Renders a view to HTML, waiting until all `async` [Resource](leptos_reactive::Resource)s
+/// loaded in `<Suspense/>` elements have finished loading, and returns the body HTML
+/// and hydration scripts separately so that they can be injected into your own
+/// HTML template.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn render_to_parts(
+    view: impl FnOnce(Scope) -> View + 'static,
+) -> RenderedParts {
+    render_to_parts_with_context(view, |_| "".into(), |_| {}).await
+}
+
+/// Renders a view to HTML, waiting until all `async` [Resource](leptos_reactive::Resource)s
+/// loaded in `<Suspense/>` elements have finished loading, and returns the head content,
+/// body HTML, and hydration scripts separately so that they can be injected into your own
+/// HTML template.
+///
+/// `additional_context` is injected before the `view` is rendered. The `head` is generated
+/// after every `<Suspense/>` has resolved, so it can include metadata set anywhere in the
+/// `view`.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn render_to_parts_with_context(
+    view: impl FnOnce(Scope) -> View + 'static,
+    head: impl FnOnce(Scope) -> Cow<'static, str> + 'static,
+    additional_context: impl FnOnce(Scope) + 'static,
+) -> RenderedParts {
+    HydrationCtx::reset_id();
+
+    // create the runtime
+    let runtime = create_runtime();
+
+    let ((chunks, pending_resources, serializers), scope_id, disposer) =
+        run_scope_undisposed(runtime, |cx| {
+            // add additional context
+            additional_context(cx);
+
+            // render view and return chunks
+            let view = view(cx);
+
+            (
+                view.into_stream_chunks(cx),
+                serde_json::to_string(&cx.pending_resources()).unwrap(),
+                cx.serialization_resolvers(),
+            )
+        });
+    let cx = Scope {
+        runtime,
+        id: scope_id,
+    };
+
+    let mut body = String::new();
+    collect_chunks(chunks, &mut body).await;
+
+    let head = head(cx).into_owned();
+
+    let mut scripts = format!(
+        "<script>__LEPTOS_PENDING_RESOURCES = \
+         {pending_resources};__LEPTOS_RESOLVED_RESOURCES = new \
+         Map();__LEPTOS_RESOURCE_RESOLVERS = new Map();</script>"
+    );
+    let mut resources = use_context::<ResourcePayload>(cx)
+        .unwrap_or_default()
+        .render(serializers);
+    while let Some(script) = resources.next().await {
+        scripts.push_str(&script);
+    }
+
+    disposer.dispose();
+    runtime.dispose();
+
+    RenderedParts {
+        head,
+        body,
+        scripts,
+    }
+}
+
 /// Renders an in-order HTML stream, pausing at `<Suspense/>` components. The stream contains,
 /// in order:
 /// 1. HTML from the `view` in order, pausing to wait for each `<Suspense/>`
@@ -122,15 +303,22 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
         runtime,
         id: scope_id,
     };
+    let resource_payload =
+        use_context::<ResourcePayload>(cx).unwrap_or_default();
 
-    let (tx, rx) = futures::channel::mpsc::unbounded();
+    let options = use_context::<StreamOptions>(cx).unwrap_or_default();
+    let (mut tx, rx) = futures::channel::mpsc::channel(options.buffer_size);
     let (prefix_tx, prefix_rx) = futures::channel::oneshot::channel();
     leptos_reactive::spawn_local(async move {
         blocking_fragments_ready.await;
-        let remaining_chunks = handle_blocking_chunks(tx.clone(), chunks).await;
+        let mut buffer = String::new();
+        let remaining_chunks =
+            handle_blocking_chunks(chunks, &mut buffer).await;
         let prefix = prefix(cx);
         prefix_tx.send(prefix).expect("to send prefix");
-        handle_chunks(tx, remaining_chunks).await;
+        handle_chunks(&mut tx, remaining_chunks, options, &mut buffer).await;
+        // send final sync chunk
+        send_chunk(&mut tx, &mut buffer).await;
     });
 
     let stream = futures::stream::once(async move {
@@ -147,7 +335,7 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
         )
     })
     .chain(rx)
-    .chain(render_serializers(serializers))
+    .chain(resource_payload.render(serializers))
     // dispose of the scope
     .chain(futures::stream::once(async move {
         disposer.dispose();
@@ -157,13 +345,29 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     (stream, runtime, scope_id)
 }
 
+#[tracing::instrument(level = "trace", skip_all)]
+#[async_recursion(?Send)]
+async fn collect_chunks(chunks: VecDeque<StreamChunk>, buffer: &mut String) {
+    for chunk in chunks {
+        match chunk {
+            StreamChunk::Sync(sync) => buffer.push_str(&sync),
+            StreamChunk::Async { chunks, .. } => {
+                collect_chunks(chunks.await, buffer).await
+            }
+            StreamChunk::Flush => {}
+        }
+    }
+}
+
+// Nothing can be read from the stream until the prefix has been sent, which
+// happens only once all blocking chunks have resolved, so they are simply
+// collected into the buffer.
 #[tracing::instrument(level = "trace", skip_all)]
 #[async_recursion(?Send)]
 async fn handle_blocking_chunks(
-    tx: UnboundedSender<String>,
     mut queued_chunks: VecDeque<StreamChunk>,
+    buffer: &mut String,
 ) -> VecDeque<StreamChunk> {
-    let mut buffer = String::new();
     while let Some(chunk) = queued_chunks.pop_front() {
         match chunk {
             StreamChunk::Sync(sync) => buffer.push_str(&sync),
@@ -172,13 +376,9 @@ async fn handle_blocking_chunks(
                 should_block,
             } => {
                 if should_block {
-                    // add static HTML before the Suspense and stream it down
-                    tx.unbounded_send(std::mem::take(&mut buffer))
-                        .expect("failed to send async HTML chunk");
-
-                    // send the inner stream
+                    // add the inner chunks
                     let suspended = chunks.await;
-                    handle_blocking_chunks(tx.clone(), suspended).await;
+                    handle_blocking_chunks(suspended, buffer).await;
                 } else {
                     // TODO: should probably first check if there are any *other* blocking chunks
                     queued_chunks.push_front(StreamChunk::Async {
@@ -188,40 +388,46 @@ async fn handle_blocking_chunks(
                     break;
                 }
             }
+            StreamChunk::Flush => {}
         }
     }
 
-    // send final sync chunk
-    tx.unbounded_send(std::mem::take(&mut buffer))
-        .expect("failed to send final HTML chunk");
-
     queued_chunks
 }
 
 #[tracing::instrument(level = "trace", skip_all)]
 #[async_recursion(?Send)]
 async fn handle_chunks(
-    tx: UnboundedSender<String>,
+    tx: &mut Sender<String>,
     chunks: VecDeque<StreamChunk>,
+    options: StreamOptions,
+    buffer: &mut String,
 ) {
-    let mut buffer = String::new();
     for chunk in chunks {
         match chunk {
             StreamChunk::Sync(sync) => buffer.push_str(&sync),
             StreamChunk::Async { chunks, .. } => {
                 // add static HTML before the Suspense and stream it down
-                tx.unbounded_send(std::mem::take(&mut buffer))
-                    .expect("failed to send async HTML chunk");
+                if buffer.len() >= options.min_chunk_size {
+                    send_chunk(tx, buffer).await;
+                }
 
                 // send the inner stream
                 let suspended = chunks.await;
-                handle_chunks(tx.clone(), suspended).await;
+                handle_chunks(tx, suspended, options, buffer).await;
             }
+            StreamChunk::Flush => send_chunk(tx, buffer).await,
         }
     }
-    // send final sync chunk
-    tx.unbounded_send(std::mem::take(&mut buffer))
-        .expect("failed to send final HTML chunk");
+}
+
+// Waits until the channel has room for the chunk, so that rendering doesn't
+// get ahead of a slow reader.
+async fn send_chunk(tx: &mut Sender<String>, buffer: &mut String) {
+    if !buffer.is_empty() {
+        // if the stream has been dropped, there's no one left to send it to
+        _ = tx.send(std::mem::take(buffer)).await;
+    }
 }
 
 impl View {
@@ -253,6 +459,7 @@ impl View {
                 chunks.push_back(StreamChunk::Sync(node.content))
             }
             View::Component(node) => {
+                let is_flush_marker = node.is_flush_marker();
                 cfg_if! {
                   if #[cfg(debug_assertions)] {
                     let name = crate::ssr::to_kebab_case(&node.name);
@@ -268,6 +475,9 @@ impl View {
                     chunks.push_back(StreamChunk::Sync(format!(r#"<!--hk={}-->"#, HydrationCtx::to_string(&node.id, true)).into()))
                   }
                 }
+                if is_flush_marker {
+                    chunks.push_back(StreamChunk::Flush);
+                }
             }
             View::Element(el) => {
                 let is_script_or_style =
@@ -0,0 +1,345 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! Snapshot testing for server-rendered HTML.
+//!
+//! The HTML a view renders to includes hydration IDs and markers that change
+//! whenever an unrelated part of the page changes, and which differ between
+//! debug and release builds. [render_to_snapshot] renders a view to HTML
+//! without them, so that it only changes when the markup does.
+//!
+//! ```
+//! # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+//! # use leptos::{*, ssr::*};
+//! let html = render_to_snapshot(|cx| {
+//!     view! { cx, <p class="greeting" id="hello">"Hello!"</p> }
+//! });
+//! assert_snapshot(
+//!     r#"<p class="greeting" id="hello" leptos-hk="_h0">Hello!</p>"#,
+//!     &html,
+//! );
+//! # }}
+//! ```
+
+use crate::{ssr::render_to_string, IntoView};
+use leptos_reactive::Scope;
+use std::{collections::HashMap, path::Path};
+
+/// The environment variable that makes [assert_snapshot_file] overwrite
+/// existing snapshots instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "LEPTOS_UPDATE_SNAPSHOTS";
+
+/// Renders a view to HTML, as [render_to_string] does, and normalizes it with
+/// [normalize_html].
+pub fn render_to_snapshot<F, N>(f: F) -> String
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    normalize_html(&render_to_string(f))
+}
+
+/// Normalizes server-rendered HTML so that it can be compared against a
+/// snapshot:
+/// - hydration marker comments are removed
+/// - hydration IDs are renumbered in the order they appear, as `_h0`, `_h1`,
+///   and so on
+/// - the attributes of each element are sorted by name
+///
+/// Normalizing HTML that has already been normalized does not change it.
+pub fn normalize_html(html: &str) -> String {
+    let mut normalized = String::with_capacity(html.len());
+    let mut ids = HashMap::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        normalized.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let (body, after) = match comment.find("-->") {
+                Some(end) => (&comment[..end], &comment[end + 3..]),
+                None => (comment, ""),
+            };
+            if !is_hydration_comment(body) {
+                normalized.push_str("<!--");
+                normalized.push_str(body);
+                normalized.push_str("-->");
+            }
+            rest = after;
+            continue;
+        }
+
+        match tag_end(rest) {
+            Some(end) => {
+                normalize_tag(&rest[..=end], &mut ids, &mut normalized);
+                rest = &rest[end + 1..];
+            }
+            None => break,
+        }
+    }
+    normalized.push_str(rest);
+
+    normalized
+}
+
+/// Compares two pieces of HTML, returning `None` if they are the same, or a
+/// line-by-line diff with each tag and piece of text on its own line if they
+/// are not. Lines only in `expected` are prefixed with `-`, and lines only in
+/// `actual` with `+`.
+pub fn diff_html(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let expected = split_lines(expected);
+    let actual = split_lines(actual);
+    let (n, m) = (expected.len(), actual.len());
+
+    // longest common subsequence of lines, from the end
+    let mut lcs = vec![vec![0_usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            diff.push_str("  ");
+            diff.push_str(expected[i]);
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push_str("- ");
+            diff.push_str(expected[i]);
+            i += 1;
+        } else {
+            diff.push_str("+ ");
+            diff.push_str(actual[j]);
+            j += 1;
+        }
+        diff.push('\n');
+    }
+
+    Some(diff)
+}
+
+/// Asserts that two pieces of HTML are the same once they have been
+/// normalized with [normalize_html].
+///
+/// # Panics
+/// Panics with a diff of the two if they are not.
+#[track_caller]
+pub fn assert_snapshot(expected: &str, actual: &str) {
+    let expected = normalize_html(expected);
+    let actual = normalize_html(actual);
+    if let Some(diff) = diff_html(&expected, &actual) {
+        panic!("HTML does not match the snapshot:\n{diff}");
+    }
+}
+
+/// Asserts that HTML is the same as the snapshot stored in the file at
+/// `path`, once both have been normalized with [normalize_html].
+///
+/// If the file does not exist yet, or the `LEPTOS_UPDATE_SNAPSHOTS`
+/// environment variable is set, the snapshot is written to it instead, so
+/// that snapshots can be created and updated by running the tests again.
+///
+/// # Panics
+/// Panics with a diff if the HTML does not match the snapshot, or if the
+/// snapshot can't be read or written.
+#[track_caller]
+pub fn assert_snapshot_file(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    let actual = normalize_html(actual);
+
+    if !path.exists() || std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                panic!("could not create {}: {e}", parent.display());
+            }
+        }
+        if let Err(e) = std::fs::write(path, &actual) {
+            panic!("could not write snapshot {}: {e}", path.display());
+        }
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => normalize_html(&expected),
+        Err(e) => panic!("could not read snapshot {}: {e}", path.display()),
+    };
+    if let Some(diff) = diff_html(&expected, &actual) {
+        panic!(
+            "HTML does not match the snapshot {}:\n{diff}\nRun the tests \
+             again with {UPDATE_SNAPSHOTS_VAR}=1 to update it.",
+            path.display()
+        );
+    }
+}
+
+pub(crate) fn is_hydration_comment(body: &str) -> bool {
+    body.starts_with("hk=") || body.starts_with("leptos-view|")
+}
+
+// Hydration IDs are `_` followed by a `HydrationKey`:
+// the offsets of the components the element is in, each followed by `-`,
+// and then the offset of the element within its component, like `_0-1-2`.
+// A keyed fragment adds a segment of `k` followed by its key.
+pub(crate) fn is_hydration_id(value: &str) -> bool {
+    let is_offset =
+        |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let Some((previous, offset)) =
+        value.strip_prefix('_').and_then(|key| key.rsplit_once('-'))
+    else {
+        return false;
+    };
+    let mut segments = previous.split('-');
+    is_offset(offset)
+        && segments.next().map_or(false, is_offset)
+        && segments.all(|segment| {
+            is_offset(segment)
+                || (segment.len() > 1 && segment.starts_with('k'))
+        })
+}
+
+fn normalize_tag(
+    tag: &str,
+    ids: &mut HashMap<String, usize>,
+    normalized: &mut String,
+) {
+    // closing tags and doctypes have nothing to normalize
+    if tag.starts_with("</") || tag.starts_with("<!") {
+        normalized.push_str(tag);
+        return;
+    }
+
+    let inner = &tag[1..tag.len() - 1];
+    let (inner, self_closing) = match inner.strip_suffix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+    let name_end = inner
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(inner.len());
+    let mut attrs = parse_attrs(&inner[name_end..]);
+    attrs.sort_by_key(|(name, _)| *name);
+
+    normalized.push('<');
+    normalized.push_str(&inner[..name_end]);
+    for (name, value) in attrs {
+        normalized.push(' ');
+        normalized.push_str(name);
+        if let Some(value) = value {
+            normalized.push('=');
+            let unquoted = unquote(value);
+            if (name == "id" || name == "leptos-hk")
+                && is_hydration_id(unquoted)
+            {
+                let next = ids.len();
+                let idx = *ids.entry(unquoted.to_string()).or_insert(next);
+                normalized.push_str(&format!("\"_h{idx}\""));
+            } else {
+                normalized.push_str(value);
+            }
+        }
+    }
+    if self_closing {
+        normalized.push('/');
+    }
+    normalized.push('>');
+}
+
+/// Finds the `>` that closes the tag at the start of `html`, ignoring any
+/// within quoted attribute values.
+#[doc(hidden)]
+pub fn tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits the attributes of a tag into their names and (still quoted)
+/// values.
+#[doc(hidden)]
+pub fn parse_attrs(mut attrs: &str) -> Vec<(&str, Option<&str>)> {
+    let mut parsed = Vec::new();
+    loop {
+        attrs = attrs.trim_start();
+        if attrs.is_empty() {
+            return parsed;
+        }
+        let name_end = attrs
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(attrs.len());
+        let name = &attrs[..name_end];
+        attrs = attrs[name_end..].trim_start();
+
+        let value = match attrs.strip_prefix('=') {
+            Some(rest) => {
+                let rest = rest.trim_start();
+                let end = match rest.chars().next() {
+                    Some(quote @ ('"' | '\'')) => rest[1..]
+                        .find(quote)
+                        .map(|end| end + 2)
+                        .unwrap_or(rest.len()),
+                    _ => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+                };
+                attrs = &rest[end..];
+                Some(&rest[..end])
+            }
+            None => None,
+        };
+        if !name.is_empty() {
+            parsed.push((name, value));
+        }
+    }
+}
+
+/// Removes the quotes around an attribute value, if it has them.
+#[doc(hidden)]
+pub fn unquote(value: &str) -> &str {
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let value = &value[1..];
+            value.strip_suffix(quote).unwrap_or(value)
+        }
+        _ => value,
+    }
+}
+
+fn split_lines(html: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for (idx, c) in html.char_indices() {
+        match c {
+            '<' if idx > start => {
+                lines.push(&html[start..idx]);
+                start = idx;
+            }
+            '>' => {
+                lines.push(&html[start..=idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < html.len() {
+        lines.push(&html[start..]);
+    }
+    lines.retain(|line| !line.trim().is_empty());
+    lines
+}
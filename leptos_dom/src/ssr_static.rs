@@ -0,0 +1,549 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! Rendering views to static HTML, for targets that can't run JavaScript, like
+//! HTML email and [AMP](https://amp.dev/) pages.
+//!
+//! [render_to_static] renders the same component tree as [render_to_string],
+//! without hydration markers, and checks it for anything that would need
+//! JavaScript to work: event listeners, `<script>` elements, inline event
+//! handler attributes and `javascript:` URLs. Rather than silently dropping
+//! them, it fails with a [StaticRenderError] that lists each one.
+//!
+//! Styles are rewritten for the target:
+//! - for [StaticTarget::Email], rules from `<style>` elements with simple
+//!   selectors (a tag name, classes and an ID, like `p`, `.note` or
+//!   `a.button`) are inlined into the `style` attribute of each element they
+//!   match, in order of specificity, as many email clients ignore `<style>`
+//!   elements. Any other rules are kept in a single `<style>` element.
+//! - for [StaticTarget::Amp], every `<style>` element is merged into a single
+//!   `<style amp-custom>` element, and `!important` is reported, as AMP
+//!   doesn't allow it. The `<style amp-boilerplate>` elements and the AMP
+//!   runtime scripts from `cdn.ampproject.org` are kept as they are.
+//!
+//! The merged `<style>` element is put at the end of `<head>`, or at the start
+//! of the HTML if the view doesn't render a `<head>`.
+//!
+//! ```
+//! # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+//! # use leptos::{*, ssr::*};
+//! let html = render_to_static(StaticTarget::Email, |cx| {
+//!     view! { cx,
+//!         <style>"p { color: gray; } .note { font-size: 12px }"</style>
+//!         <p class="note">"Thanks for signing up!"</p>
+//!     }
+//! })
+//! .unwrap();
+//! assert!(html.contains(r#"style="color: gray; font-size: 12px;""#));
+//!
+//! let error = render_to_static(StaticTarget::Email, |cx| {
+//!     view! { cx, <button on:click=|_| ()>"Unsubscribe"</button> }
+//! })
+//! .unwrap_err();
+//! assert_eq!(error.diagnostics.len(), 1);
+//! # }}
+//! ```
+
+use crate::{
+    ev::EventDescriptor,
+    ssr::render_to_string,
+    ssr_snapshot::{
+        is_hydration_comment, is_hydration_id, parse_attrs, tag_end, unquote,
+    },
+    IntoView,
+};
+use leptos_reactive::Scope;
+use std::{cell::RefCell, fmt};
+
+thread_local! {
+    static DIAGNOSTICS: RefCell<Option<Vec<String>>> = RefCell::new(None);
+}
+
+/// The kind of document a view is rendered for by [render_to_static].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticTarget {
+    /// An HTML email, with styles inlined into each element.
+    Email,
+    /// An AMP page, with styles in a single `<style amp-custom>` element.
+    Amp,
+}
+
+/// The error returned by [render_to_static] when a view uses features that
+/// don't work without JavaScript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticRenderError {
+    /// A description of each unsupported feature, in the order they were
+    /// found.
+    pub diagnostics: Vec<String>,
+}
+
+impl fmt::Display for StaticRenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the view can't be rendered to static HTML:")?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n- {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StaticRenderError {}
+
+/// Renders a view to static HTML for the given target, or returns every
+/// feature it uses that the target doesn't support. See the
+/// [module docs](self) for what is checked and how styles are rewritten.
+pub fn render_to_static<F, N>(
+    target: StaticTarget,
+    f: F,
+) -> Result<String, StaticRenderError>
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    DIAGNOSTICS.with(|d| *d.borrow_mut() = Some(Vec::new()));
+    let html = render_to_string(f);
+    let mut diagnostics = DIAGNOSTICS
+        .with(|d| d.borrow_mut().take())
+        .unwrap_or_default();
+
+    let html = to_static_html(&html, target, &mut diagnostics);
+    if diagnostics.is_empty() {
+        Ok(html)
+    } else {
+        Err(StaticRenderError { diagnostics })
+    }
+}
+
+// Called while rendering for features that are dropped on the server, like
+// event listeners, so that a static render can report them.
+fn report_unsupported(message: impl FnOnce() -> String) {
+    DIAGNOSTICS.with(|d| {
+        if let Some(diagnostics) = d.borrow_mut().as_mut() {
+            diagnostics.push(message());
+        }
+    });
+}
+
+// Called when an event listener is added on the server.
+pub(crate) fn report_event_listener<E: EventDescriptor>(event: &E) {
+    report_unsupported(|| {
+        format!(
+            "`on:{}` event listeners don't run in static HTML",
+            event.name()
+        )
+    });
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Tag(Tag<'a>),
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, Option<&'a str>)>,
+    self_closing: bool,
+}
+
+fn to_static_html(
+    html: &str,
+    target: StaticTarget,
+    diagnostics: &mut Vec<String>,
+) -> String {
+    let mut parts = Vec::new();
+    let mut css = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        parts.push(Part::Text(&rest[..start]));
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(comment.len(), |end| end + 3);
+            if !is_hydration_comment(&comment[..end]) {
+                parts.push(Part::Text(&rest[..end + 4]));
+            }
+            rest = &comment[end..];
+            continue;
+        }
+        if rest.starts_with("</") || rest.starts_with("<!") {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            parts.push(Part::Text(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let Some(end) = tag_end(rest) else {
+            break;
+        };
+        let tag = parse_tag(&rest[..=end]);
+        rest = &rest[end + 1..];
+
+        // the contents of `<style>` and `<script>` are raw text
+        if tag.name.eq_ignore_ascii_case("style")
+            || tag.name.eq_ignore_ascii_case("script")
+        {
+            let close = format!("</{}>", tag.name);
+            let (content, after) = match rest.find(&close) {
+                Some(end) => (&rest[..end], &rest[end + close.len()..]),
+                None => (rest, ""),
+            };
+            rest = after;
+            let is_style = tag.name.eq_ignore_ascii_case("style");
+            if target == StaticTarget::Amp
+                && (is_amp_script(&tag)
+                    || (is_style && has_attr(&tag, "amp-boilerplate")))
+            {
+                let close = if is_style { "</style>" } else { "</script>" };
+                parts.push(Part::Tag(tag));
+                parts.push(Part::Text(content));
+                parts.push(Part::Text(close));
+            } else if is_style {
+                css.push_str(&html_escape::decode_html_entities(content));
+                css.push('\n');
+            } else {
+                diagnostics.push(
+                    "`<script>` elements don't run in static HTML".to_string(),
+                );
+            }
+            continue;
+        }
+
+        check_attrs(&tag, diagnostics);
+        parts.push(Part::Tag(tag));
+    }
+    parts.push(Part::Text(rest));
+
+    if target == StaticTarget::Amp && css.contains("!important") {
+        diagnostics
+            .push("AMP doesn't allow `!important` in styles".to_string());
+    }
+
+    let (rules, leftover) = match target {
+        StaticTarget::Email => parse_rules(&css),
+        StaticTarget::Amp => (Vec::new(), css.trim().to_string()),
+    };
+
+    let mut style = String::new();
+    if !leftover.is_empty() {
+        style.push_str(match target {
+            StaticTarget::Email => "<style>",
+            StaticTarget::Amp => "<style amp-custom>",
+        });
+        style.push_str(&leftover);
+        style.push_str("</style>");
+    }
+
+    let mut output = String::with_capacity(html.len() + style.len());
+    for part in parts {
+        match part {
+            Part::Text(text) => {
+                if text.eq_ignore_ascii_case("</head>") {
+                    output.push_str(&std::mem::take(&mut style));
+                }
+                output.push_str(text);
+            }
+            Part::Tag(tag) => write_tag(&tag, &rules, &mut output),
+        }
+    }
+    output.insert_str(0, &style);
+    output
+}
+
+fn parse_tag(tag: &str) -> Tag<'_> {
+    let inner = &tag[1..tag.len() - 1];
+    let (inner, self_closing) = match inner.strip_suffix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+    let name_end = inner
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(inner.len());
+    let attrs = parse_attrs(&inner[name_end..])
+        .into_iter()
+        .filter(|(name, value)| {
+            let hydration_id =
+                value.map_or(false, |value| is_hydration_id(unquote(value)));
+            !(*name == "data-hk"
+                || ((*name == "id" || *name == "leptos-hk") && hydration_id))
+        })
+        .collect();
+    Tag {
+        name: &inner[..name_end],
+        attrs,
+        self_closing,
+    }
+}
+
+fn check_attrs(tag: &Tag, diagnostics: &mut Vec<String>) {
+    for (name, value) in &tag.attrs {
+        let lower = name.to_ascii_lowercase();
+        if lower.starts_with("on") {
+            diagnostics.push(format!(
+                "the `{name}` attribute on `<{}>` needs JavaScript",
+                tag.name
+            ));
+        } else if let Some(value) = value {
+            let value = html_escape::decode_html_entities(unquote(value));
+            if value
+                .trim_start()
+                .to_ascii_lowercase()
+                .starts_with("javascript:")
+            {
+                diagnostics.push(format!(
+                    "the `{name}` attribute on `<{}>` is a `javascript:` URL",
+                    tag.name
+                ));
+            }
+        }
+    }
+}
+
+fn has_attr(tag: &Tag, attr: &str) -> bool {
+    tag.attrs
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(attr))
+}
+
+fn is_amp_script(tag: &Tag) -> bool {
+    tag.attrs.iter().any(|(name, value)| {
+        *name == "src"
+            && value.map_or(false, |src| {
+                unquote(src).starts_with("https://cdn.ampproject.org/")
+            })
+    })
+}
+
+fn write_tag(tag: &Tag, rules: &[Rule], output: &mut String) {
+    // declarations are applied in order of specificity, then in the order of
+    // the stylesheet, so that the one that wins in a browser comes last
+    let mut matched = rules
+        .iter()
+        .filter_map(|rule| {
+            let specificity = rule
+                .selectors
+                .iter()
+                .filter(|selector| selector.matches(tag))
+                .map(Selector::specificity)
+                .max()?;
+            Some((specificity, &rule.declarations))
+        })
+        .collect::<Vec<_>>();
+    matched.sort_by_key(|(specificity, _)| *specificity);
+
+    let mut style = String::new();
+    for (_, declarations) in matched {
+        push_declarations(declarations, &mut style);
+    }
+
+    output.push('<');
+    output.push_str(tag.name);
+    for (name, value) in &tag.attrs {
+        // the element's own styles come last, so that they take precedence
+        if *name == "style" && !style.is_empty() {
+            if let Some(value) = value {
+                let value = html_escape::decode_html_entities(unquote(value));
+                push_declarations(&value, &mut style);
+            }
+            continue;
+        }
+        output.push(' ');
+        output.push_str(name);
+        if let Some(value) = value {
+            output.push('=');
+            output.push_str(value);
+        }
+    }
+    if !style.is_empty() {
+        output.push_str(" style=\"");
+        output.push_str(&html_escape::encode_double_quoted_attribute(&style));
+        output.push('"');
+    }
+    if tag.self_closing {
+        output.push('/');
+    }
+    output.push('>');
+}
+
+fn push_declarations(declarations: &str, style: &mut String) {
+    for declaration in declarations.split(';') {
+        let declaration = declaration.trim();
+        if !declaration.is_empty() {
+            if !style.is_empty() {
+                style.push(' ');
+            }
+            style.push_str(declaration);
+            style.push(';');
+        }
+    }
+}
+
+struct Rule {
+    selectors: Vec<Selector>,
+    declarations: String,
+}
+
+// A selector like `a`, `.button`, `#cta` or `a.button.primary`.
+struct Selector {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Option<Self> {
+        let is_name_char =
+            |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+        let tag_end = selector
+            .find(|c| c == '.' || c == '#')
+            .unwrap_or(selector.len());
+        let tag = &selector[..tag_end];
+        if !tag.chars().all(is_name_char) {
+            return None;
+        }
+        let mut parsed = Selector {
+            tag: (!tag.is_empty()).then(|| tag.to_ascii_lowercase()),
+            id: None,
+            classes: Vec::new(),
+        };
+
+        let mut rest = &selector[tag_end..];
+        while let Some(kind) = rest.chars().next() {
+            rest = &rest[1..];
+            let end = rest.find(|c| c == '.' || c == '#').unwrap_or(rest.len());
+            let name = &rest[..end];
+            if name.is_empty() || !name.chars().all(is_name_char) {
+                return None;
+            }
+            match kind {
+                '.' => parsed.classes.push(name.to_string()),
+                _ if parsed.id.is_none() => parsed.id = Some(name.to_string()),
+                _ => return None,
+            }
+            rest = &rest[end..];
+        }
+
+        (parsed.tag.is_some()
+            || parsed.id.is_some()
+            || !parsed.classes.is_empty())
+        .then_some(parsed)
+    }
+
+    // The number of IDs, classes and tag names in the selector.
+    fn specificity(&self) -> (usize, usize, usize) {
+        (
+            usize::from(self.id.is_some()),
+            self.classes.len(),
+            usize::from(self.tag.is_some()),
+        )
+    }
+
+    fn matches(&self, tag: &Tag) -> bool {
+        let attr = |attr: &str| {
+            tag.attrs
+                .iter()
+                .find(|(name, _)| *name == attr)
+                .and_then(|(_, value)| *value)
+                .map(unquote)
+        };
+        self.tag
+            .as_ref()
+            .map_or(true, |name| tag.name.eq_ignore_ascii_case(name))
+            && self.id.as_deref().map_or(true, |id| attr("id") == Some(id))
+            && self.classes.iter().all(|class| {
+                attr("class").map_or(false, |classes| {
+                    classes.split_whitespace().any(|c| c == class)
+                })
+            })
+    }
+}
+
+// Splits a stylesheet into the rules that can be inlined, and the CSS for
+// those that can't, like at-rules and rules with combinators or
+// pseudo-classes.
+fn parse_rules(css: &str) -> (Vec<Rule>, String) {
+    let mut rules = Vec::new();
+    let mut leftover = String::new();
+    let css = strip_comments(css);
+    let mut rest = css.trim();
+
+    while !rest.is_empty() {
+        // at-rules without a block, like `@import`
+        if rest.starts_with('@') {
+            if let Some(end) = rest
+                .find(';')
+                .filter(|end| rest.find('{').map_or(true, |open| end < &open))
+            {
+                leftover.push_str(&rest[..=end]);
+                rest = rest[end + 1..].trim_start();
+                continue;
+            }
+        }
+        let Some(open) = rest.find('{') else {
+            break;
+        };
+        let (block, close) = match block_end(&rest[open..]) {
+            Some(end) => (&rest[open + 1..open + end - 1], open + end),
+            None => (&rest[open + 1..], rest.len()),
+        };
+        let prelude = rest[..open].trim();
+
+        if prelude.starts_with('@') {
+            leftover.push_str(&rest[..close]);
+        } else {
+            let (simple, complex): (Vec<_>, Vec<_>) = prelude
+                .split(',')
+                .map(str::trim)
+                .partition(|selector| Selector::parse(selector).is_some());
+            if !simple.is_empty() {
+                rules.push(Rule {
+                    selectors: simple
+                        .into_iter()
+                        .filter_map(Selector::parse)
+                        .collect(),
+                    declarations: block.trim().to_string(),
+                });
+            }
+            if !complex.is_empty() {
+                leftover.push_str(&complex.join(", "));
+                leftover.push('{');
+                leftover.push_str(block.trim());
+                leftover.push('}');
+            }
+        }
+        rest = rest[close..].trim_start();
+    }
+
+    (rules, leftover)
+}
+
+// Finds the end of the block that starts at the beginning of `css`, just
+// after its closing `}`.
+fn block_end(css: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in css.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut stripped = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = rest[start + 2..]
+            .find("*/")
+            .map_or("", |end| &rest[start + end + 4..]);
+    }
+    stripped.push_str(rest);
+    stripped
+}
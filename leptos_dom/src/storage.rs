@@ -0,0 +1,298 @@
+use leptos_reactive::{
+    create_signal, ReadSignal, Scope, Serializable, WriteSignal,
+};
+use std::time::Duration;
+
+/// Which of the browser's storage areas a [create_persistent_signal] is
+/// saved in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    /// [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage),
+    /// which is kept across browser sessions and shared between tabs.
+    #[default]
+    Local,
+    /// [`sessionStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/sessionStorage),
+    /// which is cleared when the tab is closed.
+    Session,
+}
+
+/// Options for [create_persistent_signal_with_options].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PersistentSignalOptions {
+    /// Where the value is saved. Defaults to [StorageKind::Local].
+    pub storage: StorageKind,
+    /// How long to wait after the value was last set before saving it, so
+    /// that a value that changes often (like the text of an input) is not
+    /// serialized on every change. Defaults to 100ms.
+    pub write_delay: Duration,
+}
+
+impl Default for PersistentSignalOptions {
+    fn default() -> Self {
+        Self {
+            storage: StorageKind::Local,
+            write_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Creates a signal whose value is saved in `localStorage` under the given
+/// key, so that it is restored when the page is loaded again, and kept in
+/// sync with other tabs that use the same key.
+///
+/// The value is encoded with the same [Serializable] implementation as
+/// resources. If nothing has been saved yet, or the saved value can't be
+/// decoded, the signal starts with `default`.
+///
+/// Storage only exists in the browser, so the signal is always `default`
+/// during server-side rendering. When hydrating, it also starts as `default`,
+/// so that the view matches the HTML from the server, and is updated to the
+/// saved value right afterwards.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ThemeToggle(cx: Scope) -> impl IntoView {
+///     let (dark, set_dark) = create_persistent_signal(cx, "dark-mode", false);
+///
+///     view! { cx,
+///       <button on:click=move |_| set_dark.update(|dark| *dark = !*dark)>
+///         {move || if dark.get() { "Light mode" } else { "Dark mode" }}
+///       </button>
+///     }
+/// }
+/// ```
+pub fn create_persistent_signal<T>(
+    cx: Scope,
+    key: impl Into<String>,
+    default: T,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serializable + Clone + 'static,
+{
+    create_persistent_signal_with_options(
+        cx,
+        key,
+        default,
+        PersistentSignalOptions::default(),
+    )
+}
+
+/// Like [create_persistent_signal], but lets you choose the storage area and
+/// how long writes are delayed.
+///
+/// ```
+/// # use leptos::*;
+/// # use std::time::Duration;
+/// # let runtime = create_runtime();
+/// # run_scope(runtime, |cx| {
+/// let (draft, set_draft) = create_persistent_signal_with_options(
+///     cx,
+///     "comment-draft",
+///     String::new(),
+///     PersistentSignalOptions {
+///         storage: StorageKind::Session,
+///         write_delay: Duration::from_millis(500),
+///     },
+/// );
+/// # });
+/// # runtime.dispose();
+/// ```
+pub fn create_persistent_signal_with_options<T>(
+    cx: Scope,
+    key: impl Into<String>,
+    default: T,
+    options: PersistentSignalOptions,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serializable + Clone + 'static,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        browser::create(cx, key.into(), default, options)
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = key;
+        _ = options;
+        create_signal(cx, default)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+    use crate::{
+        helpers::{
+            request_animation_frame, set_timeout_with_handle, TimeoutHandle,
+        },
+        window, HydrationCtx,
+    };
+    use leptos_reactive::{
+        create_render_effect, on_cleanup, SignalSet, SignalWith,
+    };
+    use std::{cell::RefCell, rc::Rc};
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    pub(super) fn create<T>(
+        cx: Scope,
+        key: String,
+        default: T,
+        options: PersistentSignalOptions,
+    ) -> (ReadSignal<T>, WriteSignal<T>)
+    where
+        T: Serializable + Clone + 'static,
+    {
+        let Some(storage) = storage(options.storage) else {
+            crate::debug_warn!(
+                "{:?} storage is not available, so `{key}` will not be saved",
+                options.storage
+            );
+            return create_signal(cx, default);
+        };
+
+        let saved = storage.get_item(&key).ok().flatten();
+        let decode = {
+            let key = key.clone();
+            move |text: &str| match T::de(text) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    crate::debug_warn!("could not decode saved `{key}`: {e}");
+                    None
+                }
+            }
+        };
+
+        let hydrating = HydrationCtx::is_hydrating();
+        let initial = if hydrating {
+            None
+        } else {
+            saved.as_deref().and_then(&decode)
+        };
+        let (value, set_value) =
+            create_signal(cx, initial.unwrap_or_else(|| default.clone()));
+        if hydrating {
+            if let Some(saved) = saved.as_deref().and_then(&decode) {
+                request_animation_frame(move || {
+                    _ = set_value.try_set(saved);
+                });
+            }
+        }
+
+        // the text that is currently saved, to avoid writing it again after
+        // it was changed in another tab
+        let last_written = Rc::new(RefCell::new(saved));
+        let pending = Rc::new(RefCell::new(None::<(TimeoutHandle, String)>));
+        let write = {
+            let storage = storage.clone();
+            let key = key.clone();
+            let last_written = Rc::clone(&last_written);
+            move |text: String| {
+                if last_written.borrow().as_deref() != Some(text.as_str()) {
+                    if let Err(e) = storage.set_item(&key, &text) {
+                        crate::debug_warn!("could not save `{key}`: {e:?}");
+                    }
+                    *last_written.borrow_mut() = Some(text);
+                }
+            }
+        };
+
+        create_render_effect(cx, {
+            let key = key.clone();
+            let write = write.clone();
+            let pending = Rc::clone(&pending);
+            move |prev: Option<()>| {
+                let text = value.with(|value| value.ser());
+                // nothing has changed on the first run
+                if prev.is_none() {
+                    return;
+                }
+                let text = match text {
+                    Ok(text) => text,
+                    Err(e) => {
+                        crate::debug_warn!("could not encode `{key}`: {e}");
+                        return;
+                    }
+                };
+                if let Some((handle, _)) = pending.borrow_mut().take() {
+                    handle.clear();
+                }
+                if options.write_delay.is_zero() {
+                    write(text);
+                    return;
+                }
+                let handle = set_timeout_with_handle(
+                    {
+                        let pending = Rc::clone(&pending);
+                        let write = write.clone();
+                        move || {
+                            if let Some((_, text)) = pending.borrow_mut().take()
+                            {
+                                write(text);
+                            }
+                        }
+                    },
+                    options.write_delay,
+                );
+                match handle {
+                    Ok(handle) => *pending.borrow_mut() = Some((handle, text)),
+                    Err(_) => write(text),
+                }
+            }
+        });
+
+        // `storage` events are only fired in other tabs
+        let on_storage = Closure::<dyn Fn(web_sys::StorageEvent)>::new({
+            let storage = storage.clone();
+            let key = key.clone();
+            move |ev: web_sys::StorageEvent| {
+                let same_area = ev
+                    .storage_area()
+                    .map(|area| area == storage)
+                    .unwrap_or(false);
+                // a `null` key means the whole storage area was cleared
+                let same_key = ev.key().map(|k| k == key).unwrap_or(true);
+                if !same_area || !same_key {
+                    return;
+                }
+                let new_value = ev.new_value();
+                *last_written.borrow_mut() = new_value.clone();
+                let value = new_value
+                    .as_deref()
+                    .and_then(&decode)
+                    .unwrap_or_else(|| default.clone());
+                _ = set_value.try_set(value);
+            }
+        });
+        let window = window();
+        _ = window.add_event_listener_with_callback(
+            "storage",
+            on_storage.as_ref().unchecked_ref(),
+        );
+
+        on_cleanup(cx, move || {
+            _ = window.remove_event_listener_with_callback(
+                "storage",
+                on_storage.as_ref().unchecked_ref(),
+            );
+            drop(on_storage);
+            // save the last change rather than losing it
+            if let Some((handle, text)) = pending.borrow_mut().take() {
+                handle.clear();
+                write(text);
+            }
+        });
+
+        (value, set_value)
+    }
+
+    fn storage(kind: StorageKind) -> Option<web_sys::Storage> {
+        let window = window();
+        match kind {
+            StorageKind::Local => window.local_storage(),
+            StorageKind::Session => window.session_storage(),
+        }
+        .ok()
+        .flatten()
+    }
+}
@@ -0,0 +1,493 @@
+use crate::{html::ElementDescriptor, NodeRef};
+use leptos_reactive::{
+    create_signal, ReadSignal, Scope, SignalGetUntracked, WriteSignal,
+};
+
+/// Reactive access to the system clipboard, created with [use_clipboard].
+#[derive(Debug, Clone, Copy)]
+pub struct UseClipboard {
+    /// Whether the browser supports the
+    /// [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API),
+    /// which is only available on HTTPS pages and `localhost`. Always `false`
+    /// on the server, and set once the view has been mounted in the browser,
+    /// so that hydration is not affected.
+    pub is_supported: ReadSignal<bool>,
+    /// The text most recently copied with [UseClipboard::copy], once it has
+    /// been copied.
+    pub copied: ReadSignal<Option<String>>,
+    /// `true` while text is being copied.
+    pub pending: ReadSignal<bool>,
+    /// The error of the last copy that failed, e.g., because the user denied
+    /// permission.
+    pub error: ReadSignal<Option<String>>,
+    set_copied: WriteSignal<Option<String>>,
+    op: Operation,
+}
+
+impl UseClipboard {
+    /// Copies `text` to the clipboard. Does nothing if the clipboard is not
+    /// supported.
+    pub fn copy(&self, text: &str) {
+        if !self.is_supported.get_untracked() {
+            return;
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            use leptos_reactive::SignalSet;
+
+            let set_copied = self.set_copied;
+            let copied = text.to_string();
+            self.op.run(
+                browser::call(
+                    &browser::navigator_get("clipboard"),
+                    "writeText",
+                    &wasm_bindgen::JsValue::from_str(text),
+                ),
+                move || {
+                    _ = set_copied.try_set(Some(copied));
+                },
+            );
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = (text, self.set_copied, self.op);
+        }
+    }
+}
+
+/// Returns a [UseClipboard], which copies text to the clipboard and tracks
+/// whether that has worked. Does nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn CopyLink(cx: Scope, url: String) -> impl IntoView {
+///     let clipboard = use_clipboard(cx);
+///
+///     view! { cx,
+///       <button
+///         disabled=move || !clipboard.is_supported.get()
+///         on:click=move |_| clipboard.copy(&url)
+///       >
+///         {move || if clipboard.copied.get().is_some() { "Copied!" } else { "Copy link" }}
+///       </button>
+///     }
+/// }
+/// ```
+pub fn use_clipboard(cx: Scope) -> UseClipboard {
+    let (copied, set_copied) = create_signal(cx, None);
+    let (op, pending, error) = Operation::new(cx);
+
+    let is_supported = supported_after_mount(cx, || {
+        cfg_if::cfg_if! {
+          if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            !browser::navigator_get("clipboard").is_undefined()
+          } else {
+            false
+          }
+        }
+    });
+
+    UseClipboard {
+        is_supported,
+        copied,
+        pending,
+        error,
+        set_copied,
+        op,
+    }
+}
+
+/// The data shared with [UseWebShare::share].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShareData {
+    /// The title of the shared document.
+    pub title: Option<String>,
+    /// The text to share.
+    pub text: Option<String>,
+    /// The URL to share.
+    pub url: Option<String>,
+}
+
+/// Reactive access to the system's share dialog, created with
+/// [use_web_share].
+#[derive(Debug, Clone, Copy)]
+pub struct UseWebShare {
+    /// Whether the browser supports the
+    /// [Web Share API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Share_API).
+    /// Always `false` on the server, and set once the view has been mounted
+    /// in the browser.
+    pub is_supported: ReadSignal<bool>,
+    /// `true` while the share dialog is open.
+    pub pending: ReadSignal<bool>,
+    /// The error of the last share that failed. This includes the user
+    /// closing the dialog without sharing, which is an `AbortError`.
+    pub error: ReadSignal<Option<String>>,
+    op: Operation,
+}
+
+impl UseWebShare {
+    /// Opens the share dialog with the given data. Must be called in response
+    /// to a user action, like a click. Does nothing if sharing is not
+    /// supported.
+    pub fn share(&self, data: &ShareData) {
+        if !self.is_supported.get_untracked() {
+            return;
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            use wasm_bindgen::JsValue;
+
+            let object = js_sys::Object::new();
+            for (key, value) in [
+                ("title", &data.title),
+                ("text", &data.text),
+                ("url", &data.url),
+            ] {
+                if let Some(value) = value {
+                    _ = js_sys::Reflect::set(
+                        &object,
+                        &JsValue::from_str(key),
+                        &JsValue::from_str(value),
+                    );
+                }
+            }
+            let navigator = crate::window().navigator();
+            self.op
+                .run(browser::call(&navigator, "share", &object), || {});
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = (data, self.op);
+        }
+    }
+}
+
+/// Returns a [UseWebShare], which opens the system's share dialog on devices
+/// that have one. Does nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ShareButton(cx: Scope, url: String) -> impl IntoView {
+///     let share = use_web_share(cx);
+///     let data = ShareData {
+///         url: Some(url),
+///         ..Default::default()
+///     };
+///
+///     view! { cx,
+///       <Show when=move || share.is_supported.get() fallback=|_| ()>
+///         <button on:click={
+///           let data = data.clone();
+///           move |_| share.share(&data)
+///         }>
+///           "Share"
+///         </button>
+///       </Show>
+///     }
+/// }
+/// ```
+pub fn use_web_share(cx: Scope) -> UseWebShare {
+    let (op, pending, error) = Operation::new(cx);
+
+    let is_supported = supported_after_mount(cx, || {
+        cfg_if::cfg_if! {
+          if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            !browser::navigator_get("share").is_undefined()
+          } else {
+            false
+          }
+        }
+    });
+
+    UseWebShare {
+        is_supported,
+        pending,
+        error,
+        op,
+    }
+}
+
+/// Reactive access to the
+/// [Fullscreen API](https://developer.mozilla.org/en-US/docs/Web/API/Fullscreen_API)
+/// for one element, created with [use_fullscreen].
+pub struct UseFullscreen<El: ElementDescriptor + 'static> {
+    /// Whether the browser allows elements to be shown fullscreen. Always
+    /// `false` on the server, and set once the view has been mounted in the
+    /// browser.
+    pub is_supported: ReadSignal<bool>,
+    /// `true` while the element is shown fullscreen. This also changes when
+    /// the user leaves fullscreen, e.g., by pressing `Escape`.
+    pub is_fullscreen: ReadSignal<bool>,
+    /// The error of the last request to show the element fullscreen that
+    /// failed.
+    pub error: ReadSignal<Option<String>>,
+    node_ref: NodeRef<El>,
+    op: Operation,
+}
+
+impl<El: ElementDescriptor + 'static> Clone for UseFullscreen<El> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<El: ElementDescriptor + 'static> Copy for UseFullscreen<El> {}
+
+impl<El> UseFullscreen<El>
+where
+    El: ElementDescriptor + Clone + 'static,
+{
+    /// Shows the element fullscreen. Must be called in response to a user
+    /// action, like a click.
+    pub fn enter(&self) {
+        if !self.is_supported.get_untracked() {
+            return;
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            if let Some(el) = self.node_ref.get_untracked() {
+                let el: &web_sys::HtmlElement = el.element.as_ref();
+                self.op.run(
+                    browser::call(el, "requestFullscreen", &Default::default()),
+                    || {},
+                );
+            }
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = (self.node_ref, self.op);
+        }
+    }
+
+    /// Leaves fullscreen, if this element is shown fullscreen.
+    pub fn exit(&self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            if self.is_supported.get_untracked()
+                && browser::is_fullscreen(&self.node_ref)
+            {
+                crate::document().exit_fullscreen();
+            }
+        }
+    }
+
+    /// Enters or leaves fullscreen.
+    pub fn toggle(&self) {
+        if self.is_fullscreen.get_untracked() {
+            self.exit();
+        } else {
+            self.enter();
+        }
+    }
+}
+
+/// Returns a [UseFullscreen], which shows the element in `node_ref`
+/// fullscreen. Does nothing on the server.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Player(cx: Scope) -> impl IntoView {
+///     let video = create_node_ref::<html::Video>(cx);
+///     let fullscreen = use_fullscreen(cx, video);
+///
+///     view! { cx,
+///       <video _ref=video src="/intro.mp4"/>
+///       <button on:click=move |_| fullscreen.toggle()>
+///         {move || if fullscreen.is_fullscreen.get() { "Exit fullscreen" } else { "Fullscreen" }}
+///       </button>
+///     }
+/// }
+/// ```
+pub fn use_fullscreen<El>(cx: Scope, node_ref: NodeRef<El>) -> UseFullscreen<El>
+where
+    El: ElementDescriptor + Clone + 'static,
+{
+    let (is_fullscreen, set_is_fullscreen) = create_signal(cx, false);
+    let (op, _, error) = Operation::new(cx);
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use leptos_reactive::SignalSet;
+
+        if crate::document().fullscreen_enabled() {
+            browser::on_fullscreen_change(cx, move || {
+                _ = set_is_fullscreen
+                    .try_set(browser::is_fullscreen(&node_ref));
+            });
+        }
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = set_is_fullscreen;
+    }
+    let is_supported = supported_after_mount(cx, || {
+        cfg_if::cfg_if! {
+          if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            crate::document().fullscreen_enabled()
+          } else {
+            false
+          }
+        }
+    });
+
+    UseFullscreen {
+        is_supported,
+        is_fullscreen,
+        error,
+        node_ref,
+        op,
+    }
+}
+
+/// Returns a signal that is `false`, as it is on the server, until the next
+/// animation frame in the browser, when it is set to the result of `check`.
+/// A hydrated view that depends on it then matches the server-rendered HTML.
+fn supported_after_mount(
+    cx: Scope,
+    check: impl FnOnce() -> bool + 'static,
+) -> ReadSignal<bool> {
+    let (is_supported, set_is_supported) = create_signal(cx, false);
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    crate::request_animation_frame(move || {
+        use leptos_reactive::SignalSet;
+
+        _ = set_is_supported.try_set(check());
+    });
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (set_is_supported, check);
+    }
+    is_supported
+}
+
+/// Tracks whether an asynchronous browser operation is pending, and how it
+/// failed.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(all(target_arch = "wasm32", feature = "web")), allow(dead_code))]
+struct Operation {
+    set_pending: WriteSignal<bool>,
+    set_error: WriteSignal<Option<String>>,
+}
+
+impl Operation {
+    fn new(cx: Scope) -> (Self, ReadSignal<bool>, ReadSignal<Option<String>>) {
+        let (pending, set_pending) = create_signal(cx, false);
+        let (error, set_error) = create_signal(cx, None);
+        (
+            Self {
+                set_pending,
+                set_error,
+            },
+            pending,
+            error,
+        )
+    }
+
+    /// Waits for the `Promise` returned by a browser API, and calls `on_ok`
+    /// once it has resolved.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn run(
+        self,
+        promise: Result<js_sys::Promise, wasm_bindgen::JsValue>,
+        on_ok: impl FnOnce() + 'static,
+    ) {
+        use leptos_reactive::SignalSet;
+
+        _ = self.set_error.try_set(None);
+        let promise = match promise {
+            Ok(promise) => promise,
+            Err(e) => {
+                _ = self.set_error.try_set(Some(browser::error_message(&e)));
+                return;
+            }
+        };
+        _ = self.set_pending.try_set(true);
+        wasm_bindgen_futures::spawn_local(async move {
+            match wasm_bindgen_futures::JsFuture::from(promise).await {
+                Ok(_) => on_ok(),
+                Err(e) => {
+                    _ = self
+                        .set_error
+                        .try_set(Some(browser::error_message(&e)));
+                }
+            }
+            _ = self.set_pending.try_set(false);
+        });
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+    use leptos_reactive::on_cleanup;
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+    // the Clipboard and Web Share APIs are still unstable in `web_sys`, so
+    // they are called through `Reflect`
+
+    pub(super) fn navigator_get(name: &str) -> JsValue {
+        js_sys::Reflect::get(
+            &crate::window().navigator(),
+            &JsValue::from_str(name),
+        )
+        .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Calls the method `name` of `this` with one argument, and returns the
+    /// `Promise` it returns.
+    pub(super) fn call(
+        this: &JsValue,
+        name: &str,
+        arg: &JsValue,
+    ) -> Result<js_sys::Promise, JsValue> {
+        let method = js_sys::Reflect::get(this, &JsValue::from_str(name))?
+            .dyn_into::<js_sys::Function>()?;
+        let result = method.call1(this, arg)?;
+        // older browsers return nothing from `requestFullscreen`
+        Ok(result
+            .dyn_into::<js_sys::Promise>()
+            .unwrap_or_else(|_| js_sys::Promise::resolve(&JsValue::UNDEFINED)))
+    }
+
+    pub(super) fn error_message(e: &JsValue) -> String {
+        js_sys::Reflect::get(e, &JsValue::from_str("message"))
+            .ok()
+            .and_then(|message| message.as_string())
+            .unwrap_or_else(|| format!("{e:?}"))
+    }
+
+    pub(super) fn is_fullscreen<El>(node_ref: &NodeRef<El>) -> bool
+    where
+        El: ElementDescriptor + Clone + 'static,
+    {
+        match (
+            crate::document().fullscreen_element(),
+            node_ref.get_untracked(),
+        ) {
+            (Some(current), Some(el)) => {
+                let el: &web_sys::HtmlElement = el.element.as_ref();
+                let el: &web_sys::Element = el;
+                &current == el
+            }
+            _ => false,
+        }
+    }
+
+    pub(super) fn on_fullscreen_change(cx: Scope, f: impl Fn() + 'static) {
+        let on_change = Closure::<dyn Fn()>::new(f);
+        let document = crate::document();
+        _ = document.add_event_listener_with_callback(
+            "fullscreenchange",
+            on_change.as_ref().unchecked_ref(),
+        );
+        on_cleanup(cx, move || {
+            _ = document.remove_event_listener_with_callback(
+                "fullscreenchange",
+                on_change.as_ref().unchecked_ref(),
+            );
+        });
+    }
+}
@@ -0,0 +1,336 @@
+use leptos_reactive::{Serializable, SerializationError};
+use std::{fmt, future::Future, pin::Pin};
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use {
+    futures::channel::oneshot,
+    std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    },
+    wasm_bindgen::{prelude::Closure, JsCast, JsValue},
+};
+
+/// A function that can run in a
+/// [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API),
+/// so that it doesn't block the main thread. This is usually implemented by
+/// the [worker](https://docs.rs/leptos/latest/leptos/attr.worker.html) macro,
+/// for a struct that holds the arguments of the function.
+///
+/// The arguments and the output are sent between the threads as text, using
+/// their [Serializable] implementation.
+pub trait WorkerFn: Serializable + Clone + 'static {
+    /// The type returned by the function.
+    type Output: Serializable + 'static;
+
+    /// The name the function is registered with in the worker. It must be
+    /// the same in the app and in the worker bundle.
+    const NAME: &'static str;
+
+    /// Runs the function with these arguments.
+    fn run(self) -> Pin<Box<dyn Future<Output = Self::Output>>>;
+
+    /// Registers the function, so that the worker can run it when
+    /// [run_worker] is called. Call this in the `main` function of the
+    /// worker bundle.
+    fn register() {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        browser::register::<Self>();
+    }
+}
+
+/// An error from calling a [WorkerFn] through a [WorkerHandle].
+#[derive(Debug, Clone)]
+pub enum WorkerError {
+    /// The worker could not be started, e.g., because its script was not
+    /// found.
+    Unavailable(String),
+    /// The worker failed while running the function, or was terminated.
+    Crashed(String),
+    /// The function has not been [registered](WorkerFn::register) in the
+    /// worker.
+    NotRegistered(&'static str),
+    /// The arguments or the output could not be encoded or decoded.
+    Serialization(String),
+}
+
+impl fmt::Display for WorkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(e) => write!(f, "could not start worker: {e}"),
+            Self::Crashed(e) => write!(f, "worker failed: {e}"),
+            Self::NotRegistered(name) => write!(
+                f,
+                "worker function `{name}` is not registered in the worker"
+            ),
+            Self::Serialization(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}
+
+impl From<SerializationError> for WorkerError {
+    fn from(e: SerializationError) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
+
+/// A Web Worker that runs [WorkerFn]s for the app. Cloning the handle shares
+/// the same worker, which is terminated once every clone has been dropped.
+///
+/// The worker is a separate WebAssembly bundle built from the same crate,
+/// e.g., with a second binary whose `main` function registers each function
+/// and then calls [run_worker]. With [Trunk](https://trunkrs.dev/), it can be
+/// built with
+/// `<link data-trunk rel="rust" data-bin="worker" data-type="worker"/>`.
+///
+/// On the server, there are no workers, so functions are run on the current
+/// thread instead.
+///
+/// ```ignore
+/// # use leptos::*;
+/// #[worker(CountPrimes)]
+/// pub async fn count_primes(below: u32) -> usize {
+///     (2..below).filter(|n| (2..*n).all(|d| n % d != 0)).count()
+/// }
+///
+/// // in the worker bundle
+/// fn main() {
+///     CountPrimes::register();
+///     run_worker();
+/// }
+///
+/// // in the app
+/// let worker = WorkerHandle::new("/worker.js")?;
+/// let primes = worker.call(CountPrimes { below: 100_000 }).await?;
+/// ```
+#[derive(Clone)]
+pub struct WorkerHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    inner: Rc<browser::Inner>,
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    _private: (),
+}
+
+impl fmt::Debug for WorkerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerHandle").finish_non_exhaustive()
+    }
+}
+
+impl WorkerHandle {
+    /// Starts a worker from the script at the given URL.
+    pub fn new(url: &str) -> Result<Self, WorkerError> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            Ok(Self {
+                inner: Rc::new(browser::Inner::new(url)?),
+            })
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = url;
+            Ok(Self { _private: () })
+        }
+    }
+
+    /// Runs the function in the worker, and resolves to its output.
+    pub fn call<F: WorkerFn>(
+        &self,
+        args: F,
+    ) -> impl Future<Output = Result<F::Output, WorkerError>> {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let reply = self.inner.send(F::NAME, args.ser());
+            async move {
+                let text = reply?.await.map_err(|_| {
+                    WorkerError::Crashed("the worker was terminated".into())
+                })??;
+                Ok(F::Output::de(&text)?)
+            }
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            async move { Ok(args.run().await) }
+        }
+    }
+}
+
+/// Runs the functions that the app sends to this worker. Call this at the
+/// end of the `main` function of the worker bundle, after
+/// [registering](WorkerFn::register) each function.
+pub fn run_worker() {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    browser::run_worker();
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod browser {
+    use super::*;
+
+    // Requests are `<id>\n<name>\n<args>`, and replies
+    // `<id>\n<status>\n<output or error>`.
+    const OK: &str = "ok";
+    const FAILED: &str = "err";
+    const MISSING: &str = "missing";
+
+    type Handler =
+        fn(&str) -> Pin<Box<dyn Future<Output = Result<String, String>>>>;
+    type Reply = oneshot::Sender<Result<String, WorkerError>>;
+
+    thread_local! {
+        static REGISTRY: RefCell<HashMap<&'static str, Handler>> =
+            Default::default();
+    }
+
+    pub(super) fn register<F: WorkerFn>() {
+        REGISTRY.with(|registry| {
+            registry.borrow_mut().insert(F::NAME, handle::<F>)
+        });
+    }
+
+    fn handle<F: WorkerFn>(
+        args: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, String>>>> {
+        let args = F::de(args);
+        Box::pin(async move {
+            let args = args.map_err(|e| e.to_string())?;
+            args.run().await.ser().map_err(|e| e.to_string())
+        })
+    }
+
+    pub(super) fn run_worker() {
+        let scope: web_sys::DedicatedWorkerGlobalScope =
+            js_sys::global().unchecked_into();
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new({
+            let scope = scope.clone();
+            move |ev: web_sys::MessageEvent| {
+                let Some(message) = ev.data().as_string() else {
+                    return;
+                };
+                let mut parts = message.splitn(3, '\n');
+                let (Some(id), Some(name), Some(args)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    return;
+                };
+                let id = id.to_string();
+                let handler = REGISTRY
+                    .with(|registry| registry.borrow().get(name).copied());
+                let reply = match handler {
+                    Some(handler) => Ok(handler(args)),
+                    None => Err(name.to_string()),
+                };
+                let scope = scope.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let reply = match reply {
+                        Ok(fut) => match fut.await {
+                            Ok(output) => format!("{id}\n{OK}\n{output}"),
+                            Err(e) => format!("{id}\n{FAILED}\n{e}"),
+                        },
+                        Err(name) => format!("{id}\n{MISSING}\n{name}"),
+                    };
+                    _ = scope.post_message(&JsValue::from_str(&reply));
+                });
+            }
+        });
+        scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        // the worker handles messages for as long as it runs
+        on_message.forget();
+    }
+
+    pub(super) struct Inner {
+        worker: web_sys::Worker,
+        next_id: Cell<u64>,
+        pending: Rc<RefCell<HashMap<u64, (&'static str, Reply)>>>,
+        _on_message: Closure<dyn Fn(web_sys::MessageEvent)>,
+        _on_error: Closure<dyn Fn(web_sys::ErrorEvent)>,
+    }
+
+    impl Inner {
+        pub(super) fn new(url: &str) -> Result<Self, WorkerError> {
+            let worker = web_sys::Worker::new(url)
+                .map_err(|e| WorkerError::Unavailable(format!("{e:?}")))?;
+            let pending: Rc<RefCell<HashMap<u64, (&'static str, Reply)>>> =
+                Default::default();
+
+            let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new({
+                let pending = Rc::clone(&pending);
+                move |ev: web_sys::MessageEvent| {
+                    let Some(message) = ev.data().as_string() else {
+                        return;
+                    };
+                    let mut parts = message.splitn(3, '\n');
+                    let (Some(id), Some(status), Some(text)) =
+                        (parts.next(), parts.next(), parts.next())
+                    else {
+                        return;
+                    };
+                    let Some((name, reply)) = id
+                        .parse()
+                        .ok()
+                        .and_then(|id| pending.borrow_mut().remove(&id))
+                    else {
+                        return;
+                    };
+                    _ = reply.send(match status {
+                        OK => Ok(text.to_string()),
+                        MISSING => Err(WorkerError::NotRegistered(name)),
+                        _ => Err(WorkerError::Crashed(text.to_string())),
+                    });
+                }
+            });
+            worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+            // an uncaught error in the worker fails every pending call, as
+            // there is no way to tell which one caused it
+            let on_error = Closure::<dyn Fn(web_sys::ErrorEvent)>::new({
+                let pending = Rc::clone(&pending);
+                move |ev: web_sys::ErrorEvent| {
+                    for (_, (_, reply)) in pending.borrow_mut().drain() {
+                        _ = reply.send(Err(WorkerError::Crashed(ev.message())));
+                    }
+                }
+            });
+            worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+            Ok(Self {
+                worker,
+                next_id: Cell::new(0),
+                pending,
+                _on_message: on_message,
+                _on_error: on_error,
+            })
+        }
+
+        pub(super) fn send(
+            &self,
+            name: &'static str,
+            args: Result<String, SerializationError>,
+        ) -> Result<oneshot::Receiver<Result<String, WorkerError>>, WorkerError>
+        {
+            let args = args?;
+            let id = self.next_id.get();
+            self.next_id.set(id + 1);
+            let (tx, rx) = oneshot::channel();
+            self.pending.borrow_mut().insert(id, (name, tx));
+            let message = format!("{id}\n{name}\n{args}");
+            if let Err(e) =
+                self.worker.post_message(&JsValue::from_str(&message))
+            {
+                self.pending.borrow_mut().remove(&id);
+                return Err(WorkerError::Crashed(format!("{e:?}")));
+            }
+            Ok(rx)
+        }
+    }
+
+    impl Drop for Inner {
+        fn drop(&mut self) {
+            self.worker.set_onmessage(None);
+            self.worker.set_onerror(None);
+            self.worker.terminate();
+        }
+    }
+}
@@ -7,14 +7,62 @@ use itertools::Itertools;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
-    parse::Parse, parse_quote, spanned::Spanned,
-    AngleBracketedGenericArguments, Attribute, FnArg, GenericArgument, Item,
-    ItemFn, Lit, LitStr, Meta, MetaNameValue, Pat, PatIdent, Path,
-    PathArguments, ReturnType, Stmt, Type, TypePath, Visibility,
+    parenthesized, parse::Parse, parse_quote, punctuated::Punctuated,
+    spanned::Spanned, AngleBracketedGenericArguments, Attribute, FnArg,
+    GenericArgument, Item, ItemFn, Lit, LitStr, Meta, MetaNameValue, Pat,
+    PatIdent, Path, PathArguments, ReturnType, Stmt, Token, Type, TypePath,
+    Visibility,
 };
 
+/// The arguments of `#[component(..)]`: `transparent`, and
+/// `props(derive(..))` for extra derives on the generated props struct.
+#[derive(Default)]
+pub struct ComponentArgs {
+    pub is_transparent: bool,
+    pub props_derives: Vec<Path>,
+}
+
+impl Parse for ComponentArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let arg: Ident = input.parse()?;
+            if arg == "transparent" {
+                args.is_transparent = true;
+            } else if arg == "props" {
+                let props;
+                parenthesized!(props in input);
+                let derive: Ident = props.parse()?;
+                if derive != "derive" {
+                    return Err(syn::Error::new(
+                        derive.span(),
+                        "expected `derive`, e.g., \
+                         `#[component(props(derive(Clone, Debug)))]`",
+                    ));
+                }
+                let derives;
+                parenthesized!(derives in props);
+                args.props_derives.extend(
+                    Punctuated::<Path, Token![,]>::parse_terminated(&derives)?,
+                );
+            } else {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "only `transparent` and `props(derive(..))` are \
+                     supported, e.g., `#[component(transparent)]`",
+                ));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
 pub struct Model {
     is_transparent: bool,
+    props_derives: Vec<Path>,
     docs: Docs,
     vis: Visibility,
     name: Ident,
@@ -79,6 +127,7 @@ impl Parse for Model {
 
         Ok(Self {
             is_transparent: false,
+            props_derives: Vec::new(),
             docs,
             vis: item.vis.clone(),
             name: convert_from_snake_case(&item.sig.ident),
@@ -119,6 +168,7 @@ impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             is_transparent,
+            props_derives,
             docs,
             vis,
             name,
@@ -215,7 +265,7 @@ impl ToTokens for Model {
             #[doc = ""]
             #docs
             #component_fn_prop_docs
-            #[derive(::leptos::typed_builder::TypedBuilder)]
+            #[derive(::leptos::typed_builder::TypedBuilder, #(#props_derives),*)]
             #[builder(doc)]
             #vis struct #props_name #generics #where_clause {
                 #prop_builder_fields
@@ -247,6 +297,7 @@ impl ToTokens for Model {
             {
                 #body
 
+                #[allow(deprecated)]
                 let #props_name {
                     #prop_names
                 } = props;
@@ -268,6 +319,12 @@ impl Model {
 
         self
     }
+
+    pub fn props_derives(mut self, props_derives: Vec<Path>) -> Self {
+        self.props_derives = props_derives;
+
+        self
+    }
 }
 
 struct Prop {
@@ -462,6 +519,8 @@ struct PropOpt {
     #[attribute(example = "5 * 10")]
     default: Option<syn::Expr>,
     into: bool,
+    #[attribute(example = "\"use `value` instead\"")]
+    deprecated: Option<String>,
 }
 
 struct TypedBuilderOpts {
@@ -541,11 +600,19 @@ fn prop_builder_fields(vis: &Visibility, props: &[Prop]) -> TokenStream {
                 quote!()
             };
 
+            // typed-builder copies `#[deprecated]` onto the setter, so that
+            // setting the prop in `view!` warns where it is set
+            let deprecated = prop_opts.deprecated.as_ref().map(|note| {
+                let note = LitStr::new(note, name.ident.span());
+                quote!(#[deprecated = #note])
+            });
+
             quote! {
                 #docs
                 #builder_docs
                 #builder_attrs
                 #allow_missing_docs
+                #deprecated
                 #vis #name: #ty,
             }
         })
@@ -686,6 +753,11 @@ fn prop_to_doc(
 
     let pretty_ty = &pretty_ty[16..&pretty_ty.len() - 2];
 
+    let deprecation = prop_opts
+        .deprecated
+        .as_ref()
+        .map(|note| format!("**Deprecated**: {note}"));
+
     match style {
         PropDocStyle::List => {
             let arg_ty_doc = LitStr::new(
@@ -702,30 +774,40 @@ fn prop_to_doc(
 
             let arg_user_docs = docs.padded();
 
+            let deprecation = deprecation.map(|deprecation| {
+                let deprecation = LitStr::new(
+                    &format!("      {deprecation}"),
+                    name.ident.span(),
+                );
+                quote!(#[doc = #deprecation])
+            });
+
             quote! {
                 #[doc = #arg_ty_doc]
+                #deprecation
                 #arg_user_docs
             }
         }
         PropDocStyle::Inline => {
-            let arg_ty_doc = LitStr::new(
-                &if !prop_opts.into {
-                    format!(
-                        "**{}**: [`{}`]{}",
-                        quote!(#name),
-                        pretty_ty,
-                        docs.typed_builder()
-                    )
-                } else {
-                    format!(
-                        "**{}**: `impl`[`Into<{}>`]{}",
-                        quote!(#name),
-                        pretty_ty,
-                        docs.typed_builder()
-                    )
-                },
-                name.ident.span(),
-            );
+            let mut arg_ty_doc = if !prop_opts.into {
+                format!(
+                    "**{}**: [`{}`]{}",
+                    quote!(#name),
+                    pretty_ty,
+                    docs.typed_builder()
+                )
+            } else {
+                format!(
+                    "**{}**: `impl`[`Into<{}>`]{}",
+                    quote!(#name),
+                    pretty_ty,
+                    docs.typed_builder()
+                )
+            };
+            if let Some(deprecation) = deprecation {
+                arg_ty_doc.push_str(&format!("\n\n{deprecation}"));
+            }
+            let arg_ty_doc = LitStr::new(&arg_ty_doc, name.ident.span());
 
             quote! {
                 #[builder(setter(doc = #arg_ty_doc))]
@@ -0,0 +1,132 @@
+use attribute_derive::Attribute as AttributeDerive;
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields};
+
+#[derive(Clone, Debug, AttributeDerive)]
+#[attribute(ident = into_view)]
+struct IntoViewOpt {
+    #[attribute(example = "render_money")]
+    with: Option<syn::Path>,
+}
+
+pub fn impl_into_view(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    let opts = IntoViewOpt::from_attributes(&ast.attrs).unwrap_or_else(|e| {
+        abort!(e.span(), e.to_string());
+    });
+
+    let body = if let Some(with) = opts.with {
+        quote! {
+            ::leptos::IntoView::into_view(#with(self, cx), cx)
+        }
+    } else {
+        match &ast.data {
+            Data::Struct(data) => match marked_field(&data.fields) {
+                Some((index, field)) => {
+                    let (pattern, binding) =
+                        destructure(&data.fields, index, field);
+                    quote! {
+                        let Self #pattern = self;
+                        ::leptos::IntoView::into_view(#binding, cx)
+                    }
+                }
+                None => {
+                    let display = display();
+                    quote! {
+                        let this = self;
+                        #display
+                    }
+                }
+            },
+            Data::Enum(data) => {
+                let arms = data.variants.iter().filter_map(|variant| {
+                    let ident = &variant.ident;
+                    let (index, field) = marked_field(&variant.fields)?;
+                    let (pattern, binding) =
+                        destructure(&variant.fields, index, field);
+                    Some(quote! {
+                        Self::#ident #pattern => {
+                            ::leptos::IntoView::into_view(#binding, cx)
+                        }
+                    })
+                });
+                let display = display();
+                quote! {
+                    match self {
+                        #(#arms)*
+                        this => { #display }
+                    }
+                }
+            }
+            Data::Union(data) => abort!(
+                data.union_token.span(),
+                "IntoView can't be derived for unions"
+            ),
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::leptos::IntoView for #name #ty_generics
+            #where_clause
+        {
+            // the last arm of an enum is unreachable if every variant has
+            // a field marked with #[into_view]
+            #[allow(unreachable_patterns)]
+            fn into_view(self, cx: ::leptos::Scope) -> ::leptos::View {
+                #body
+            }
+        }
+    }
+}
+
+/// Renders `this` with its `Display` implementation.
+fn display() -> TokenStream {
+    quote! {
+        ::leptos::IntoView::into_view(::std::string::ToString::to_string(&this), cx)
+    }
+}
+
+/// Finds the field marked with `#[into_view]`, if any.
+fn marked_field(fields: &Fields) -> Option<(usize, &syn::Field)> {
+    let mut marked = fields.iter().enumerate().filter(|(_, field)| {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("into_view"))
+    });
+    let first = marked.next();
+    if let Some((_, second)) = marked.next() {
+        abort!(
+            second.span(),
+            "only one field can be marked with #[into_view]"
+        );
+    }
+    first
+}
+
+/// A pattern that binds only the given field, and the name it is bound to.
+fn destructure(
+    fields: &Fields,
+    index: usize,
+    field: &syn::Field,
+) -> (TokenStream, TokenStream) {
+    match &field.ident {
+        Some(ident) => (quote! { { #ident, .. } }, quote! { #ident }),
+        None => {
+            let binding = format_ident!("field");
+            let patterns = (0..fields.len()).map(|i| {
+                if i == index {
+                    quote! { #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            (quote! { ( #(#patterns),* ) }, quote! { #binding })
+        }
+    }
+}
@@ -6,7 +6,7 @@ extern crate proc_macro_error;
 
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenTree};
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use server_fn_macro::{server_macro_impl, ServerContext};
 use syn::parse_macro_input;
 use syn_rsx::{parse, NodeAttribute};
@@ -30,6 +30,7 @@ impl Default for Mode {
     }
 }
 
+mod into_view;
 mod params;
 mod view;
 use template::render_template;
@@ -37,6 +38,7 @@ use view::render_view;
 mod component;
 mod slot;
 mod template;
+mod worker;
 
 /// The `view` macro uses RSX (like JSX, but Rust!) It follows most of the
 /// same rules as HTML, with the following differences:
@@ -274,6 +276,22 @@ mod template;
 /// # });
 /// ```
 ///
+/// 12. The `cx` argument can be left out, in which case the view is created in the
+///     [current scope](https://docs.rs/leptos/latest/leptos/struct.Scope.html#method.current),
+///     such as the body of a component, an effect or memo, or an `on:` event listener.
+///     Other code that runs later, like a task passed to `spawn_local` or a timer
+///     callback, doesn't have a current scope unless it sets one with
+///     `Scope::with_current`, so this will panic if the macro is used there.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { <p>"No scope needed here."</p> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::*;
@@ -311,64 +329,99 @@ mod template;
 )]
 pub fn view(tokens: TokenStream) -> TokenStream {
     let tokens: proc_macro2::TokenStream = tokens.into();
-    let mut tokens = tokens.into_iter();
-    let (cx, comma) = (tokens.next(), tokens.next());
-
-    match (cx, comma) {
+    let mut tokens = tokens.into_iter().peekable();
+    let first = tokens.next();
+    let explicit_cx = match (&first, tokens.peek()) {
         (Some(TokenTree::Ident(cx)), Some(TokenTree::Punct(punct)))
             if punct.as_char() == ',' =>
         {
-            let first = tokens.next();
-            let second = tokens.next();
-            let third = tokens.next();
-            let fourth = tokens.next();
-            let global_class = match (&first, &second) {
-                (Some(TokenTree::Ident(first)), Some(TokenTree::Punct(eq)))
-                    if *first == "class" && eq.as_char() == '=' =>
+            Some(cx.clone())
+        }
+        _ => None,
+    };
+
+    match explicit_cx {
+        Some(cx) => {
+            // skip the comma after `cx`
+            tokens.next();
+            view_with_scope(
+                &proc_macro2::Ident::new(&cx.to_string(), cx.span()),
+                tokens,
+            )
+        }
+        // no `cx` was given, so use the Scope that is currently running
+        // the identifier is hygienic, so it can't clash with any `cx`
+        // that the user's own code refers to
+        None => {
+            let cx = proc_macro2::Ident::new("cx", Span::mixed_site());
+            let view = view_with_scope(&cx, first.into_iter().chain(tokens));
+            quote! {
                 {
-                    match &fourth {
-                        Some(TokenTree::Punct(comma))
-                            if comma.as_char() == ',' =>
-                        {
-                            third.clone()
-                        }
-                        _ => {
-                            abort!(
-                                punct, "To create a scope class with the view! macro you must put a comma `,` after the value";
-                                help = r#"e.g., view!{cx, class="my-class", <div>...</div>}"#
-                            )
-                        }
-                    }
+                    let #cx = ::leptos::Scope::current().expect(
+                        "view! was called without a `cx` outside of any \
+                         reactive Scope, e.g., in a task passed to \
+                         spawn_local or a timer callback. Pass a Scope \
+                         explicitly with view! { cx, ... }"
+                    );
+                    #view
                 }
-                _ => None,
-            };
-            let tokens = if global_class.is_some() {
-                tokens.collect::<proc_macro2::TokenStream>()
-            } else {
-                [first, second, third, fourth]
-                    .into_iter()
-                    .flatten()
-                    .chain(tokens)
-                    .collect()
-            };
+            }
+        }
+    }
+    .into()
+}
 
-            match parse(tokens.into()) {
-                Ok(nodes) => render_view(
-                    &proc_macro2::Ident::new(&cx.to_string(), cx.span()),
-                    &nodes,
-                    Mode::default(),
-                    global_class.as_ref(),
-                    normalized_call_site(proc_macro::Span::call_site()),
-                ),
-                Err(error) => error.to_compile_error(),
+fn view_with_scope(
+    cx: &proc_macro2::Ident,
+    mut tokens: impl Iterator<Item = TokenTree>,
+) -> proc_macro2::TokenStream {
+    let first = tokens.next();
+    let second = tokens.next();
+    let third = tokens.next();
+    let fourth = tokens.next();
+    let global_class = match (&first, &second) {
+        (Some(TokenTree::Ident(first)), Some(TokenTree::Punct(eq)))
+            if *first == "class" && eq.as_char() == '=' =>
+        {
+            match &fourth {
+                Some(TokenTree::Punct(comma)) if comma.as_char() == ',' => {
+                    third.clone()
+                }
+                _ => {
+                    abort!(
+                        eq, "To create a scope class with the view! macro you must put a comma `,` after the value";
+                        help = r#"e.g., view!{cx, class="my-class", <div>...</div>}"#
+                    )
+                }
             }
-            .into()
         }
-        _ => {
-            abort_call_site!(
-                "view! macro needs a context and RSX: e.g., view! {{ cx, \
-                 <div>...</div> }}"
-            )
+        _ => None,
+    };
+    let tokens = if global_class.is_some() {
+        tokens.collect::<proc_macro2::TokenStream>()
+    } else {
+        [first, second, third, fourth]
+            .into_iter()
+            .flatten()
+            .chain(tokens)
+            .collect()
+    };
+
+    match parse(tokens.clone().into()) {
+        Ok(nodes) => render_view(
+            cx,
+            &nodes,
+            Mode::default(),
+            global_class.as_ref(),
+            normalized_call_site(proc_macro::Span::call_site()),
+        ),
+        Err(error) => {
+            // syn-rsx only points at the `<` of a close tag that doesn't
+            // match, without saying which tag it should have closed
+            if error.to_string() == "close tag has no corresponding open tag" {
+                view::check_close_tags(tokens);
+            }
+            error.to_compile_error()
         }
     }
 }
@@ -616,6 +669,8 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 /// * `#[prop(optional_no_strip)]`: The same as `optional`, but requires values to be passed as `None` or
 ///   `Some(T)` explicitly. This means that the optional property can be omitted (and be `None`), or explicitly
 ///   specified as either `None` or `Some(T)`.
+/// * ``#[prop(deprecated = "use `label` instead")]``: Marks the property as deprecated. Setting it
+///   warns wherever it is set, with this note, and the note is added to its docs.
 /// ```rust
 /// # use leptos::*;
 ///
@@ -644,27 +699,34 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// ## Deriving Traits for the Props
+/// `#[component(props(derive(..)))]` adds derives to the generated `{Component}Props` struct,
+/// e.g., so that props can be built ahead of time, stored in a table, cloned, or logged. Every
+/// property type must implement the derived traits.
+/// ```rust
+/// # use leptos::*;
+/// #[component(props(derive(Clone, Debug, PartialEq)))]
+/// pub fn Badge(cx: Scope, label: String, #[prop(optional)] count: u32) -> impl IntoView {
+///     view! { cx, <span>{label} " " {count}</span> }
+/// }
+///
+/// let props = BadgeProps::builder().label("Inbox".to_string()).count(3).build();
+/// assert_eq!(props.clone(), props);
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// let badge = Badge(cx, props);
+/// # });
+/// # }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    let is_transparent = if !args.is_empty() {
-        let transparent = parse_macro_input!(args as syn::Ident);
-
-        if transparent != "transparent" {
-            abort!(
-                transparent,
-                "only `transparent` is supported";
-                help = "try `#[component(transparent)]` or `#[component]`"
-            );
-        }
-
-        true
-    } else {
-        false
-    };
+    let args = parse_macro_input!(args as component::ComponentArgs);
 
     parse_macro_input!(s as component::Model)
-        .is_transparent(is_transparent)
+        .is_transparent(args.is_transparent)
+        .props_derives(args.props_derives)
         .into_token_stream()
         .into()
 }
@@ -797,7 +859,7 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// If you call a server function from the client (i.e., when the `csr` or `hydrate` features
 /// are enabled), it will instead make a network request to the server.
 ///
-/// You can specify one, two, or three arguments to the server function:
+/// You can specify up to four arguments to the server function:
 /// 1. **Required**: A type name that will be used to identify and register the server function
 ///   (e.g., `MyServerFn`).
 /// 2. *Optional*: A URL prefix at which the function will be mounted when it’s registered
@@ -806,6 +868,10 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///   serialization) or `"Url"` (specifying that it should be use a URL-encoded form-data string).
 ///   Defaults to `"Url"`. If you want to use this server function to power a `<form>` that will
 ///   work without WebAssembly, the encoding must be `"Url"`.
+/// 4. *Optional*: `dedupe` or `no_dedupe`. Concurrent calls with the same arguments from the
+///   client share one request if the function uses the `"GetJson"` or `"GetCbor"` encoding.
+///   `dedupe` does the same for the `POST` encodings, and `no_dedupe` sends a new request for
+///   every call.
 ///
 /// The server function itself can take any number of arguments, each of which should be serializable
 /// and deserializable with `serde`. Optionally, its first argument can be a Leptos
@@ -844,6 +910,29 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// - **The `Scope` comes from the server.** Optionally, the first argument of a server function
 ///   can be a Leptos `Scope`. This scope can be used to inject dependencies like the HTTP request
 ///   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
+///
+/// ## Calling Server Functions Manually
+/// Server functions are usually called through an action or a resource, but they can be called
+/// anywhere, e.g., from an event handler or from other server code:
+/// - The function itself can be called and awaited like any `async fn`.
+/// - `ServerFn::call` does the same given only the argument type, e.g.,
+///   `ReadPosts { how_many: 10, query }.call(cx).await`: on the client, it sends a request to
+///   the server; on the server, it runs the function directly.
+/// - On the server, `ReadPosts::run_body` runs the body of the function with the given arguments,
+///   without going through its HTTP endpoint.
+///
+/// ```ignore
+/// # use leptos::*;
+/// use leptos::server_fn::ServerFn;
+/// # #[server(ReadPosts, "/api")]
+/// # pub async fn read_posts(how_many: u8, query: String) -> Result<Vec<String>, ServerFnError> {
+/// #   todo!()
+/// # }
+/// let posts = ReadPosts { how_many: 10, query: "leptos".into() };
+/// let fetched = posts.clone().call(cx).await?;
+/// #[cfg(feature = "ssr")]
+/// let ran = posts.run_body().await?;
+/// ```
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
@@ -862,6 +951,56 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     }
 }
 
+/// Declares a function that can run in a
+/// [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API),
+/// so that CPU-heavy work doesn't block the main thread of the app.
+///
+/// The macro takes the name of a struct that holds the arguments of the
+/// function (e.g., `CountPrimes`), and implements
+/// [WorkerFn](https://docs.rs/leptos/latest/leptos/trait.WorkerFn.html) for it.
+/// The function itself is left as it is, and can still be called directly.
+///
+/// The worker runs a second WebAssembly bundle built from the same crate.
+/// Its `main` function must register every worker function with
+/// `T::register()`, and then call `run_worker()`. In the app, the function is
+/// called through a `WorkerHandle`, or an action created with
+/// `create_worker_action`.
+///
+/// ```ignore
+/// # use leptos::*;
+/// #[worker(CountPrimes)]
+/// pub async fn count_primes(below: u32) -> usize {
+///     (2..below).filter(|n| (2..*n).all(|d| n % d != 0)).count()
+/// }
+///
+/// // src/bin/worker.rs
+/// fn main() {
+///     CountPrimes::register();
+///     run_worker();
+/// }
+///
+/// // in a component
+/// let worker = WorkerHandle::new("/worker.js").unwrap();
+/// let count = create_worker_action::<CountPrimes>(cx, worker);
+/// count.dispatch(CountPrimes { below: 100_000 });
+/// ```
+///
+/// Note the following:
+/// - **Worker functions must be `async`**, and can't be generic or take `self`.
+/// - **Arguments must implement [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html)
+///   and [`DeserializeOwned`](https://docs.rs/serde/latest/serde/de/trait.DeserializeOwned.html)**,
+///   and the return type must be [Serializable](https://docs.rs/leptos/latest/leptos/trait.Serializable.html),
+///   as they are sent between the threads as text.
+/// - **Worker functions can't use the reactive system of the app**, which runs on another thread.
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn worker(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    match worker::worker_macro_impl(args.into(), s.into()) {
+        Err(e) => e.to_compile_error().into(),
+        Ok(s) => s.into(),
+    }
+}
+
 /// Derives a trait that parses a map of string keys and values into a typed
 /// data structure, e.g., for route params.
 #[proc_macro_derive(Params, attributes(params))]
@@ -874,6 +1013,68 @@ pub fn params_derive(
     }
 }
 
+/// Derives [`IntoView`](leptos::IntoView) for a struct or enum, so that it can
+/// be used directly in the [view](crate::view!) macro.
+///
+/// By default, the value is rendered as text with its
+/// [`Display`](std::fmt::Display) implementation. Mark one field with
+/// `#[into_view]` to render that field instead (for an enum, this is done per
+/// variant), or render the value with your own function with
+/// `#[into_view(with = path::to::function)]`, where the function takes the
+/// value and the [Scope](leptos::Scope) and returns anything that implements
+/// `IntoView`.
+///
+/// ```rust
+/// # use leptos::*;
+/// use std::fmt;
+///
+/// #[derive(IntoView)]
+/// enum Status {
+///     Active,
+///     Suspended,
+///     // rendered with the view in the field
+///     Custom(#[into_view] View),
+/// }
+///
+/// impl fmt::Display for Status {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Status::Active => write!(f, "Active"),
+///             Status::Suspended => write!(f, "Suspended"),
+///             Status::Custom(_) => Ok(()),
+///         }
+///     }
+/// }
+///
+/// #[derive(IntoView)]
+/// #[into_view(with = render_money)]
+/// struct Money {
+///     cents: i64,
+/// }
+///
+/// fn render_money(money: Money, cx: Scope) -> impl IntoView {
+///     view! { cx,
+///       <span class="money">{format!("${}.{:02}", money.cents / 100, money.cents % 100)}</span>
+///     }
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///   <p>{Status::Active} ": " {Money { cents: 1250 }}</p>
+/// }
+/// # });
+/// ```
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(IntoView, attributes(into_view))]
+pub fn into_view_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input) {
+        Ok(ast) => into_view::impl_into_view(&ast).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 pub(crate) fn attribute_value(attr: &NodeAttribute) -> &syn::Expr {
     match &attr.value {
         Some(value) => value.as_ref(),
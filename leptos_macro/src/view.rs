@@ -549,9 +549,11 @@ fn attribute_to_tokens_ssr<'a>(
         // ignore refs on SSR
     } else if let Some(name) = name.strip_prefix("on:") {
         let handler = attribute_value(node);
+        let warning = misspelled_event_warning(name, node.key.span());
         let (event_type, _, _) = parse_event_name(name);
 
         exprs_for_compiler.push(quote! {
+            #warning
             leptos::leptos_dom::helpers::ssr_event_listener(::leptos::ev::#event_type, #handler);
         })
     } else if name.strip_prefix("prop:").is_some()
@@ -1184,6 +1186,7 @@ fn attribute_to_tokens(
         }
     } else if let Some(name) = name.strip_prefix("on:") {
         let handler = attribute_value(node);
+        let warning = misspelled_event_warning(name, node.key.span());
 
         let (event_type, is_custom, is_force_undelegated) =
             parse_event_name(name);
@@ -1244,7 +1247,7 @@ fn attribute_to_tokens(
         };
 
         quote! {
-            #on(#event_type, #handler)
+            #on({ #warning #event_type }, #handler)
         }
     } else if let Some(name) = name.strip_prefix("prop:") {
         let value = attribute_value(node);
@@ -1412,8 +1415,13 @@ pub(crate) fn slot_to_tokens(
                 })
                 .unwrap_or_else(|| quote! { #name });
 
-            quote! {
-                .#name(#[allow(unused_braces)] #value)
+            // spanning the setter to the attribute key means that a
+            // misspelled or mistyped prop is reported on the prop itself,
+            // rather than on the whole `view!` invocation, and that rustc
+            // suggests a prop with a similar name for a misspelled one
+            let span = attr.key.span();
+            quote_spanned! {
+                span => .#name(#[allow(unused_braces)] #value)
             }
         });
 
@@ -1532,8 +1540,13 @@ pub(crate) fn component_to_tokens(
                 })
                 .unwrap_or_else(|| quote! { #name });
 
-            quote! {
-                .#name(#[allow(unused_braces)] #value)
+            // spanning the setter to the attribute key means that a
+            // misspelled or mistyped prop is reported on the prop itself,
+            // rather than on the whole `view!` invocation, and that rustc
+            // suggests a prop with a similar name for a misspelled one
+            let span = attr.key.span();
+            quote_spanned! {
+                span => .#name(#[allow(unused_braces)] #value)
             }
         });
 
@@ -1613,6 +1626,12 @@ pub(crate) fn component_to_tokens(
         }
     });
 
+    // a missing required prop shows up as an error on `.build()`,
+    // so it should point at the component's tag name
+    let build = quote_spanned! {
+        span => .build()
+    };
+
     let component = quote! {
         #name(
             #cx,
@@ -1620,7 +1639,7 @@ pub(crate) fn component_to_tokens(
                 #(#props)*
                 #(#slots)*
                 #children
-                .build()
+                #build
         )
     };
 
@@ -1649,21 +1668,29 @@ pub(crate) fn event_from_attribute_node(
 
     #[allow(unused_variables)]
     let (name, name_undelegated) = parse_event(&event_name);
+    let warning = misspelled_event_warning(name, attr.key.span());
 
     let event_type = TYPED_EVENTS
         .iter()
         .find(|e| **e == name)
         .copied()
         .unwrap_or("Custom");
+    let is_custom = event_type == "Custom";
 
     let Ok(event_type) = event_type.parse::<TokenStream>() else {
         abort!(attr.key, "couldn't parse event name");
     };
 
+    let event_type = if is_custom {
+        quote! { Custom::new(#name) }
+    } else {
+        event_type
+    };
+
     let event_type = if force_undelegated || name_undelegated {
-        quote! { ::leptos::leptos_dom::ev::undelegated(::leptos::leptos_dom::ev::#event_type) }
+        quote! { { #warning ::leptos::leptos_dom::ev::undelegated(::leptos::leptos_dom::ev::#event_type) } }
     } else {
-        quote! { ::leptos::leptos_dom::ev::#event_type }
+        quote! { { #warning ::leptos::leptos_dom::ev::#event_type } }
     };
     (event_type, handler)
 }
@@ -1886,6 +1913,144 @@ fn is_ambiguous_element(tag: &str) -> bool {
     tag == "a" || tag == "script" || tag == "title"
 }
 
+/// Aborts on the first close tag in `tokens` that doesn't close the tag that
+/// is open at that point, naming the tag it should have closed.
+///
+/// Like syn-rsx, this takes each tag to end at the first `>` after its `<`.
+pub(crate) fn check_close_tags(tokens: TokenStream) {
+    fn is_punct(token: &TokenTree, ch: char) -> bool {
+        matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch)
+    }
+
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut open_tags = Vec::new();
+    let mut start = 0;
+    while let Some(offset) = tokens[start..]
+        .iter()
+        .position(|token| is_punct(token, '<'))
+    {
+        let tag_start = start + offset;
+        let Some(len) = tokens[tag_start..]
+            .iter()
+            .position(|token| is_punct(token, '>'))
+        else {
+            return;
+        };
+        let tag = &tokens[tag_start..=tag_start + len];
+        start = tag_start + len + 1;
+
+        if is_punct(&tag[1], '!') {
+            // a doctype or a comment
+        } else if is_punct(&tag[1], '/') {
+            let name = tag_name(&tag[2..]);
+            let close_tag = tag.iter().cloned().collect::<TokenStream>();
+            match open_tags.pop() {
+                Some(open_name) if open_name == name => {}
+                Some(open_name) => abort!(
+                    close_tag,
+                    "`</{}>` doesn't close `<{}>`, which is still open",
+                    name, open_name;
+                    help = "close `<{}>` with `</{}>` first", open_name, open_name
+                ),
+                None => abort!(
+                    close_tag,
+                    "`</{}>` doesn't close any open tag", name;
+                    help = "remove it, or add a `<{}>` before it", name
+                ),
+            }
+        } else if !is_punct(&tag[tag.len() - 2], '/') {
+            open_tags.push(tag_name(&tag[1..]));
+        }
+    }
+}
+
+/// The name of the tag whose tokens after the `<` or `</` are `tokens`, or an
+/// empty string for a fragment.
+fn tag_name(tokens: &[TokenTree]) -> String {
+    let mut name = String::new();
+    let mut expects_ident = true;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) if expects_ident => {
+                name.push_str(&ident.to_string());
+                expects_ident = false;
+            }
+            TokenTree::Punct(punct)
+                if matches!(punct.as_char(), '-' | ':') && !name.is_empty() =>
+            {
+                name.push(punct.as_char());
+                expects_ident = punct.spacing() == proc_macro2::Spacing::Alone;
+            }
+            _ => break,
+        }
+    }
+    name
+}
+
+/// Any event name that isn't one of the [`TYPED_EVENTS`] is treated as a
+/// custom event, so a typo like `on:clik` compiles but never fires.
+/// If the name is very close to a known one, returns a statement that warns
+/// about it with a suggestion.
+///
+/// `emit_warning!` only reaches the user on nightly, so the warning is the
+/// deprecation of a constant that the statement uses, which also works on
+/// stable.
+fn misspelled_event_warning(
+    event_name: &str,
+    span: Span,
+) -> Option<TokenStream> {
+    let (name, _) = parse_event(event_name);
+    if TYPED_EVENTS.contains(&name) {
+        return None;
+    }
+    let suggestion = closest_match(name, TYPED_EVENTS.iter().copied())?;
+    let note = format!(
+        "`{name}` is not a known event, so it will be treated as a custom \
+         event. Did you mean `on:{suggestion}`?"
+    );
+    Some(quote_spanned! {span=>
+        #[allow(non_upper_case_globals)]
+        #[deprecated(note = #note)]
+        const unknown_event: () = ();
+        #[allow(clippy::let_unit_value)]
+        let _ = unknown_event;
+    })
+}
+
+/// Returns the candidate closest to `name`, if one is close enough that
+/// `name` is probably a typo for it.
+fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = (name.len() / 3).clamp(1, 3);
+    candidates
+        .into_iter()
+        .map(|candidate| {
+            (candidate, edit_distance(&name, &candidate.to_lowercase()))
+        })
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, a_char) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != *b_char);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
 fn parse_event(event_name: &str) -> (&str, bool) {
     if let Some(event_name) = event_name.strip_suffix(":undelegated") {
         (event_name, true)
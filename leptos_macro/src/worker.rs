@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, FnArg, Ident, ItemFn, Pat, ReturnType};
+
+pub fn worker_macro_impl(
+    args: TokenStream,
+    body: TokenStream,
+) -> syn::Result<TokenStream> {
+    let struct_name: Ident = syn::parse2(args).map_err(|e| {
+        syn::Error::new(
+            e.span(),
+            "expected the name of the struct for the arguments, e.g., \
+             `#[worker(CountPrimes)]`",
+        )
+    })?;
+    let body: ItemFn = syn::parse2(body)?;
+    let sig = &body.sig;
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new(
+            sig.fn_token.span(),
+            "worker functions must be async",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "worker functions can't be generic",
+        ));
+    }
+
+    let fn_name = &sig.ident;
+    let vis = &body.vis;
+    let output = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    let (names, types): (Vec<_>, Vec<_>) = sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(pat) => Ok((pat.ident.clone(), arg.ty.clone())),
+                pat => Err(syn::Error::new(
+                    pat.span(),
+                    "the arguments of worker functions must be named",
+                )),
+            },
+            FnArg::Receiver(arg) => Err(syn::Error::new(
+                arg.span(),
+                "worker functions can't take `self`",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    let struct_doc = format!(
+        "The arguments of [`{fn_name}`], which can be run in a Web Worker \
+         with a [`WorkerHandle`](::leptos::WorkerHandle)."
+    );
+
+    Ok(quote! {
+        #body
+
+        #[doc = #struct_doc]
+        #[derive(
+            Clone,
+            ::leptos::serde::Serialize,
+            ::leptos::serde::Deserialize,
+        )]
+        #[serde(crate = "::leptos::serde")]
+        #vis struct #struct_name {
+            #(pub #names: #types),*
+        }
+
+        impl ::leptos::WorkerFn for #struct_name {
+            type Output = #output;
+
+            const NAME: &'static str =
+                concat!(module_path!(), "::", stringify!(#fn_name));
+
+            fn run(
+                self,
+            ) -> ::std::pin::Pin<
+                Box<dyn ::std::future::Future<Output = Self::Output>>,
+            > {
+                let Self { #(#names),* } = self;
+                Box::pin(#fn_name(#(#names),*))
+            }
+        }
+    })
+}
@@ -26,3 +26,41 @@ fn component() {
     assert_eq!(cp.default, NonZeroUsize::new(10).unwrap());
     assert_eq!(cp.into, "");
 }
+
+#[component]
+fn DeprecatedProp(
+    _cx: Scope,
+    #[prop(optional)] label: String,
+    #[prop(optional, deprecated = "use `label` instead")] title: String,
+) -> impl IntoView {
+    _ = label;
+    _ = title;
+}
+
+#[test]
+#[allow(deprecated)]
+fn component_deprecated_prop() {
+    let cp = DeprecatedPropProps::builder().title("Old".to_string()).build();
+    assert_eq!(cp.title, "Old");
+    assert_eq!(cp.label, "");
+}
+
+#[component(props(derive(Clone, Debug, PartialEq)))]
+fn DerivedProps(
+    _cx: Scope,
+    label: String,
+    #[prop(optional)] count: u32,
+) -> impl IntoView {
+    _ = label;
+    _ = count;
+}
+
+#[test]
+fn component_props_derive() {
+    let cp = DerivedPropsProps::builder()
+        .label("Inbox".to_string())
+        .count(3)
+        .build();
+    assert_eq!(cp.clone(), cp);
+    assert!(format!("{cp:?}").contains("Inbox"));
+}
@@ -0,0 +1,11 @@
+use leptos::*;
+
+fn mismatched_close_tag(_cx: Scope) -> impl IntoView {
+    view! { _cx, <div><span>"text"</div></span> }
+}
+
+fn unopened_close_tag(_cx: Scope) -> impl IntoView {
+    view! { _cx, <my-element class="a"><br/></my-element></div> }
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use leptos::*;
+
+// the arguments are serialized without depending on `serde` directly
+#[worker(AddNumbers)]
+pub async fn add_numbers(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[worker(Greet)]
+async fn greet(name: String) {
+    _ = name;
+}
+
+#[test]
+fn worker_fn_runs_the_function() {
+    let sum = futures::executor::block_on(AddNumbers { a: 2, b: 3 }.run());
+    assert_eq!(sum, 5);
+    futures::executor::block_on(
+        Greet {
+            name: "Leptos".into(),
+        }
+        .run(),
+    );
+}
+
+#[test]
+fn worker_fn_is_named_after_the_function() {
+    assert_eq!(AddNumbers::NAME, "worker::add_numbers");
+    assert_eq!(Greet::NAME, "worker::greet");
+}
+
+#[test]
+fn worker_fn_arguments_are_serializable() {
+    let args = AddNumbers { a: 2, b: 3 }.ser().unwrap();
+    let args = AddNumbers::de(&args).unwrap();
+    assert_eq!((args.a, args.b), (2, 3));
+}
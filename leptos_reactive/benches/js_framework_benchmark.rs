@@ -0,0 +1,119 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// These approximate the reactive work done by the keyed
+// js-framework-benchmark: each row owns a child scope with a label signal,
+// a selection check and an effect that "renders" the label. The same rows are
+// created with each version of the reactive system.
+macro_rules! rows_benchmark {
+    ($name:ident, $reactive:ident) => {
+        fn $name(c: &mut Criterion) {
+            use std::{cell::Cell, rc::Rc};
+            use $reactive::*;
+
+            c.bench_function(stringify!($name), |b| {
+                let runtime = create_runtime();
+                b.iter(|| {
+                    create_scope(runtime, |cx| {
+                        let selected = create_rw_signal(cx, None::<usize>);
+                        let renders = Rc::new(Cell::new(0));
+
+                        // create 1,000 rows
+                        let rows = (0..1000)
+                            .map(|id| {
+                                let renders = Rc::clone(&renders);
+                                let (label, disposer) =
+                                    cx.run_child_scope(|cx| {
+                                        let label = create_rw_signal(
+                                            cx,
+                                            id.to_string(),
+                                        );
+                                        let is_selected =
+                                            create_memo(cx, move |_| {
+                                                selected.get() == Some(id)
+                                            });
+                                        create_isomorphic_effect(
+                                            cx,
+                                            move |_| {
+                                                label.with(|_| {
+                                                    is_selected.get()
+                                                });
+                                                renders.set(renders.get() + 1);
+                                            },
+                                        );
+                                        label
+                                    });
+                                (label, disposer)
+                            })
+                            .collect::<Vec<_>>();
+                        assert_eq!(renders.get(), 1000);
+
+                        // update every 10th row
+                        for (label, _) in rows.iter().step_by(10) {
+                            label.update(|label| label.push_str(" !!!"));
+                        }
+                        assert_eq!(renders.get(), 1100);
+
+                        // select a row
+                        selected.set(Some(1));
+                        assert_eq!(renders.get(), 1101);
+
+                        // clear rows
+                        for (_, disposer) in rows {
+                            disposer.dispose();
+                        }
+                    })
+                    .dispose()
+                });
+                runtime.dispose();
+            });
+        }
+    };
+}
+
+// Removes rows one at a time, oldest first, as when deleting a single row from
+// a long list, which is slow if each removal has to shift the other rows.
+macro_rules! remove_rows_benchmark {
+    ($name:ident, $reactive:ident) => {
+        fn $name(c: &mut Criterion) {
+            use $reactive::*;
+
+            c.bench_function(stringify!($name), |b| {
+                let runtime = create_runtime();
+                b.iter(|| {
+                    create_scope(runtime, |cx| {
+                        // create 10,000 rows
+                        let rows = (0..10_000)
+                            .map(|id| {
+                                cx.run_child_scope(|cx| {
+                                    create_rw_signal(cx, id.to_string())
+                                })
+                                .1
+                            })
+                            .collect::<Vec<_>>();
+
+                        // remove them one by one
+                        for disposer in rows {
+                            disposer.dispose();
+                        }
+                    })
+                    .dispose()
+                });
+                runtime.dispose();
+            });
+        }
+    };
+}
+
+rows_benchmark!(l021_rows, l021);
+rows_benchmark!(leptos_rows, leptos_reactive);
+remove_rows_benchmark!(l021_remove_rows, l021);
+remove_rows_benchmark!(leptos_remove_rows, leptos_reactive);
+
+criterion_group!(
+    js_framework_benchmark,
+    l021_rows,
+    leptos_rows,
+    l021_remove_rows,
+    leptos_remove_rows
+);
+criterion_main!(js_framework_benchmark);
@@ -0,0 +1,92 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use leptos_reactive::{bench::*, *};
+
+fn create_n_signals(c: &mut Criterion) {
+    c.bench_function("create_1000_signals", |b| {
+        let runtime = create_runtime();
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                black_box(create_signals(cx, 1000));
+            })
+            .dispose()
+        });
+        runtime.dispose();
+    });
+}
+
+fn propagate_fan_out(c: &mut Criterion) {
+    c.bench_function("propagate_100_updates_to_1000_memos", |b| {
+        let runtime = create_runtime();
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let (source, memos) = create_fan_out(cx, 1000);
+                for _ in 0..100 {
+                    propagate_updates(source, 1);
+                    black_box(sum(&memos));
+                }
+            })
+            .dispose()
+        });
+        runtime.dispose();
+    });
+}
+
+fn propagate_chain(c: &mut Criterion) {
+    c.bench_function("propagate_100_updates_through_1000_memos", |b| {
+        let runtime = create_runtime();
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let (source, last) = create_chain(cx, 1000);
+                for _ in 0..100 {
+                    propagate_updates(source, 1);
+                    black_box(last.get());
+                }
+            })
+            .dispose()
+        });
+        runtime.dispose();
+    });
+}
+
+fn propagate_fan_in(c: &mut Criterion) {
+    c.bench_function("propagate_1000_updates_into_1_memo", |b| {
+        let runtime = create_runtime();
+        b.iter(|| {
+            create_scope(runtime, |cx| {
+                let (sources, memo) = create_fan_in(cx, 1000);
+                for source in sources {
+                    propagate_updates(source, 1);
+                    black_box(memo.get());
+                }
+            })
+            .dispose()
+        });
+        runtime.dispose();
+    });
+}
+
+fn dispose_scope(c: &mut Criterion) {
+    c.bench_function("dispose_scope_with_1000_memos", |b| {
+        let runtime = create_runtime();
+        b.iter_batched(
+            || {
+                create_scope(runtime, |cx| {
+                    black_box(create_fan_out(cx, 1000));
+                })
+            },
+            |disposer| disposer.dispose(),
+            criterion::BatchSize::SmallInput,
+        );
+        runtime.dispose();
+    });
+}
+
+criterion_group!(
+    primitives,
+    create_n_signals,
+    propagate_fan_out,
+    propagate_chain,
+    propagate_fan_in,
+    dispose_scope
+);
+criterion_main!(primitives);
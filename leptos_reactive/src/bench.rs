@@ -0,0 +1,89 @@
+#![forbid(unsafe_code)]
+//! Building blocks for benchmarking the reactive system.
+//!
+//! Each function sets up or exercises one common shape of reactive graph and
+//! returns whatever it creates, so that a benchmark can pass the results to
+//! [`std::hint::black_box`] and measure each step on its own.
+//!
+//! ```
+//! # use leptos_reactive::{bench::*, *};
+//! # use std::hint::black_box;
+//! let total = with_bench_scope(|cx| {
+//!     let (source, memos) = create_fan_out(cx, 100);
+//!     propagate_updates(source, 10);
+//!     black_box(sum(&memos))
+//! });
+//! assert_eq!(total, 1000);
+//! ```
+
+use crate::{
+    create_memo, create_runtime, create_rw_signal, run_scope, Memo, RwSignal,
+    Scope, SignalGet, SignalUpdate,
+};
+
+/// Creates a new runtime and root scope, runs `f` in it, and disposes of
+/// both, returning the result of `f`.
+pub fn with_bench_scope<T>(f: impl FnOnce(Scope) -> T + 'static) -> T {
+    let runtime = create_runtime();
+    let result = run_scope(runtime, f);
+    runtime.dispose();
+    result
+}
+
+/// Creates `n` independent signals.
+pub fn create_signals(cx: Scope, n: usize) -> Vec<RwSignal<usize>> {
+    (0..n).map(|idx| create_rw_signal(cx, idx)).collect()
+}
+
+/// Creates one source signal and `n` memos that each read it directly.
+/// Every update to the source notifies all `n` memos.
+pub fn create_fan_out(
+    cx: Scope,
+    n: usize,
+) -> (RwSignal<usize>, Vec<Memo<usize>>) {
+    let source = create_rw_signal(cx, 0);
+    let memos = (0..n)
+        .map(|_| create_memo(cx, move |_| source.get()))
+        .collect();
+    (source, memos)
+}
+
+/// Creates one source signal and a chain of `depth` memos, each of which
+/// reads the previous one. Returns the source and the last memo, so that an
+/// update to the source has to propagate through the whole chain.
+pub fn create_chain(cx: Scope, depth: usize) -> (RwSignal<usize>, Memo<usize>) {
+    let source = create_rw_signal(cx, 0);
+    let mut last = create_memo(cx, move |_| source.get());
+    for _ in 1..depth {
+        let prev = last;
+        last = create_memo(cx, move |_| prev.get() + 1);
+    }
+    (source, last)
+}
+
+/// Creates `n` source signals and one memo that reads all of them.
+/// Each update to any source notifies the memo.
+pub fn create_fan_in(
+    cx: Scope,
+    n: usize,
+) -> (Vec<RwSignal<usize>>, Memo<usize>) {
+    let sources = create_signals(cx, n);
+    let memo = create_memo(cx, {
+        let sources = sources.clone();
+        move |_| sources.iter().map(|source| source.get()).sum()
+    });
+    (sources, memo)
+}
+
+/// Increments `source` `m` times, one update at a time.
+pub fn propagate_updates(source: RwSignal<usize>, m: usize) {
+    for _ in 0..m {
+        source.update(|n| *n += 1);
+    }
+}
+
+/// Reads every memo, returning the sum of their values, which forces any
+/// that are out of date to re-run.
+pub fn sum(memos: &[Memo<usize>]) -> usize {
+    memos.iter().map(|memo| memo.get()).sum()
+}
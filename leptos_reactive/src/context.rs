@@ -1,7 +1,12 @@
 #![forbid(unsafe_code)]
 
 use crate::{runtime::with_runtime, Scope};
-use std::any::{Any, TypeId};
+use rustc_hash::FxHashMap;
+use std::{
+    any::{Any, TypeId},
+    fmt,
+    rc::Rc,
+};
 
 /// Provides a context value of type `T` to the current reactive [`Scope`](crate::Scope)
 /// and all of its descendants. This can be consumed using [`use_context`](crate::use_context).
@@ -14,6 +19,9 @@ use std::any::{Any, TypeId};
 /// the component tree can be used lower down, but a context that is provided lower
 /// in the tree cannot be used higher up.
 ///
+/// Providing a context is `O(1)`; see [`use_context`](crate::use_context) for the cost of
+/// looking it up.
+///
 /// ```
 /// use leptos::*;
 ///
@@ -60,7 +68,7 @@ where
     _ = with_runtime(cx.runtime, |runtime| {
         let mut contexts = runtime.scope_contexts.borrow_mut();
         let context = contexts.entry(cx.id).unwrap().or_default();
-        context.insert(id, Box::new(value) as Box<dyn Any>);
+        context.insert(id, Rc::new(value) as Rc<dyn Any>);
     });
 }
 
@@ -77,6 +85,9 @@ where
 /// the component tree can be used lower down, but a context that is provided lower
 /// in the tree cannot be used higher up.
 ///
+/// Looking up a context is `O(d)`, where `d` is the number of scopes between this one and
+/// the one that provided it.
+///
 /// ```
 /// use leptos::*;
 ///
@@ -206,3 +217,96 @@ where
         )
     })
 }
+
+/// Every context value that can be used from a [`Scope`](crate::Scope), as
+/// captured by [`snapshot_context`].
+///
+/// Context is looked up through a scope's parents, so code that runs in a
+/// scope that is not a descendant of the one providing the context, like a
+/// view mounted elsewhere in the document with
+/// [`mount_to`](https://docs.rs/leptos/latest/leptos/fn.mount_to.html) or a
+/// scope created with [`create_scope`](crate::create_scope), can't see it.
+/// Providing a snapshot to that scope makes the same values available there.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Theme(&'static str);
+///
+/// let runtime = create_runtime();
+/// let snapshot = run_scope(runtime, |cx| {
+///     provide_context(cx, Theme("dark"));
+///     snapshot_context(cx)
+/// });
+///
+/// // a scope that is not a child of the one above
+/// create_scope(runtime, move |cx| {
+///     assert_eq!(use_context::<Theme>(cx), None);
+///     snapshot.provide(cx);
+///     assert_eq!(use_context::<Theme>(cx), Some(Theme("dark")));
+/// });
+/// # runtime.dispose();
+/// ```
+#[derive(Clone, Default)]
+pub struct ContextSnapshot(Rc<FxHashMap<TypeId, Rc<dyn Any>>>);
+
+impl ContextSnapshot {
+    /// Provides every value in the snapshot to `cx`, as if each had been
+    /// passed to [`provide_context`]. Values already provided to `cx` itself
+    /// are kept.
+    pub fn provide(&self, cx: Scope) {
+        _ = with_runtime(cx.runtime, |runtime| {
+            let mut contexts = runtime.scope_contexts.borrow_mut();
+            let context = contexts.entry(cx.id).unwrap().or_default();
+            for (id, value) in self.0.iter() {
+                context.entry(*id).or_insert_with(|| Rc::clone(value));
+            }
+        });
+    }
+
+    /// The number of context values in the snapshot.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the snapshot contains no context values.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for ContextSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextSnapshot")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// Captures every context value that [`use_context`] can find from `cx`,
+/// i.e., the nearest value of each type provided to `cx` or any of its
+/// parents, so that they can be provided to another scope with
+/// [`ContextSnapshot::provide`].
+///
+/// The values themselves are shared rather than cloned, so signals and other
+/// handles in the snapshot still refer to the same reactive state.
+pub fn snapshot_context(cx: Scope) -> ContextSnapshot {
+    let values = with_runtime(cx.runtime, |runtime| {
+        let contexts = runtime.scope_contexts.borrow();
+        let parents = runtime.scope_parents.borrow();
+        let mut values = FxHashMap::default();
+        let mut scope = Some(cx.id);
+        while let Some(id) = scope {
+            if let Some(context) = contexts.get(id) {
+                for (ty, value) in context {
+                    // the nearest scope wins, as it does in `use_context`
+                    values.entry(*ty).or_insert_with(|| Rc::clone(value));
+                }
+            }
+            scope = parents.get(id).copied();
+        }
+        values
+    })
+    .unwrap_or_default();
+    ContextSnapshot(Rc::new(values))
+}
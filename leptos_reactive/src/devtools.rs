@@ -0,0 +1,585 @@
+#![forbid(unsafe_code)]
+//! Introspection APIs for debugging the reactive graph.
+//!
+//! Signals, memos and effects can be given a name when they are created (see
+//! [`create_signal_named`], [`create_rw_signal_named`], [`create_memo_named`]
+//! and [`create_effect_named`]). Named nodes keep a formatter for their value,
+//! so that [`RuntimeId::graph_snapshot`] can report the last value of each one
+//! as a [`Debug`] string.
+//!
+//! A DevTools integration can call [`RuntimeId::on_node_update`] to be notified
+//! each time a named node changes, and forward the serialized
+//! [`NodeSnapshot`]s to a browser extension that visualizes the graph.
+//! `connect_devtools` in `leptos_dom` streams them over a websocket.
+use crate::{
+    node::{NodeId, ReactiveNodeType},
+    runtime::{with_runtime, Runtime, RuntimeId},
+    Memo, ReadSignal, RwSignal, Scope, WriteSignal,
+};
+use cfg_if::cfg_if;
+#[cfg(debug_assertions)]
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use slotmap::Key;
+use std::{any::Any, borrow::Cow, fmt::Debug, rc::Rc};
+
+slotmap::new_key_type! {
+    /// Unique ID of a callback registered with
+    /// [`RuntimeId::on_node_update`].
+    pub struct NodeUpdateHookId;
+}
+
+/// The name and value formatter of a named reactive node, along with the
+/// functions used to record and restore the value of a named signal.
+pub(crate) struct NodeDebugInfo {
+    pub name: Cow<'static, str>,
+    pub fmt_value: fn(&dyn Any) -> Option<String>,
+    pub clone_value: Option<fn(&dyn Any) -> Option<Box<dyn Any>>>,
+    pub restore_value: Option<fn(&mut dyn Any, &dyn Any)>,
+}
+
+impl NodeDebugInfo {
+    fn signal<T: Clone + Debug + 'static>(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            fmt_value: fmt_value::<T>,
+            clone_value: Some(clone_value::<T>),
+            restore_value: Some(restore_value::<T>),
+        }
+    }
+
+    fn computation<T: Debug + 'static>(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            fmt_value: fmt_optional_value::<T>,
+            clone_value: None,
+            restore_value: None,
+        }
+    }
+}
+
+/// The kind of a node in the reactive graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum NodeKind {
+    /// A [`Trigger`](crate::Trigger).
+    Trigger,
+    /// A signal, created with [`create_signal`](crate::create_signal) or
+    /// [`create_rw_signal`](crate::create_rw_signal).
+    Signal,
+    /// A [`Memo`].
+    Memo,
+    /// An effect, created with [`create_effect`](crate::create_effect) or
+    /// [`create_isomorphic_effect`](crate::create_isomorphic_effect).
+    Effect,
+}
+
+/// A point-in-time description of a single node in the reactive graph.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NodeSnapshot {
+    /// A unique identifier for the node within its runtime.
+    pub id: u64,
+    /// What kind of reactive node this is.
+    pub kind: NodeKind,
+    /// The name given to the node when it was created, if any.
+    pub name: Option<String>,
+    /// The source location at which a memo or effect was created.
+    pub defined_at: Option<String>,
+    /// The [`Debug`] representation of the node's most recent value. This is
+    /// only available for named nodes.
+    pub value: Option<String>,
+}
+
+/// A point-in-time description of the whole reactive graph, as returned by
+/// [`RuntimeId::graph_snapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GraphSnapshot {
+    /// Every signal, memo, effect and trigger that currently exists.
+    pub nodes: Vec<NodeSnapshot>,
+    /// Each edge is a `(source, subscriber)` pair of node IDs: the
+    /// subscriber will rerun when the source changes.
+    pub edges: Vec<(u64, u64)>,
+}
+
+fn fmt_value<T: Debug + 'static>(value: &dyn Any) -> Option<String> {
+    value.downcast_ref::<T>().map(|value| format!("{value:?}"))
+}
+
+fn fmt_optional_value<T: Debug + 'static>(value: &dyn Any) -> Option<String> {
+    value
+        .downcast_ref::<Option<T>>()
+        .and_then(|value| value.as_ref().map(|value| format!("{value:?}")))
+}
+
+fn clone_value<T: Clone + 'static>(value: &dyn Any) -> Option<Box<dyn Any>> {
+    value
+        .downcast_ref::<T>()
+        .map(|value| Box::new(value.clone()) as Box<dyn Any>)
+}
+
+fn restore_value<T: Clone + 'static>(slot: &mut dyn Any, value: &dyn Any) {
+    if let (Some(slot), Some(value)) =
+        (slot.downcast_mut::<T>(), value.downcast_ref::<T>())
+    {
+        *slot = value.clone();
+    }
+}
+
+impl Runtime {
+    pub(crate) fn set_node_name(&self, node: NodeId, info: NodeDebugInfo) {
+        self.node_debug_info.borrow_mut().insert(node, info);
+    }
+
+    pub(crate) fn node_snapshot(&self, id: NodeId) -> Option<NodeSnapshot> {
+        let nodes = self.nodes.borrow();
+        let node = nodes.get(id)?;
+        let (kind, defined_at) = match &node.node_type {
+            ReactiveNodeType::Trigger => (NodeKind::Trigger, None),
+            ReactiveNodeType::Signal => (NodeKind::Signal, None),
+            ReactiveNodeType::Memo { f } => (NodeKind::Memo, f.defined_at()),
+            ReactiveNodeType::Effect { f } => {
+                (NodeKind::Effect, f.defined_at())
+            }
+        };
+        let debug_info = self.node_debug_info.borrow();
+        let debug_info = debug_info.get(id);
+        let value = debug_info.and_then(|info| {
+            let value = node.value.as_ref()?.try_borrow().ok()?;
+            (info.fmt_value)(&*value)
+        });
+        Some(NodeSnapshot {
+            id: id.data().as_ffi(),
+            kind,
+            name: debug_info.map(|info| info.name.to_string()),
+            defined_at: defined_at.map(ToString::to_string),
+            value,
+        })
+    }
+
+    pub(crate) fn graph_snapshot(&self) -> GraphSnapshot {
+        let ids = self.nodes.borrow().keys().collect::<Vec<_>>();
+        let nodes = ids
+            .iter()
+            .filter_map(|id| self.node_snapshot(*id))
+            .collect();
+        let edges = self
+            .node_subscribers
+            .borrow()
+            .iter()
+            .flat_map(|(source, subscribers)| {
+                subscribers
+                    .borrow()
+                    .iter()
+                    .map(|sub| (source.data().as_ffi(), sub.data().as_ffi()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        GraphSnapshot { nodes, edges }
+    }
+
+    /// Notifies any DevTools hooks that a named node has changed.
+    #[inline(always)]
+    pub(crate) fn notify_devtools(&self, node: NodeId) {
+        if self.devtools_hooks.borrow().is_empty()
+            || !self.node_debug_info.borrow().contains_key(node)
+        {
+            return;
+        }
+        if let Some(snapshot) = self.node_snapshot(node) {
+            // clone the hooks so they can safely access the runtime
+            let hooks = self
+                .devtools_hooks
+                .borrow()
+                .values()
+                .cloned()
+                .collect::<Vec<_>>();
+            for hook in hooks {
+                hook(&snapshot);
+            }
+        }
+    }
+}
+
+impl RuntimeId {
+    /// Returns a snapshot of every node in the reactive graph and the
+    /// dependencies between them. Named nodes include a [`Debug`]
+    /// representation of their current value.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # let runtime = create_runtime();
+    /// # create_scope(runtime, |cx| {
+    /// let (count, _) = create_signal_named(cx, "count", 1);
+    /// let double = create_memo_named(cx, "double", move |_| count() * 2);
+    /// assert_eq!(double(), 2);
+    ///
+    /// let graph = cx.runtime.graph_snapshot();
+    /// let count = graph
+    ///     .nodes
+    ///     .iter()
+    ///     .find(|node| node.name.as_deref() == Some("count"))
+    ///     .unwrap();
+    /// assert_eq!(count.value.as_deref(), Some("1"));
+    /// assert_eq!(graph.edges.len(), 1);
+    /// # }).dispose();
+    /// # runtime.dispose();
+    /// ```
+    pub fn graph_snapshot(self) -> GraphSnapshot {
+        with_runtime(self, |runtime| runtime.graph_snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Registers a callback that will be run every time a named signal is
+    /// set, or a named memo or effect reruns, with a snapshot of that node.
+    ///
+    /// This is intended as an extension point for DevTools, which can
+    /// serialize each [`NodeSnapshot`] and stream it to a browser extension.
+    /// The callback is kept until it is removed with
+    /// [`RuntimeId::remove_node_update`] or the runtime is disposed.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::Cell, rc::Rc};
+    /// # let runtime = create_runtime();
+    /// # create_scope(runtime, |cx| {
+    /// let (_, set_count) = create_signal_named(cx, "count", 0);
+    /// let updates = Rc::new(Cell::new(0));
+    /// let hook = cx.runtime.on_node_update({
+    ///     let updates = Rc::clone(&updates);
+    ///     move |_| updates.set(updates.get() + 1)
+    /// });
+    ///
+    /// set_count(1);
+    /// cx.runtime.remove_node_update(hook);
+    /// set_count(2);
+    /// assert_eq!(updates.get(), 1);
+    /// # }).dispose();
+    /// # runtime.dispose();
+    /// ```
+    pub fn on_node_update(
+        self,
+        f: impl Fn(&NodeSnapshot) + 'static,
+    ) -> NodeUpdateHookId {
+        with_runtime(self, |runtime| {
+            runtime.devtools_hooks.borrow_mut().insert(Rc::new(f))
+        })
+        .unwrap_or_default()
+    }
+
+    /// Removes a callback registered with [`RuntimeId::on_node_update`].
+    pub fn remove_node_update(self, id: NodeUpdateHookId) {
+        _ = with_runtime(self, |runtime| {
+            runtime.devtools_hooks.borrow_mut().remove(id)
+        });
+    }
+
+    pub(crate) fn set_node_name(self, node: NodeId, info: NodeDebugInfo) {
+        _ = with_runtime(self, |runtime| runtime.set_node_name(node, info));
+    }
+}
+
+/// Works exactly as [`create_signal`](crate::create_signal), but gives the
+/// signal a name that will be used to identify it in a
+/// [`GraphSnapshot`] or DevTools. In debug mode, updates to named signals
+/// can be recorded and replayed with [`RuntimeId::start_recording`].
+#[track_caller]
+pub fn create_signal_named<T>(
+    cx: Scope,
+    name: impl Into<Cow<'static, str>>,
+    value: T,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Clone + Debug + 'static,
+{
+    let (read, write) = crate::create_signal(cx, value);
+    cx.runtime
+        .set_node_name(read.id, NodeDebugInfo::signal::<T>(name.into()));
+    (read, write)
+}
+
+/// Works exactly as [`create_rw_signal`](crate::create_rw_signal), but gives
+/// the signal a name that will be used to identify it in a
+/// [`GraphSnapshot`] or DevTools. In debug mode, updates to named signals
+/// can be recorded and replayed with [`RuntimeId::start_recording`].
+#[track_caller]
+pub fn create_rw_signal_named<T>(
+    cx: Scope,
+    name: impl Into<Cow<'static, str>>,
+    value: T,
+) -> RwSignal<T>
+where
+    T: Clone + Debug + 'static,
+{
+    let signal = crate::create_rw_signal(cx, value);
+    cx.runtime
+        .set_node_name(signal.id, NodeDebugInfo::signal::<T>(name.into()));
+    signal
+}
+
+/// Works exactly as [`create_memo`](crate::create_memo), but gives the memo a
+/// name that will be used to identify it in a [`GraphSnapshot`] or DevTools.
+#[track_caller]
+pub fn create_memo_named<T>(
+    cx: Scope,
+    name: impl Into<Cow<'static, str>>,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + Debug + 'static,
+{
+    let memo = crate::create_memo(cx, f);
+    cx.runtime
+        .set_node_name(memo.id, NodeDebugInfo::computation::<T>(name.into()));
+    memo
+}
+
+/// Works exactly as [`create_effect`](crate::create_effect), but gives the
+/// effect a name that will be used to identify it in a [`GraphSnapshot`] or
+/// DevTools.
+#[track_caller]
+pub fn create_effect_named<T>(
+    cx: Scope,
+    name: impl Into<Cow<'static, str>>,
+    f: impl Fn(Option<T>) -> T + 'static,
+) where
+    T: Debug + 'static,
+{
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            let e = cx.runtime.create_effect(crate::effect::with_scope(cx, f));
+            cx.runtime.set_node_name(
+                e,
+                NodeDebugInfo::computation::<T>(name.into()),
+            );
+            cx.push_scope_property(crate::ScopeProperty::Effect(e))
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = name;
+            _ = f;
+        }
+    }
+}
+
+/// A single recorded update to a named signal, as returned by
+/// [`RuntimeId::signal_history`].
+#[cfg(debug_assertions)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SignalHistoryEntry {
+    /// The identifier of the signal, matching [`NodeSnapshot::id`].
+    pub id: u64,
+    /// The name given to the signal when it was created.
+    pub name: String,
+    /// The [`Debug`] representation of the signal's new value.
+    pub value: Option<String>,
+    /// The number of milliseconds between the start of the recording and
+    /// this update.
+    pub timestamp: f64,
+}
+
+#[cfg(debug_assertions)]
+struct RecordedUpdate {
+    node: NodeId,
+    entry: SignalHistoryEntry,
+    value: Box<dyn Any>,
+}
+
+/// The recorded updates to named signals, used for time-travel debugging.
+#[cfg(debug_assertions)]
+pub(crate) struct SignalHistory {
+    started_at: f64,
+    baseline: Vec<(NodeId, Box<dyn Any>)>,
+    updates: Vec<RecordedUpdate>,
+    paused: bool,
+}
+
+#[cfg(debug_assertions)]
+fn now() -> f64 {
+    cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            js_sys::Date::now()
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|since| since.as_secs_f64() * 1000.0)
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Runtime {
+    fn clone_named_value(&self, node: NodeId) -> Option<Box<dyn Any>> {
+        let clone_value =
+            self.node_debug_info.borrow().get(node)?.clone_value?;
+        let value = self.get_value(node)?;
+        let value = value.try_borrow().ok()?;
+        clone_value(&*value)
+    }
+
+    /// Records the new value of a named signal, if a recording is running.
+    pub(crate) fn record_update(&self, node: NodeId) {
+        let mut history = self.signal_history.borrow_mut();
+        let history = match history.as_mut() {
+            Some(history) if !history.paused => history,
+            _ => return,
+        };
+        if let Some(value) = self.clone_named_value(node) {
+            let debug_info = self.node_debug_info.borrow();
+            let info = debug_info
+                .get(node)
+                .expect("named signal to have debug info");
+            history.updates.push(RecordedUpdate {
+                node,
+                entry: SignalHistoryEntry {
+                    id: node.data().as_ffi(),
+                    name: info.name.to_string(),
+                    value: (info.fmt_value)(&*value),
+                    timestamp: now() - history.started_at,
+                },
+                value,
+            });
+        }
+    }
+
+    fn start_recording(&self) {
+        let nodes = self.node_debug_info.borrow().keys().collect::<Vec<_>>();
+        let baseline = nodes
+            .into_iter()
+            .filter_map(|node| Some((node, self.clone_named_value(node)?)))
+            .collect();
+        *self.signal_history.borrow_mut() = Some(SignalHistory {
+            started_at: now(),
+            baseline,
+            updates: Vec::new(),
+            paused: false,
+        });
+    }
+
+    /// Restores every recorded signal to the value it held after the first
+    /// `len` recorded updates, then reruns anything that depends on them.
+    fn rewind(&self, len: usize) -> bool {
+        let restored = {
+            let mut history = self.signal_history.borrow_mut();
+            let history = match history.as_mut() {
+                Some(history) if len <= history.updates.len() => history,
+                _ => return false,
+            };
+
+            // the most recent value of each signal, as of the given update
+            let mut targets = FxHashMap::default();
+            for (node, value) in &history.baseline {
+                targets.insert(*node, value);
+            }
+            for update in &history.updates[..len] {
+                targets.insert(update.node, &update.value);
+            }
+
+            let debug_info = self.node_debug_info.borrow();
+            let restored = targets
+                .into_iter()
+                .filter_map(|(node, value)| {
+                    let restore_value = debug_info.get(node)?.restore_value?;
+                    let slot = self.get_value(node)?;
+                    let mut slot = slot.try_borrow_mut().ok()?;
+                    restore_value(&mut *slot, &**value);
+                    Some(node)
+                })
+                .collect::<Vec<_>>();
+
+            // don't record the updates caused by rewinding
+            history.paused = true;
+            restored
+        };
+
+        for node in restored {
+            self.mark_dirty(node);
+        }
+        self.run_effects();
+
+        if let Some(history) = self.signal_history.borrow_mut().as_mut() {
+            history.paused = false;
+        }
+        true
+    }
+}
+
+#[cfg(debug_assertions)]
+impl RuntimeId {
+    /// Begins recording every update to a named signal (see
+    /// [`create_signal_named`] and [`create_rw_signal_named`]), discarding
+    /// any previous recording. Only available in debug mode.
+    ///
+    /// The recording can be inspected with [`RuntimeId::signal_history`], and
+    /// the runtime can be returned to an earlier state with
+    /// [`RuntimeId::rewind_to`], which is useful for reproducing UI states
+    /// that are difficult to reach by hand.
+    pub fn start_recording(self) {
+        _ = with_runtime(self, |runtime| runtime.start_recording());
+    }
+
+    /// Stops recording signal updates and discards the recorded history.
+    pub fn stop_recording(self) {
+        _ = with_runtime(self, |runtime| {
+            runtime.signal_history.borrow_mut().take()
+        });
+    }
+
+    /// Returns every signal update recorded since
+    /// [`RuntimeId::start_recording`] was called, from oldest to newest.
+    pub fn signal_history(self) -> Vec<SignalHistoryEntry> {
+        with_runtime(self, |runtime| {
+            runtime
+                .signal_history
+                .borrow()
+                .as_ref()
+                .map(|history| {
+                    history
+                        .updates
+                        .iter()
+                        .map(|update| update.entry.clone())
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Restores every recorded signal to the value it held just after the
+    /// update at `index` in [`RuntimeId::signal_history`], rerunning any
+    /// memos and effects that depend on them.
+    ///
+    /// Rewinding does not discard the history, so it's possible to move back
+    /// and forth between states. Returns `false` if nothing is being recorded
+    /// or `index` is out of bounds.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # let runtime = create_runtime();
+    /// # create_scope(runtime, |cx| {
+    /// let (count, set_count) = create_signal_named(cx, "count", 0);
+    /// cx.runtime.start_recording();
+    ///
+    /// set_count(1);
+    /// set_count(2);
+    /// set_count(3);
+    /// assert_eq!(cx.runtime.signal_history().len(), 3);
+    ///
+    /// assert!(cx.runtime.rewind_to(0));
+    /// assert_eq!(count(), 1);
+    /// cx.runtime.rewind_to_start();
+    /// assert_eq!(count(), 0);
+    /// assert!(cx.runtime.rewind_to(2));
+    /// assert_eq!(count(), 3);
+    /// # }).dispose();
+    /// # runtime.dispose();
+    /// ```
+    pub fn rewind_to(self, index: usize) -> bool {
+        with_runtime(self, |runtime| runtime.rewind(index + 1)).unwrap_or(false)
+    }
+
+    /// Restores every recorded signal to the value it held when
+    /// [`RuntimeId::start_recording`] was called.
+    pub fn rewind_to_start(self) -> bool {
+        with_runtime(self, |runtime| runtime.rewind(0)).unwrap_or(false)
+    }
+}
@@ -17,16 +17,18 @@ pub(crate) struct AccessDiagnostics {}
 /// This just tracks whether we're currently in a context in which it really doesn't
 /// matter whether something is reactive: for example, in an event listener or timeout.
 /// Entering this zone basically turns off the warnings, and exiting it turns them back on.
-/// All of this is a no-op in release mode.
+/// Zones can be nested: the warnings stay off until every zone that was entered has
+/// been exited. All of this is a no-op in release mode.
 #[doc(hidden)]
 pub struct SpecialNonReactiveZone {}
 
 cfg_if::cfg_if! {
     if #[cfg(debug_assertions)] {
-        use std::cell::Cell;
+        use std::{cell::{Cell, RefCell}, collections::HashSet, panic::Location};
 
         thread_local! {
-            static IS_SPECIAL_ZONE: Cell<bool> = Cell::new(false);
+            static SPECIAL_ZONE_DEPTH: Cell<usize> = Cell::new(0);
+            static WARNED_AT: RefCell<HashSet<&'static Location<'static>>> = Default::default();
         }
     }
 }
@@ -37,7 +39,7 @@ impl SpecialNonReactiveZone {
     pub(crate) fn is_inside() -> bool {
         #[cfg(debug_assertions)]
         {
-            IS_SPECIAL_ZONE.with(|val| val.get())
+            SPECIAL_ZONE_DEPTH.with(|depth| depth.get() > 0)
         }
         #[cfg(not(debug_assertions))]
         false
@@ -47,7 +49,7 @@ impl SpecialNonReactiveZone {
     pub fn enter() {
         #[cfg(debug_assertions)]
         {
-            IS_SPECIAL_ZONE.with(|val| val.set(true))
+            SPECIAL_ZONE_DEPTH.with(|depth| depth.set(depth.get() + 1))
         }
     }
 
@@ -55,9 +57,36 @@ impl SpecialNonReactiveZone {
     pub fn exit() {
         #[cfg(debug_assertions)]
         {
-            IS_SPECIAL_ZONE.with(|val| val.set(false))
+            SPECIAL_ZONE_DEPTH
+                .with(|depth| depth.set(depth.get().saturating_sub(1)))
         }
     }
+
+    // Enters the zone until the returned guard is dropped.
+    #[inline(always)]
+    pub(crate) fn enter_until_dropped() -> SpecialNonReactiveZoneGuard {
+        Self::enter();
+        SpecialNonReactiveZoneGuard
+    }
+}
+
+pub(crate) struct SpecialNonReactiveZoneGuard;
+
+impl Drop for SpecialNonReactiveZoneGuard {
+    fn drop(&mut self) {
+        SpecialNonReactiveZone::exit();
+    }
+}
+
+// Returns `true` the first time it is called for a given location on this
+// thread, so that a warning can be shown once rather than every time the code
+// that causes it runs.
+#[cfg(debug_assertions)]
+#[allow(dead_code)] // allowed for SSR
+pub(crate) fn is_first_warning_at(
+    location: &'static Location<'static>,
+) -> bool {
+    WARNED_AT.with(|warned| warned.borrow_mut().insert(location))
 }
 
 #[doc(hidden)]
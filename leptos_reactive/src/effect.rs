@@ -1,5 +1,5 @@
 #![forbid(unsafe_code)]
-use crate::{Scope, ScopeProperty};
+use crate::{node::NodeId, with_runtime, RuntimeId, Scope, ScopeProperty};
 use cfg_if::cfg_if;
 use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc};
 
@@ -19,6 +19,9 @@ use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc};
 /// By default, effects **do not run on the server**. This means you can call browser-specific
 /// APIs within the effect function without causing issues. If you need an effect to run on
 /// the server, use [`create_isomorphic_effect`].
+///
+/// Each time an effect runs, it costs as much as its function does, plus `O(n)` in the
+/// number of signals it reads, as its dependencies are tracked again from scratch.
 /// ```
 /// # use leptos_reactive::*;
 /// # use log::*;
@@ -65,7 +68,16 @@ where
 {
     cfg_if! {
         if #[cfg(not(feature = "ssr"))] {
-            let e = cx.runtime.create_effect(f);
+            #[cfg(debug_assertions)]
+            if crate::is_strict_mode() {
+                let e = cx.runtime.create_effect(
+                    crate::strict::run_first_twice(with_scope(cx, f)),
+                );
+                cx.push_scope_property(ScopeProperty::Effect(e));
+                return;
+            }
+
+            let e = cx.runtime.create_effect(with_scope(cx, f));
             //eprintln!("created effect {e:?}");
             cx.push_scope_property(ScopeProperty::Effect(e))
         } else {
@@ -121,11 +133,23 @@ pub fn create_isomorphic_effect<T>(
 ) where
     T: 'static,
 {
-    let e = cx.runtime.create_effect(f);
+    #[cfg(debug_assertions)]
+    if crate::is_strict_mode() {
+        let e = cx
+            .runtime
+            .create_effect(crate::strict::run_first_twice(with_scope(cx, f)));
+        cx.push_scope_property(ScopeProperty::Effect(e));
+        return;
+    }
+
+    let e = cx.runtime.create_effect(with_scope(cx, f));
     //eprintln!("created effect {e:?}");
     cx.push_scope_property(ScopeProperty::Effect(e))
 }
 
+/// Creates an effect for the renderer. Unlike [create_effect], it is never
+/// run twice in [strict mode](crate::strict), because the renderer's own
+/// effects are not idempotent.
 #[doc(hidden)]
 #[cfg_attr(
     any(debug_assertions, feature="ssr"),
@@ -143,7 +167,175 @@ pub fn create_render_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: 'static,
 {
-    create_effect(cx, f);
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            let e = cx.runtime.create_effect(with_scope(cx, f));
+            cx.push_scope_property(ScopeProperty::Effect(e))
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = f;
+        }
+    }
+}
+
+/// When an effect created with [`create_effect_with_timing`] runs, relative to
+/// the change that caused it to run.
+///
+/// Whatever the timing, an effect runs at most once for all the changes to its
+/// dependencies made before it runs, and always sees their latest values.
+/// Within each timing, effects run in the order in which they were scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EffectTiming {
+    /// Runs synchronously, before the write to the signal returns, like an
+    /// effect created with [`create_effect`].
+    #[default]
+    Sync,
+    /// Runs in a microtask, once the code that changed the signal has
+    /// finished, but before the browser paints. Several writes in a row
+    /// cause one run.
+    Microtask,
+    /// Runs once the browser is idle, after it has painted, using
+    /// `requestIdleCallback` (or `setTimeout` where it is not supported).
+    /// Use this for expensive work that does not affect what is on the
+    /// screen, like analytics or writing to storage.
+    Idle,
+}
+
+/// Creates an effect, like [`create_effect`], that runs with the given
+/// [`EffectTiming`] rather than synchronously. Its first run is deferred in
+/// the same way.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (query, set_query) = create_signal(cx, String::new());
+///
+/// // doesn't delay rendering the results while the user is typing
+/// create_effect_with_timing(cx, EffectTiming::Idle, move |_| {
+///     log::debug!("searched for {}", query());
+/// });
+///
+/// set_query("leptos".to_string());
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+#[inline(always)]
+pub fn create_effect_with_timing<T>(
+    cx: Scope,
+    timing: EffectTiming,
+    f: impl Fn(Option<T>) -> T + 'static,
+) where
+    T: 'static,
+{
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            if timing == EffectTiming::Sync {
+                create_effect(cx, f);
+                return;
+            }
+
+            let e = cx.runtime.create_deferred_effect(timing, with_scope(cx, f));
+            cx.push_scope_property(ScopeProperty::Effect(e))
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = timing;
+            _ = f;
+        }
+    }
+}
+
+// Runs an effect with the scope that owns it as the current scope, so that
+// code in it which isn't passed a `cx`, like `view!` without one, finds it.
+pub(crate) fn with_scope<T>(
+    cx: Scope,
+    f: impl Fn(Option<T>) -> T + 'static,
+) -> impl Fn(Option<T>) -> T + 'static {
+    move |value| cx.with_current(|| f(value))
+}
+
+/// Bookkeeping for an effect that does not run synchronously.
+pub(crate) struct DeferredEffect {
+    pub(crate) runtime: RuntimeId,
+    pub(crate) timing: EffectTiming,
+    /// Whether a run has been scheduled and has not happened yet.
+    pub(crate) scheduled: bool,
+}
+
+/// Schedules the next run of a deferred effect.
+pub(crate) fn schedule_effect(
+    runtime: RuntimeId,
+    id: NodeId,
+    timing: EffectTiming,
+) {
+    let run = move || {
+        _ = with_runtime(runtime, |runtime| {
+            if let Some(effect) =
+                runtime.deferred_effects.borrow_mut().get_mut(id)
+            {
+                effect.scheduled = false;
+            }
+            runtime.update_if_necessary(id);
+        });
+    };
+
+    cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            match timing {
+                EffectTiming::Idle => {
+                    let window = web_sys::window().expect("no window");
+                    let callback = Closure::once_into_js(run);
+                    // Safari doesn't support `requestIdleCallback`
+                    if window
+                        .request_idle_callback(callback.unchecked_ref())
+                        .is_err()
+                    {
+                        _ = window.set_timeout_with_callback(
+                            callback.unchecked_ref(),
+                        );
+                    }
+                }
+                _ => crate::queue_microtask(run),
+            }
+        } else {
+            if crate::TestExecutor::is_installed() {
+                match timing {
+                    EffectTiming::Idle => {
+                        let mut run = Some(run);
+                        crate::TestExecutor::set_timer(
+                            Box::new(move || {
+                                if let Some(run) = run.take() {
+                                    run();
+                                }
+                            }),
+                            std::time::Duration::ZERO,
+                            None,
+                        );
+                    }
+                    _ => crate::spawn_local(async move { run() }),
+                }
+                return;
+            }
+
+            // without a browser, there is nothing to defer to
+            _ = timing;
+            run();
+        }
+    }
 }
 
 pub(crate) struct Effect<T, F>
@@ -159,6 +351,11 @@ where
 
 pub(crate) trait AnyComputation {
     fn run(&self, value: Rc<RefCell<dyn Any>>) -> bool;
+
+    /// Where this computation was created, if known, for use in diagnostics.
+    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
 }
 
 impl<T, F> AnyComputation for Effect<T, F>
@@ -203,4 +400,34 @@ where
 
         true
     }
+
+    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        #[cfg(any(debug_assertions, feature = "ssr"))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, feature = "ssr")))]
+        {
+            None
+        }
+    }
+}
+
+/// Runs a computation with the component stack from where it was created, so
+/// that errors in effects and memos that rerun later are reported with it.
+#[cfg(debug_assertions)]
+pub(crate) struct WithComponentStack {
+    pub(crate) computation: Rc<dyn AnyComputation>,
+    pub(crate) stack: crate::ComponentStack,
+}
+
+#[cfg(debug_assertions)]
+impl AnyComputation for WithComponentStack {
+    fn run(&self, value: Rc<RefCell<dyn Any>>) -> bool {
+        self.stack.run(|| self.computation.run(value))
+    }
+
+    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        self.computation.defined_at()
+    }
 }
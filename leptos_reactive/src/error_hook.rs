@@ -0,0 +1,256 @@
+#![forbid(unsafe_code)]
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    fmt,
+    panic::{Location, PanicInfo},
+    rc::Rc,
+};
+
+thread_local! {
+    static HANDLER: RefCell<Option<Rc<dyn Fn(&ErrorReport)>>> = RefCell::new(None);
+    static SOURCES: RefCell<Vec<ErrorSource>> = RefCell::new(Vec::new());
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    // from the outermost component to the innermost
+    static COMPONENT_STACK: RefCell<Vec<Cow<'static, str>>> = RefCell::new(Vec::new());
+}
+
+/// A panic or error reported to the handler set with [on_error].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ErrorReport {
+    /// The panic message, or the message passed to [report_error].
+    pub message: String,
+    /// The file, line, and column where the panic happened, if known.
+    pub location: Option<String>,
+    /// In debug builds, what was running when the error happened. In release
+    /// builds, this is always [ErrorSource::Other].
+    pub source: ErrorSource,
+    /// In debug builds, the names of the components that were being created
+    /// when the error happened, or that created the effect, memo or event
+    /// handler that was running, from the innermost to the outermost.
+    pub component_stack: Vec<String>,
+}
+
+/// What was running when an error was reported. See [ErrorReport].
+///
+/// This is only tracked in debug builds, so that release builds don't pay for
+/// it every time an effect, memo or event handler runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorSource {
+    /// An effect was running. `defined_at` is where it was created.
+    Effect {
+        /// Where the effect was created.
+        defined_at: Option<&'static Location<'static>>,
+    },
+    /// A memo was running. `defined_at` is where it was created.
+    Memo {
+        /// Where the memo was created.
+        defined_at: Option<&'static Location<'static>>,
+    },
+    /// An event listener was running.
+    EventHandler {
+        /// The name of the event, like `click`.
+        event: Cow<'static, str>,
+    },
+    /// None of the above, for example while the view was first being
+    /// created.
+    Other,
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Effect {
+                defined_at: Some(loc),
+            } => {
+                write!(f, "effect defined at {loc}")
+            }
+            Self::Effect { defined_at: None } => write!(f, "effect"),
+            Self::Memo {
+                defined_at: Some(loc),
+            } => {
+                write!(f, "memo defined at {loc}")
+            }
+            Self::Memo { defined_at: None } => write!(f, "memo"),
+            Self::EventHandler { event } => write!(f, "{event} event handler"),
+            Self::Other => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Sets the function that receives every panic and error reported on the
+/// current thread, replacing any that was set before. This can be used to
+/// send them to an error-tracking service, along with what was running when
+/// they happened.
+///
+/// In the browser, a panic aborts the program, so it can't be caught with
+/// `catch_unwind`; instead, panics are reported from the panic hook. The hook
+/// that `leptos::init_client` installs does this, and an app that sets its
+/// own hook should call [report_panic] from it.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// on_error(|report| {
+///     // send this to an error-tracking service instead
+///     eprintln!(
+///         "{} (in {}, at {:?})",
+///         report.message, report.source, report.location
+///     );
+/// });
+/// ```
+pub fn on_error(handler: impl Fn(&ErrorReport) + 'static) {
+    HANDLER.with(|h| *h.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// Removes the handler set with [on_error].
+pub fn clear_error_handler() {
+    HANDLER.with(|h| *h.borrow_mut() = None);
+}
+
+/// Reports a panic to the handler set with [on_error], if any. This should be
+/// called from a panic hook.
+pub fn report_panic(info: &PanicInfo) {
+    let payload = info.payload();
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        info.to_string()
+    };
+    report(message, info.location().map(ToString::to_string));
+}
+
+/// Reports an error that did not panic to the handler set with [on_error],
+/// if any.
+#[track_caller]
+pub fn report_error(message: impl Into<String>) {
+    report(message.into(), Some(Location::caller().to_string()));
+}
+
+fn report(message: String, location: Option<String>) {
+    // clone the handler out, so that it can call `on_error` itself
+    let Some(handler) = HANDLER.with(|h| h.borrow().clone()) else {
+        return;
+    };
+    let source = SOURCES
+        .with(|sources| sources.borrow().last().cloned())
+        .unwrap_or(ErrorSource::Other);
+    let component_stack = current_component_stack()
+        .into_iter()
+        .map(Cow::into_owned)
+        .collect();
+    handler(&ErrorReport {
+        message,
+        location,
+        source,
+        component_stack,
+    });
+}
+
+/// The names of the components that are being created, from the innermost
+/// to the outermost, or those that created the effect, memo or event handler
+/// that is running. Always empty in release builds.
+#[doc(hidden)]
+pub fn current_component_stack() -> Vec<Cow<'static, str>> {
+    #[cfg(debug_assertions)]
+    {
+        COMPONENT_STACK
+            .with(|stack| stack.borrow().iter().rev().cloned().collect())
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        Vec::new()
+    }
+}
+
+/// Keeps a component's name on the component stack until it is dropped,
+/// including when a panic unwinds past it. Used by `leptos_dom` while a
+/// component is created.
+#[doc(hidden)]
+#[must_use]
+pub struct ComponentStackEntry(());
+
+impl ComponentStackEntry {
+    /// Pushes `name` onto the component stack.
+    pub fn push(name: Cow<'static, str>) -> Self {
+        #[cfg(debug_assertions)]
+        COMPONENT_STACK.with(|stack| stack.borrow_mut().push(name));
+        #[cfg(not(debug_assertions))]
+        _ = name;
+        Self(())
+    }
+}
+
+impl Drop for ComponentStackEntry {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        COMPONENT_STACK.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+/// The component stack at the time an effect, memo or event handler was
+/// created, so that errors that happen when it runs later can be reported
+/// with it. Empty in release builds.
+#[doc(hidden)]
+#[derive(Clone, Debug, Default)]
+pub struct ComponentStack {
+    #[cfg(debug_assertions)]
+    names: Rc<[Cow<'static, str>]>,
+}
+
+impl ComponentStack {
+    /// Captures the current component stack.
+    pub fn capture() -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            names: COMPONENT_STACK
+                .with(|stack| stack.borrow().as_slice().into()),
+        }
+    }
+
+    /// Runs `f` with this as the component stack, and then restores the
+    /// current one, even if `f` unwinds.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        #[cfg(debug_assertions)]
+        let _restore = RestoreComponentStack(COMPONENT_STACK.with(|stack| {
+            std::mem::replace(&mut *stack.borrow_mut(), self.names.to_vec())
+        }));
+        f()
+    }
+}
+
+#[cfg(debug_assertions)]
+struct RestoreComponentStack(Vec<Cow<'static, str>>);
+
+#[cfg(debug_assertions)]
+impl Drop for RestoreComponentStack {
+    fn drop(&mut self) {
+        COMPONENT_STACK.with(|stack| {
+            *stack.borrow_mut() = std::mem::take(&mut self.0);
+        });
+    }
+}
+
+/// Runs `f`, recording `source` as what is running for any error reported
+/// in the meantime.
+#[doc(hidden)]
+pub fn with_error_source<T>(source: ErrorSource, f: impl FnOnce() -> T) -> T {
+    SOURCES.with(|sources| sources.borrow_mut().push(source));
+    // pop even if `f` unwinds
+    let _guard = PopSource;
+    f()
+}
+
+struct PopSource;
+
+impl Drop for PopSource {
+    fn drop(&mut self) {
+        SOURCES.with(|sources| sources.borrow_mut().pop());
+    }
+}
@@ -0,0 +1,310 @@
+#![forbid(unsafe_code)]
+use futures::task::{waker, ArcWake};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::Context,
+    time::Duration,
+};
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<ExecutorState>>> = RefCell::new(None);
+}
+
+/// A manual executor for testing reactive code on native targets, without a
+/// browser or an async runtime.
+///
+/// While a `TestExecutor` exists, futures passed to [`spawn_local`](crate::spawn_local)
+/// on the same thread (including the fetchers of resources) are queued rather
+/// than run, and the timer helpers in `leptos_dom` (like `set_timeout` and
+/// `debounce`) use a mock clock. Nothing happens until the test calls
+/// [`step`](TestExecutor::step), [`run_until_stalled`](TestExecutor::run_until_stalled),
+/// or [`advance_time`](TestExecutor::advance_time), so it can assert on every
+/// intermediate state.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// use futures::FutureExt;
+/// # create_scope(create_runtime(), |cx| {
+/// let executor = TestExecutor::new();
+/// let (tx, rx) = futures::channel::oneshot::channel::<i32>();
+/// // the fetcher can run more than once, so each run waits on a clone
+/// let rx = rx.shared();
+/// let resource = create_resource(
+///     cx,
+///     || (),
+///     // waits until the test sends a value
+///     move |_| rx.clone().map(Result::unwrap),
+/// );
+///
+/// executor.run_until_stalled();
+/// assert!(resource.loading().get_untracked());
+///
+/// tx.send(42).unwrap();
+/// executor.run_until_stalled();
+/// assert_eq!(resource.read(cx), Some(42));
+/// # }).dispose();
+/// ```
+#[derive(Debug)]
+pub struct TestExecutor {
+    state: Rc<ExecutorState>,
+    suspense_clock: crate::scope::Sleep,
+}
+
+#[derive(Default)]
+struct ExecutorState {
+    tasks: RefCell<VecDeque<Task>>,
+    timers: RefCell<Vec<Timer>>,
+    now: Cell<Duration>,
+    next_timer_id: Cell<i32>,
+}
+
+impl std::fmt::Debug for ExecutorState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutorState")
+            .field("tasks", &self.tasks.borrow().len())
+            .field("timers", &self.timers.borrow().len())
+            .field("now", &self.now.get())
+            .finish()
+    }
+}
+
+struct Task {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    woken: Arc<WakeFlag>,
+}
+
+struct WakeFlag(AtomicBool);
+
+impl ArcWake for WakeFlag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+struct Timer {
+    id: i32,
+    deadline: Duration,
+    interval: Option<Duration>,
+    callback: Rc<RefCell<Box<dyn FnMut()>>>,
+}
+
+impl TestExecutor {
+    /// Creates a new executor and installs it on the current thread. It is
+    /// uninstalled when it is dropped.
+    ///
+    /// # Panics
+    /// Panics if another `TestExecutor` is already installed on this thread.
+    #[track_caller]
+    pub fn new() -> Self {
+        let state = Rc::new(ExecutorState::default());
+        EXECUTOR.with(|executor| {
+            let mut executor = executor.borrow_mut();
+            if executor.is_some() {
+                panic!("a TestExecutor is already installed on this thread");
+            }
+            *executor = Some(Rc::clone(&state));
+        });
+        let suspense_clock = crate::scope::set_suspense_clock(mock_sleep);
+        Self {
+            state,
+            suspense_clock,
+        }
+    }
+
+    /// Polls every queued task that has been woken since it was last polled,
+    /// once each, in the order they were spawned. Tasks spawned during the
+    /// step are polled in the next one.
+    ///
+    /// Returns `true` if any task was polled.
+    pub fn step(&self) -> bool {
+        let ready = {
+            let mut tasks = self.state.tasks.borrow_mut();
+            let (ready, waiting): (VecDeque<_>, VecDeque<_>) = tasks
+                .drain(..)
+                .partition(|task| task.woken.0.swap(false, Ordering::SeqCst));
+            *tasks = waiting;
+            ready
+        };
+        let progressed = !ready.is_empty();
+
+        for mut task in ready {
+            let waker = waker(Arc::clone(&task.woken));
+            let mut context = Context::from_waker(&waker);
+            if task.future.as_mut().poll(&mut context).is_pending() {
+                self.state.tasks.borrow_mut().push_back(task);
+            }
+        }
+
+        progressed
+    }
+
+    /// Calls [`step`](TestExecutor::step) until no queued task can make
+    /// progress without something else happening first, like a timer firing
+    /// or a value being sent on a channel.
+    pub fn run_until_stalled(&self) {
+        while self.step() {}
+    }
+
+    /// The number of tasks that have been spawned but have not yet completed.
+    pub fn pending_tasks(&self) -> usize {
+        self.state.tasks.borrow().len()
+    }
+
+    /// The number of timers and intervals that are waiting to fire.
+    pub fn pending_timers(&self) -> usize {
+        self.state.timers.borrow().len()
+    }
+
+    /// The time on the mock clock, measured from when the executor was
+    /// created.
+    pub fn now(&self) -> Duration {
+        self.state.now.get()
+    }
+
+    /// Moves the mock clock forward by `duration`, firing every timer that
+    /// falls due in order and running tasks until they stall after each one.
+    pub fn advance_time(&self, duration: Duration) {
+        let target = self.state.now.get() + duration;
+        self.run_until_stalled();
+        while let Some(timer) = self.next_timer_before(target) {
+            self.state.now.set(timer.deadline);
+            if let Some(interval) = timer.interval {
+                self.state.timers.borrow_mut().push(Timer {
+                    id: timer.id,
+                    deadline: timer.deadline + interval.max(MIN_INTERVAL),
+                    interval: timer.interval,
+                    callback: Rc::clone(&timer.callback),
+                });
+            }
+            (timer.callback.borrow_mut())();
+            self.run_until_stalled();
+        }
+        self.state.now.set(target);
+    }
+
+    fn next_timer_before(&self, target: Duration) -> Option<Timer> {
+        let mut timers = self.state.timers.borrow_mut();
+        // earliest deadline first; timers with the same deadline fire in the
+        // order they were set
+        let idx = timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= target)
+            .min_by_key(|(_, timer)| (timer.deadline, timer.id))
+            .map(|(idx, _)| idx)?;
+        Some(timers.remove(idx))
+    }
+
+    /// Queues the future on the current thread's executor, or returns it if
+    /// there is none.
+    pub(crate) fn try_spawn<F>(fut: F) -> Result<(), F>
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        match current() {
+            Some(state) => {
+                state.tasks.borrow_mut().push_back(Task {
+                    future: Box::pin(fut),
+                    woken: Arc::new(WakeFlag(AtomicBool::new(true))),
+                });
+                Ok(())
+            }
+            None => Err(fut),
+        }
+    }
+
+    /// Whether a `TestExecutor` is installed on the current thread.
+    pub fn is_installed() -> bool {
+        EXECUTOR.with(|executor| executor.borrow().is_some())
+    }
+
+    /// Sets a timer on the mock clock of the current thread's executor,
+    /// returning its ID. With an `interval`, the timer fires repeatedly until
+    /// it is cleared.
+    ///
+    /// # Panics
+    /// Panics if there is no executor installed on the current thread.
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn set_timer(
+        callback: Box<dyn FnMut()>,
+        delay: Duration,
+        interval: Option<Duration>,
+    ) -> i32 {
+        let state = current().expect("no TestExecutor installed");
+        let id = state.next_timer_id.get() + 1;
+        state.next_timer_id.set(id);
+        state.timers.borrow_mut().push(Timer {
+            id,
+            deadline: state.now.get() + delay,
+            interval,
+            callback: Rc::new(RefCell::new(callback)),
+        });
+        id
+    }
+
+    /// Clears a timer set with [`set_timer`](TestExecutor::set_timer).
+    /// Returns `false` if there is no executor on the current thread.
+    #[doc(hidden)]
+    pub fn clear_timer(id: i32) -> bool {
+        match current() {
+            Some(state) => {
+                state.timers.borrow_mut().retain(|timer| timer.id != id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// like browsers, don't let a zero-length interval fire forever at one instant
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+impl Default for TestExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestExecutor {
+    fn drop(&mut self) {
+        EXECUTOR.with(|executor| {
+            let mut executor = executor.borrow_mut();
+            if matches!(&*executor, Some(state) if Rc::ptr_eq(state, &self.state))
+            {
+                *executor = None;
+                crate::scope::set_suspense_clock(self.suspense_clock);
+            }
+        });
+    }
+}
+
+// resolves once the mock clock has been advanced past `duration`
+fn mock_sleep(duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    let mut tx = Some(tx);
+    TestExecutor::set_timer(
+        Box::new(move || {
+            if let Some(tx) = tx.take() {
+                _ = tx.send(());
+            }
+        }),
+        duration,
+        None,
+    );
+    Box::pin(async move {
+        _ = rx.await;
+    })
+}
+
+fn current() -> Option<Rc<ExecutorState>> {
+    EXECUTOR.with(|executor| executor.borrow().clone())
+}
@@ -1,6 +1,9 @@
 #![forbid(unsafe_code)]
 use crate::{runtime::PinnedFuture, suspense::StreamChunk, ResourceId};
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine as _};
 use cfg_if::cfg_if;
+use ciborium::value::Value;
+use slotmap::{Key, KeyData};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 pub struct SharedContext {
@@ -59,9 +62,21 @@ impl Default for SharedContext {
                 )
                 .unwrap_or(wasm_bindgen::JsValue::NULL);
 
-                let resolved_resources =
+                let mut resolved_resources: HashMap<ResourceId, String> =
                     serde_wasm_bindgen::from_value(resolved_resources).unwrap_or_default();
 
+                // resources sent in one binary payload, rather than a script each
+                if let Some(payload) = web_sys::window()
+                    .unwrap()
+                    .document()
+                    .and_then(|document| document.get_element_by_id(RESOURCE_PAYLOAD_ID))
+                    .and_then(|payload| payload.text_content())
+                {
+                    resolved_resources.extend(
+                        decode_resource_payload(payload.trim()).unwrap_or_default(),
+                    );
+                }
+
                 Self {
                     events: Default::default(),
                     pending_resources,
@@ -79,3 +94,55 @@ impl Default for SharedContext {
         }
     }
 }
+
+/// The `id` of the element that holds the data of resolved resources, when
+/// it is sent as one payload created by [`encode_resource_payload`].
+pub const RESOURCE_PAYLOAD_ID: &str = "__LEPTOS_RESOURCE_PAYLOAD";
+
+/// Packs the serialized data of resolved [`Resource`](crate::Resource)s into
+/// one payload, which the client reads from the element with the id
+/// [`RESOURCE_PAYLOAD_ID`] when it starts to hydrate.
+///
+/// The payload is a base64-encoded CBOR array of `[id, value]` pairs. Data
+/// that is valid JSON, as the default serializers produce, is stored as the
+/// CBOR value it describes, and any other data as a byte string. Because
+/// base64 never needs to be escaped, the payload can't break out of the
+/// element it is in.
+pub fn encode_resource_payload<'a>(
+    resources: impl IntoIterator<Item = (ResourceId, &'a str)>,
+) -> String {
+    let resources = resources
+        .into_iter()
+        .map(|(id, data)| {
+            // JSON never becomes a byte string, so those can hold other data
+            let value = serde_json::from_str::<Value>(data)
+                .unwrap_or_else(|_| Value::Bytes(data.as_bytes().to_vec()));
+            (id.data().as_ffi(), value)
+        })
+        .collect::<Vec<_>>();
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&resources, &mut bytes)
+        .expect("serializing CBOR into a Vec cannot fail");
+    STANDARD_NO_PAD.encode(bytes)
+}
+
+/// Unpacks a payload created by [`encode_resource_payload`], turning each
+/// value back into the data it was created from. Returns `None` if the
+/// payload is not valid.
+pub fn decode_resource_payload(
+    payload: &str,
+) -> Option<Vec<(ResourceId, String)>> {
+    let bytes = STANDARD_NO_PAD.decode(payload).ok()?;
+    let resources: Vec<(u64, Value)> =
+        ciborium::de::from_reader(bytes.as_slice()).ok()?;
+    resources
+        .into_iter()
+        .map(|(id, value)| {
+            let data = match value {
+                Value::Bytes(bytes) => String::from_utf8(bytes).ok()?,
+                value => serde_json::to_string(&value).ok()?,
+            };
+            Some((ResourceId::from(KeyData::from_ffi(id)), data))
+        })
+        .collect()
+}
@@ -72,14 +72,25 @@ extern crate tracing;
 
 #[macro_use]
 mod signal;
+pub mod bench;
 mod context;
 #[macro_use]
 mod diagnostics;
+mod devtools;
 mod effect;
+mod error_hook;
+#[cfg(not(all(
+    target_arch = "wasm32",
+    any(feature = "csr", feature = "hydrate")
+)))]
+mod executor;
 mod hydration;
+mod loading;
 mod memo;
 mod node;
+mod paginated;
 mod resource;
+mod retry;
 mod runtime;
 mod scope;
 mod selector;
@@ -90,16 +101,33 @@ mod slice;
 mod spawn;
 mod spawn_microtask;
 mod stored_value;
+pub mod strict;
 pub mod suspense;
+#[cfg(feature = "wasm-threads")]
+mod threads;
+mod transition;
 mod trigger;
 
 pub use context::*;
+pub use devtools::*;
 pub use diagnostics::SpecialNonReactiveZone;
 pub use effect::*;
+pub use error_hook::*;
+#[cfg(not(all(
+    target_arch = "wasm32",
+    any(feature = "csr", feature = "hydrate")
+)))]
+pub use executor::*;
+pub use hydration::{
+    decode_resource_payload, encode_resource_payload, RESOURCE_PAYLOAD_ID,
+};
+pub use loading::*;
 pub use memo::*;
+pub use paginated::*;
 pub use resource::*;
+pub use retry::*;
 use runtime::*;
-pub use runtime::{create_runtime, RuntimeId};
+pub use runtime::{create_runtime, RuntimeId, DEFAULT_MAX_EFFECT_DEPTH};
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
@@ -110,7 +138,11 @@ pub use slice::*;
 pub use spawn::*;
 pub use spawn_microtask::*;
 pub use stored_value::*;
+pub use strict::{is_strict_mode, set_strict_mode};
 pub use suspense::SuspenseContext;
+#[cfg(feature = "wasm-threads")]
+pub use threads::*;
+pub use transition::*;
 pub use trigger::*;
 
 mod macros {
@@ -0,0 +1,83 @@
+#![forbid(unsafe_code)]
+use crate::{
+    runtime::{with_runtime, RuntimeId},
+    ReadSignal, Scope, SignalUpdate, WriteSignal,
+};
+
+/// Returns a signal with the number of resources and actions that are loading
+/// anywhere in the app, which can be used to show a global progress bar.
+///
+/// Every time a resource starts to fetch its data, or an action is
+/// dispatched, the count goes up by one, and it goes down by one when that
+/// fetch or action finishes. Other `async` work can be counted with a
+/// [LoadingGuard].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let loading = use_global_loading(cx);
+/// let show_progress_bar = move || loading.get() > 0;
+/// assert!(!show_progress_bar());
+///
+/// let guard = LoadingGuard::new(cx);
+/// assert!(show_progress_bar());
+/// drop(guard);
+/// assert!(!show_progress_bar());
+/// # }).dispose();
+/// ```
+pub fn use_global_loading(cx: Scope) -> ReadSignal<usize> {
+    global_loading(cx.runtime)
+        .expect(
+            "tried to call use_global_loading() in a runtime that has been \
+             disposed",
+        )
+        .0
+}
+
+/// Counts as one loading task in [use_global_loading] until it is dropped.
+///
+/// Resources and actions use this while they wait for their `Future`s. It can
+/// also be held over other `async` work that should show up as loading.
+#[must_use = "the task stops counting as loading as soon as the guard is \
+              dropped"]
+#[derive(Debug)]
+pub struct LoadingGuard(RuntimeId);
+
+impl LoadingGuard {
+    /// Increments the global loading count of the runtime `cx` belongs to.
+    pub fn new(cx: Scope) -> Self {
+        Self::for_runtime(cx.runtime)
+    }
+
+    pub(crate) fn for_runtime(runtime: RuntimeId) -> Self {
+        if let Some((_, set_count)) = global_loading(runtime) {
+            set_count.update(|n| *n += 1);
+        }
+        Self(runtime)
+    }
+}
+
+impl Drop for LoadingGuard {
+    fn drop(&mut self) {
+        // the runtime may have been disposed in the meantime
+        if let Some((_, set_count)) = global_loading(self.0) {
+            _ = set_count.try_update(|n| *n = n.saturating_sub(1));
+        }
+    }
+}
+
+// The signal is created the first time it is needed, and lives as long as
+// the runtime does.
+fn global_loading(
+    runtime_id: RuntimeId,
+) -> Option<(ReadSignal<usize>, WriteSignal<usize>)> {
+    with_runtime(runtime_id, |runtime| match runtime.global_loading.get() {
+        Some(signal) => signal,
+        None => {
+            let signal = runtime_id.create_signal(0);
+            runtime.global_loading.set(Some(signal));
+            signal
+        }
+    })
+    .ok()
+}
@@ -20,6 +20,10 @@ use std::{any::Any, cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
 /// create a derived signal. But if the derivation calculation is expensive, you should
 /// create a memo.
 ///
+/// Reading a memo that is up to date is `O(1)`. When one of its sources changes, the memo
+/// re-runs its function at most once, and only notifies its own subscribers if the new value
+/// is different from the old one.
+///
 /// As with [`create_effect`](crate::create_effect), the argument to the memo function is the previous value,
 /// i.e., the current value of the memo, which will be `None` for the initial calculation.
 ///
@@ -80,11 +84,98 @@ pub fn create_memo<T>(
 where
     T: PartialEq + 'static,
 {
-    let memo = cx.runtime.create_memo(f);
+    // like effects, memos run with the scope that owns them as the current
+    // scope
+    let memo = cx
+        .runtime
+        .create_memo(move |value| cx.with_current(|| f(value)));
+    cx.push_scope_property(ScopeProperty::Effect(memo.id));
+    memo
+}
+
+/// Works exactly as [`create_memo`], but uses the given function rather than
+/// [`PartialEq`] to decide whether the new value is the same as the previous
+/// one. Dependents are only notified when `compare` returns `false`.
+///
+/// This is useful when domain-specific equality is cheaper or more useful
+/// than structural equality, for example comparing floats within some epsilon
+/// or [`Rc`]s by pointer.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (value, set_value) = create_signal(cx, 1.0_f64);
+///
+/// // only notifies when the value changes by at least 0.1
+/// let rounded = create_memo_with_compare(
+///     cx,
+///     move |_| value(),
+///     |prev, next| (prev - next).abs() < 0.1,
+/// );
+/// assert_eq!(rounded(), 1.0);
+///
+/// set_value(1.05);
+/// assert_eq!(rounded(), 1.0);
+///
+/// set_value(1.5);
+/// assert_eq!(rounded(), 1.5);
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+#[inline(always)]
+pub fn create_memo_with_compare<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+    compare: impl Fn(&T, &T) -> bool + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    let memo = cx.runtime.create_memo_with_compare(
+        move |value| cx.with_current(|| f(value)),
+        compare,
+    );
     cx.push_scope_property(ScopeProperty::Effect(memo.id));
     memo
 }
 
+/// Works exactly as [`create_memo`], but never compares the new value with the
+/// previous one, notifying its dependents every time it reruns. This allows
+/// memoizing types that do not implement [`PartialEq`].
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>()
+        )
+    )
+)]
+#[track_caller]
+#[inline(always)]
+pub fn create_memo_always_notify<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    create_memo_with_compare(cx, f, |_, _| false)
+}
+
 /// An efficient derived reactive value based on other reactive values.
 ///
 /// Unlike a "derived signal," a memo comes with two guarantees:
@@ -245,7 +336,11 @@ impl<T> SignalWithUntracked<T> for Memo<T> {
         // Unwrapping here is fine for the same reasons as <Memo as
         // UntrackedSignal>::get_untracked
         with_runtime(self.runtime, |runtime| {
-            match self.id.try_with_no_subscription(runtime, |v: &T| f(v)) {
+            // memo is stored as Option<T>, but will always have T available
+            // once it has been read
+            match self.id.try_with_no_subscription(runtime, |v: &Option<T>| {
+                f(v.as_ref().unwrap())
+            }) {
                 Ok(t) => t,
                 Err(_) => panic_getting_dead_memo(
                     #[cfg(any(debug_assertions, feature = "ssr"))]
@@ -272,7 +367,11 @@ impl<T> SignalWithUntracked<T> for Memo<T> {
     #[inline]
     fn try_with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
         with_runtime(self.runtime, |runtime| {
-            self.id.try_with_no_subscription(runtime, |v: &T| f(v)).ok()
+            self.id
+                .try_with_no_subscription(runtime, |v: &Option<T>| {
+                    f(v.as_ref().unwrap())
+                })
+                .ok()
         })
         .ok()
         .flatten()
@@ -432,21 +531,24 @@ impl<T> SignalDispose for Memo<T> {
 
 impl_get_fn_traits![Memo];
 
-pub(crate) struct MemoState<T, F>
+pub(crate) struct MemoState<T, F, C>
 where
-    T: PartialEq + 'static,
+    T: 'static,
     F: Fn(Option<&T>) -> T,
+    C: Fn(&T, &T) -> bool,
 {
     pub f: F,
+    pub compare: C,
     pub t: PhantomData<T>,
     #[cfg(any(debug_assertions, feature = "ssr"))]
     pub(crate) defined_at: &'static std::panic::Location<'static>,
 }
 
-impl<T, F> AnyComputation for MemoState<T, F>
+impl<T, F, C> AnyComputation for MemoState<T, F, C>
 where
-    T: PartialEq + 'static,
+    T: 'static,
     F: Fn(Option<&T>) -> T,
+    C: Fn(&T, &T) -> bool,
 {
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -469,7 +571,10 @@ where
 
             // run the effect
             let new_value = (self.f)(curr_value.as_ref());
-            let is_different = curr_value.as_ref() != Some(&new_value);
+            let is_different = match curr_value {
+                Some(curr_value) => !(self.compare)(curr_value, &new_value),
+                None => true,
+            };
             (new_value, is_different)
         };
         if is_different {
@@ -482,6 +587,17 @@ where
 
         is_different
     }
+
+    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        #[cfg(any(debug_assertions, feature = "ssr"))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, feature = "ssr")))]
+        {
+            None
+        }
+    }
 }
 
 #[cold]
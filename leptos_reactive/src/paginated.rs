@@ -0,0 +1,244 @@
+#![forbid(unsafe_code)]
+use crate::{
+    create_effect, create_memo, create_rw_signal, spawn_local, store_value,
+    LoadingGuard, ReadSignal, RwSignal, Scope, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith, SignalWithUntracked, StoredValue,
+};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::Rc,
+};
+
+/// One page of items loaded by the fetcher of a [PaginatedResource].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<C, T> {
+    /// The items on this page, in order.
+    pub items: Vec<T>,
+    /// The cursor passed to the fetcher to load the next page, or `None` if
+    /// this is the last page.
+    pub next: Option<C>,
+}
+
+/// Loads a list one page at a time, like a feed with infinite scrolling,
+/// keeping every page loaded so far in one reactive list.
+///
+/// Created with [create_paginated_resource].
+pub struct PaginatedResource<T, E>(StoredValue<PaginatedState<T, E>>)
+where
+    T: 'static,
+    E: 'static;
+
+struct PaginatedState<T, E>
+where
+    T: 'static,
+    E: 'static,
+{
+    items: RwSignal<Vec<T>>,
+    has_more: RwSignal<bool>,
+    loading: RwSignal<bool>,
+    error: RwSignal<Option<E>>,
+    load_next: Rc<dyn Fn()>,
+    reset: Rc<dyn Fn()>,
+}
+
+impl<T, E> PaginatedResource<T, E>
+where
+    T: 'static,
+    E: 'static,
+{
+    /// Every item loaded so far, from all pages in order.
+    pub fn items(&self) -> ReadSignal<Vec<T>> {
+        self.0.with_value(|state| state.items.read_only())
+    }
+
+    /// `false` once the last page has been loaded.
+    pub fn has_more(&self) -> ReadSignal<bool> {
+        self.0.with_value(|state| state.has_more.read_only())
+    }
+
+    /// `true` while a page is being loaded.
+    pub fn loading(&self) -> ReadSignal<bool> {
+        self.0.with_value(|state| state.loading.read_only())
+    }
+
+    /// The error returned by the fetcher the last time a page failed to
+    /// load, until the next page is loaded.
+    pub fn error(&self) -> ReadSignal<Option<E>> {
+        self.0.with_value(|state| state.error.read_only())
+    }
+
+    /// Loads the next page and appends its items to [items](Self::items).
+    ///
+    /// Does nothing while a page is already loading or once the last page has
+    /// been loaded. After an error, calling this again retries the same page.
+    pub fn load_next(&self) {
+        if let Some(load_next) =
+            self.0.try_with_value(|state| Rc::clone(&state.load_next))
+        {
+            load_next();
+        }
+    }
+
+    /// Clears the items loaded so far and loads the first page again.
+    pub fn reset(&self) {
+        if let Some(reset) =
+            self.0.try_with_value(|state| Rc::clone(&state.reset))
+        {
+            reset();
+        }
+    }
+}
+
+impl<T, E> Clone for PaginatedResource<T, E>
+where
+    T: 'static,
+    E: 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E> Copy for PaginatedResource<T, E>
+where
+    T: 'static,
+    E: 'static,
+{
+}
+
+/// Creates a [PaginatedResource], which loads a list one page at a time.
+///
+/// The `fetcher` is given the current value of `source` and the cursor of the
+/// page to load: `None` for the first page, and then the [`next`](Page::next)
+/// cursor of the previous page. The first page is loaded when the resource is
+/// created, and the following ones each time
+/// [load_next](PaginatedResource::load_next) is called. Whenever the `source`
+/// changes, the pages loaded so far are cleared and the first page is loaded
+/// again, e.g., for a new search query.
+///
+/// Pages are loaded in the browser, like a [local resource](crate::create_local_resource),
+/// rather than on the server. To load the next page when the user scrolls to
+/// the end of the list, call `load_next` from `on_visible` in `leptos_dom`
+/// with an element after the last item.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn search_posts(
+///     query: String,
+///     page: Option<usize>,
+/// ) -> Result<Page<usize, String>, String> {
+///     let page = page.unwrap_or(0);
+///     Ok(Page {
+///         items: vec![format!("{query} #{page}")],
+///         next: (page < 10).then_some(page + 1),
+///     })
+/// }
+///
+/// let (query, set_query) = create_signal(cx, "rust".to_string());
+/// # if false {
+/// let posts = create_paginated_resource(cx, query, search_posts);
+///
+/// // after the first page has loaded
+/// posts.load_next();
+/// // after the second page has loaded
+/// assert_eq!(posts.items().get_untracked().len(), 2);
+/// assert!(posts.has_more().get_untracked());
+///
+/// // starts again from the first page
+/// set_query("leptos".to_string());
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_paginated_resource<S, C, T, E, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S, Option<C>) -> Fu + 'static,
+) -> PaginatedResource<T, E>
+where
+    S: PartialEq + Clone + 'static,
+    C: Clone + 'static,
+    T: 'static,
+    E: 'static,
+    Fu: Future<Output = Result<Page<C, T>, E>> + 'static,
+{
+    let items = create_rw_signal(cx, Vec::new());
+    let has_more = create_rw_signal(cx, true);
+    let loading = create_rw_signal(cx, false);
+    let error = create_rw_signal(cx, None);
+    let source = create_memo(cx, move |_| source());
+
+    let cursor = Rc::new(RefCell::new(None::<C>));
+    // bumped on every reset, so that pages requested before it are ignored
+    let generation = Rc::new(Cell::new(0_usize));
+
+    let load_next = Rc::new({
+        let cursor = Rc::clone(&cursor);
+        let generation = Rc::clone(&generation);
+        move || {
+            if loading.get_untracked() || !has_more.get_untracked() {
+                return;
+            }
+            loading.set(true);
+            let fut = fetcher(
+                source.with_untracked(S::clone),
+                cursor.borrow().clone(),
+            );
+            let cursor = Rc::clone(&cursor);
+            let generation = Rc::clone(&generation);
+            let requested = generation.get();
+            let loading_guard = LoadingGuard::new(cx);
+            spawn_local(async move {
+                let page = fut.await;
+                drop(loading_guard);
+                if generation.get() != requested {
+                    return;
+                }
+                match page {
+                    Ok(page) => {
+                        _ = has_more.try_set(page.next.is_some());
+                        *cursor.borrow_mut() = page.next;
+                        _ = items.try_update(|items| items.extend(page.items));
+                        _ = error.try_set(None);
+                    }
+                    Err(e) => _ = error.try_set(Some(e)),
+                }
+                _ = loading.try_set(false);
+            });
+        }
+    });
+
+    let reset = Rc::new({
+        let load_next = Rc::clone(&load_next);
+        move || {
+            generation.set(generation.get() + 1);
+            *cursor.borrow_mut() = None;
+            items.set(Vec::new());
+            has_more.set(true);
+            loading.set(false);
+            error.set(None);
+            load_next();
+        }
+    });
+
+    create_effect(cx, {
+        let reset = Rc::clone(&reset);
+        move |_| {
+            source.track();
+            cx.untrack(|| reset());
+        }
+    });
+
+    PaginatedResource(store_value(
+        cx,
+        PaginatedState {
+            items,
+            has_more,
+            loading,
+            error,
+            load_next,
+            reset,
+        },
+    ))
+}
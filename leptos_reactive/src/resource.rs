@@ -3,13 +3,15 @@ use crate::{
     create_effect, create_isomorphic_effect, create_memo, create_signal,
     queue_microtask,
     runtime::{with_runtime, RuntimeId},
-    serialization::Serializable,
+    serialization::{ResourceCodec, Serializable},
     spawn::spawn_local,
-    use_context, Memo, ReadSignal, Scope, ScopeProperty, SignalGetUntracked,
-    SignalSet, SignalUpdate, SignalWith, SuspenseContext, WriteSignal,
+    use_context, LoadingGuard, Memo, ReadSignal, Scope, ScopeProperty,
+    SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked, SuspenseContext, WriteSignal,
 };
 use std::{
     any::Any,
+    borrow::Cow,
     cell::{Cell, RefCell},
     collections::HashSet,
     fmt::Debug,
@@ -129,6 +131,63 @@ where
         fetcher,
         initial_value,
         ResourceSerialization::Serializable,
+        ResourceCodec::default(),
+    )
+}
+
+/// Creates a [`Resource`](crate::Resource) that uses the given
+/// [`ResourceCodec`] to serialize its value on the server and deserialize it
+/// on the client, rather than its [`Serializable`] implementation.
+///
+/// This allows choosing a serialization format for a single resource, or
+/// serializing a type that doesn't implement the traits required by the
+/// serialization crate selected by feature flags.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// let (id, _) = create_signal(cx, 0);
+/// let values = create_resource_with_codec(
+///     cx,
+///     id,
+///     |id| async move { vec![id] },
+///     ResourceCodec::json(),
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_resource_with_codec<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    codec: ResourceCodec<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_resource_helper(
+        cx,
+        source,
+        fetcher,
+        None,
+        ResourceSerialization::Serializable,
+        codec,
     )
 }
 
@@ -175,19 +234,136 @@ where
         fetcher,
         None,
         ResourceSerialization::Blocking,
+        ResourceCodec::default(),
     )
 }
 
+/// Creates a [`Resource`](crate::Resource) whose `fetcher` is also given the
+/// [`Scope`] the resource was created in, so that it can read an HTTP client,
+/// an auth token or a base URL from context with
+/// [`use_context`](crate::use_context) each time it runs, rather than
+/// capturing them when the resource is created.
+///
+/// Otherwise, this is the same as [`create_resource()`].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone)]
+/// struct ApiUrl(String);
+///
+/// async fn fetch_user(base: String, id: u32) -> String {
+///     format!("{base}/users/{id}")
+/// }
+///
+/// provide_context(cx, ApiUrl("https://example.com/api".into()));
+/// let (id, _) = create_signal(cx, 1);
+/// # if false {
+/// let user = create_resource_with_scope(cx, id, |cx, id| {
+///     let ApiUrl(base) = use_context::<ApiUrl>(cx).expect("an API URL");
+///     fetch_user(base, id)
+/// });
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_resource_with_scope<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(Scope, S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_resource(cx, source, move |source| fetcher(cx, source))
+}
+
+/// Creates a [`Resource`](crate::Resource) whose `fetcher` is called again
+/// when it fails, waiting between attempts as described by the
+/// [`RetryPolicy`](crate::RetryPolicy).
+///
+/// The resource stays loading while the fetcher is retried. If every attempt
+/// fails, the resource resolves to the last error, so it can be shown by an
+/// `<ErrorBoundary/>` like any other `Err`.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///     Err(format!("user {id} could not be loaded"))
+/// }
+///
+/// let (id, _) = create_signal(cx, 1);
+/// # if false {
+/// let user = create_resource_with_retry(
+///     cx,
+///     id,
+///     fetch_user,
+///     RetryPolicy::default().max_attempts(5),
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_resource_with_retry<S, T, E, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    policy: crate::RetryPolicy<E>,
+) -> Resource<S, Result<T, E>>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+    Result<T, E>: Serializable,
+    Fu: Future<Output = Result<T, E>> + 'static,
+{
+    let fetcher = Rc::new(fetcher);
+    create_resource(cx, source, move |source| {
+        let fetcher = Rc::clone(&fetcher);
+        let policy = policy.clone();
+        async move { crate::retry(&policy, || fetcher(source.clone())).await }
+    })
+}
+
 fn create_resource_helper<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
     initial_value: Option<T>,
     serializable: ResourceSerialization,
+    codec: ResourceCodec<T>,
 ) -> Resource<S, T>
 where
     S: PartialEq + Clone + 'static,
-    T: Serializable + 'static,
+    T: 'static,
     Fu: Future<Output = T> + 'static,
 {
     let resolved = initial_value.is_some();
@@ -212,7 +388,9 @@ where
         scheduled: Rc::new(Cell::new(false)),
         preempted: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        cx,
         serializable,
+        codec: Some(codec),
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
@@ -298,6 +476,35 @@ where
     create_local_resource_with_initial_value(cx, source, fetcher, initial_value)
 }
 
+/// Creates a _local_ [`Resource`](crate::Resource) whose `fetcher` is also
+/// given the [`Scope`] the resource was created in, as in
+/// [`create_resource_with_scope()`].
+#[cfg_attr(
+    any(debug_assertions, feature="ssr"),
+    instrument(
+        level = "info",
+        skip_all,
+        fields(
+            scope = ?cx.id,
+            ty = %std::any::type_name::<T>(),
+            signal_ty = %std::any::type_name::<S>(),
+        )
+    )
+)]
+#[track_caller]
+pub fn create_local_resource_with_scope<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(Scope, S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_local_resource(cx, source, move |source| fetcher(cx, source))
+}
+
 /// Creates a _local_ [`Resource`](crate::Resource) with the given initial value,
 /// which will only generate and run a [`Future`] using the `fetcher` when the
 /// `source` changes.
@@ -349,7 +556,9 @@ where
         scheduled: Rc::new(Cell::new(false)),
         preempted: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        cx,
         serializable: ResourceSerialization::Local,
+        codec: None,
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
@@ -377,6 +586,46 @@ where
     }
 }
 
+/// Creates a [`Resource`](crate::Resource) whose value is never embedded in
+/// the server-rendered HTML.
+///
+/// During server rendering the `fetcher` does not run: any `<Suspense/>` that
+/// reads the resource renders its `fallback`, and the value is fetched in the
+/// browser once the app has hydrated. Use this for data that should not end
+/// up in the page source, like secrets or user-specific tokens, or that is
+/// too large to be worth serializing, even if it implements [`Serializable`].
+///
+/// This behaves exactly like [`create_local_resource`]; the separate name
+/// documents why the resource is not sent from the server.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if cfg!(not(any(feature = "csr", feature = "hydrate"))) {
+/// async fn fetch_api_key() -> String {
+///     // a value we don't want in the HTML
+///     "secret".to_string()
+/// }
+///
+/// let api_key =
+///     create_resource_non_serializable(cx, || (), |_| fetch_api_key());
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_resource_non_serializable<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_local_resource(cx, source, fetcher)
+}
+
 #[cfg(not(feature = "hydrate"))]
 fn load_resource<S, T>(_cx: Scope, _id: ResourceId, r: Rc<ResourceState<S, T>>)
 where
@@ -394,10 +643,14 @@ where
 fn load_resource<S, T>(cx: Scope, id: ResourceId, r: Rc<ResourceState<S, T>>)
 where
     S: PartialEq + Clone + 'static,
-    T: Serializable + 'static,
+    T: 'static,
 {
     use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
+    let codec = r
+        .codec
+        .expect("serializable resources to have a ResourceCodec");
+
     _ = with_runtime(cx.runtime, |runtime| {
         let mut context = runtime.shared_context.borrow_mut();
         if let Some(data) = context.resolved_resources.remove(&id) {
@@ -406,7 +659,7 @@ where
             context.pending_resources.remove(&id); // no longer pending
             r.resolved.set(true);
 
-            let res = T::de(&data)
+            let res = (codec.de)(&data)
                 .expect_throw("could not deserialize Resource JSON");
 
             r.set_value.update(|n| *n = Some(res));
@@ -425,11 +678,13 @@ where
                 let set_value = r.set_value;
                 let set_loading = r.set_loading;
                 move |res: String| {
-                    let res = T::de(&res)
+                    let res = (codec.de)(&res)
                         .expect_throw("could not deserialize Resource JSON");
                     resolved.set(true);
-                    set_value.update(|n| *n = Some(res));
-                    set_loading.update(|n| *n = false);
+                    // the resource may have been disposed while waiting for
+                    // the server to send its value
+                    _ = set_value.try_update(|n| *n = Some(res));
+                    _ = set_loading.try_update(|n| *n = false);
                 }
             };
             let resolve = wasm_bindgen::closure::Closure::wrap(
@@ -514,6 +769,8 @@ where
     }
 
     /// Returns a signal that indicates whether the resource is currently loading.
+    /// To know whether any resource is loading, use
+    /// [use_global_loading](crate::use_global_loading).
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -543,6 +800,36 @@ where
         });
     }
 
+    /// Tags the resource with a cache key, so that it is refetched whenever
+    /// the key is passed to [invalidate_resources], e.g., by an action that
+    /// invalidates the key after each successful mutation. A resource can
+    /// have several keys, and a key can be shared by many resources.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # async fn load_todos() -> Vec<String> { vec![] }
+    /// # if false {
+    /// let todos =
+    ///     create_resource(cx, || (), |_| load_todos()).with_cache_key("todos");
+    ///
+    /// // after adding a todo, refetches `todos`
+    /// invalidate_resources(cx, "todos");
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn with_cache_key(self, key: impl Into<Cow<'static, str>>) -> Self {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime
+                .resource_keys
+                .borrow_mut()
+                .entry(key.into())
+                .or_default()
+                .push((self.id, Rc::new(move || self.refetch())));
+        });
+        self
+    }
+
     /// Returns a [`Future`] that will resolve when the resource has loaded,
     /// yield its [`ResourceId`] and a JSON string.
     #[cfg(any(feature = "ssr", doc))]
@@ -553,10 +840,7 @@ where
     pub async fn to_serialization_resolver(
         &self,
         cx: Scope,
-    ) -> (ResourceId, String)
-    where
-        T: Serializable,
-    {
+    ) -> (ResourceId, String) {
         with_runtime(self.runtime, |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.to_serialization_resolver(cx, self.id)
@@ -570,6 +854,129 @@ where
     }
 }
 
+/// Reading a resource through the signal traits subscribes the running effect
+/// to it, just like [`Resource::read`] and [`Resource::with`]. Because these
+/// methods don't take a [`Scope`], the resource is registered with the
+/// nearest `<Suspense/>` of the [running scope](Scope::current), or of the
+/// scope the resource was created in if no scope is running.
+impl<S, T> SignalWith<Option<T>> for Resource<S, T>
+where
+    S: Clone,
+{
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::with()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match SignalWith::try_with(self, f) {
+            Some(value) => value,
+            None => panic!(
+                "tried to access a Resource that has already been disposed"
+            ),
+        }
+    }
+
+    #[cfg_attr(
+        debug_assertions,
+        instrument(
+            level = "trace",
+            name = "Resource::try_with()",
+            skip_all,
+            fields(
+                id = ?self.id,
+                defined_at = %self.defined_at,
+                ty = %std::any::type_name::<T>()
+            )
+        )
+    )]
+    #[track_caller]
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        let location = std::panic::Location::caller();
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+                let (has_value, value) =
+                    resource.value.try_with(|n| (n.is_some(), f(n))).ok()?;
+                // like `read(cx)`, register with the nearest `<Suspense/>`,
+                // found from the running scope or the resource's own
+                let cx = Scope::current().unwrap_or(resource.cx);
+                resource.track_suspense(cx, has_value, location);
+                Some(value)
+            })
+        })
+        .ok()
+        .flatten()
+    }
+}
+
+impl<S, T> SignalGet<Option<T>> for Resource<S, T>
+where
+    S: Clone,
+    T: Clone,
+{
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        SignalWith::with(self, Option::clone)
+    }
+
+    #[track_caller]
+    fn try_get(&self) -> Option<Option<T>> {
+        SignalWith::try_with(self, Option::clone)
+    }
+}
+
+impl<S, T> SignalWithUntracked<Option<T>> for Resource<S, T>
+where
+    S: Clone,
+{
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_untracked(f) {
+            Some(value) => value,
+            None => panic!(
+                "tried to access a Resource that has already been disposed"
+            ),
+        }
+    }
+
+    fn try_with_untracked<O>(
+        &self,
+        f: impl FnOnce(&Option<T>) -> O,
+    ) -> Option<O> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+                resource.value.try_with_untracked(f)
+            })
+        })
+        .ok()
+        .flatten()
+    }
+}
+
+impl<S, T> SignalGetUntracked<Option<T>> for Resource<S, T>
+where
+    S: Clone,
+    T: Clone,
+{
+    #[track_caller]
+    fn get_untracked(&self) -> Option<T> {
+        self.with_untracked(Option::clone)
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<T>> {
+        self.try_with_untracked(Option::clone)
+    }
+}
+
 impl<S, T> SignalUpdate<Option<T>> for Resource<S, T> {
     #[cfg_attr(
         debug_assertions,
@@ -722,6 +1129,32 @@ where
     pub(crate) defined_at: &'static std::panic::Location<'static>,
 }
 
+/// Refetches every [`Resource`] tagged with the cache `key` by
+/// [`Resource::with_cache_key`].
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    instrument(level = "trace", skip_all,)
+)]
+pub fn invalidate_resources(cx: Scope, key: &str) {
+    let refetches = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .resource_keys
+            .borrow()
+            .get(key)
+            .map(|tagged| {
+                tagged
+                    .iter()
+                    .map(|(_, refetch)| Rc::clone(refetch))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    })
+    .unwrap_or_default();
+    for refetch in refetches {
+        refetch();
+    }
+}
+
 // Resources
 slotmap::new_key_type! {
     /// Unique ID assigned to a [`Resource`](crate::Resource).
@@ -773,7 +1206,10 @@ where
     scheduled: Rc<Cell<bool>>,
     preempted: Rc<Cell<bool>>,
     suspense_contexts: Rc<RefCell<HashSet<SuspenseContext>>>,
+    // the scope the resource was created in, for reads without a `cx`
+    cx: Scope,
     serializable: ResourceSerialization,
+    codec: Option<ResourceCodec<T>>,
 }
 
 /// Whether and how the resource can be serialized.
@@ -820,16 +1256,26 @@ where
         f: impl FnOnce(&T) -> U,
         location: &'static Location<'static>,
     ) -> Option<U> {
-        let suspense_cx = use_context::<SuspenseContext>(cx);
-
         let v = self
             .value
             .try_with(|n| n.as_ref().map(|n| Some(f(n))))
             .ok()?
             .flatten();
 
+        self.track_suspense(cx, v.is_some(), location);
+        v
+    }
+
+    /// Registers this resource with the nearest `<Suspense/>`, if any.
+    #[track_caller]
+    fn track_suspense(
+        &self,
+        cx: Scope,
+        has_value: bool,
+        location: &'static Location<'static>,
+    ) {
+        let suspense_cx = use_context::<SuspenseContext>(cx);
         let suspense_contexts = self.suspense_contexts.clone();
-        let has_value = v.is_some();
 
         let serializable = self.serializable;
         if let Some(suspense_cx) = &suspense_cx {
@@ -885,7 +1331,6 @@ where
         };
 
         create_isomorphic_effect(cx, increment);
-        v
     }
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -933,6 +1378,22 @@ where
                 }
             }
 
+            // the fetch runs in its own span, within the span that was
+            // current when it started (e.g., the request being rendered)
+            #[cfg(any(debug_assertions, feature = "ssr"))]
+            let fut = {
+                use tracing::Instrument;
+                fut.instrument(tracing::info_span!(
+                    "resource fetch",
+                    ty = %std::any::type_name::<T>(),
+                    refetching
+                ))
+            };
+
+            // counts as loading in `use_global_loading` until it's dropped
+            let loading_guard =
+                LoadingGuard::for_runtime(self.set_loading.runtime);
+
             // run the Future
             let serializable = self.serializable;
             spawn_local({
@@ -944,11 +1405,13 @@ where
                     let res = fut.await;
                     resolved.set(true);
 
+                    // the resource may have been disposed while the Future
+                    // was running, in which case there's nothing to update
                     if !preempted.get() {
-                        set_value.update(|n| *n = Some(res));
+                        _ = set_value.try_update(|n| *n = Some(res));
                     }
 
-                    set_loading.update(|n| *n = false);
+                    _ = set_loading.try_update(|n| *n = false);
 
                     if !preempted.get() {
                         for suspense_context in
@@ -960,6 +1423,7 @@ where
                         }
                     }
                     preempted.set(false);
+                    drop(loading_guard);
                 }
             })
         });
@@ -973,11 +1437,12 @@ where
         cx: Scope,
         id: ResourceId,
     ) -> std::pin::Pin<Box<dyn futures::Future<Output = (ResourceId, String)>>>
-    where
-        T: Serializable,
     {
         use futures::StreamExt;
 
+        let codec = self
+            .codec
+            .expect("serializable resources to have a ResourceCodec");
         let (tx, mut rx) = futures::channel::mpsc::channel(1);
         let value = self.value;
         create_isomorphic_effect(cx, move |_| {
@@ -987,7 +1452,8 @@ where
                     if let Some(value) = value.as_ref() {
                         tx.try_send((
                             id,
-                            value.ser().expect("could not serialize Resource"),
+                            (codec.ser)(value)
+                                .expect("could not serialize Resource"),
                         ))
                         .expect(
                             "failed while trying to write to Resource \
@@ -1023,7 +1489,6 @@ pub(crate) trait SerializableResource {
 impl<S, T> SerializableResource for ResourceState<S, T>
 where
     S: Clone,
-    T: Serializable,
 {
     fn as_any(&self) -> &dyn Any {
         self
@@ -0,0 +1,201 @@
+#![forbid(unsafe_code)]
+use cfg_if::cfg_if;
+use std::{fmt, future::Future, rc::Rc, time::Duration};
+
+/// Describes how often, and how long to wait before, a failed `async` call is
+/// tried again. Used by [`retry`], [`create_resource_with_retry`](crate::create_resource_with_retry),
+/// and server actions created with a retry policy.
+///
+/// The delay before each retry starts at `initial_delay` and is multiplied by
+/// `multiplier` after every attempt, up to `max_delay`. By default, a call is
+/// tried 3 times, waiting 200ms and then 400ms, and every error is retried.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// #[derive(Debug)]
+/// enum FetchError {
+///     Offline,
+///     NotFound,
+/// }
+///
+/// let policy = RetryPolicy::default()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(100), 2.0)
+///     .retry_on(|e: &FetchError| matches!(e, FetchError::Offline));
+///
+/// assert_eq!(policy.delay(1), Duration::from_millis(100));
+/// assert_eq!(policy.delay(3), Duration::from_millis(400));
+/// assert!(policy.should_retry(&FetchError::Offline));
+/// assert!(!policy.should_retry(&FetchError::NotFound));
+/// ```
+pub struct RetryPolicy<E> {
+    max_attempts: u32,
+    initial_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    #[allow(clippy::type_complexity)]
+    retry_on: Option<Rc<dyn Fn(&E) -> bool>>,
+}
+
+impl<E> RetryPolicy<E> {
+    /// A policy that tries the call once and never retries it.
+    pub fn never() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets how many times the call is made in total, including the first
+    /// attempt. Values less than `1` are treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry, and the factor it grows by
+    /// after each attempt.
+    pub fn backoff(mut self, initial_delay: Duration, multiplier: f64) -> Self {
+        self.initial_delay = initial_delay;
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Sets the longest delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Only retries errors for which `retry_on` returns `true`. Other errors
+    /// are returned immediately.
+    pub fn retry_on(mut self, retry_on: impl Fn(&E) -> bool + 'static) -> Self {
+        self.retry_on = Some(Rc::new(retry_on));
+        self
+    }
+
+    /// How many times the call is made in total.
+    pub fn attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// How long to wait after the given failed attempt, counting from `1`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        Duration::try_from_secs_f64(self.initial_delay.as_secs_f64() * factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Whether the policy allows `error` to be retried.
+    pub fn should_retry(&self, error: &E) -> bool {
+        self.retry_on.as_ref().map(|f| f(error)).unwrap_or(true)
+    }
+}
+
+impl<E> Default for RetryPolicy<E> {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(200),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(10),
+            retry_on: None,
+        }
+    }
+}
+
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            max_attempts: self.max_attempts,
+            initial_delay: self.initial_delay,
+            multiplier: self.multiplier,
+            max_delay: self.max_delay,
+            retry_on: self.retry_on.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_delay", &self.initial_delay)
+            .field("multiplier", &self.multiplier)
+            .field("max_delay", &self.max_delay)
+            .field("retry_on", &self.retry_on.is_some())
+            .finish()
+    }
+}
+
+/// Calls `f` until the [`Future`] it returns resolves to `Ok`, waiting
+/// between attempts as described by the `policy`. Once the attempts are used
+/// up, or an error the policy does not retry is returned, that error is
+/// returned.
+///
+/// The wait uses `setTimeout` in the browser, Tokio on the server, and the
+/// mock clock of a [`TestExecutor`](crate::TestExecutor) if one is installed.
+pub async fn retry<T, E, F, Fu>(policy: &RetryPolicy<E>, f: F) -> Result<T, E>
+where
+    F: Fn() -> Fu,
+    Fu: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e)
+                if attempt < policy.max_attempts && policy.should_retry(&e) =>
+            {
+                sleep(policy.delay(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+async fn sleep(duration: Duration) {
+    cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let (tx, rx) = futures::channel::oneshot::channel::<()>();
+            let callback = Closure::once_into_js(move || {
+                _ = tx.send(());
+            });
+            _ = web_sys::window()
+                .expect("no window")
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    callback.unchecked_ref(),
+                    duration.as_millis() as i32,
+                );
+            _ = rx.await;
+        } else {
+            if crate::TestExecutor::is_installed() {
+                let (tx, rx) = futures::channel::oneshot::channel::<()>();
+                let mut tx = Some(tx);
+                crate::TestExecutor::set_timer(
+                    Box::new(move || {
+                        if let Some(tx) = tx.take() {
+                            _ = tx.send(());
+                        }
+                    }),
+                    duration,
+                    None,
+                );
+                _ = rx.await;
+                return;
+            }
+
+            cfg_if! {
+                if #[cfg(feature = "ssr")] {
+                    tokio::time::sleep(duration).await;
+                } else {
+                    // without a browser or an async runtime, futures are
+                    // run with `block_on`, so blocking the thread is fine
+                    std::thread::sleep(duration);
+                }
+            }
+        }
+    }
+}
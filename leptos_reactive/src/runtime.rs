@@ -1,20 +1,24 @@
 #![forbid(unsafe_code)]
+#[cfg(debug_assertions)]
+use crate::error_hook::{with_error_source, ErrorSource};
 use crate::{
+    devtools::{NodeDebugInfo, NodeSnapshot, NodeUpdateHookId},
     hydration::SharedContext,
     node::{NodeId, ReactiveNode, ReactiveNodeState, ReactiveNodeType},
-    AnyComputation, AnyResource, Effect, Memo, MemoState, ReadSignal,
-    ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer, ScopeId,
-    ScopeProperty, SerializableResource, StoredValueId, Trigger,
-    UnserializableResource, WriteSignal,
+    AnyComputation, AnyResource, DeferredEffect, Effect, EffectTiming, Memo,
+    MemoState, ReadSignal, ResourceId, ResourceState, RwSignal, Scope,
+    ScopeDisposer, ScopeId, ScopeProperty, SerializableResource, StoredValueId,
+    Trigger, UnserializableResource, WriteSignal,
 };
 use cfg_if::cfg_if;
 use core::hash::BuildHasherDefault;
 use futures::stream::FuturesUnordered;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use rustc_hash::{FxHashMap, FxHasher};
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
+    borrow::Cow,
     cell::{Cell, RefCell},
     fmt::Debug,
     future::Future,
@@ -32,12 +36,13 @@ cfg_if! {
         }
     } else {
         thread_local! {
-            pub(crate) static RUNTIMES: RefCell<SlotMap<RuntimeId, Runtime>> = Default::default();
+            pub(crate) static RUNTIMES: RefCell<SlotMap<RuntimeKey, Runtime>> = Default::default();
         }
     }
 }
 
-type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
+pub(crate) type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
+pub(crate) type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 
 // The data structure that owns all the signals, memos, effects,
 // and other data included in the reactive system.
@@ -47,10 +52,14 @@ pub(crate) struct Runtime {
     pub observer: Cell<Option<NodeId>>,
     pub scopes: RefCell<SlotMap<ScopeId, RefCell<Vec<ScopeProperty>>>>,
     pub scope_parents: RefCell<SparseSecondaryMap<ScopeId, ScopeId>>,
-    pub scope_children: RefCell<SparseSecondaryMap<ScopeId, Vec<ScopeId>>>,
+    // each child is stored with the order it was created in, so that it can
+    // be removed in constant time and the children still disposed in order
+    pub scope_children:
+        RefCell<SparseSecondaryMap<ScopeId, FxIndexMap<ScopeId, usize>>>,
+    pub next_child_order: Cell<usize>,
     #[allow(clippy::type_complexity)]
     pub scope_contexts:
-        RefCell<SparseSecondaryMap<ScopeId, FxHashMap<TypeId, Box<dyn Any>>>>,
+        RefCell<SparseSecondaryMap<ScopeId, FxHashMap<TypeId, Rc<dyn Any>>>>,
     #[allow(clippy::type_complexity)]
     pub scope_cleanups:
         RefCell<SparseSecondaryMap<ScopeId, Vec<Box<dyn FnOnce()>>>>,
@@ -61,10 +70,30 @@ pub(crate) struct Runtime {
     pub node_sources:
         RefCell<SecondaryMap<NodeId, RefCell<FxIndexSet<NodeId>>>>,
     pub pending_effects: RefCell<Vec<NodeId>>,
+    pub deferred_effects: RefCell<SparseSecondaryMap<NodeId, DeferredEffect>>,
+    pub running_effects: RefCell<Vec<NodeId>>,
+    pub max_effect_depth: Cell<Option<usize>>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    #[allow(clippy::type_complexity)]
+    pub resource_keys:
+        RefCell<FxHashMap<Cow<'static, str>, Vec<(ResourceId, Rc<dyn Fn()>)>>>,
     pub batching: Cell<bool>,
+    pub transition_effects: RefCell<Vec<NodeId>>,
+    pub transition_scheduled: Cell<bool>,
+    pub node_debug_info: RefCell<SparseSecondaryMap<NodeId, NodeDebugInfo>>,
+    #[allow(clippy::type_complexity)]
+    pub devtools_hooks:
+        RefCell<SlotMap<NodeUpdateHookId, Rc<dyn Fn(&NodeSnapshot)>>>,
+    #[cfg(debug_assertions)]
+    pub signal_history: RefCell<Option<crate::devtools::SignalHistory>>,
+    pub global_loading: Cell<Option<(ReadSignal<usize>, WriteSignal<usize>)>>,
 }
 
+/// The default number of effects that can be nested inside one another,
+/// each one triggered by a signal update in the last, before the runtime
+/// assumes that they form a cycle. See [`RuntimeId::set_max_effect_depth`].
+pub const DEFAULT_MAX_EFFECT_DEPTH: usize = 100;
+
 // This core Runtime impl block handles all the work of marking and updating
 // the reactive graph.
 //
@@ -121,18 +150,43 @@ impl Runtime {
                 ReactiveNodeType::Memo { ref f }
                 | ReactiveNodeType::Effect { ref f } => {
                     let value = node.value();
+                    // errors only report what was running in debug builds
+                    #[cfg(debug_assertions)]
+                    let run = {
+                        let source = match node.node_type {
+                            ReactiveNodeType::Memo { .. } => {
+                                ErrorSource::Memo {
+                                    defined_at: f.defined_at(),
+                                }
+                            }
+                            _ => ErrorSource::Effect {
+                                defined_at: f.defined_at(),
+                            },
+                        };
+                        move || with_error_source(source, || f.run(value))
+                    };
+                    #[cfg(not(debug_assertions))]
+                    let run = move || f.run(value);
                     // set this node as the observer
                     self.with_observer(node_id, move || {
                         // clean up sources of this memo/effect
                         self.cleanup(node_id);
 
-                        f.run(value)
+                        run()
                     })
                 }
             };
 
             // mark children dirty
             if changed {
+                if matches!(
+                    node.node_type,
+                    ReactiveNodeType::Memo { .. }
+                        | ReactiveNodeType::Effect { .. }
+                ) {
+                    self.notify_devtools(node_id);
+                }
+
                 let subs = self.node_subscribers.borrow();
 
                 if let Some(subs) = subs.get(node_id) {
@@ -333,17 +387,137 @@ impl Runtime {
 
     pub(crate) fn run_effects(&self) {
         if !self.batching.get() {
-            let effects = self.pending_effects.take();
-            for effect_id in effects {
+            // if this stops early, because of a cycle or a panic, the
+            // effects that haven't run yet still run the next time
+            let mut effects = RequeueEffectsOnDrop(
+                self,
+                self.pending_effects.take().into_iter(),
+            );
+            for effect_id in effects.1.by_ref() {
+                // an effect that updates a signal runs its subscribers
+                // before returning, so a cycle of effects that keep
+                // triggering one another would otherwise recurse forever
+                let depth = self.running_effects.borrow().len();
+                if depth
+                    >= self
+                        .max_effect_depth
+                        .get()
+                        .unwrap_or(DEFAULT_MAX_EFFECT_DEPTH)
+                {
+                    self.report_effect_cycle(effect_id);
+                    return;
+                }
+
+                // effects with a later timing are run by their own task
+                if self.schedule_deferred_effect(effect_id) {
+                    continue;
+                }
+
+                self.running_effects.borrow_mut().push(effect_id);
+                let _running = PopRunningEffectOnDrop(self);
                 self.update_if_necessary(effect_id);
             }
         }
     }
 
+    #[cold]
+    #[inline(never)]
+    fn report_effect_cycle(&self, effect_id: NodeId) {
+        // the effects on the stack repeat in the order of the cycle, so
+        // listing each one once describes the whole chain
+        let mut chain = Vec::new();
+        for id in self
+            .running_effects
+            .borrow()
+            .iter()
+            .chain(std::iter::once(&effect_id))
+        {
+            if !chain.contains(id) {
+                chain.push(*id);
+            }
+        }
+
+        let nodes = self.nodes.borrow();
+        let chain = chain
+            .into_iter()
+            .map(|id| {
+                let defined_at =
+                    nodes.get(id).and_then(|node| match &node.node_type {
+                        ReactiveNodeType::Effect { f } => f.defined_at(),
+                        _ => None,
+                    });
+                match defined_at {
+                    Some(defined_at) => {
+                        format!("  effect defined at {defined_at}")
+                    }
+                    None => format!("  effect {id:?}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n    → updates a signal that reruns\n");
+        let msg = format!(
+            "Detected a reactive cycle: effects were nested more than {} \
+             levels deep, each one updating a signal that the next one reads. \
+             The effects involved are:\n{chain}\n    → which reruns the first \
+             effect again.\n\nEffects should not write to signals that they \
+             (directly or indirectly) read. Consider using a memo or a \
+             derived signal instead.",
+            self.max_effect_depth
+                .get()
+                .unwrap_or(DEFAULT_MAX_EFFECT_DEPTH)
+        );
+        drop(nodes);
+
+        if cfg!(debug_assertions) {
+            panic!("{msg}");
+        } else {
+            crate::console_warn(&msg);
+        }
+    }
+
+    /// Schedules a run of the effect if it was created with a timing other
+    /// than [`EffectTiming::Sync`], unless one is already scheduled. Returns
+    /// `false` for synchronous effects.
+    fn schedule_deferred_effect(&self, effect_id: NodeId) -> bool {
+        let to_schedule =
+            match self.deferred_effects.borrow_mut().get_mut(effect_id) {
+                None => return false,
+                Some(effect) if effect.scheduled => None,
+                Some(effect) => {
+                    effect.scheduled = true;
+                    Some((effect.runtime, effect.timing))
+                }
+            };
+        if let Some((runtime, timing)) = to_schedule {
+            crate::effect::schedule_effect(runtime, effect_id, timing);
+        }
+        true
+    }
+
     pub(crate) fn dispose_node(&self, node: NodeId) {
+        self.deferred_effects.borrow_mut().remove(node);
         self.node_sources.borrow_mut().remove(node);
         self.node_subscribers.borrow_mut().remove(node);
         self.nodes.borrow_mut().remove(node);
+        self.node_debug_info.borrow_mut().remove(node);
+    }
+}
+
+// Pops the effect from the stack of running effects, even if it panics.
+struct PopRunningEffectOnDrop<'a>(&'a Runtime);
+
+impl Drop for PopRunningEffectOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.running_effects.borrow_mut().pop();
+    }
+}
+
+// Puts the effects that haven't run yet back in the queue of pending effects.
+struct RequeueEffectsOnDrop<'a>(&'a Runtime, std::vec::IntoIter<NodeId>);
+
+impl Drop for RequeueEffectsOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.pending_effects.borrow_mut().extend(&mut self.1);
     }
 }
 
@@ -375,9 +549,16 @@ pub(crate) fn with_runtime<T>(
             _ = id;
             Ok(RUNTIME.with(|runtime| f(runtime)))
         } else {
+            if id.thread != current_thread() {
+                crate::macros::debug_warn!(
+                    "tried to use a reactive runtime on a different thread \
+                     from the one that created it"
+                );
+                return Err(());
+            }
             RUNTIMES.with(|runtimes| {
                 let runtimes = runtimes.borrow();
-                match runtimes.get(id) {
+                match runtimes.get(id.key) {
                     None => Err(()),
                     Some(runtime) => Ok(f(runtime))
                 }
@@ -389,20 +570,75 @@ pub(crate) fn with_runtime<T>(
 #[doc(hidden)]
 #[must_use = "Runtime will leak memory if Runtime::dispose() is never called."]
 /// Creates a new reactive [`Runtime`]. This should almost always be handled by the framework.
+///
+/// On the server, every call creates an independent runtime, so that each
+/// request can be rendered in its own runtime while others are rendered
+/// concurrently. Disposing one runtime, or a scope in it, never affects the
+/// signals of another. In the browser, there is only one runtime, and this
+/// returns a handle to it.
 pub fn create_runtime() -> RuntimeId {
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             Default::default()
         } else {
-            RUNTIMES.with(|runtimes| runtimes.borrow_mut().insert(Runtime::new()))
+            let key = RUNTIMES.with(|runtimes| runtimes.borrow_mut().insert(Runtime::new()));
+            RuntimeId { key, thread: current_thread() }
         }
     }
 }
 
 #[cfg(not(any(feature = "csr", feature = "hydrate")))]
 slotmap::new_key_type! {
-    /// Unique ID assigned to a Runtime.
-    pub struct RuntimeId;
+    struct RuntimeKey;
+}
+
+/// Unique ID assigned to a Runtime.
+///
+/// Runtimes are not thread-safe: a runtime, and the signals, effects and
+/// scopes in it, can only be used on the thread that created it. On any
+/// other thread, they behave as if the runtime had been disposed, rather
+/// than reaching a runtime of that thread. An async server should render
+/// each request on a single thread, as the integrations do with a local
+/// task pool.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RuntimeId {
+    key: RuntimeKey,
+    // runtimes are stored separately on each thread, so the key alone could
+    // also refer to a runtime on another thread
+    thread: u64,
+}
+
+// In debug builds, effects and memos keep the component stack from where they
+// were created, for errors that happen when they run.
+fn with_component_stack(
+    computation: Rc<dyn AnyComputation>,
+) -> Rc<dyn AnyComputation> {
+    #[cfg(debug_assertions)]
+    {
+        Rc::new(crate::effect::WithComponentStack {
+            computation,
+            stack: crate::ComponentStack::capture(),
+        })
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        computation
+    }
+}
+
+/// Identifies the current thread, for [`RuntimeId`]. Unlike
+/// [`std::thread::ThreadId`], it fits in a `u64` on stable Rust, and `0` is
+/// never used, so a default [`RuntimeId`] is never valid.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn current_thread() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD.with(|thread| *thread)
 }
 
 /// Unique ID assigned to a Runtime.
@@ -415,12 +651,30 @@ impl RuntimeId {
     pub fn dispose(self) {
         cfg_if! {
             if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
-                let runtime = RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self));
+                if self.thread != current_thread() {
+                    return;
+                }
+                let runtime = RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self.key));
                 drop(runtime);
             }
         }
     }
 
+    /// Sets the number of effects that can be nested inside one another,
+    /// each triggered by a signal update in the one before, before the
+    /// runtime decides that they form a cycle and stops running them. Defaults
+    /// to [`DEFAULT_MAX_EFFECT_DEPTH`].
+    ///
+    /// When a cycle is detected, the runtime panics in debug mode with the
+    /// chain of effects involved, and logs a warning in release mode. The
+    /// effect that would have closed the cycle doesn't run, and any other
+    /// effects that were waiting to run do so the next time effects are run.
+    pub fn set_max_effect_depth(self, depth: usize) {
+        _ = with_runtime(self, |runtime| {
+            runtime.max_effect_depth.set(Some(depth))
+        });
+    }
+
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
@@ -458,7 +712,7 @@ impl RuntimeId {
     ) -> (T, ScopeId, ScopeDisposer) {
         let (scope, disposer) = self.raw_scope_and_disposer_with_parent(parent);
 
-        (f(scope), scope.id, disposer)
+        (scope.with_current(|| f(scope)), scope.id, disposer)
     }
 
     #[inline(always)]
@@ -622,6 +876,7 @@ impl RuntimeId {
         value: Rc<RefCell<dyn Any>>,
         effect: Rc<dyn AnyComputation>,
     ) -> NodeId {
+        let effect = with_component_stack(effect);
         with_runtime(self, |runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(Rc::clone(&value)),
@@ -635,6 +890,14 @@ impl RuntimeId {
             let prev_observer = runtime.observer.take();
             runtime.observer.set(Some(id));
 
+            #[cfg(debug_assertions)]
+            with_error_source(
+                ErrorSource::Effect {
+                    defined_at: effect.defined_at(),
+                },
+                || effect.run(value),
+            );
+            #[cfg(not(debug_assertions))]
             effect.run(value);
 
             runtime.observer.set(prev_observer);
@@ -649,6 +912,7 @@ impl RuntimeId {
         value: Rc<RefCell<dyn Any>>,
         computation: Rc<dyn AnyComputation>,
     ) -> NodeId {
+        let computation = with_component_stack(computation);
         with_runtime(self, |runtime| {
             runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(value),
@@ -681,6 +945,47 @@ impl RuntimeId {
         )
     }
 
+    /// Creates an effect that first runs, and later reruns, at the given
+    /// `timing` rather than synchronously.
+    #[track_caller]
+    pub(crate) fn create_deferred_effect<T>(
+        self,
+        timing: EffectTiming,
+        f: impl Fn(Option<T>) -> T + 'static,
+    ) -> NodeId
+    where
+        T: Any + 'static,
+    {
+        let effect = with_component_stack(Rc::new(Effect {
+            f,
+            ty: PhantomData,
+            #[cfg(any(debug_assertions, feature = "ssr"))]
+            defined_at: std::panic::Location::caller(),
+        }));
+        let id = with_runtime(self, |runtime| {
+            let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
+                value: Some(Rc::new(RefCell::new(None::<T>))),
+                // dirty until its first run, which tracks its sources
+                state: ReactiveNodeState::Dirty,
+                node_type: ReactiveNodeType::Effect { f: effect },
+            });
+            runtime.deferred_effects.borrow_mut().insert(
+                id,
+                DeferredEffect {
+                    runtime: self,
+                    timing,
+                    scheduled: true,
+                },
+            );
+            id
+        })
+        .expect(
+            "tried to create an effect in a runtime that has been disposed",
+        );
+        crate::effect::schedule_effect(self, id, timing);
+        id
+    }
+
     #[track_caller]
     #[inline(always)]
     pub(crate) fn create_memo<T>(
@@ -689,6 +994,19 @@ impl RuntimeId {
     ) -> Memo<T>
     where
         T: PartialEq + Any + 'static,
+    {
+        self.create_memo_with_compare(f, PartialEq::eq)
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn create_memo_with_compare<T>(
+        self,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        compare: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
     {
         Memo {
             runtime: self,
@@ -696,6 +1014,7 @@ impl RuntimeId {
                 Rc::new(RefCell::new(None::<T>)),
                 Rc::new(MemoState {
                     f,
+                    compare,
                     t: PhantomData,
                     #[cfg(any(debug_assertions, feature = "ssr"))]
                     defined_at: std::panic::Location::caller(),
@@ -10,10 +10,15 @@ use crate::{
 };
 use futures::stream::FuturesUnordered;
 use std::{
+    cell::Cell,
     collections::{HashMap, VecDeque},
     fmt,
 };
 
+thread_local! {
+    static CURRENT_SCOPE: Cell<Option<Scope>> = const { Cell::new(None) };
+}
+
 #[doc(hidden)]
 #[must_use = "Scope will leak memory if the disposer function is never called"]
 /// Creates a new reactive system and root reactive scope and runs the function within it.
@@ -109,6 +114,53 @@ impl Scope {
         self.id
     }
 
+    /// Returns the [`Scope`] that is currently running, if any.
+    ///
+    /// This is the innermost scope whose function is being run by
+    /// [`create_scope`], [`run_scope`], or [`Scope::child_scope`], or which
+    /// has been set with [`Scope::with_current`]. Effects and memos run with
+    /// the scope they were created in, as do event listeners added to an
+    /// element. It allows code that is not passed a `cx` to find the scope
+    /// that owns it.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// assert_eq!(Scope::current(), Some(cx));
+    /// cx.child_scope(|child| {
+    ///     assert_eq!(Scope::current(), Some(child));
+    /// });
+    /// assert_eq!(Scope::current(), Some(cx));
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn current() -> Option<Scope> {
+        CURRENT_SCOPE.with(Cell::get)
+    }
+
+    // the current scope, for functions that are called without a `cx`
+    #[track_caller]
+    pub(crate) fn expect_current(name: &str) -> Scope {
+        Self::current().unwrap_or_else(|| {
+            panic!(
+                "{name} was called without a `cx` outside of any reactive \
+                 Scope. Use the version of it that takes a Scope instead."
+            )
+        })
+    }
+
+    /// Runs the given function with this scope as the
+    /// [current scope](Scope::current).
+    ///
+    /// This is useful when running code later, for example in an event
+    /// listener or after an `async` task resolves, which wants to create
+    /// reactive values owned by this scope.
+    #[inline(always)]
+    pub fn with_current<T>(self, f: impl FnOnce() -> T) -> T {
+        let prev = CURRENT_SCOPE.with(|current| current.replace(Some(self)));
+        let _reset = ResetCurrentScopeOnDrop(prev);
+        f()
+    }
+
     /// Returns the chain of scope IDs beginning with this one, going to its parent, grandparents, etc.
     pub fn ancestry(&self) -> Vec<ScopeId> {
         let mut ids = vec![self.id];
@@ -166,6 +218,8 @@ impl Scope {
 
     fn push_child(&self, child_id: ScopeId) {
         _ = with_runtime(self.runtime, |runtime| {
+            let order = runtime.next_child_order.get();
+            runtime.next_child_order.set(order + 1);
             let mut children = runtime.scope_children.borrow_mut();
             children
                 .entry(self.id)
@@ -174,7 +228,7 @@ impl Scope {
                      disposed",
                 )
                 .or_default()
-                .push(child_id);
+                .insert(child_id, order);
         });
     }
 
@@ -210,20 +264,17 @@ impl Scope {
     #[inline(always)]
     pub fn untrack<T>(&self, f: impl FnOnce() -> T) -> T {
         with_runtime(self.runtime, |runtime| {
-            let untracked_result;
-
-            SpecialNonReactiveZone::enter();
+            // exits the zone even if `f` panics
+            let _zone = SpecialNonReactiveZone::enter_until_dropped();
 
             let prev_observer =
                 SetObserverOnDrop(self.runtime, runtime.observer.take());
 
-            untracked_result = f();
+            let untracked_result = f();
 
             runtime.observer.set(prev_observer.1);
             std::mem::forget(prev_observer); // avoid Drop
 
-            SpecialNonReactiveZone::exit();
-
             untracked_result
         })
         .expect(
@@ -233,6 +284,14 @@ impl Scope {
     }
 }
 
+struct ResetCurrentScopeOnDrop(Option<Scope>);
+
+impl Drop for ResetCurrentScopeOnDrop {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.with(|current| current.set(self.0));
+    }
+}
+
 struct SetObserverOnDrop(RuntimeId, Option<NodeId>);
 
 impl Drop for SetObserverOnDrop {
@@ -252,6 +311,9 @@ impl Scope {
     /// 1. dispose of all child `Scope`s
     /// 2. run all cleanup functions defined for this scope by [`on_cleanup`](crate::on_cleanup).
     /// 3. dispose of all signals, effects, and resources owned by this `Scope`.
+    ///
+    /// This is `O(n)` in the number of scopes, cleanup functions, and reactive nodes
+    /// owned by this scope and its descendants.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -265,7 +327,11 @@ impl Scope {
             };
 
             if let Some(children) = children {
-                for id in children {
+                // siblings are reordered as they are removed, so sort them
+                // back into the order they were created in
+                let mut children = children.into_iter().collect::<Vec<_>>();
+                children.sort_unstable_by_key(|(_, order)| *order);
+                for (id, _) in children {
                     Scope {
                         runtime: self.runtime,
                         id,
@@ -282,7 +348,17 @@ impl Scope {
                 }
             }
 
-            runtime.scope_parents.borrow_mut().remove(self.id);
+            // remove this scope from its parent's children, so that a
+            // long-lived parent (like a list) doesn't accumulate the IDs of
+            // every child it has ever created
+            let parent = runtime.scope_parents.borrow_mut().remove(self.id);
+            if let Some(parent) = parent {
+                if let Some(siblings) =
+                    runtime.scope_children.borrow_mut().get_mut(parent)
+                {
+                    siblings.swap_remove(&self.id);
+                }
+            }
 
             // remove everything we own and run cleanups
             let owned = {
@@ -296,6 +372,7 @@ impl Scope {
                         | ScopeProperty::Trigger(id) => {
                             // remove the signal
                             runtime.nodes.borrow_mut().remove(id);
+                            runtime.node_debug_info.borrow_mut().remove(id);
                             let subs = runtime
                                 .node_subscribers
                                 .borrow_mut()
@@ -317,9 +394,17 @@ impl Scope {
                         ScopeProperty::Effect(id) => {
                             runtime.nodes.borrow_mut().remove(id);
                             runtime.node_sources.borrow_mut().remove(id);
+                            runtime.node_debug_info.borrow_mut().remove(id);
                         }
                         ScopeProperty::Resource(id) => {
                             runtime.resources.borrow_mut().remove(id);
+                            // forget its cache keys, if it had any
+                            runtime.resource_keys.borrow_mut().retain(
+                                |_, tagged| {
+                                    tagged.retain(|(other, _)| *other != id);
+                                    !tagged.is_empty()
+                                },
+                            );
                         }
                         ScopeProperty::StoredValue(id) => {
                             runtime.stored_values.borrow_mut().remove(id);
@@ -387,6 +472,37 @@ pub fn on_cleanup(cx: Scope, cleanup_fn: impl FnOnce() + 'static) {
     push_cleanup(cx, Box::new(cleanup_fn))
 }
 
+/// Runs `f` without tracking any of the signals it reads, so that the
+/// running effect or memo does not subscribe to them. This is the same as
+/// [Scope::untrack], and works for anything that reads a signal, including
+/// derived signals that are plain closures.
+///
+/// To read a single signal, memo, or resource without tracking it, use
+/// [`get_untracked`](crate::SignalGetUntracked::get_untracked) or
+/// [`with_untracked`](crate::SignalWithUntracked::with_untracked).
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (a, set_a) = create_signal(cx, 1);
+/// let (b, set_b) = create_signal(cx, 1);
+/// let double_b = move || b.get() * 2;
+///
+/// // only depends on `a`, even though it also reads `b`
+/// let c = create_memo(cx, move |_| a.get() + untrack(cx, double_b));
+/// assert_eq!(c.get(), 3);
+///
+/// set_b.set(2);
+/// assert_eq!(c.get(), 3);
+///
+/// set_a.set(2);
+/// assert_eq!(c.get(), 6);
+/// # }).dispose();
+/// ```
+#[inline(always)]
+pub fn untrack<T>(cx: Scope, f: impl FnOnce() -> T) -> T {
+    cx.untrack(f)
+}
+
 slotmap::new_key_type! {
     /// Unique ID assigned to a [`Scope`](crate::Scope).
     pub struct ScopeId;
@@ -461,7 +577,8 @@ impl Scope {
     }
 
     /// Registers the given [`SuspenseContext`](crate::SuspenseContext) with the current scope,
-    /// calling the `resolver` when its resources are all resolved.
+    /// calling the `resolver` when its resources are all resolved, or when its
+    /// [timeout](crate::SuspenseContext::set_timeout) elapses.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -499,11 +616,11 @@ impl Scope {
                 key.to_string(),
                 FragmentData {
                     out_of_order: Box::pin(async move {
-                        rx1.next().await;
+                        ready_or_timed_out(context, rx1.next()).await;
                         out_of_order_resolver()
                     }),
                     in_order: Box::pin(async move {
-                        rx2.next().await;
+                        ready_or_timed_out(context, rx2.next()).await;
                         in_order_resolver()
                     }),
                     should_block: context.should_block(),
@@ -618,3 +735,56 @@ impl fmt::Debug for ScopeDisposer {
         f.debug_tuple("ScopeDisposer").finish()
     }
 }
+
+/// Waits until `ready` resolves or the suspense context's timeout elapses,
+/// marking the context as timed out in the latter case.
+async fn ready_or_timed_out(
+    context: SuspenseContext,
+    ready: impl std::future::Future,
+) {
+    use futures::future::{select, Either};
+
+    match context.timeout.get_value() {
+        None => {
+            ready.await;
+        }
+        Some(timeout) => {
+            let ready = Box::pin(ready);
+            let timeout = sleep(timeout);
+            if let Either::Right(_) = select(ready, timeout).await {
+                context.timed_out.set_value(true);
+            }
+        }
+    }
+}
+
+/// Returns a future that resolves once the duration has elapsed.
+pub(crate) type Sleep = fn(std::time::Duration) -> PinnedFuture<()>;
+
+thread_local! {
+    // the clock Suspense timeouts are measured on, which a `TestExecutor`
+    // replaces with its mock clock while it is installed
+    static SLEEP: Cell<Sleep> = Cell::new(platform_sleep);
+}
+
+/// Replaces the clock used for Suspense timeouts on the current thread,
+/// returning the previous one.
+pub(crate) fn set_suspense_clock(sleep: Sleep) -> Sleep {
+    SLEEP.with(|current| current.replace(sleep))
+}
+
+fn sleep(duration: std::time::Duration) -> PinnedFuture<()> {
+    SLEEP.with(Cell::get)(duration)
+}
+
+fn platform_sleep(duration: std::time::Duration) -> PinnedFuture<()> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "ssr")] {
+            Box::pin(tokio::time::sleep(duration))
+        } else {
+            // fragments are only streamed on the server
+            _ = duration;
+            Box::pin(futures::future::pending())
+        }
+    }
+}
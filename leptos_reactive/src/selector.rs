@@ -52,7 +52,15 @@ pub fn create_selector<T>(
 where
     T: PartialEq + Eq + Clone + Hash + 'static,
 {
-    create_selector_with_fn(cx, source, PartialEq::eq)
+    selector(cx, source, PartialEq::eq, |subs, prev, next| {
+        // only the previous and next keys can have changed, so they can be
+        // looked up directly rather than checking every subscriber
+        [prev, Some(next)]
+            .into_iter()
+            .flatten()
+            .filter_map(|key| subs.get(key).map(|(_, set)| *set))
+            .collect()
+    })
 }
 
 /// Creates a conditional signal that only notifies subscribers when a change
@@ -69,27 +77,43 @@ pub fn create_selector_with_fn<T>(
 where
     T: PartialEq + Eq + Clone + Hash + 'static,
 {
-    #[allow(clippy::type_complexity)]
-    let subs: Rc<
-        RefCell<HashMap<T, (ReadSignal<bool>, WriteSignal<bool>)>>,
-    > = Rc::new(RefCell::new(HashMap::new()));
+    selector(cx, source, f.clone(), move |subs, prev, next| {
+        subs.iter()
+            .filter(|(key, _)| {
+                f(key, next) || prev.map(|prev| f(key, prev)).unwrap_or(false)
+            })
+            .map(|(_, (_, set))| *set)
+            .collect()
+    })
+}
+
+type SelectorSubs<T> = HashMap<T, (ReadSignal<bool>, WriteSignal<bool>)>;
+
+fn selector<T>(
+    cx: Scope,
+    source: impl Fn() -> T + Clone + 'static,
+    f: impl Fn(&T, &T) -> bool + Clone + 'static,
+    affected: impl Fn(&SelectorSubs<T>, Option<&T>, &T) -> Vec<WriteSignal<bool>>
+        + 'static,
+) -> impl Fn(T) -> bool + Clone
+where
+    T: PartialEq + Eq + Clone + Hash + 'static,
+{
+    let subs: Rc<RefCell<SelectorSubs<T>>> =
+        Rc::new(RefCell::new(HashMap::new()));
     let v = Rc::new(RefCell::new(None));
 
     create_isomorphic_effect(cx, {
         let subs = Rc::clone(&subs);
-        let f = f.clone();
         let v = Rc::clone(&v);
         move |prev: Option<T>| {
             let next_value = source();
             *v.borrow_mut() = Some(next_value.clone());
             if prev.as_ref() != Some(&next_value) {
-                let subs = { subs.borrow().clone() };
-                for (key, signal) in subs.into_iter() {
-                    if f(&key, &next_value)
-                        || (prev.is_some() && f(&key, prev.as_ref().unwrap()))
-                    {
-                        signal.1.update(|n| *n = true);
-                    }
+                let affected =
+                    { affected(&subs.borrow(), prev.as_ref(), &next_value) };
+                for set in affected {
+                    set.update(|n| *n = true);
                 }
             }
             next_value
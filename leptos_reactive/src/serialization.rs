@@ -120,3 +120,113 @@ cfg_if! {
         }
     }
 }
+
+/// The functions used to serialize a [`Resource`](crate::Resource)’s value on
+/// the server and deserialize it on the client.
+///
+/// By default, resources use their [`Serializable`] implementation, which
+/// depends on which serialization crate feature is enabled. A codec can be
+/// passed to [`create_resource_with_codec`](crate::create_resource_with_codec)
+/// to choose a different format for a single resource.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// let codec = ResourceCodec::<Vec<u32>>::json();
+/// let serialized = (codec.ser)(&vec![1, 2, 3]).unwrap();
+/// assert_eq!(serialized, "[1,2,3]");
+/// assert_eq!((codec.de)(&serialized).unwrap(), vec![1, 2, 3]);
+/// ```
+pub struct ResourceCodec<T> {
+    /// Serializes the value to a string.
+    pub ser: fn(&T) -> Result<String, SerializationError>,
+    /// Deserializes the value from a string.
+    pub de: fn(&str) -> Result<T, SerializationError>,
+}
+
+impl<T> Clone for ResourceCodec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ResourceCodec<T> {}
+
+impl<T> std::fmt::Debug for ResourceCodec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceCodec").finish_non_exhaustive()
+    }
+}
+
+impl<T: Serializable> Default for ResourceCodec<T> {
+    fn default() -> Self {
+        Self {
+            ser: T::ser,
+            de: T::de,
+        }
+    }
+}
+
+impl<T> ResourceCodec<T> {
+    /// Creates a codec from a pair of serialization functions.
+    pub fn new(
+        ser: fn(&T) -> Result<String, SerializationError>,
+        de: fn(&str) -> Result<T, SerializationError>,
+    ) -> Self {
+        Self { ser, de }
+    }
+
+    /// Serializes values as JSON using `serde`.
+    pub fn json() -> Self
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        Self {
+            ser: |value| {
+                serde_json::to_string(value)
+                    .map_err(|e| SerializationError::Serialize(Rc::new(e)))
+            },
+            de: |json| {
+                serde_json::from_str(json)
+                    .map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+            },
+        }
+    }
+
+    /// Serializes values as JSON using `serde-lite`.
+    #[cfg(feature = "serde-lite")]
+    pub fn serde_lite() -> Self
+    where
+        T: serde_lite::Serialize + serde_lite::Deserialize,
+    {
+        Self {
+            ser: |value| {
+                let intermediate = value
+                    .serialize()
+                    .map_err(|e| SerializationError::Serialize(Rc::new(e)))?;
+                serde_json::to_string(&intermediate)
+                    .map_err(|e| SerializationError::Serialize(Rc::new(e)))
+            },
+            de: |json| {
+                let intermediate = serde_json::from_str(json)
+                    .map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
+                T::deserialize(&intermediate)
+                    .map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+            },
+        }
+    }
+
+    /// Serializes values as JSON using `miniserde`.
+    #[cfg(feature = "miniserde")]
+    pub fn miniserde() -> Self
+    where
+        T: miniserde::Serialize + miniserde::Deserialize,
+    {
+        Self {
+            ser: |value| Ok(miniserde::json::to_string(value)),
+            de: |json| {
+                miniserde::json::from_str(json)
+                    .map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+            },
+        }
+    }
+}
@@ -303,6 +303,10 @@ pub trait SignalDispose {
 /// and returns a tuple containing a [`ReadSignal`] and a [`WriteSignal`],
 /// each of which can be called as a function.
 ///
+/// Creating a signal is `O(1)`, as is reading it (plus the cost of cloning the
+/// value, for `.get()`). Setting it is `O(n)` in the number of memos and effects
+/// that depend on it, directly or indirectly.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # create_scope(create_runtime(), |cx| {
@@ -352,6 +356,25 @@ pub fn create_signal<T>(
     s
 }
 
+/// Works exactly as [`create_signal`], but creates the signal in the
+/// [current scope](Scope::current), so it can be called without a `cx` in the
+/// body of a component.
+///
+/// # Panics
+/// Panics if no scope is running.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = signal(0);
+/// set_count(1);
+/// assert_eq!(count(), 1);
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>) {
+    create_signal(Scope::expect_current("signal"), value)
+}
+
 /// Works exactly as [`create_signal`], but creates multiple signals at once.
 #[cfg_attr(
  any(debug_assertions, features="ssr"),
@@ -1051,7 +1074,12 @@ impl<T> SignalSet<T> for WriteSignal<T> {
         )
     )]
     fn set(&self, new_value: T) {
-        self.id.update(self.runtime, |n| *n = new_value);
+        if self.id.update(self.runtime, |n| *n = new_value).is_none() {
+            warn_updating_dead_signal(
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            );
+        }
     }
 
     #[cfg_attr(
@@ -1129,6 +1157,16 @@ pub fn create_rw_signal<T>(cx: Scope, value: T) -> RwSignal<T> {
     s
 }
 
+/// Works exactly as [`create_rw_signal`], but creates the signal in the
+/// [current scope](Scope::current), like [`signal`].
+///
+/// # Panics
+/// Panics if no scope is running.
+#[track_caller]
+pub fn rw_signal<T>(value: T) -> RwSignal<T> {
+    create_rw_signal(Scope::expect_current("rw_signal"), value)
+}
+
 /// A signal that combines the getter and setter into one value, rather than
 /// separating them into a [`ReadSignal`] and a [`WriteSignal`]. You may prefer this
 /// its style, or it may be easier to pass around in a context or as a function argument.
@@ -1154,6 +1192,13 @@ pub fn create_rw_signal<T>(cx: Scope, value: T) -> RwSignal<T> {
 ///   in place without notifying its subscribers.
 /// - [`.to_stream()`](#impl-SignalStream<T>-for-RwSignal<T>) converts the signal to an `async` stream of values.
 ///
+/// ## Passing Half of the Signal
+/// [`.read_only()`](RwSignal::read_only) and [`.write_only()`](RwSignal::write_only)
+/// return a [`ReadSignal`] or a [`WriteSignal`] for the same value. Like the
+/// `RwSignal`, these are `Copy` handles, so nothing is cloned. Passing a
+/// `ReadSignal` to a child component makes sure, at the type level, that it
+/// can read the parent's state but not change it.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # create_scope(create_runtime(), |cx| {
@@ -1629,7 +1674,12 @@ impl<T> SignalSet<T> for RwSignal<T> {
         )
     )]
     fn set(&self, value: T) {
-        self.id.update(self.runtime, |n| *n = value);
+        if self.id.update(self.runtime, |n| *n = value).is_none() {
+            warn_updating_dead_signal(
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            );
+        }
     }
 
     #[cfg_attr(
@@ -1803,6 +1853,70 @@ impl<T> RwSignal<T> {
             },
         )
     }
+
+    /// Joins a getter and setter back into an `RwSignal`, like the pair
+    /// returned by [`create_signal`] or by [`split`](RwSignal::split).
+    ///
+    /// Returns `None` if they belong to different signals.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// let count = RwSignal::join(count, set_count).unwrap();
+    /// count.set(1);
+    /// assert_eq!(count(), 1);
+    ///
+    /// let (other, _) = create_signal(cx, 0);
+    /// assert!(RwSignal::join(other, set_count).is_none());
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn join(read: ReadSignal<T>, write: WriteSignal<T>) -> Option<Self> {
+        if read.runtime == write.runtime && read.id == write.id {
+            Some(Self {
+                runtime: read.runtime,
+                id: read.id,
+                ty: PhantomData,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                defined_at: std::panic::Location::caller(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> From<RwSignal<T>> for ReadSignal<T> {
+    #[track_caller]
+    fn from(value: RwSignal<T>) -> Self {
+        value.read_only()
+    }
+}
+
+impl<T> From<RwSignal<T>> for WriteSignal<T> {
+    #[track_caller]
+    fn from(value: RwSignal<T>) -> Self {
+        value.write_only()
+    }
+}
+
+impl<T> From<RwSignal<T>> for (ReadSignal<T>, WriteSignal<T>) {
+    #[track_caller]
+    fn from(value: RwSignal<T>) -> Self {
+        value.split()
+    }
+}
+
+impl<T> TryFrom<(ReadSignal<T>, WriteSignal<T>)> for RwSignal<T> {
+    /// The getter and setter, if they belong to different signals.
+    type Error = (ReadSignal<T>, WriteSignal<T>);
+
+    #[track_caller]
+    fn try_from(
+        (read, write): (ReadSignal<T>, WriteSignal<T>),
+    ) -> Result<Self, Self::Error> {
+        RwSignal::join(read, write).ok_or((read, write))
+    }
 }
 
 #[derive(Debug, Error)]
@@ -1839,11 +1953,15 @@ impl NodeId {
         } else {
             #[cfg(all(debug_assertions, not(feature = "ssr")))]
             {
-                if !SpecialNonReactiveZone::is_inside() {
-                    let AccessDiagnostics {
-                        called_at,
-                        defined_at,
-                    } = diagnostics;
+                let AccessDiagnostics {
+                    called_at,
+                    defined_at,
+                } = diagnostics;
+                // only warn once for each place a signal is read, because
+                // this code usually runs every time a component is created
+                if !SpecialNonReactiveZone::is_inside()
+                    && crate::diagnostics::is_first_warning_at(called_at)
+                {
                     crate::macros::debug_warn!(
                         "At {called_at}, you access a signal or memo (defined \
                          at {defined_at}) outside a reactive tracking \
@@ -1948,14 +2066,8 @@ impl NodeId {
                     None
                 }
             } else {
-                debug_warn!(
-                    "[Signal::update] You’re trying to update a Signal<{}> \
-                     that has already been disposed of. This is probably \
-                     either a logic error in a component that creates and \
-                     disposes of scopes, or a Resource resolving after its \
-                     scope has been dropped without having been cleaned up.",
-                    std::any::type_name::<T>()
-                );
+                // the signal has been disposed: callers can decide whether
+                // this deserves a warning
                 None
             }
         })
@@ -1985,19 +2097,17 @@ impl NodeId {
                     None
                 }
             } else {
-                debug_warn!(
-                    "[Signal::update] You’re trying to update a Signal<{}> \
-                     that has already been disposed of. This is probably \
-                     either a logic error in a component that creates and \
-                     disposes of scopes, or a Resource resolving after its \
-                     scope has been dropped without having been cleaned up.",
-                    std::any::type_name::<T>()
-                );
+                // the signal has been disposed: callers can decide whether
+                // this deserves a warning
                 None
             };
 
             // notify subscribers
             if updated.is_some() {
+                runtime.notify_devtools(*self);
+                #[cfg(debug_assertions)]
+                runtime.record_update(*self);
+
                 // mark descendants dirty
                 runtime.mark_dirty(*self);
 
@@ -725,6 +725,40 @@ where
     pub fn derive(cx: Scope, derived_signal: impl Fn() -> T + 'static) -> Self {
         Self::Dynamic(Signal::derive(cx, derived_signal))
     }
+
+    /// Converts this into a [`Signal<T>`], so it can be passed to APIs that
+    /// only accept reactive values. A static value is stored in the given
+    /// [`Scope`] and cloned each time the signal is read.
+    /// ```rust
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 2);
+    ///
+    /// fn double(cx: Scope, arg: MaybeSignal<i32>) -> Signal<i32> {
+    ///     let arg = arg.into_signal(cx);
+    ///     Signal::derive(cx, move || arg.get() * 2)
+    /// }
+    ///
+    /// assert_eq!(double(cx, 3.into()).get(), 6);
+    /// let doubled = double(cx, count.into());
+    /// assert_eq!(doubled.get(), 4);
+    /// set_count(5);
+    /// assert_eq!(doubled.get(), 10);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn into_signal(self, cx: Scope) -> Signal<T>
+    where
+        T: Clone,
+    {
+        match self {
+            Self::Static(value) => {
+                let value = store_value(cx, value);
+                Signal::derive(cx, move || value.get_value())
+            }
+            Self::Dynamic(signal) => signal,
+        }
+    }
 }
 
 impl<T> From<T> for MaybeSignal<T> {
@@ -1,10 +1,14 @@
 #![forbid(unsafe_code)]
+use crate::{on_cleanup, Scope};
 use cfg_if::cfg_if;
+use futures::future::abortable;
 use std::future::Future;
 
 /// Spawns and runs a thread-local [`Future`] in a platform-independent way.
 ///
-/// This can be used to interface with any `async` code.
+/// This can be used to interface with any `async` code. If a
+/// [`TestExecutor`](crate::TestExecutor) is installed on the current thread,
+/// the future is queued on it instead.
 pub fn spawn_local<F>(fut: F)
 where
     F: Future<Output = ()> + 'static,
@@ -14,11 +18,97 @@ where
             wasm_bindgen_futures::spawn_local(fut)
         }
         else if #[cfg(any(test, doctest))] {
-            tokio_test::block_on(fut);
+            if let Err(fut) = crate::TestExecutor::try_spawn(fut) {
+                tokio_test::block_on(fut);
+            }
         } else if #[cfg(feature = "ssr")] {
-            tokio::task::spawn_local(fut);
+            if let Err(fut) = crate::TestExecutor::try_spawn(fut) {
+                tokio::task::spawn_local(fut);
+            }
         }  else {
-            futures::executor::block_on(fut)
+            if let Err(fut) = crate::TestExecutor::try_spawn(fut) {
+                futures::executor::block_on(fut)
+            }
         }
     }
 }
+
+/// Runs the [`Future`] returned by `create_task` on one of a pool of threads
+/// shared by the whole server, and returns a handle that resolves to its
+/// output.
+///
+/// A reactive runtime, and its scopes and signals, can only be used on the
+/// thread that created it (see [`RuntimeId`](crate::RuntimeId)), so
+/// everything rendered for one request has to stay on one thread. Because
+/// `create_task` is called on a pool thread, the future it returns can create
+/// a runtime and hold signals across `.await` points, while the handle is
+/// `Send` and can be awaited in a handler on a multi-threaded executor, like
+/// the one started by `#[tokio::main]`, without a `LocalSet`. Each thread of
+/// the pool, one per CPU, runs many tasks concurrently.
+///
+/// The task keeps running if the handle is dropped. This must be called from
+/// within a Tokio runtime.
+///
+/// ```ignore
+/// async fn handler() -> String {
+///     spawn_pinned(|| async {
+///         let runtime = create_runtime();
+///         let html = run_scope(runtime, |cx| render_page(cx)).await;
+///         runtime.dispose();
+///         html
+///     })
+///     .await
+///     .expect("rendering task panicked")
+/// }
+/// ```
+#[cfg(feature = "ssr")]
+pub fn spawn_pinned<F, Fut>(
+    create_task: F,
+) -> tokio::task::JoinHandle<Fut::Output>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future + 'static,
+    Fut::Output: Send + 'static,
+{
+    use std::{sync::OnceLock, thread::available_parallelism};
+    use tokio_util::task::LocalPoolHandle;
+
+    static POOL: OnceLock<LocalPoolHandle> = OnceLock::new();
+    POOL.get_or_init(|| {
+        LocalPoolHandle::new(
+            available_parallelism().map(Into::into).unwrap_or(1),
+        )
+    })
+    .spawn_pinned(create_task)
+}
+
+/// Spawns a thread-local [`Future`] that is owned by the given [`Scope`]: if
+/// the scope is disposed before the future completes, the future is dropped
+/// without being polled again.
+///
+/// This is useful when an effect or event handler kicks off some `async` work
+/// that will eventually write to a signal. With [`spawn_local`], the task can
+/// resolve after its scope (and the signal) have been disposed; with
+/// `spawn_local_scoped`, it is simply cancelled.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (value, set_value) = create_signal(cx, 0);
+///
+/// spawn_local_scoped(cx, async move {
+///     // some async work...
+///     set_value(42);
+/// });
+/// # }).dispose();
+/// ```
+pub fn spawn_local_scoped<F>(cx: Scope, fut: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    let (fut, handle) = abortable(fut);
+    on_cleanup(cx, move || handle.abort());
+    spawn_local(async move {
+        _ = fut.await;
+    })
+}
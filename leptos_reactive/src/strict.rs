@@ -0,0 +1,83 @@
+#![forbid(unsafe_code)]
+//! Strict mode runs view closures and effects twice in debug builds, to
+//! surface code that isn't safe to run more than once.
+//!
+//! Leptos may run the same closure more than once: a view is rendered once on
+//! the server and again when the client hydrates, and a
+//! [Transition](https://docs.rs/leptos/latest/leptos/fn.Transition.html)
+//! re-runs its children while it waits for new data. Code that has side
+//! effects while it builds a view, or that assumes it only runs once, tends to
+//! break in exactly those cases, and only some of the time.
+//!
+//! With strict mode on, the first time an effect created with
+//! [create_effect](crate::create_effect) or
+//! [create_isomorphic_effect](crate::create_isomorphic_effect) runs, it runs
+//! twice and the first result is thrown away; and every time a reactive part
+//! of a view (like `move || view! { cx, ... }`) runs on the client, it is
+//! rendered twice and the first view is thrown away. Anything that behaves
+//! differently the second time, or that leaves something behind the first
+//! time, will show up right away.
+//!
+//! Strict mode does nothing in release builds.
+//!
+//! ```
+//! # use leptos_reactive::*;
+//! # create_scope(create_runtime(), |cx| {
+//! set_strict_mode(true);
+//!
+//! let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+//! create_isomorphic_effect(cx, {
+//!     let runs = runs.clone();
+//!     move |_| runs.set(runs.get() + 1)
+//! });
+//! assert_eq!(runs.get(), if cfg!(debug_assertions) { 2 } else { 1 });
+//!
+//! set_strict_mode(false);
+//! # }).dispose();
+//! ```
+
+#[cfg(debug_assertions)]
+use std::cell::Cell;
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static STRICT_MODE: Cell<bool> = Cell::new(false);
+}
+
+/// Turns strict mode on or off for the current thread. See the
+/// [module-level docs](crate::strict) for what it does.
+pub fn set_strict_mode(enabled: bool) {
+    #[cfg(debug_assertions)]
+    STRICT_MODE.with(|strict| strict.set(enabled));
+    #[cfg(not(debug_assertions))]
+    {
+        _ = enabled;
+    }
+}
+
+/// Whether strict mode is on for the current thread. This is always `false`
+/// in release builds.
+pub fn is_strict_mode() -> bool {
+    #[cfg(debug_assertions)]
+    {
+        STRICT_MODE.with(Cell::get)
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        false
+    }
+}
+
+// Wraps an effect so that its first run happens twice, discarding the result
+// of the first.
+#[cfg(debug_assertions)]
+pub(crate) fn run_first_twice<T>(
+    f: impl Fn(Option<T>) -> T + 'static,
+) -> impl Fn(Option<T>) -> T + 'static {
+    move |prev| {
+        if prev.is_none() {
+            _ = f(None);
+        }
+        f(prev)
+    }
+}
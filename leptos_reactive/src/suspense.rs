@@ -6,7 +6,7 @@ use crate::{
     RwSignal, Scope, SignalUpdate, StoredValue, WriteSignal,
 };
 use futures::Future;
-use std::{borrow::Cow, collections::VecDeque, pin::Pin};
+use std::{borrow::Cow, collections::VecDeque, pin::Pin, time::Duration};
 
 /// Tracks [`Resource`](crate::Resource)s that are read under a suspense context,
 /// i.e., within a [`Suspense`](https://docs.rs/leptos_core/latest/leptos_core/fn.Suspense.html) component.
@@ -18,6 +18,10 @@ pub struct SuspenseContext {
     pub(crate) pending_serializable_resources: RwSignal<usize>,
     pub(crate) has_local_only: StoredValue<bool>,
     pub(crate) should_block: StoredValue<bool>,
+    pub(crate) timeout: StoredValue<Option<Duration>>,
+    pub(crate) timed_out: StoredValue<bool>,
+    // counted right away, rather than in a microtask like `pending_resources`
+    unresolved: StoredValue<usize>,
 }
 
 impl SuspenseContext {
@@ -32,6 +36,18 @@ impl SuspenseContext {
     pub fn should_block(&self) -> bool {
         self.should_block.get_value()
     }
+
+    /// Sets how long the server should wait for this suspense context's
+    /// resources before giving up and streaming its fallback instead.
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        self.timeout.set_value(timeout);
+    }
+
+    /// Whether the server gave up waiting for this suspense context's
+    /// resources because its timeout elapsed.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out.get_value()
+    }
 }
 
 impl std::hash::Hash for SuspenseContext {
@@ -55,12 +71,18 @@ impl SuspenseContext {
         let pending_serializable_resources = create_rw_signal(cx, 0);
         let has_local_only = store_value(cx, true);
         let should_block = store_value(cx, false);
+        let timeout = store_value(cx, None);
+        let timed_out = store_value(cx, false);
+        let unresolved = store_value(cx, 0);
         Self {
             pending_resources,
             set_pending_resources,
             pending_serializable_resources,
             has_local_only,
             should_block,
+            timeout,
+            timed_out,
+            unresolved,
         }
     }
 
@@ -69,6 +91,7 @@ impl SuspenseContext {
         let setter = self.set_pending_resources;
         let serializable_resources = self.pending_serializable_resources;
         let has_local_only = self.has_local_only;
+        _ = self.unresolved.try_update_value(|n| *n += 1);
         queue_microtask(move || {
             setter.update(|n| *n += 1);
             if serializable {
@@ -82,6 +105,9 @@ impl SuspenseContext {
     pub fn decrement(&self, serializable: bool) {
         let setter = self.set_pending_resources;
         let serializable_resources = self.pending_serializable_resources;
+        _ = self
+            .unresolved
+            .try_update_value(|n| *n = n.saturating_sub(1));
         queue_microtask(move || {
             setter.update(|n| {
                 if *n > 0 {
@@ -104,6 +130,15 @@ impl SuspenseContext {
             .try_with(|n| *n == 0)
             .unwrap_or(false)
     }
+
+    /// Tests whether all of the resources read under this suspense context
+    /// have resolved, like [ready](Self::ready), but without tracking. This
+    /// includes the resources that have just started loading, which
+    /// [pending_resources](Self::pending_resources) only counts in the next
+    /// microtask, so it can be checked right after the children are created.
+    pub fn all_resolved(&self) -> bool {
+        self.unresolved.try_get_value().map_or(false, |n| n == 0)
+    }
 }
 
 /// Represents a chunk in a stream of HTML.
@@ -117,6 +152,9 @@ pub enum StreamChunk {
         /// Whether this should block the stream.
         should_block: bool,
     },
+    /// Sends all the HTML buffered so far down the stream, rather than
+    /// waiting for the next `<Suspense/>` boundary.
+    Flush,
 }
 
 impl std::fmt::Debug for StreamChunk {
@@ -124,6 +162,7 @@ impl std::fmt::Debug for StreamChunk {
         match self {
             StreamChunk::Sync(data) => write!(f, "StreamChunk::Sync({data:?})"),
             StreamChunk::Async { .. } => write!(f, "StreamChunk::Async(_)"),
+            StreamChunk::Flush => write!(f, "StreamChunk::Flush"),
         }
     }
 }
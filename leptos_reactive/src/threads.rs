@@ -0,0 +1,85 @@
+#![forbid(unsafe_code)]
+//! Runs CPU-heavy work on a pool of threads, so that it doesn't block the
+//! reactive runtime. Only available with the `wasm-threads` feature.
+//!
+//! In the browser, the pool is made of Web Workers that share the memory of
+//! the app, which needs a nightly compiler and a build with `atomics` enabled
+//! (see the [`wasm-bindgen-rayon`](https://docs.rs/wasm-bindgen-rayon)
+//! docs). The pool has to be started from JavaScript before it is used:
+//!
+//! ```js
+//! import init, { initThreadPool } from "./pkg/app.js";
+//!
+//! await init();
+//! await initThreadPool(navigator.hardwareConcurrency);
+//! ```
+//!
+//! On the server, the work runs on the global `rayon` thread pool.
+
+use crate::{create_local_resource, Resource, Scope};
+use std::{future::Future, sync::Arc};
+#[cfg(target_arch = "wasm32")]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+/// Runs `f` on the thread pool, and returns a [`Future`] that resolves to its
+/// output without blocking the current thread.
+///
+/// `f` can't use the reactive system, because signals can only be read on
+/// the thread that created them: read what it needs first, and move the
+/// values into it.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # futures::executor::block_on(async {
+/// let numbers = (1..=1_000_u64).collect::<Vec<_>>();
+/// let sum = spawn_blocking_wasm(move || numbers.iter().sum::<u64>()).await;
+/// assert_eq!(sum, 500_500);
+/// # });
+/// ```
+///
+/// # Panics
+/// The future panics if `f` panics.
+pub fn spawn_blocking_wasm<F, T>(f: F) -> impl Future<Output = T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = futures::channel::oneshot::channel();
+    rayon::spawn(move || {
+        _ = tx.send(f());
+    });
+    async move { rx.await.expect("the blocking task panicked") }
+}
+
+/// Creates a [`Resource`] whose value is computed by `compute` on the thread
+/// pool (see [`spawn_blocking_wasm`]), e.g., to parse a large file or process
+/// an image whenever `source` changes.
+///
+/// Like [`create_local_resource`], the value is only computed on the client,
+/// and isn't serialized from the server.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (text, _set_text) = create_signal(cx, String::from("a b c"));
+/// let word_count = create_threaded_resource(cx, text, |text| {
+///     text.split_whitespace().count()
+/// });
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_threaded_resource<S, T>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    compute: impl Fn(S) -> T + Send + Sync + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + Send + 'static,
+    T: Send + 'static,
+{
+    let compute = Arc::new(compute);
+    create_local_resource(cx, source, move |source| {
+        let compute = Arc::clone(&compute);
+        spawn_blocking_wasm(move || compute(source))
+    })
+}
@@ -0,0 +1,104 @@
+#![forbid(unsafe_code)]
+use crate::{with_runtime, RuntimeId, Scope};
+use cfg_if::cfg_if;
+
+/// Runs `f`, marking the signal updates it makes as low-priority.
+///
+/// Effects that depend on the signals updated in `f`, including the
+/// renderer's own, don't run immediately. Instead, they run together in a
+/// new task, once the browser has had a chance to handle input and paint.
+/// Updates made outside of a transition in the meantime, like the value of
+/// a text input as the user types, still run their effects synchronously. If
+/// another transition starts before the first one's effects have run, the
+/// two are merged, and each effect runs once, with the latest values.
+///
+/// An effect that depends on both an urgent update and a pending transition
+/// waits for the transition.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (query, set_query) = create_signal(cx, String::new());
+/// let (filter, set_filter) = create_signal(cx, String::new());
+///
+/// let on_input = move |value: String| {
+///     // the input stays responsive...
+///     set_query(value.clone());
+///     // ...while the list filtered by it is updated afterwards
+///     start_transition(cx, move || set_filter(value));
+/// };
+/// # on_input("leptos".to_string());
+/// # }).dispose();
+/// ```
+///
+/// # Panics
+/// Panics if the runtime this scope belongs to has already been disposed.
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    instrument(level = "trace", skip_all,)
+)]
+pub fn start_transition<T>(cx: Scope, f: impl FnOnce() -> T) -> T {
+    let (value, schedule) = with_runtime(cx.runtime, |runtime| {
+        // effects that are already pending are not part of the transition
+        let already_pending = runtime.pending_effects.borrow().len();
+        let value = cx.batch(|| {
+            let value = f();
+            let mut pending = runtime.pending_effects.borrow_mut();
+            let len = pending.len();
+            let effects = pending.split_off(already_pending.min(len));
+            drop(pending);
+            runtime.transition_effects.borrow_mut().extend(effects);
+            value
+        });
+        let schedule = !runtime.transition_effects.borrow().is_empty()
+            && !runtime.transition_scheduled.replace(true);
+        (value, schedule)
+    })
+    .expect("tried to start a transition in a runtime that has been disposed");
+
+    if schedule {
+        schedule_transition(cx.runtime);
+    }
+    value
+}
+
+/// Runs the effects of every pending transition in a new task.
+fn schedule_transition(runtime: RuntimeId) {
+    let run = move || {
+        _ = with_runtime(runtime, |runtime| {
+            runtime.transition_scheduled.set(false);
+            let effects = runtime.transition_effects.take();
+            runtime.pending_effects.borrow_mut().extend(effects);
+            runtime.run_effects();
+        });
+    };
+
+    cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            _ = web_sys::window()
+                .expect("no window")
+                .set_timeout_with_callback(
+                    Closure::once_into_js(run).unchecked_ref(),
+                );
+        } else {
+            if crate::TestExecutor::is_installed() {
+                let mut run = Some(run);
+                crate::TestExecutor::set_timer(
+                    Box::new(move || {
+                        if let Some(run) = run.take() {
+                            run();
+                        }
+                    }),
+                    std::time::Duration::ZERO,
+                    None,
+                );
+                return;
+            }
+
+            // without a browser, there is nothing to defer to
+            run();
+        }
+    }
+}
@@ -133,3 +133,171 @@ fn batching_actually_batches() {
     })
     .dispose()
 }
+
+#[cfg(all(debug_assertions, not(feature = "stable")))]
+#[test]
+#[should_panic(expected = "Detected a reactive cycle")]
+fn effect_cycle_is_detected() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let (b, set_b) = create_signal(cx, 0);
+
+        // each effect updates the signal the other one reads
+        create_isomorphic_effect(cx, move |_| set_b(a() + 1));
+        create_isomorphic_effect(cx, move |_| set_a(b() + 1));
+    })
+    .dispose()
+}
+
+#[cfg(all(debug_assertions, not(feature = "stable")))]
+#[test]
+fn effects_waiting_when_a_cycle_is_detected_still_run() {
+    use std::{
+        cell::Cell,
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let runtime = create_runtime();
+    runtime.set_max_effect_depth(2);
+    create_scope(runtime, |cx| {
+        let (start, set_start) = create_signal(cx, false);
+        let (a, set_a) = create_signal(cx, 0);
+        let (b, set_b) = create_signal(cx, 0);
+        let (other, set_other) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, move |_| {
+            if start() {
+                set_b(a() + 1);
+            }
+        });
+        create_isomorphic_effect(cx, move |_| {
+            if start() {
+                set_a(b() + 1);
+            }
+        });
+        // waits behind the first effect when `a` changes
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                a();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        let cycle = catch_unwind(AssertUnwindSafe(|| set_start(true)));
+        assert!(cycle.is_err());
+        assert_eq!(runs.get(), 1);
+
+        // runs the effects that were waiting
+        set_other(1);
+        assert_eq!(runs.get(), 2);
+        _ = other;
+    })
+    .dispose()
+}
+
+#[cfg(all(debug_assertions, not(feature = "stable")))]
+#[test]
+fn panicking_effect_is_no_longer_running() {
+    use std::{
+        cell::Cell,
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let runtime = create_runtime();
+    runtime.set_max_effect_depth(2);
+    create_scope(runtime, |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        create_isomorphic_effect(cx, move |_| {
+            if a() == 1 {
+                panic!("a was 1");
+            }
+        });
+        _ = catch_unwind(AssertUnwindSafe(|| set_a(1)));
+
+        // a chain of two effects is still allowed after the panic
+        let (b, set_b) = create_signal(cx, 0);
+        let (c, set_c) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, move |_| set_c(b()));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                c();
+                runs.set(runs.get() + 1);
+            }
+        });
+        set_b(1);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[cfg(all(debug_assertions, not(feature = "stable")))]
+#[test]
+fn strict_mode_runs_effects_twice_at_first() {
+    use leptos_reactive::set_strict_mode;
+    use std::{cell::Cell, rc::Rc};
+
+    set_strict_mode(true);
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |prev: Option<i32>| {
+                runs.set(runs.get() + 1);
+                prev.unwrap_or_default() + a()
+            }
+        });
+        assert_eq!(runs.get(), 2);
+
+        // later runs only happen once
+        set_a(1);
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose();
+    set_strict_mode(false);
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn effects_and_memos_rerun_in_their_scope() {
+    use leptos_reactive::{run_scope_undisposed, Scope, SignalGetUntracked};
+    use std::{cell::Cell, rc::Rc};
+
+    let runtime = create_runtime();
+    let ((cx, set_a, effect_scope, memo), _, disposer) =
+        run_scope_undisposed(runtime, |cx| {
+            let (a, set_a) = create_signal(cx, 0);
+            let effect_scope = Rc::new(Cell::new(None));
+            create_isomorphic_effect(cx, {
+                let effect_scope = Rc::clone(&effect_scope);
+                move |_| {
+                    a();
+                    effect_scope.set(Scope::current());
+                }
+            });
+            let memo = create_memo(cx, move |_| {
+                a();
+                Scope::current()
+            });
+            (cx, set_a, effect_scope, memo)
+        });
+
+    // e.g., in a timer callback
+    assert_eq!(Scope::current(), None);
+    effect_scope.set(None);
+    set_a.set(1);
+    assert_eq!(effect_scope.get(), Some(cx));
+    assert_eq!(memo.get_untracked(), Some(cx));
+    assert_eq!(Scope::current(), None);
+
+    disposer.dispose();
+    runtime.dispose();
+}
@@ -0,0 +1,93 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_rw_signal, create_scope,
+    on_error, report_error, report_panic, ComponentStackEntry, ErrorSource,
+    SignalGet, SignalSet,
+};
+use std::{
+    cell::RefCell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+};
+
+#[test]
+fn reports_panics_in_effects() {
+    let prev_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(report_panic));
+
+    let reports = Rc::new(RefCell::new(Vec::new()));
+    on_error({
+        let reports = Rc::clone(&reports);
+        move |report| reports.borrow_mut().push(report.clone())
+    });
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_rw_signal(cx, 0);
+        create_isomorphic_effect(cx, move |_| {
+            if count.get() == 1 {
+                panic!("count was 1");
+            }
+        });
+
+        report_error("not in an effect");
+        _ = catch_unwind(AssertUnwindSafe(|| count.set(1)));
+    })
+    .dispose();
+
+    std::panic::set_hook(prev_hook);
+
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].message, "not in an effect");
+    assert_eq!(reports[0].source, ErrorSource::Other);
+    assert_eq!(reports[1].message, "count was 1");
+    assert!(reports[1]
+        .location
+        .as_deref()
+        .unwrap()
+        .contains("error_hook.rs"));
+    // only known in debug builds
+    if cfg!(debug_assertions) {
+        match &reports[1].source {
+            ErrorSource::Effect {
+                defined_at: Some(defined_at),
+            } => assert!(defined_at.file().ends_with("error_hook.rs")),
+            other => panic!("expected an effect, found {other:?}"),
+        }
+    } else {
+        assert_eq!(reports[1].source, ErrorSource::Other);
+    }
+}
+
+#[test]
+fn effects_keep_the_component_stack_they_were_created_in() {
+    let reports = Rc::new(RefCell::new(Vec::new()));
+    on_error({
+        let reports = Rc::clone(&reports);
+        move |report| reports.borrow_mut().push(report.clone())
+    });
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_rw_signal(cx, 0);
+        {
+            let _app = ComponentStackEntry::push("App".into());
+            let _counter = ComponentStackEntry::push("Counter".into());
+            create_isomorphic_effect(cx, move |_| {
+                if count.get() == 1 {
+                    report_error("count was 1");
+                }
+            });
+        }
+
+        // the effect reruns after the components have been rendered
+        count.set(1);
+    })
+    .dispose();
+
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 1);
+    if cfg!(debug_assertions) {
+        assert_eq!(reports[0].component_stack, ["Counter", "App"]);
+    } else {
+        assert!(reports[0].component_stack.is_empty());
+    }
+}
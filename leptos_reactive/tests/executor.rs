@@ -0,0 +1,363 @@
+#[cfg(not(feature = "stable"))]
+use leptos_reactive::{
+    create_resource, create_resource_with_scope, create_runtime, create_scope,
+    provide_context, spawn_local, use_context, SignalGetUntracked,
+    TestExecutor,
+};
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn resource_loads_step_by_step() {
+    use futures::{channel::oneshot, FutureExt};
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let (tx, rx) = oneshot::channel::<i32>();
+        // the fetcher can run more than once, so it can't own the receiver
+        let rx = rx.shared();
+        let resource =
+            create_resource(cx, || (), move |_| rx.clone().map(Result::unwrap));
+
+        assert_eq!(executor.pending_tasks(), 1);
+        assert!(resource.loading().get_untracked());
+
+        executor.run_until_stalled();
+        assert_eq!(executor.pending_tasks(), 1);
+        assert_eq!(resource.read(cx), None);
+
+        tx.send(42).unwrap();
+        assert!(executor.step());
+        assert_eq!(executor.pending_tasks(), 0);
+        assert!(!resource.loading().get_untracked());
+        assert_eq!(resource.read(cx), Some(42));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn tasks_spawned_during_a_step_run_in_the_next() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let executor = TestExecutor::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    spawn_local({
+        let log = Rc::clone(&log);
+        async move {
+            log.borrow_mut().push("outer");
+            spawn_local(async move {
+                log.borrow_mut().push("inner");
+            });
+        }
+    });
+    assert!(log.borrow().is_empty());
+
+    executor.step();
+    assert_eq!(*log.borrow(), ["outer"]);
+
+    executor.step();
+    assert_eq!(*log.borrow(), ["outer", "inner"]);
+    assert!(!executor.step());
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn mock_timers_fire_in_order() {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    let executor = TestExecutor::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let interval = TestExecutor::set_timer(
+        Box::new({
+            let log = Rc::clone(&log);
+            move || log.borrow_mut().push("interval")
+        }),
+        Duration::from_millis(10),
+        Some(Duration::from_millis(10)),
+    );
+    TestExecutor::set_timer(
+        Box::new({
+            let log = Rc::clone(&log);
+            move || log.borrow_mut().push("timeout")
+        }),
+        Duration::from_millis(15),
+        None,
+    );
+
+    executor.advance_time(Duration::from_millis(5));
+    assert!(log.borrow().is_empty());
+
+    executor.advance_time(Duration::from_millis(20));
+    assert_eq!(*log.borrow(), ["interval", "timeout", "interval"]);
+    assert_eq!(executor.now(), Duration::from_millis(25));
+
+    assert!(TestExecutor::clear_timer(interval));
+    assert_eq!(executor.pending_timers(), 0);
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn global_loading_counts_resource_fetches() {
+    use futures::{channel::oneshot, FutureExt};
+    use leptos_reactive::use_global_loading;
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let loading = use_global_loading(cx);
+        assert_eq!(loading.get_untracked(), 0);
+
+        let (tx_a, rx_a) = oneshot::channel::<i32>();
+        let (tx_b, rx_b) = oneshot::channel::<i32>();
+        let (rx_a, rx_b) = (rx_a.shared(), rx_b.shared());
+        let _a = create_resource(
+            cx,
+            || (),
+            move |_| rx_a.clone().map(Result::unwrap),
+        );
+        let _b = create_resource(
+            cx,
+            || (),
+            move |_| rx_b.clone().map(Result::unwrap),
+        );
+        assert_eq!(loading.get_untracked(), 2);
+
+        tx_a.send(1).unwrap();
+        executor.run_until_stalled();
+        assert_eq!(loading.get_untracked(), 1);
+
+        tx_b.send(2).unwrap();
+        executor.run_until_stalled();
+        assert_eq!(loading.get_untracked(), 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn resource_fetcher_reads_context() {
+    #[derive(Clone)]
+    struct BaseUrl(&'static str);
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        provide_context(cx, BaseUrl("/api"));
+        let resource = create_resource_with_scope(
+            cx,
+            || 1,
+            |cx, id| {
+                let base = use_context::<BaseUrl>(cx).unwrap().0;
+                async move { format!("{base}/users/{id}") }
+            },
+        );
+
+        executor.run_until_stalled();
+        assert_eq!(resource.read(cx), Some("/api/users/1".to_string()));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn resource_retries_with_backoff() {
+    use leptos_reactive::{create_resource_with_retry, RetryPolicy};
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let calls = Rc::new(Cell::new(0));
+        let resource = create_resource_with_retry(
+            cx,
+            || (),
+            {
+                let calls = Rc::clone(&calls);
+                move |_| {
+                    calls.set(calls.get() + 1);
+                    let attempt = calls.get();
+                    async move { Err::<(), _>(format!("attempt {attempt}")) }
+                }
+            },
+            RetryPolicy::default()
+                .max_attempts(3)
+                .backoff(Duration::from_millis(100), 2.0),
+        );
+
+        executor.run_until_stalled();
+        assert_eq!(calls.get(), 1);
+        assert!(resource.loading().get_untracked());
+
+        executor.advance_time(Duration::from_millis(100));
+        assert_eq!(calls.get(), 2);
+        assert!(resource.loading().get_untracked());
+
+        // the second delay is doubled
+        executor.advance_time(Duration::from_millis(100));
+        assert_eq!(calls.get(), 2);
+        executor.advance_time(Duration::from_millis(100));
+        assert_eq!(calls.get(), 3);
+
+        assert!(!resource.loading().get_untracked());
+        assert_eq!(resource.read(cx), Some(Err("attempt 3".to_string())));
+        assert_eq!(executor.pending_timers(), 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn paginated_resource_accumulates_pages() {
+    use leptos_reactive::{
+        create_paginated_resource, create_signal, Page, SignalSet,
+    };
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let (query, set_query) = create_signal(cx, "a");
+        let posts = create_paginated_resource(cx, query, |query, page| {
+            let page = page.unwrap_or(0);
+            async move {
+                Ok::<_, ()>(Page {
+                    items: vec![format!("{query}{page}")],
+                    next: (page < 1).then_some(page + 1),
+                })
+            }
+        });
+
+        assert!(posts.loading().get_untracked());
+        executor.run_until_stalled();
+        assert_eq!(posts.items().get_untracked(), vec!["a0"]);
+        assert!(posts.has_more().get_untracked());
+
+        posts.load_next();
+        executor.run_until_stalled();
+        assert_eq!(posts.items().get_untracked(), vec!["a0", "a1"]);
+        assert!(!posts.has_more().get_untracked());
+
+        // there are no more pages to load
+        posts.load_next();
+        assert_eq!(executor.pending_tasks(), 0);
+
+        set_query.set("b");
+        executor.run_until_stalled();
+        assert_eq!(posts.items().get_untracked(), vec!["b0"]);
+        assert!(posts.has_more().get_untracked());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn invalidating_a_cache_key_refetches_resources() {
+    use leptos_reactive::invalidate_resources;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let fetches = Rc::new(Cell::new(0));
+        let todos = create_resource(cx, || (), {
+            let fetches = Rc::clone(&fetches);
+            move |_| {
+                fetches.set(fetches.get() + 1);
+                let n = fetches.get();
+                async move { n }
+            }
+        })
+        .with_cache_key("todos");
+
+        executor.run_until_stalled();
+        assert_eq!(todos.read(cx), Some(1));
+
+        invalidate_resources(cx, "users");
+        executor.run_until_stalled();
+        assert_eq!(todos.read(cx), Some(1));
+
+        invalidate_resources(cx, "todos");
+        executor.run_until_stalled();
+        assert_eq!(todos.read(cx), Some(2));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn effects_run_at_their_timing() {
+    use leptos_reactive::{
+        create_effect, create_effect_with_timing, create_signal, EffectTiming,
+        SignalSet,
+    };
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (count, set_count) = create_signal(cx, 0);
+
+        create_effect_with_timing(cx, EffectTiming::Idle, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("idle {}", count()))
+        });
+        create_effect_with_timing(cx, EffectTiming::Microtask, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("microtask {}", count()))
+        });
+        create_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("sync {}", count()))
+        });
+        assert_eq!(*log.borrow(), ["sync 0"]);
+
+        executor.advance_time(Duration::ZERO);
+        assert_eq!(*log.borrow(), ["sync 0", "microtask 0", "idle 0"]);
+        log.borrow_mut().clear();
+
+        // two writes in a row only cause one deferred run
+        set_count.set(1);
+        set_count.set(2);
+        assert_eq!(*log.borrow(), ["sync 1", "sync 2"]);
+
+        executor.advance_time(Duration::ZERO);
+        assert_eq!(
+            *log.borrow(),
+            ["sync 1", "sync 2", "microtask 2", "idle 2"]
+        );
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn transitions_run_their_effects_later() {
+    use leptos_reactive::{
+        create_effect, create_signal, start_transition, SignalSet,
+    };
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (query, set_query) = create_signal(cx, 0);
+        let (filter, set_filter) = create_signal(cx, 0);
+
+        create_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("query {}", query()))
+        });
+        create_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(format!("filter {}", filter()))
+        });
+        log.borrow_mut().clear();
+
+        start_transition(cx, || set_filter.set(1));
+        set_query.set(1);
+        start_transition(cx, || set_filter.set(2));
+        set_query.set(2);
+        assert_eq!(*log.borrow(), ["query 1", "query 2"]);
+
+        // both transitions are merged into one run
+        executor.advance_time(Duration::ZERO);
+        assert_eq!(*log.borrow(), ["query 1", "query 2", "filter 2"]);
+    })
+    .dispose()
+}
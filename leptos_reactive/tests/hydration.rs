@@ -0,0 +1,89 @@
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine as _};
+use ciborium::value::Value;
+use leptos_reactive::{
+    decode_resource_payload, encode_resource_payload, ResourceId,
+};
+use slotmap::KeyData;
+
+// an occupied slot at `idx`, as the runtime would create
+fn ffi(idx: u64) -> u64 {
+    (1 << 32) | idx
+}
+
+fn id(idx: u64) -> ResourceId {
+    ResourceId::from(KeyData::from_ffi(ffi(idx)))
+}
+
+#[test]
+fn resource_payload_round_trips() {
+    let long = format!("{:?}", "x".repeat(300));
+    let resources = [
+        (id(0), r#"{"title":"</script><script>alert(1)</script>"}"#),
+        (
+            id(1),
+            r#"{"b":1,"a":[true,null,-2.5,18446744073709551615]}"#,
+        ),
+        (id(2), ""),
+        (id(3), "ünïcödé \u{2028} ✓"),
+        (id(4), long.as_str()),
+    ];
+
+    let payload = encode_resource_payload(resources);
+    // only base64, so it never needs to be escaped in HTML
+    assert!(payload
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/'));
+
+    let decoded = decode_resource_payload(&payload).unwrap();
+    assert_eq!(
+        decoded,
+        resources
+            .iter()
+            .map(|(id, data)| (*id, data.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn resource_payload_stores_json_as_cbor_values() {
+    let payload =
+        encode_resource_payload([(id(0), "[1,2,3]"), (id(1), "not json")]);
+
+    let bytes = STANDARD_NO_PAD.decode(payload).unwrap();
+    let value: Value = ciborium::de::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(
+        value,
+        Value::Array(vec![
+            Value::Array(vec![
+                Value::Integer(ffi(0).into()),
+                Value::Array(vec![
+                    Value::Integer(1.into()),
+                    Value::Integer(2.into()),
+                    Value::Integer(3.into()),
+                ]),
+            ]),
+            Value::Array(vec![
+                Value::Integer(ffi(1).into()),
+                Value::Bytes(b"not json".to_vec()),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn empty_resource_payload_has_no_resources() {
+    let payload = encode_resource_payload([]);
+    assert_eq!(decode_resource_payload(&payload), Some(Vec::new()));
+}
+
+#[test]
+fn invalid_resource_payload_is_rejected() {
+    let payload = encode_resource_payload([(id(0), "some data")]);
+
+    // cut off in the middle of the data
+    let bytes = STANDARD_NO_PAD.decode(payload).unwrap();
+    let truncated = STANDARD_NO_PAD.encode(&bytes[..bytes.len() - 2]);
+    assert_eq!(decode_resource_payload(&truncated), None);
+
+    assert_eq!(decode_resource_payload("not base64!"), None);
+}
@@ -189,3 +189,35 @@ fn dynamic_dependencies() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_with_untracked() {
+    use leptos_reactive::{create_isomorphic_effect, SignalWithUntracked};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (name, set_name) = create_signal(cx, "Alice".to_string());
+        let greeting = create_memo(cx, move |_| format!("Hello, {}", name()));
+        assert_eq!(greeting.with_untracked(|g| g.len()), 12);
+        assert_eq!(
+            greeting.try_with_untracked(|g| g.clone()).as_deref(),
+            Some("Hello, Alice")
+        );
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                greeting.with_untracked(|_| ());
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        set_name("Bob".to_string());
+        assert_eq!(runs.get(), 1);
+        assert_eq!(greeting.with_untracked(String::clone), "Hello, Bob");
+    })
+    .dispose()
+}
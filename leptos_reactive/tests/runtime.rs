@@ -0,0 +1,81 @@
+#[cfg(not(feature = "stable"))]
+use leptos_reactive::{
+    create_runtime, create_scope, create_signal, run_scope_undisposed,
+    SignalGetUntracked, SignalSet,
+};
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn disposing_runtime_leaves_others_intact() {
+    let first = create_runtime();
+    let second = create_runtime();
+
+    let ((a, _), _, first_disposer) =
+        run_scope_undisposed(first, |cx| create_signal(cx, 1));
+    let ((b, set_b), _, second_disposer) =
+        run_scope_undisposed(second, |cx| create_signal(cx, 2));
+
+    first_disposer.dispose();
+    first.dispose();
+
+    assert_eq!(a.try_get_untracked(), None);
+    set_b.set(3);
+    assert_eq!(b.get_untracked(), 3);
+
+    second_disposer.dispose();
+    second.dispose();
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn runtimes_are_not_shared_between_threads() {
+    // the first runtime created on each thread
+    let other_thread = std::thread::spawn(|| {
+        let runtime = create_runtime();
+        let ((signal, _), _, _) = run_scope_undisposed(runtime, |cx| {
+            create_signal(cx, "other thread")
+        });
+        signal
+    })
+    .join()
+    .unwrap();
+
+    let runtime = create_runtime();
+    _ = create_scope(runtime, move |cx| {
+        let (this_thread, _) = create_signal(cx, "this thread");
+        assert_eq!(this_thread.get_untracked(), "this thread");
+        assert_eq!(other_thread.try_get_untracked(), None);
+    });
+    runtime.dispose();
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn child_scopes_are_disposed_in_the_order_they_were_created() {
+    use leptos_reactive::on_cleanup;
+    use std::{cell::RefCell, rc::Rc};
+
+    let runtime = create_runtime();
+    let disposed = Rc::new(RefCell::new(Vec::new()));
+    let disposer = create_scope(runtime, {
+        let disposed = Rc::clone(&disposed);
+        move |cx| {
+            let mut children = (0..5)
+                .map(|id| {
+                    let disposed = Rc::clone(&disposed);
+                    cx.run_child_scope(move |cx| {
+                        on_cleanup(cx, move || disposed.borrow_mut().push(id))
+                    })
+                    .1
+                })
+                .collect::<Vec<_>>();
+            // removing a child moves its siblings around, and the rest are
+            // disposed along with the parent
+            children.remove(1).dispose();
+        }
+    });
+    disposer.dispose();
+
+    assert_eq!(*disposed.borrow(), vec![1, 0, 2, 3, 4]);
+    runtime.dispose();
+}
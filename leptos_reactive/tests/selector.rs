@@ -0,0 +1,38 @@
+#[cfg(not(feature = "stable"))]
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_scope, create_selector,
+    create_signal,
+};
+#[cfg(not(feature = "stable"))]
+use std::{cell::Cell, rc::Rc};
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn selector_only_notifies_affected_keys() {
+    create_scope(create_runtime(), |cx| {
+        let (selected, set_selected) = create_signal(cx, 0);
+        let is_selected = create_selector(cx, selected);
+        let runs = Rc::new(Cell::new(0));
+
+        for row in 0..100 {
+            let is_selected = is_selected.clone();
+            let runs = Rc::clone(&runs);
+            create_isomorphic_effect(cx, move |_| {
+                is_selected(row);
+                runs.set(runs.get() + 1);
+            });
+        }
+        assert_eq!(runs.get(), 100);
+
+        // deselects row 0 and selects row 50
+        set_selected(50);
+        assert_eq!(runs.get(), 102);
+        assert!(is_selected(50));
+        assert!(!is_selected(0));
+
+        // rows without a subscriber aren't affected
+        set_selected(500);
+        assert_eq!(runs.get(), 103);
+    })
+    .dispose()
+}
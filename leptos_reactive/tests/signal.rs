@@ -1,5 +1,8 @@
 #[cfg(not(feature = "stable"))]
-use leptos_reactive::{create_runtime, create_scope, create_signal};
+use leptos_reactive::{
+    create_runtime, create_rw_signal, create_scope, create_signal, SignalGet,
+    SignalSet, SignalUpdate,
+};
 
 #[cfg(not(feature = "stable"))]
 #[test]
@@ -28,3 +31,49 @@ fn derived_signals() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn access_after_disposal() {
+    use leptos_reactive::run_scope;
+
+    let runtime = create_runtime();
+    let ((a, set_a), b) = run_scope(runtime, |cx| {
+        (create_signal(cx, 0), create_rw_signal(cx, 0))
+    });
+
+    assert_eq!(a.try_get(), None);
+    assert_eq!(set_a.try_set(1), Some(1));
+    assert_eq!(set_a.try_update(|n| *n += 1), None);
+    assert_eq!(b.try_get(), None);
+    assert_eq!(b.try_set(1), Some(1));
+    assert_eq!(b.try_update(|n| *n += 1), None);
+    runtime.dispose();
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn split_and_join_signals() {
+    use leptos_reactive::{ReadSignal, RwSignal, WriteSignal};
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_rw_signal(cx, 0);
+        let (read, write): (ReadSignal<i32>, WriteSignal<i32>) = count.into();
+        write(1);
+        assert_eq!(read(), 1);
+
+        let joined = RwSignal::try_from((read, write)).unwrap();
+        joined.set(3);
+        assert_eq!(count.get(), 3);
+
+        let (other, set_other) = create_signal(cx, 0);
+        assert!(RwSignal::join(other, write).is_none());
+        assert!(RwSignal::try_from((read, set_other)).is_err());
+
+        let read: ReadSignal<i32> = joined.into();
+        let write: WriteSignal<i32> = joined.into();
+        write(2);
+        assert_eq!(read(), 2);
+    })
+    .dispose()
+}
@@ -0,0 +1,54 @@
+use leptos_reactive::{create_runtime, create_scope, SuspenseContext};
+
+#[test]
+fn all_resolved_counts_pending_resources() {
+    create_scope(create_runtime(), |cx| {
+        let context = SuspenseContext::new(cx);
+        assert!(context.all_resolved());
+
+        context.increment(true);
+        context.increment(false);
+        assert!(!context.all_resolved());
+
+        context.decrement(true);
+        assert!(!context.all_resolved());
+        context.decrement(false);
+        assert!(context.all_resolved());
+        assert!(context.ready());
+
+        // a resource that resolves twice can't make it negative
+        context.decrement(false);
+        context.increment(false);
+        assert!(!context.all_resolved());
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn signal_reads_register_resource_without_current_scope() {
+    use leptos_reactive::{
+        create_resource, provide_context, run_scope_undisposed, Scope,
+        SignalGet, TestExecutor,
+    };
+
+    let runtime = create_runtime();
+    let executor = TestExecutor::new();
+    let ((resource, context), _, disposer) =
+        run_scope_undisposed(runtime, |cx| {
+            let context = SuspenseContext::new(cx);
+            provide_context(cx, context);
+            (create_resource(cx, || (), |_| async { 1 }), context)
+        });
+
+    // e.g., in an event listener
+    assert_eq!(Scope::current(), None);
+    assert_eq!(resource.get(), None);
+    assert!(!context.all_resolved());
+
+    executor.run_until_stalled();
+    assert!(context.all_resolved());
+    assert_eq!(resource.get(), Some(1));
+    disposer.dispose();
+    runtime.dispose();
+}
@@ -0,0 +1,32 @@
+#![cfg(all(feature = "wasm-threads", not(feature = "stable")))]
+
+use leptos_reactive::{
+    create_runtime, create_scope, create_signal, create_threaded_resource,
+    SignalGetUntracked, TestExecutor,
+};
+use std::time::{Duration, Instant};
+
+#[test]
+fn threaded_resource_computes_on_the_pool() {
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let (numbers, _) =
+            create_signal(cx, (1..=1_000_u64).collect::<Vec<_>>());
+        let sum = create_threaded_resource(cx, numbers, |numbers| {
+            (std::thread::current().id(), numbers.iter().sum::<u64>())
+        });
+
+        // the work finishes on another thread, which wakes the fetcher
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while sum.loading().get_untracked() {
+            assert!(Instant::now() < deadline, "the work never finished");
+            executor.run_until_stalled();
+            std::thread::yield_now();
+        }
+
+        let (thread, sum) = sum.read(cx).unwrap();
+        assert_ne!(thread, std::thread::current().id());
+        assert_eq!(sum, 500_500);
+    })
+    .dispose()
+}
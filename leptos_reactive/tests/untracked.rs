@@ -77,3 +77,38 @@ fn untracked_get_doesnt_trigger_effect() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn untracked_reads_of_every_signal_type() {
+    use leptos_reactive::{
+        create_memo, untrack, MaybeSignal, Signal, SignalWithUntracked,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let memo = create_memo(cx, move |_| a.get() * 2);
+        let derived = Signal::derive(cx, move || a.get() * 3);
+        let maybe: MaybeSignal<i32> = memo.into();
+        let closure = move || a.get() * 4;
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                _ = memo.get_untracked();
+                _ = derived.with_untracked(|n| *n);
+                _ = maybe.get_untracked();
+                _ = untrack(cx, closure);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        set_a.set(2);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(untrack(cx, closure), 8);
+    })
+    .dispose()
+}
@@ -1,9 +1,10 @@
 use crate::{ServerFn, ServerFnError};
 use leptos_reactive::{
-    create_rw_signal, signal_prelude::*, spawn_local, store_value, ReadSignal,
-    RwSignal, Scope, StoredValue,
+    create_rw_signal, invalidate_resources, retry, signal_prelude::*,
+    spawn_local, store_value, LoadingGuard, ReadSignal, RetryPolicy, RwSignal,
+    Scope, StoredValue,
 };
-use std::{future::Future, pin::Pin, rc::Rc};
+use std::{borrow::Cow, future::Future, pin::Pin, rc::Rc};
 
 /// An action synchronizes an imperative `async` call to the synchronous reactive system.
 ///
@@ -164,6 +165,41 @@ where
     }
 }
 
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Refetches every resource tagged with one of these cache keys (see
+    /// [Resource::with_cache_key](leptos_reactive::Resource::with_cache_key))
+    /// each time the action resolves to `Ok`.
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// # async fn load_todos() -> Vec<String> { vec![] }
+    /// # async fn add_todo(title: String) -> Result<(), ServerFnError> { Ok(()) }
+    /// # if false {
+    /// let todos =
+    ///     create_resource(cx, || (), |_| load_todos()).with_cache_key("todos");
+    /// let add_todo = create_action(cx, |title: &String| add_todo(title.clone()))
+    ///     .invalidates(["todos"]);
+    /// # }
+    /// # });
+    /// ```
+    pub fn invalidates<K>(self, keys: impl IntoIterator<Item = K>) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        self.0.update_value(|state| {
+            state.invalidates.extend(keys.into_iter().map(Into::into));
+            state.succeeded = Result::is_ok;
+        });
+        self
+    }
+}
+
 impl<I, O> Clone for Action<I, O>
 where
     I: 'static,
@@ -186,6 +222,7 @@ where
     I: 'static,
     O: 'static,
 {
+    cx: Scope,
     /// How many times the action has successfully resolved.
     pub version: RwSignal<usize>,
     /// The current argument that was dispatched to the `async` function.
@@ -195,6 +232,9 @@ where
     pub value: RwSignal<Option<O>>,
     pending: RwSignal<bool>,
     url: Option<String>,
+    /// The cache keys of the resources refetched after each success.
+    invalidates: Vec<Cow<'static, str>>,
+    succeeded: fn(&O) -> bool,
     #[allow(clippy::complexity)]
     action_fn: Rc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O>>>>,
 }
@@ -216,13 +256,25 @@ where
         let version = self.version;
         let pending = self.pending;
         let value = self.value;
+        let cx = self.cx;
+        let invalidates = self.invalidates.clone();
+        let succeeded = self.succeeded;
         pending.set(true);
+        let loading_guard = LoadingGuard::new(cx);
         spawn_local(async move {
             let new_value = fut.await;
-            value.set(Some(new_value));
-            input.set(None);
-            pending.set(false);
-            version.update(|n| *n += 1);
+            let succeeded = succeeded(&new_value);
+            // the action may have been disposed while the Future was running
+            _ = value.try_set(Some(new_value));
+            _ = input.try_set(None);
+            _ = pending.try_set(false);
+            _ = version.try_update(|n| *n += 1);
+            if succeeded {
+                for key in &invalidates {
+                    invalidate_resources(cx, key);
+                }
+            }
+            drop(loading_guard);
         })
     }
 }
@@ -324,11 +376,14 @@ where
     Action(store_value(
         cx,
         ActionState {
+            cx,
             version,
             url: None,
             input,
             value,
             pending,
+            invalidates: Vec::new(),
+            succeeded: |_| true,
             action_fn,
         },
     ))
@@ -358,9 +413,47 @@ pub fn create_server_action<S>(
 where
     S: Clone + ServerFn,
 {
-    #[cfg(feature = "ssr")]
-    let c = move |args: &S| S::call_fn(args.clone(), cx);
-    #[cfg(not(feature = "ssr"))]
-    let c = move |args: &S| S::call_fn_client(args.clone(), cx);
+    let c = move |args: &S| S::call(args.clone(), cx);
+    create_action(cx, c).using_server_fn::<S>()
+}
+
+/// Creates an [Action] that calls a server function, calling it again if it
+/// fails, as described by the [RetryPolicy](leptos_reactive::RetryPolicy).
+///
+/// The action stays pending while the call is retried, and its value is set to
+/// the last error once every attempt has failed.
+///
+/// ```rust
+/// # use leptos::*;
+///
+/// #[server(MyServerFn)]
+/// async fn my_server_fn() -> Result<(), ServerFnError> {
+///     todo!()
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// // only retry when the server could not be reached
+/// let policy = RetryPolicy::default()
+///     .retry_on(|e| matches!(e, ServerFnError::Request(_)));
+/// let my_server_action =
+///     create_server_action_with_retry::<MyServerFn>(cx, policy);
+/// # });
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+pub fn create_server_action_with_retry<S>(
+    cx: Scope,
+    policy: RetryPolicy<ServerFnError>,
+) -> Action<S, Result<S::Output, ServerFnError>>
+where
+    S: Clone + ServerFn,
+{
+    let c = move |args: &S| {
+        let args = args.clone();
+        let policy = policy.clone();
+        async move { retry(&policy, || S::call(args.clone(), cx)).await }
+    };
     create_action(cx, c).using_server_fn::<S>()
 }
@@ -0,0 +1,55 @@
+use leptos_reactive::Scope;
+pub use server_fn::csrf::{
+    csrf_error, csrf_policy, set_csrf_policy, CsrfCheck, CsrfPolicy,
+    CSRF_COOKIE, CSRF_FIELD, CSRF_HEADER,
+};
+
+/// The CSRF token the server gave the browser that made the current request.
+///
+/// The server integrations provide this as context while rendering, if a
+/// [CsrfPolicy] is enabled. Use [use_csrf_token] to read it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrfToken(pub String);
+
+/// Returns the [CsrfPolicy] for the current request: one provided as context,
+/// e.g., in the `additional_context` of a server integration, or else the one
+/// set with [set_csrf_policy].
+///
+/// The server integrations use this both to decide whether to give the
+/// browser a token and to check the requests that call server functions, so
+/// that a token is always issued when it will be checked.
+pub fn use_csrf_policy(cx: Scope) -> CsrfPolicy {
+    leptos_reactive::use_context::<CsrfPolicy>(cx).unwrap_or_else(csrf_policy)
+}
+
+/// Returns the CSRF token that a form calling a server function should send
+/// in its [CSRF_FIELD] field, or `None` if CSRF protection is not enabled.
+///
+/// `<ActionForm/>` and `<MultiActionForm/>` already include it, so this is
+/// only needed for a hand-written `<form>` that posts to a server function.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn DeleteAccount(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///       <form method="post" action="/api/delete_account">
+///         {use_csrf_token(cx).map(|token| view! { cx,
+///           <input type="hidden" name=CSRF_FIELD value=token/>
+///         })}
+///         <input type="submit" value="Delete my account"/>
+///       </form>
+///     }
+/// }
+/// ```
+pub fn use_csrf_token(cx: Scope) -> Option<String> {
+    #[cfg(feature = "ssr")]
+    {
+        leptos_reactive::use_context::<CsrfToken>(cx).map(|token| token.0)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        _ = cx;
+        server_fn::csrf::browser_csrf_token()
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{create_server_action, Action, ServerFn, ServerFnError};
+use leptos_reactive::{
+    create_memo, create_rw_signal, Memo, RwSignal, Scope, Signal, SignalGet,
+    SignalSet, SignalSetter, SignalUpdate, SignalWith, SignalWithUntracked,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Validation errors, with one message per field name. Errors that do not
+/// belong to one field are stored under [FORM_ERROR].
+pub type FieldErrors = HashMap<String, String>;
+
+/// The key in [FieldErrors] for errors that do not belong to one field: the
+/// values of the form could not be deserialized for a reason other than the
+/// value of one field, or the server function returned an error.
+pub const FORM_ERROR: &str = "";
+
+/// Checks whether the arguments of a server function are valid, before
+/// [FormState] sends them to the server. Implement it by hand, or by calling a
+/// validation library like `validator` or `garde`.
+///
+/// The server function should validate its arguments again, as requests can
+/// be sent without the form.
+pub trait Validate {
+    /// Returns an error message for each invalid field.
+    fn validate(&self) -> Result<(), FieldErrors>;
+}
+
+/// The reactive state of a form, created with [create_form], whose values
+/// are the arguments of the server function `T`.
+///
+/// Each field is identified by the name of the argument. Its value is kept
+/// as text, like the value of an `<input>`, and deserialized into `T` (in
+/// the same way as a form submitted to the server function) whenever it
+/// changes, so that errors are always up to date.
+pub struct FormState<T>
+where
+    T: ServerFn + Clone + 'static,
+{
+    cx: Scope,
+    values: RwSignal<BTreeMap<String, String>>,
+    touched: RwSignal<HashSet<String>>,
+    submitted: RwSignal<bool>,
+    errors: Memo<FieldErrors>,
+    action: Action<T, Result<T::Output, ServerFnError>>,
+}
+
+impl<T> Clone for FormState<T>
+where
+    T: ServerFn + Clone + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FormState<T> where T: ServerFn + Clone + 'static {}
+
+/// The state of one field of a [FormState], returned by [FormState::field].
+#[derive(Clone, Copy)]
+pub struct FormField {
+    /// The current value of the field.
+    pub value: Signal<String>,
+    /// Sets the value of the field, e.g., in an `on:input` handler.
+    pub set_value: SignalSetter<String>,
+    /// The error message for the current value, if it is invalid.
+    pub error: Signal<Option<String>>,
+    /// Whether the user has left the field (see [FormState::touch]) or tried to
+    /// submit the form. Errors are usually only shown once this is `true`.
+    pub touched: Signal<bool>,
+}
+
+impl<T> FormState<T>
+where
+    T: ServerFn + Validate + Clone + 'static,
+{
+    /// The current value of the field with the given name.
+    pub fn value(&self, name: &str) -> String {
+        self.values
+            .with(|values| values.get(name).cloned().unwrap_or_default())
+    }
+
+    /// Sets the value of the field with the given name. This clears the
+    /// error returned by the last call to the server function, which may no
+    /// longer apply to the new values.
+    pub fn set_value(&self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        self.values.update(|values| {
+            values.insert(name.to_string(), value);
+        });
+        self.clear_server_error();
+    }
+
+    /// Marks the field with the given name as touched, e.g., in an
+    /// `on:blur` handler.
+    pub fn touch(&self, name: &str) {
+        if !self
+            .touched
+            .with_untracked(|touched| touched.contains(name))
+        {
+            self.touched.update(|touched| {
+                touched.insert(name.to_string());
+            });
+        }
+    }
+
+    /// Whether the field with the given name is touched, or the user has
+    /// tried to submit the form.
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.submitted.get()
+            || self.touched.with(|touched| touched.contains(name))
+    }
+
+    /// The error for the field with the given name, or, for [FORM_ERROR],
+    /// the error for the whole form, including an error returned by the
+    /// server function.
+    pub fn error(&self, name: &str) -> Option<String> {
+        if name == FORM_ERROR {
+            let server_error = self.action.value().with(|value| match value {
+                Some(Err(e)) => Some(e.to_string()),
+                _ => None,
+            });
+            if server_error.is_some() {
+                return server_error;
+            }
+        }
+        self.errors.with(|errors| errors.get(name).cloned())
+    }
+
+    /// All current errors.
+    pub fn errors(&self) -> Memo<FieldErrors> {
+        self.errors
+    }
+
+    /// Whether every field is valid.
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(|errors| errors.is_empty())
+    }
+
+    /// Returns the signals for the field with the given name.
+    pub fn field(&self, name: &str) -> FormField {
+        let form = *self;
+        let name = name.to_string();
+        FormField {
+            value: Signal::derive(self.cx, {
+                let name = name.clone();
+                move || form.value(&name)
+            }),
+            set_value: SignalSetter::map(self.cx, {
+                let name = name.clone();
+                move |value| form.set_value(&name, value)
+            }),
+            error: Signal::derive(self.cx, {
+                let name = name.clone();
+                move || form.error(&name)
+            }),
+            touched: Signal::derive(self.cx, move || form.is_touched(&name)),
+        }
+    }
+
+    /// Sends the values to the server function if they are valid. Otherwise,
+    /// marks every field as touched, so that all errors are shown.
+    pub fn submit(&self) {
+        self.submitted.set(true);
+        if !self.errors.with_untracked(|errors| errors.is_empty()) {
+            return;
+        }
+        if let Ok(args) = self.values.with_untracked(deserialize::<T>) {
+            self.action.dispatch(args);
+        }
+    }
+
+    /// Clears every value, whether fields have been touched, and the value
+    /// returned by the server function.
+    pub fn reset(&self) {
+        self.values.set(BTreeMap::new());
+        self.touched.set(HashSet::new());
+        self.submitted.set(false);
+        self.action.value().set(None);
+    }
+
+    /// The [Action] that calls the server function, for its `pending` and
+    /// `value` signals.
+    pub fn action(&self) -> Action<T, Result<T::Output, ServerFnError>> {
+        self.action
+    }
+
+    fn clear_server_error(&self) {
+        let value = self.action.value();
+        if value.with_untracked(|value| matches!(value, Some(Err(_)))) {
+            value.set(None);
+        }
+    }
+}
+
+/// Creates a [FormState] with empty values for the arguments of the server
+/// function `T`, which is called with them when the form is submitted and
+/// its values are valid.
+///
+/// ```
+/// # use leptos::*;
+/// # use std::collections::HashMap;
+/// #[server(SignUp, "/api")]
+/// async fn sign_up(email: String, age: u8) -> Result<(), ServerFnError> {
+///     Ok(())
+/// }
+///
+/// impl Validate for SignUp {
+///     fn validate(&self) -> Result<(), FieldErrors> {
+///         let mut errors = HashMap::new();
+///         if !self.email.contains('@') {
+///             errors.insert("email".into(), "Enter an email address".into());
+///         }
+///         if self.age < 18 {
+///             errors.insert("age".into(), "You must be 18 or older".into());
+///         }
+///         if errors.is_empty() {
+///             Ok(())
+///         } else {
+///             Err(errors)
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn SignUpForm(cx: Scope) -> impl IntoView {
+///     let form = create_form::<SignUp>(cx);
+///     let email = form.field("email");
+///
+///     view! { cx,
+///       <form on:submit=move |ev| {
+///         ev.prevent_default();
+///         form.submit();
+///       }>
+///         <input
+///           prop:value=move || email.value.get()
+///           on:input=move |ev| email.set_value.set(event_target_value(&ev))
+///           on:blur=move |_| form.touch("email")
+///         />
+///         <FieldError form=form field="email"/>
+///         <input
+///           type="number"
+///           on:input=move |ev| form.set_value("age", event_target_value(&ev))
+///           on:blur=move |_| form.touch("age")
+///         />
+///         <FieldError form=form field="age"/>
+///         <FieldError form=form field=FORM_ERROR/>
+///         <button type="submit">"Sign up"</button>
+///       </form>
+///     }
+/// }
+/// ```
+pub fn create_form<T>(cx: Scope) -> FormState<T>
+where
+    T: ServerFn + Validate + Clone + 'static,
+{
+    create_form_with_values(cx, BTreeMap::new())
+}
+
+/// Creates a [FormState] whose fields start with the given values, e.g., to
+/// edit an existing record.
+pub fn create_form_with_values<T>(
+    cx: Scope,
+    values: BTreeMap<String, String>,
+) -> FormState<T>
+where
+    T: ServerFn + Validate + Clone + 'static,
+{
+    let values = create_rw_signal(cx, values);
+    let errors = create_memo(cx, move |_| values.with(field_errors::<T>));
+
+    FormState {
+        cx,
+        values,
+        touched: create_rw_signal(cx, HashSet::new()),
+        submitted: create_rw_signal(cx, false),
+        errors,
+        action: create_server_action::<T>(cx),
+    }
+}
+
+/// Validates the values, or, if they cannot be deserialized, finds the fields
+/// whose values are invalid.
+fn field_errors<T: ServerFn + Validate>(
+    values: &BTreeMap<String, String>,
+) -> FieldErrors {
+    let error = match deserialize::<T>(values) {
+        Ok(args) => return args.validate().err().unwrap_or_default(),
+        Err(e) => e,
+    };
+
+    // serde_qs doesn't say which field failed, but the value of a field is
+    // deserialized before missing fields are reported, so a field whose value
+    // is invalid fails on its own with another error
+    let mut errors = values
+        .iter()
+        .filter_map(|(name, value)| {
+            let field = BTreeMap::from([(name.clone(), value.clone())]);
+            match deserialize::<T>(&field) {
+                Err(e) if missing_field(&e).is_none() => {
+                    Some((name.clone(), e))
+                }
+                _ => None,
+            }
+        })
+        .collect::<FieldErrors>();
+    if errors.is_empty() {
+        let name = missing_field(&error).unwrap_or(FORM_ERROR).to_string();
+        errors.insert(name, error);
+    }
+    errors
+}
+
+/// The name of the missing field in a deserialization error, if it is one.
+fn missing_field(error: &str) -> Option<&str> {
+    let (_, rest) = error.split_once("missing field `")?;
+    rest.split_once('`').map(|(name, _)| name)
+}
+
+/// Deserializes the values in the same way as a form that is submitted to a
+/// server function.
+fn deserialize<T: ServerFn>(
+    values: &BTreeMap<String, String>,
+) -> Result<T, String> {
+    let query = serde_qs::to_string(values).map_err(|e| e.to_string())?;
+    serde_qs::from_str(&query).map_err(|e| e.to_string())
+}
@@ -80,11 +80,22 @@
 //!   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
 
 use leptos_reactive::*;
-pub use server_fn::{Encoding, Payload, ServerFnError};
+pub use server_fn::{
+    auth::{authorize, clear_server_fn_auth, set_server_fn_auth, AuthRequest},
+    limits::{
+        server_fn_limits, set_server_fn_limits, MemoryRateLimitStore,
+        RateLimit, RateLimitStore, ServerFnLimits, UnknownClient,
+    },
+    Encoding, FileBody, FileResponse, Payload, ServerFnError,
+};
 
 mod action;
+mod csrf;
+mod form;
 mod multi_action;
 pub use action::*;
+pub use csrf::*;
+pub use form::*;
 pub use multi_action::*;
 extern crate tracing;
 
@@ -1,7 +1,7 @@
 use crate::{ServerFn, ServerFnError};
 use leptos_reactive::{
-    create_rw_signal, signal_prelude::*, spawn_local, store_value, ReadSignal,
-    RwSignal, Scope, StoredValue,
+    create_rw_signal, signal_prelude::*, spawn_local, store_value,
+    LoadingGuard, ReadSignal, RwSignal, Scope, StoredValue,
 };
 use std::{future::Future, pin::Pin, rc::Rc};
 
@@ -233,16 +233,21 @@ where
         let pending = submission.pending;
         let value = submission.value;
         let version = self.version;
+        let loading_guard = LoadingGuard::new(cx);
 
         spawn_local(async move {
             let new_value = fut.await;
-            let canceled = cx.untrack(move || canceled.get());
+            // the submission may have been disposed while the Future was
+            // running, in which case it's treated as canceled
+            let canceled =
+                cx.untrack(move || canceled.try_get().unwrap_or(true));
             if !canceled {
-                value.set(Some(new_value));
+                _ = value.try_set(Some(new_value));
             }
-            input.set(None);
-            pending.set(false);
-            version.update(|n| *n += 1);
+            _ = input.try_set(None);
+            _ = pending.try_set(false);
+            _ = version.try_update(|n| *n += 1);
+            drop(loading_guard);
         })
     }
 
@@ -360,9 +365,6 @@ pub fn create_server_multi_action<S>(
 where
     S: Clone + ServerFn,
 {
-    #[cfg(feature = "ssr")]
-    let c = move |args: &S| S::call_fn(args.clone(), cx);
-    #[cfg(not(feature = "ssr"))]
-    let c = move |args: &S| S::call_fn_client(args.clone(), cx);
+    let c = move |args: &S| S::call(args.clone(), cx);
     create_multi_action(cx, c).using_server_fn::<S>()
 }
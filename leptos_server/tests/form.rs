@@ -0,0 +1,153 @@
+use leptos_reactive::{
+    create_runtime, create_scope, Scope, SignalGetUntracked, SignalSet,
+};
+use leptos_server::{
+    create_form, create_form_with_values, FieldErrors, ServerFnError, Validate,
+    FORM_ERROR,
+};
+use serde::{Deserialize, Serialize};
+use server_fn::{Encoding, ServerFn};
+use std::{collections::BTreeMap, future::Future, pin::Pin};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SignUp {
+    email: String,
+    age: u8,
+}
+
+impl ServerFn<Scope> for SignUp {
+    type Output = ();
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "sign_up"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::Url
+    }
+
+    #[cfg(feature = "ssr")]
+    fn call_fn(
+        self,
+        _cx: Scope,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    fn call_fn_client(
+        self,
+        _cx: Scope,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl Validate for SignUp {
+    fn validate(&self) -> Result<(), FieldErrors> {
+        if self.email.contains('@') {
+            Ok(())
+        } else {
+            Err(FieldErrors::from([(
+                "email".to_string(),
+                "Enter an email address".to_string(),
+            )]))
+        }
+    }
+}
+
+fn values(values: &[(&str, &str)]) -> BTreeMap<String, String> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn validates_deserialized_values() {
+    create_scope(create_runtime(), |cx| {
+        let form = create_form_with_values::<SignUp>(
+            cx,
+            values(&[("email", "me"), ("age", "20")]),
+        );
+        assert_eq!(
+            form.error("email").as_deref(),
+            Some("Enter an email address")
+        );
+        assert_eq!(form.error("age"), None);
+
+        form.set_value("email", "me@example.com");
+        assert!(form.is_valid());
+    })
+    .dispose()
+}
+
+#[test]
+fn invalid_value_is_an_error_of_its_field() {
+    create_scope(create_runtime(), |cx| {
+        let form = create_form_with_values::<SignUp>(
+            cx,
+            values(&[("email", "me@example.com"), ("age", "twenty")]),
+        );
+        assert!(form.error("age").is_some());
+        assert_eq!(form.error("email"), None);
+        assert_eq!(form.error(FORM_ERROR), None);
+
+        // the invalid field is reported before missing ones
+        form.reset();
+        form.set_value("age", "-1");
+        assert!(form.error("age").is_some());
+        assert_eq!(form.error("email"), None);
+    })
+    .dispose()
+}
+
+#[test]
+fn missing_value_is_an_error_of_its_field() {
+    create_scope(create_runtime(), |cx| {
+        let form = create_form::<SignUp>(cx);
+        form.set_value("email", "me@example.com");
+        assert!(form.error("age").is_some());
+        assert_eq!(form.error(FORM_ERROR), None);
+    })
+    .dispose()
+}
+
+#[test]
+fn editing_clears_server_error() {
+    create_scope(create_runtime(), |cx| {
+        let form = create_form_with_values::<SignUp>(
+            cx,
+            values(&[("email", "me@example.com"), ("age", "20")]),
+        );
+        form.action()
+            .value()
+            .set(Some(Err(ServerFnError::ServerError("taken".into()))));
+        assert!(form.error(FORM_ERROR).is_some());
+
+        form.set_value("email", "you@example.com");
+        assert_eq!(form.error(FORM_ERROR), None);
+        assert!(form.action().value().get_untracked().is_none());
+    })
+    .dispose()
+}
+
+#[test]
+fn reset_clears_server_value() {
+    create_scope(create_runtime(), |cx| {
+        let form = create_form::<SignUp>(cx);
+        form.set_value("email", "me@example.com");
+        form.touch("email");
+        form.action().value().set(Some(Ok(())));
+
+        form.reset();
+        assert_eq!(form.value("email"), "");
+        assert!(!form.is_touched("email"));
+        assert!(form.action().value().get_untracked().is_none());
+    })
+    .dispose()
+}
@@ -0,0 +1,50 @@
+use leptos_reactive::{
+    create_runtime, create_scope, use_global_loading, SignalGetUntracked,
+    TestExecutor,
+};
+use leptos_server::{create_action, create_multi_action};
+
+#[test]
+fn global_loading_counts_dispatched_actions() {
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let loading = use_global_loading(cx);
+        let action = create_action(cx, |n: &u32| {
+            let n = *n;
+            async move { n * 2 }
+        });
+
+        action.dispatch(1);
+        action.dispatch(2);
+        assert_eq!(loading.get_untracked(), 2);
+        assert!(action.pending().get_untracked());
+
+        executor.run_until_stalled();
+        assert_eq!(loading.get_untracked(), 0);
+        assert_eq!(action.value().get_untracked(), Some(4));
+        assert_eq!(action.version().get_untracked(), 2);
+    })
+    .dispose()
+}
+
+#[test]
+fn global_loading_counts_multi_action_submissions() {
+    create_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        let loading = use_global_loading(cx);
+        let action = create_multi_action(cx, |n: &u32| {
+            let n = *n;
+            async move { n * 2 }
+        });
+
+        action.dispatch(1);
+        assert_eq!(loading.get_untracked(), 1);
+        action.dispatch(2);
+        assert_eq!(loading.get_untracked(), 2);
+
+        executor.run_until_stalled();
+        assert_eq!(loading.get_untracked(), 0);
+        assert_eq!(action.version().get_untracked(), 2);
+    })
+    .dispose()
+}
@@ -0,0 +1,204 @@
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+//! Utilities for testing Leptos components in the browser, with
+//! [`wasm-bindgen-test`](https://docs.rs/wasm-bindgen-test).
+//!
+//! Enable the `csr` feature on `leptos` in your tests, so that views are
+//! actually rendered into the DOM.
+//!
+//! ```no_run
+//! use leptos::*;
+//! use leptos_test::*;
+//!
+//! // #[wasm_bindgen_test]
+//! async fn counter_increments() {
+//!     let app = mount_to_body_for_test(|cx| {
+//!         let (count, set_count) = create_signal(cx, 0);
+//!         view! { cx,
+//!             <button on:click=move |_| set_count.update(|n| *n += 1)>
+//!                 {count}
+//!             </button>
+//!         }
+//!     });
+//!
+//!     let button = app.get::<web_sys::HtmlElement>("button");
+//!     click(&button);
+//!     tick().await;
+//!
+//!     assert_eq!(button.text_content().unwrap(), "1");
+//! }
+//! ```
+
+use leptos::{IntoView, RuntimeId, Scope};
+use wasm_bindgen::JsCast;
+
+/// A view that has been mounted for a test. When this is dropped, the view is
+/// removed from the document and its reactive system is disposed.
+#[derive(Debug)]
+pub struct TestApp {
+    container: web_sys::HtmlElement,
+    runtime: RuntimeId,
+}
+
+/// Mounts the view returned by `f` in a new `<div>` at the end of the
+/// document's `<body>`, so that it can be queried and interacted with.
+pub fn mount_to_body_for_test<F, N>(f: F) -> TestApp
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    let container = leptos::document()
+        .create_element("div")
+        .expect("to create a test container")
+        .unchecked_into::<web_sys::HtmlElement>();
+    leptos::document()
+        .body()
+        .expect("body element to exist")
+        .append_child(&container)
+        .expect("to append the test container");
+
+    let runtime = leptos::mount_to_with_runtime(container.clone(), f);
+
+    TestApp { container, runtime }
+}
+
+impl TestApp {
+    /// The element the view was mounted in.
+    pub fn container(&self) -> &web_sys::HtmlElement {
+        &self.container
+    }
+
+    /// The text content of the whole view.
+    pub fn text(&self) -> String {
+        self.container.text_content().unwrap_or_default()
+    }
+
+    /// Returns the first element in the view that matches the CSS `selector`,
+    /// if it is of type `E`.
+    pub fn query<E: JsCast>(&self, selector: &str) -> Option<E> {
+        self.container
+            .query_selector(selector)
+            .ok()
+            .flatten()
+            .and_then(|el| el.dyn_into().ok())
+    }
+
+    /// Returns every element in the view that matches the CSS `selector` and
+    /// is of type `E`.
+    pub fn query_all<E: JsCast>(&self, selector: &str) -> Vec<E> {
+        let nodes = match self.container.query_selector_all(selector) {
+            Ok(nodes) => nodes,
+            Err(_) => return Vec::new(),
+        };
+        (0..nodes.length())
+            .filter_map(|idx| nodes.item(idx))
+            .filter_map(|node| node.dyn_into().ok())
+            .collect()
+    }
+
+    /// Returns the first element in the view that matches the CSS `selector`.
+    ///
+    /// # Panics
+    /// Panics if there is no such element, or it is not of type `E`.
+    #[track_caller]
+    pub fn get<E: JsCast>(&self, selector: &str) -> E {
+        match self.query(selector) {
+            Some(el) => el,
+            None => panic!(
+                "no element of type {} matches `{selector}`",
+                std::any::type_name::<E>()
+            ),
+        }
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        self.container.remove();
+        self.runtime.dispose();
+    }
+}
+
+/// Dispatches a `click` event on the element.
+pub fn click(el: &web_sys::Element) {
+    let mut init = web_sys::MouseEventInit::new();
+    init.bubbles(true).cancelable(true);
+    let event =
+        web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &init)
+            .expect("to create a click event");
+    dispatch(el, &event);
+}
+
+/// Sets the value of an `<input>`, `<textarea>`, or `<select>` element and
+/// dispatches an `input` event on it, as if the user had typed the value.
+///
+/// # Panics
+/// Panics if the element is not one of those.
+#[track_caller]
+pub fn input(el: &web_sys::Element, value: &str) {
+    if let Some(input) = el.dyn_ref::<web_sys::HtmlInputElement>() {
+        input.set_value(value);
+    } else if let Some(textarea) = el.dyn_ref::<web_sys::HtmlTextAreaElement>()
+    {
+        textarea.set_value(value);
+    } else if let Some(select) = el.dyn_ref::<web_sys::HtmlSelectElement>() {
+        select.set_value(value);
+    } else {
+        panic!("`input` can only set the value of a form control");
+    }
+
+    let mut init = web_sys::InputEventInit::new();
+    init.bubbles(true).data(Some(value));
+    let event = web_sys::InputEvent::new_with_event_init_dict("input", &init)
+        .expect("to create an input event");
+    dispatch(el, &event);
+}
+
+/// Dispatches a `submit` event on the form, without navigating away from the
+/// page if nothing prevents the default behavior.
+pub fn submit(form: &web_sys::HtmlFormElement) {
+    let mut init = web_sys::EventInit::new();
+    init.bubbles(true).cancelable(true);
+    let event = web_sys::Event::new_with_event_init_dict("submit", &init)
+        .expect("to create a submit event");
+    dispatch(form, &event);
+}
+
+fn dispatch(target: &web_sys::EventTarget, event: &web_sys::Event) {
+    target.dispatch_event(event).expect("to dispatch the event");
+}
+
+/// Waits until every microtask that has already been queued has run,
+/// including deferred effects and the futures of resources that have already
+/// resolved.
+///
+/// This doesn't wait for a timer, so it doesn't depend on how long anything
+/// takes, but work that is queued by those microtasks may need another tick.
+pub async fn tick() {
+    // microtasks run in the order they were queued, so this one resolves
+    // after all the others
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        leptos::queue_microtask(move || {
+            _ = resolve.call0(&wasm_bindgen::JsValue::UNDEFINED);
+        });
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Waits, a [tick] at a time, until the `condition` is true, for example
+/// until a resource has loaded.
+///
+/// # Panics
+/// Panics if the `condition` is still false after `max_ticks` ticks.
+pub async fn wait_until(max_ticks: usize, condition: impl Fn() -> bool) {
+    for _ in 0..max_ticks {
+        if condition() {
+            return;
+        }
+        tick().await;
+    }
+    if !condition() {
+        panic!("condition was still false after {max_ticks} ticks");
+    }
+}
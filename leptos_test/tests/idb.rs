@@ -0,0 +1,62 @@
+#![cfg(target_arch = "wasm32")]
+
+use leptos::*;
+use leptos_test::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// databases outlive the test, so each run uses new ones
+fn db_name(name: &str) -> String {
+    format!("leptos-test-{name}-{}", js_sys::Date::now())
+}
+
+#[wasm_bindgen_test]
+async fn concurrent_first_use_opens_database_once() {
+    let upgrades = Rc::new(RefCell::new(0));
+    let store =
+        IdbStore::new(&db_name("concurrent"), 1, "drafts").on_upgrade({
+            let upgrades = Rc::clone(&upgrades);
+            move |_| *upgrades.borrow_mut() += 1
+        });
+
+    let results = Rc::new(RefCell::new(Vec::new()));
+    for key in ["a", "b", "c"] {
+        let store = store.clone();
+        let results = Rc::clone(&results);
+        spawn_local(async move {
+            let result = store.put(key, &key.to_string()).await;
+            results.borrow_mut().push(result);
+        });
+    }
+    wait_until(100, || results.borrow().len() == 3).await;
+
+    assert!(results.borrow().iter().all(Result::is_ok));
+    assert_eq!(*upgrades.borrow(), 1);
+    assert_eq!(store.keys().await.unwrap(), ["a", "b", "c"]);
+    store.clear().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn open_store_does_not_block_upgrade() {
+    let name = db_name("upgrade");
+    let old = IdbStore::new(&name, 1, "drafts");
+    old.put("post-1", &"Hello".to_string()).await.unwrap();
+
+    // the old connection is still open, and is closed for the upgrade
+    let new = IdbStore::new(&name, 2, "drafts");
+    let upgraded = Rc::new(RefCell::new(None));
+    spawn_local({
+        let upgraded = Rc::clone(&upgraded);
+        async move {
+            let draft = new.get::<String>("post-1").await;
+            *upgraded.borrow_mut() = Some(draft);
+        }
+    });
+    wait_until(100, || upgraded.borrow().is_some()).await;
+
+    assert_eq!(upgraded.take().unwrap().unwrap().as_deref(), Some("Hello"));
+    // the database now has a newer version than the old store asks for
+    assert!(matches!(old.keys().await, Err(IdbError::Js(_))));
+}
@@ -0,0 +1,111 @@
+#![cfg(target_arch = "wasm32")]
+
+use leptos::*;
+use leptos_test::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn click_runs_event_handler() {
+    let app = mount_to_body_for_test(|cx| {
+        let (count, set_count) = create_signal(cx, 0);
+        view! { cx,
+            <button on:click=move |_| set_count.update(|n| *n += 1)>
+                {count}
+            </button>
+        }
+    });
+
+    let button = app.get::<web_sys::HtmlElement>("button");
+    click(&button);
+    click(&button);
+    assert_eq!(app.text(), "2");
+}
+
+#[wasm_bindgen_test]
+fn input_sets_value_and_dispatches_event() {
+    let app = mount_to_body_for_test(|cx| {
+        let (name, set_name) = create_signal(cx, String::new());
+        view! { cx,
+            <input on:input=move |ev| set_name.set(event_target_value(&ev))/>
+            <p>{name}</p>
+        }
+    });
+
+    let field = app.get::<web_sys::HtmlInputElement>("input");
+    input(&field, "Leptos");
+    assert_eq!(field.value(), "Leptos");
+    assert_eq!(
+        app.get::<web_sys::HtmlElement>("p").text_content().unwrap(),
+        "Leptos"
+    );
+}
+
+#[wasm_bindgen_test]
+fn queries_only_match_inside_the_view() {
+    let outside = mount_to_body_for_test(|cx| view! { cx, <li>"outside"</li> });
+    let app = mount_to_body_for_test(|cx| {
+        view! { cx, <ul><li>"a"</li><li>"b"</li></ul> }
+    });
+
+    assert_eq!(app.query_all::<web_sys::HtmlElement>("li").len(), 2);
+    assert!(app.query::<web_sys::HtmlElement>("p").is_none());
+    // an element of the wrong type doesn't match
+    assert!(app.query::<web_sys::HtmlInputElement>("ul").is_none());
+    drop(outside);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn get_panics_without_a_match() {
+    let app = mount_to_body_for_test(|cx| view! { cx, <p/> });
+    _ = app.get::<web_sys::HtmlElement>("button");
+}
+
+#[wasm_bindgen_test]
+fn dropping_app_removes_it_from_document() {
+    let app = mount_to_body_for_test(|cx| view! { cx, <p id="dropped"/> });
+    let container = app.container().clone();
+    assert!(container.is_connected());
+
+    drop(app);
+    assert!(!container.is_connected());
+    assert!(document().get_element_by_id("dropped").is_none());
+}
+
+#[wasm_bindgen_test]
+async fn tick_runs_queued_microtasks() {
+    let ran = Rc::new(Cell::new(false));
+    queue_microtask({
+        let ran = Rc::clone(&ran);
+        move || ran.set(true)
+    });
+    assert!(!ran.get());
+
+    tick().await;
+    assert!(ran.get());
+}
+
+#[wasm_bindgen_test]
+async fn wait_until_resource_has_loaded() {
+    let loaded = Rc::new(Cell::new(false));
+    let app = mount_to_body_for_test({
+        let loaded = Rc::clone(&loaded);
+        move |cx| {
+            let data = create_local_resource(cx, || (), |_| async { 42 });
+            create_effect(cx, move |_| {
+                if data.read(cx).is_some() {
+                    loaded.set(true);
+                }
+            });
+            view! { cx,
+                <p>{move || data.read(cx).map(|n| n.to_string())}</p>
+            }
+        }
+    });
+
+    wait_until(10, || loaded.get()).await;
+    assert_eq!(app.text(), "42");
+}
@@ -0,0 +1,159 @@
+use indexmap::IndexMap;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// The attributes set on the `<html>` or `<body>` element by the
+/// [`<Html/>`](crate::Html) or [`<Body/>`](crate::Body) components that are
+/// currently mounted.
+///
+/// Each component adds its attributes when it is created and removes them
+/// when it is unmounted. If several components set the same attribute, the
+/// most recently created one wins, except for `class`, whose values are all
+/// kept.
+#[derive(Clone, Default)]
+pub(crate) struct ElementAttributes {
+    next_id: Rc<Cell<usize>>,
+    entries: Rc<RefCell<Vec<Entry>>>,
+    // the values the attributes had before being set by a component, which
+    // are restored once no component sets them
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    original: Rc<RefCell<IndexMap<String, Option<String>>>>,
+}
+
+struct Entry {
+    id: usize,
+    attributes: Vec<(&'static str, TextProp)>,
+    additional: Option<MaybeSignal<AdditionalAttributes>>,
+}
+
+impl ElementAttributes {
+    /// Adds the attributes of a component, and removes them once its scope
+    /// is disposed. `el` returns the element in the browser.
+    #[cfg_attr(
+        not(any(feature = "csr", feature = "hydrate")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn register(
+        &self,
+        cx: Scope,
+        el: fn() -> Option<web_sys::Element>,
+        attributes: Vec<(&'static str, Option<TextProp>)>,
+        additional: Option<MaybeSignal<AdditionalAttributes>>,
+    ) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.entries.borrow_mut().push(Entry {
+            id,
+            attributes: attributes
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value?)))
+                .collect(),
+            additional,
+        });
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                let el = el()
+                    .expect("there to be an <html> or <body> element");
+                create_render_effect(cx, {
+                    let this = self.clone();
+                    let el = el.clone();
+                    move |_| this.update_el(&el)
+                });
+
+                let this = self.clone();
+                on_cleanup(cx, move || {
+                    this.remove(id);
+                    cx.untrack(|| this.update_el(&el));
+                });
+            } else {
+                let this = self.clone();
+                on_cleanup(cx, move || this.remove(id));
+            }
+        }
+    }
+
+    fn remove(&self, id: usize) {
+        self.entries.borrow_mut().retain(|entry| entry.id != id);
+    }
+
+    /// Returns the name and value of each attribute that is set.
+    fn resolve(&self) -> IndexMap<String, String> {
+        let mut resolved = IndexMap::<String, String>::new();
+        let mut set = |name: &str, value: String| {
+            if name == "class" {
+                let class = resolved.entry(name.to_string()).or_default();
+                if !class.is_empty() && !value.is_empty() {
+                    class.push(' ');
+                }
+                class.push_str(&value);
+            } else {
+                resolved.insert(name.to_string(), value);
+            }
+        };
+        for entry in self.entries.borrow().iter() {
+            for (name, value) in &entry.attributes {
+                set(name, value.get());
+            }
+            if let Some(additional) = &entry.additional {
+                additional.with(|additional| {
+                    for (name, value) in additional {
+                        set(name, value.get());
+                    }
+                });
+            }
+        }
+        resolved
+    }
+
+    /// Converts the attributes into an HTML string, starting with a space.
+    pub(crate) fn as_string(&self) -> Option<String> {
+        use leptos::leptos_dom::ssr::escape_attr;
+
+        let resolved = self.resolve();
+        if resolved.is_empty() {
+            None
+        } else {
+            Some(
+                resolved
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(" {name}=\"{}\"", escape_attr(value))
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn update_el(&self, el: &web_sys::Element) {
+        let resolved = self.resolve();
+        let mut original = self.original.borrow_mut();
+        for (name, value) in &resolved {
+            if !original.contains_key(name) {
+                // when hydrating, all the attributes of the element were
+                // rendered by these components on the server
+                let value = if cfg!(feature = "hydrate") {
+                    None
+                } else {
+                    el.get_attribute(name)
+                };
+                original.insert(name.clone(), value);
+            }
+            _ = el.set_attribute(name, value);
+        }
+        original.retain(|name, value| {
+            if resolved.contains_key(name) {
+                return true;
+            }
+            _ = match value {
+                Some(value) => el.set_attribute(name, value),
+                None => el.remove_attribute(name),
+            };
+            false
+        });
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{use_head, TextProp};
+use cfg_if::cfg_if;
+use leptos::*;
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use web_sys::HtmlLinkElement;
+
+// the `id` of the `<link>` rendered on the server, so it can be hydrated
+const ID: &str = "leptos-favicon";
+
+/// Contains the current state of the document's favicon.
+///
+/// Like the [TitleContext](crate::TitleContext), each [`<Favicon/>`](Favicon)
+/// pushes its icon onto a stack, and removes it when it is unmounted, so the
+/// icon it replaced is shown again.
+#[derive(Clone, Default)]
+pub struct FaviconContext {
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    el: Rc<RefCell<Option<HtmlLinkElement>>>,
+    // the `href` and `type` of a favicon that was already in the document
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fallback: Rc<RefCell<Option<(String, String)>>>,
+    next_id: Rc<Cell<usize>>,
+    #[allow(clippy::type_complexity)]
+    icons: Rc<RefCell<Vec<(usize, TextProp, Option<Cow<'static, str>>)>>>,
+}
+
+impl FaviconContext {
+    /// Converts the favicon into a `<link rel="icon">` tag.
+    pub fn as_string(&self) -> Option<String> {
+        use leptos::leptos_dom::ssr::escape_attr;
+
+        self.icons.borrow().last().map(|(_, href, type_)| {
+            let type_ = type_
+                .as_ref()
+                .map(|type_| format!(" type=\"{}\"", escape_attr(type_)))
+                .unwrap_or_default();
+            format!(
+                "<link id=\"{ID}\" rel=\"icon\" href=\"{}\"{type_}>",
+                escape_attr(&href.get())
+            )
+        })
+    }
+
+    fn push(&self, href: TextProp, type_: Option<Cow<'static, str>>) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.icons.borrow_mut().push((id, href, type_));
+        id
+    }
+
+    fn remove(&self, id: usize) {
+        self.icons.borrow_mut().retain(|(other, _, _)| *other != id);
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn update_el(&self) {
+        let Some(el) = self.el.borrow().clone() else {
+            return;
+        };
+        let icon = self
+            .icons
+            .borrow()
+            .last()
+            .map(|(_, href, type_)| {
+                (href.get(), type_.as_deref().unwrap_or_default().to_string())
+            })
+            .or_else(|| self.fallback.borrow().clone());
+        match icon {
+            Some((href, type_)) => {
+                el.set_href(&href);
+                el.set_type(&type_);
+            }
+            // there was no favicon before the first <Favicon/>
+            None => {
+                el.remove();
+                self.el.take();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for FaviconContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FaviconContext").finish()
+    }
+}
+
+/// A component to set the document’s favicon, by updating its
+/// `<link rel="icon">` or adding one.
+///
+/// `href` can be reactive, e.g., to show a badge with the number of unread
+/// messages. When the `<Favicon/>` is unmounted, the icon it replaced is
+/// shown again: that of another `<Favicon/>` higher in the tree, or the one
+/// that was already in the HTML document.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Inbox(cx: Scope) -> impl IntoView {
+///     provide_meta_context(cx);
+///     let (unread, set_unread) = create_signal(cx, 0);
+///
+///     view! { cx,
+///       <Favicon
+///         href=move || if unread.get() > 0 {
+///             "/favicon-unread.svg".to_string()
+///         } else {
+///             "/favicon.svg".to_string()
+///         }
+///         type_="image/svg+xml"
+///       />
+///       <button on:click=move |_| set_unread.set(0)>
+///         "Mark all as read"
+///       </button>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn Favicon(
+    cx: Scope,
+    /// The URL of the icon.
+    #[prop(into)]
+    href: TextProp,
+    /// The MIME type of the icon, like `image/png` or `image/svg+xml`.
+    #[prop(optional, into)]
+    type_: Option<Cow<'static, str>>,
+) -> impl IntoView {
+    let meta = use_head(cx);
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let id = meta.favicon.push(href.clone(), type_);
+
+            {
+                let mut el_ref = meta.favicon.el.borrow_mut();
+                if el_ref.is_none() {
+                    let hydrated = document().get_element_by_id(ID);
+                    let el = match hydrated {
+                        Some(el) => el.unchecked_into::<HtmlLinkElement>(),
+                        None => match document()
+                            .query_selector("link[rel~='icon']")
+                        {
+                            Ok(Some(el)) => {
+                                let el = el.unchecked_into::<HtmlLinkElement>();
+                                *meta.favicon.fallback.borrow_mut() =
+                                    Some((el.href(), el.type_()));
+                                el
+                            }
+                            _ => {
+                                let el = document()
+                                    .create_element("link")
+                                    .unwrap_throw()
+                                    .unchecked_into::<HtmlLinkElement>();
+                                el.set_id(ID);
+                                el.set_rel("icon");
+                                document()
+                                    .head()
+                                    .unwrap_throw()
+                                    .append_child(&el)
+                                    .unwrap_throw();
+                                el
+                            }
+                        },
+                    };
+                    *el_ref = Some(el);
+                }
+            }
+
+            create_render_effect(cx, {
+                let meta = meta.clone();
+                move |_| {
+                    // track this icon's own `href`, so that it is set again
+                    // when it changes after a child's icon has been removed
+                    href.get();
+                    meta.favicon.update_el();
+                }
+            });
+
+            // restore the icon this one replaced
+            on_cleanup(cx, move || {
+                meta.favicon.remove(id);
+                cx.untrack(|| meta.favicon.update_el());
+            });
+        } else {
+            let id = meta.favicon.push(href, type_);
+            on_cleanup(cx, move || meta.favicon.remove(id));
+        }
+    }
+}
@@ -1,65 +1,34 @@
-use cfg_if::cfg_if;
+use crate::ElementAttributes;
 use leptos::*;
-use std::{cell::RefCell, rc::Rc};
 
 /// Contains the current metadata for the document's `<html>`.
 #[derive(Clone, Default)]
 pub struct HtmlContext {
-    lang: Rc<RefCell<Option<TextProp>>>,
-    dir: Rc<RefCell<Option<TextProp>>>,
-    class: Rc<RefCell<Option<TextProp>>>,
-    attributes: Rc<RefCell<Option<MaybeSignal<AdditionalAttributes>>>>,
+    attributes: ElementAttributes,
 }
 
 impl HtmlContext {
     /// Converts the `<html>` metadata into an HTML string.
     pub fn as_string(&self) -> Option<String> {
-        let lang = self
-            .lang
-            .borrow()
-            .as_ref()
-            .map(|val| format!("lang=\"{}\"", val.get()));
-        let dir = self
-            .dir
-            .borrow()
-            .as_ref()
-            .map(|val| format!("dir=\"{}\"", val.get()));
-        let class = self
-            .class
-            .borrow()
-            .as_ref()
-            .map(|val| format!("class=\"{}\"", val.get()));
-        let attributes = self.attributes.borrow().as_ref().map(|val| {
-            val.with(|val| {
-                val.into_iter()
-                    .map(|(n, v)| format!("{}=\"{}\"", n, v.get()))
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            })
-        });
-        let mut val = [lang, dir, class, attributes]
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .join(" ");
-        if val.is_empty() {
-            None
-        } else {
-            val.insert(0, ' ');
-            Some(val)
-        }
+        self.attributes.as_string()
     }
 }
 
 impl std::fmt::Debug for HtmlContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("TitleContext").finish()
+        f.debug_tuple("HtmlContext").finish()
     }
 }
 
 /// A component to set metadata on the document’s `<html>` element from
 /// within the application.
 ///
+/// The attributes are rendered on the server and updated reactively in the
+/// browser. When the component is unmounted, the attributes it set are
+/// removed, or set back to their value from another `<Html/>` that is still
+/// mounted. The `class`es of all the mounted components are combined; for
+/// other attributes, the most recently created component wins.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -96,55 +65,11 @@ pub fn Html(
     #[prop(optional, into)]
     attributes: Option<MaybeSignal<AdditionalAttributes>>,
 ) -> impl IntoView {
-    #[cfg(debug_assertions)]
-    crate::feature_warning();
-
-    cfg_if! {
-        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-            let el = document().document_element().expect("there to be a <html> element");
-
-            if let Some(lang) = lang {
-                let el = el.clone();
-                create_render_effect(cx, move |_| {
-                    let value = lang.get();
-                    _ = el.set_attribute("lang", &value);
-                });
-            }
-
-            if let Some(dir) = dir {
-                let el = el.clone();
-                create_render_effect(cx, move |_| {
-                    let value = dir.get();
-                    _ = el.set_attribute("dir", &value);
-                });
-            }
-
-            if let Some(class) = class {
-                let el = el.clone();
-                create_render_effect(cx, move |_| {
-                    let value = class.get();
-                    _ = el.set_attribute("class", &value);
-                });
-            }
-
-            if let Some(attributes) = attributes {
-                let attributes = attributes.get();
-                for (attr_name, attr_value) in attributes.into_iter() {
-                    let el = el.clone();
-                    let attr_name = attr_name.to_owned();
-                    let attr_value = attr_value.to_owned();
-                    create_render_effect(cx, move |_|{
-                        let value = attr_value.get();
-                            _ = el.set_attribute(&attr_name, &value);
-                    });
-                }
-            }
-        } else {
-            let meta = crate::use_head(cx);
-            *meta.html.lang.borrow_mut() = lang;
-            *meta.html.dir.borrow_mut() = dir;
-            *meta.html.class.borrow_mut() = class;
-            *meta.html.attributes.borrow_mut() = attributes;
-        }
-    }
+    let meta = crate::use_head(cx);
+    meta.html.attributes.register(
+        cx,
+        || document().document_element(),
+        vec![("lang", lang), ("dir", dir), ("class", class)],
+        attributes,
+    );
 }
@@ -60,6 +60,8 @@ use std::{
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 mod body;
+mod element_attributes;
+mod favicon;
 mod html;
 mod link;
 mod meta_tags;
@@ -68,6 +70,8 @@ mod style;
 mod stylesheet;
 mod title;
 pub use body::*;
+use element_attributes::*;
+pub use favicon::*;
 pub use html::*;
 pub use link::*;
 pub use meta_tags::*;
@@ -86,6 +90,8 @@ pub struct MetaContext {
     pub html: HtmlContext,
     /// Metadata associated with the `<title>` element.
     pub title: TitleContext,
+    /// Metadata associated with the favicon's `<link rel="icon">` element.
+    pub favicon: FaviconContext,
     /// Metadata associated with the `<body>` element
     pub body: BodyContext,
     /// Other metadata tags.
@@ -265,19 +271,23 @@ impl MetaContext {
     pub fn dehydrate(&self) -> String {
         use leptos::leptos_dom::HydrationCtx;
 
-        let prev_key = HydrationCtx::peek();
-        let mut tags = String::new();
+        HydrationCtx::scoped(|| {
+            let mut tags = String::new();
 
-        // Title
-        if let Some(title) = self.title.as_string() {
-            tags.push_str("<title>");
-            tags.push_str(&title);
-            tags.push_str("</title>");
-        }
-        tags.push_str(&self.tags.as_string());
+            // Title
+            if let Some(title) = self.title.as_string() {
+                tags.push_str("<title>");
+                tags.push_str(&title);
+                tags.push_str("</title>");
+            }
+            // Favicon
+            if let Some(favicon) = self.favicon.as_string() {
+                tags.push_str(&favicon);
+            }
+            tags.push_str(&self.tags.as_string());
 
-        HydrationCtx::continue_from(prev_key);
-        tags
+            tags
+        })
     }
 }
 
@@ -1,31 +1,63 @@
 use crate::{use_head, TextProp};
 use cfg_if::cfg_if;
 use leptos::*;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 #[cfg(any(feature = "csr", feature = "hydrate"))]
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 /// Contains the current state of the document's `<title>`.
+///
+/// Each [`<Title/>`](Title) pushes its `text` and `formatter` onto a stack
+/// when it is created, and removes them when it is unmounted, so that the
+/// title set by a parent (e.g., a parent route) is restored once the child
+/// that overrode it is gone.
 #[derive(Clone, Default)]
 pub struct TitleContext {
     #[cfg(any(feature = "csr", feature = "hydrate"))]
     el: Rc<RefCell<Option<web_sys::HtmlTitleElement>>>,
-    formatter: Rc<RefCell<Option<Formatter>>>,
-    text: Rc<RefCell<Option<TextProp>>>,
+    next_id: Rc<Cell<usize>>,
+    formatter: Rc<RefCell<Vec<(usize, Formatter)>>>,
+    text: Rc<RefCell<Vec<(usize, TextProp)>>>,
 }
 
 impl TitleContext {
     /// Converts the title into a string that can be used as the text content of a `<title>` tag.
     pub fn as_string(&self) -> Option<String> {
-        let title = self.text.borrow().as_ref().map(|f| f.get());
+        let title = self.text.borrow().last().map(|(_, text)| text.get());
         title.map(|title| {
-            if let Some(formatter) = &*self.formatter.borrow() {
+            if let Some((_, formatter)) = self.formatter.borrow().last() {
                 (formatter.0)(title)
             } else {
                 title
             }
         })
     }
+
+    fn push(
+        &self,
+        formatter: Option<Formatter>,
+        text: Option<TextProp>,
+    ) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        if let Some(formatter) = formatter {
+            self.formatter.borrow_mut().push((id, formatter));
+        }
+        if let Some(text) = text {
+            self.text.borrow_mut().push((id, text));
+        }
+        id
+    }
+
+    fn remove(&self, id: usize) {
+        self.formatter
+            .borrow_mut()
+            .retain(|(other, _)| *other != id);
+        self.text.borrow_mut().retain(|(other, _)| *other != id);
+    }
 }
 
 impl std::fmt::Debug for TitleContext {
@@ -53,6 +85,9 @@ where
 /// The `title` and `formatter` can be set independently of one another. For example, you can create a root-level
 /// `<Title formatter=.../>` that will wrap each of the text values of `<Title/>` components created lower in the tree.
 ///
+/// The most recently created `<Title/>` wins. When it is unmounted, e.g., when navigating away from
+/// a nested route, the `text` and `formatter` of the `<Title/>` it overrode are used again.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -102,24 +137,11 @@ pub fn Title(
 
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-            if let Some(formatter) = formatter {
-                *meta.title.formatter.borrow_mut() = Some(formatter);
-            }
-            if let Some(text) = text {
-                *meta.title.text.borrow_mut() = Some(text);
-            }
+            let id = meta.title.push(formatter, text.clone());
 
             let el = {
                 let mut el_ref = meta.title.el.borrow_mut();
                 let el = if let Some(el) = &*el_ref {
-                    let prev_text = el.inner_text();
-                    on_cleanup(cx, {
-                        let el = el.clone();
-                        move || {
-                            _ = el.set_text(&prev_text);
-                        }
-                    });
-
                     el.clone()
                 } else {
                     match document().query_selector("title") {
@@ -132,8 +154,10 @@ pub fn Title(
 
                             on_cleanup(cx, {
                                 let el = el.clone();
+                                let el_ref = meta.title.el.clone();
                                 move || {
                                     _ = head.remove_child(&el);
+                                    el_ref.take();
                                 }
                             });
 
@@ -147,18 +171,32 @@ pub fn Title(
                 el
             };
 
-            create_render_effect(cx, move |_| {
-                let text = meta.title.as_string().unwrap_or_default();
+            create_render_effect(cx, {
+                let meta = meta.clone();
+                let el = el.clone();
+                move |_| {
+                    // track this title's own text, so that it is set again
+                    // when it changes after a child's title has been removed
+                    if let Some(text) = &text {
+                        text.get();
+                    }
+                    let text = meta.title.as_string().unwrap_or_default();
+
+                    el.set_text_content(Some(&text));
+                }
+            });
 
+            // restore the title this one overrode
+            on_cleanup(cx, move || {
+                meta.title.remove(id);
+                let text = cx
+                    .untrack(|| meta.title.as_string())
+                    .unwrap_or_default();
                 el.set_text_content(Some(&text));
             });
         } else {
-            if let Some(formatter) = formatter {
-                *meta.title.formatter.borrow_mut() = Some(formatter);
-            }
-            if let Some(text) = text {
-                *meta.title.text.borrow_mut() = Some(text);
-            }
+            let id = meta.title.push(formatter, text);
+            on_cleanup(cx, move || meta.title.remove(id));
         }
     }
 }
@@ -0,0 +1,236 @@
+use crate::{RouterIntegrationContext, ServerIntegration};
+use leptos::{
+    ssr::{parse_attrs, tag_end, unquote},
+    *,
+};
+use std::fmt;
+
+/// A problem found by [audit_html] or [audit_route]: a part of the page that
+/// will not work until the WASM has loaded, or if JavaScript is disabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnhancementIssue {
+    /// What is wrong.
+    pub kind: EnhancementIssueKind,
+    /// The opening tag of the element, as rendered.
+    pub tag: String,
+}
+
+/// The kinds of [EnhancementIssue].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnhancementIssueKind {
+    /// A `<form>` has no `action`, so it can only be handled by JavaScript.
+    FormWithoutAction,
+    /// A `<form>` has a `method` other than `get` or `post`, which browsers
+    /// can't submit without JavaScript.
+    FormInvalidMethod(String),
+    /// An `<a>` has no `href`, so it can only be followed by JavaScript.
+    LinkWithoutHref,
+    /// An `<a>` has an `href` that only does something with JavaScript,
+    /// like `#` or a `javascript:` URL.
+    LinkWithScriptHref(String),
+    /// A `<button>` that is not a submit button within a `<form>`, so it
+    /// can only do something with JavaScript.
+    ButtonOutsideForm,
+}
+
+impl fmt::Display for EnhancementIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            EnhancementIssueKind::FormWithoutAction => {
+                write!(f, "form has no `action`: {}", self.tag)
+            }
+            EnhancementIssueKind::FormInvalidMethod(method) => write!(
+                f,
+                "form has method `{method}`, which requires JavaScript: {}",
+                self.tag
+            ),
+            EnhancementIssueKind::LinkWithoutHref => {
+                write!(f, "link has no `href`: {}", self.tag)
+            }
+            EnhancementIssueKind::LinkWithScriptHref(href) => write!(
+                f,
+                "link has `href` `{href}`, which requires JavaScript: {}",
+                self.tag
+            ),
+            EnhancementIssueKind::ButtonOutsideForm => write!(
+                f,
+                "button does not submit a form, so requires JavaScript: {}",
+                self.tag
+            ),
+        }
+    }
+}
+
+/// Renders the app at the given `path` on the server and checks that its
+/// forms, links, and buttons work without JavaScript. See [audit_html].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// let issues = audit_route("/", |cx| {
+///     view! { cx,
+///       <Router>
+///         <Routes>
+///           <Route path="/" view=|cx| view! { cx, <a href="/about">"About"</a> }/>
+///         </Routes>
+///       </Router>
+///     }
+/// });
+/// assert!(issues.is_empty());
+/// ```
+pub fn audit_route<IV>(
+    path: &str,
+    app_fn: impl FnOnce(Scope) -> IV + 'static,
+) -> Vec<EnhancementIssue>
+where
+    IV: IntoView + 'static,
+{
+    let path = format!("http://leptos.dev{path}");
+    let html = leptos::ssr::render_to_string(move |cx| {
+        let integration = ServerIntegration { path };
+        provide_context(cx, RouterIntegrationContext::new(integration));
+        app_fn(cx)
+    });
+    audit_html(&html)
+}
+
+/// Checks server-rendered HTML for forms, links, and buttons that will not
+/// work until the WASM has loaded, or if JavaScript is disabled:
+/// - every `<form>` (including an [ActionForm](crate::ActionForm)) needs an
+///   `action`, and a `method` of `get` or `post`
+/// - every `<a>` needs an `href` that goes somewhere
+/// - every `<button>` needs to submit a `<form>`
+///
+/// Event handlers are never rendered on the server, so any element that is
+/// only interactive through one is reported by one of these checks.
+pub fn audit_html(html: &str) -> Vec<EnhancementIssue> {
+    let mut issues = Vec::new();
+    let mut form_depth = 0_usize;
+
+    for tag in Tags(html) {
+        let issue = |kind| EnhancementIssue {
+            kind,
+            tag: tag.source.to_string(),
+        };
+        match (tag.name.as_str(), tag.closing) {
+            ("form", true) => form_depth = form_depth.saturating_sub(1),
+            ("form", false) => {
+                form_depth += 1;
+                if tag.attr("action").is_none() {
+                    issues.push(issue(EnhancementIssueKind::FormWithoutAction));
+                }
+                if let Some(method) = tag.attr("method") {
+                    if !method.eq_ignore_ascii_case("get")
+                        && !method.eq_ignore_ascii_case("post")
+                    {
+                        issues.push(issue(
+                            EnhancementIssueKind::FormInvalidMethod(
+                                method.to_string(),
+                            ),
+                        ));
+                    }
+                }
+            }
+            ("a", false) => match tag.attr("href") {
+                None => {
+                    issues.push(issue(EnhancementIssueKind::LinkWithoutHref))
+                }
+                Some(href)
+                    if href == "#"
+                        || href
+                            .trim_start()
+                            .to_ascii_lowercase()
+                            .starts_with("javascript:") =>
+                {
+                    issues.push(issue(
+                        EnhancementIssueKind::LinkWithScriptHref(
+                            href.to_string(),
+                        ),
+                    ))
+                }
+                _ => {}
+            },
+            ("button", false) => {
+                let is_submit = tag
+                    .attr("type")
+                    .map(|ty| ty.eq_ignore_ascii_case("submit"))
+                    .unwrap_or(true);
+                if form_depth == 0 || !is_submit {
+                    issues.push(issue(EnhancementIssueKind::ButtonOutsideForm));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    issues
+}
+
+struct Tag<'a> {
+    source: &'a str,
+    name: String,
+    closing: bool,
+    attrs: Vec<(String, &'a str)>,
+}
+
+impl<'a> Tag<'a> {
+    fn attr(&self, name: &str) -> Option<&'a str> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Iterates over the opening and closing tags in an HTML string, skipping
+/// comments.
+struct Tags<'a>(&'a str);
+
+impl<'a> Iterator for Tags<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.0.find('<')?;
+            let rest = &self.0[start..];
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                self.0 = comment
+                    .find("-->")
+                    .map(|end| &comment[end + 3..])
+                    .unwrap_or_default();
+                continue;
+            }
+            let end = tag_end(rest)?;
+            let source = &rest[..=end];
+            self.0 = &rest[end + 1..];
+
+            let inner = source[1..source.len() - 1].trim_end_matches('/');
+            let (closing, inner) = match inner.strip_prefix('/') {
+                Some(inner) => (true, inner),
+                None => (false, inner),
+            };
+            let name_end = inner
+                .find(|c: char| c.is_whitespace())
+                .unwrap_or(inner.len());
+            let name = inner[..name_end].to_ascii_lowercase();
+            if name.is_empty() || name.starts_with('!') {
+                continue;
+            }
+
+            return Some(Tag {
+                source,
+                name,
+                closing,
+                attrs: parse_attrs(&inner[name_end..])
+                    .into_iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_ascii_lowercase(),
+                            value.map(unquote).unwrap_or_default(),
+                        )
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
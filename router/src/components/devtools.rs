@@ -0,0 +1,177 @@
+use crate::{use_is_routing, use_location, use_matched, Location};
+use leptos::*;
+
+// How many navigations are listed in the timings.
+const MAX_TIMINGS: usize = 10;
+
+const STYLE: &str =
+    "position: fixed; right: 0.5rem; bottom: 0.5rem; z-index: 2147483647; \
+     max-width: min(40rem, 95vw); max-height: 50vh; overflow: auto; padding: \
+     0.5rem; border-radius: 0.25rem; background: #1e1e1e; color: #eee; font: \
+     12px/1.4 monospace; box-shadow: 0 0 0.5rem rgba(0, 0, 0, 0.5);";
+
+/// An overlay that shows the state of the router while developing: the
+/// current location, the chain of matched routes with their params and
+/// `meta` data, whether a navigation is pending, and how long the last
+/// navigations took, from the change of location until the resources read
+/// under a [Suspense] or [Transition] by the new routes had loaded.
+///
+/// The overlay is hidden until it is toggled with the `hotkey`, which
+/// defaults to `Ctrl+Shift+D`. It is only available with the `devtools`
+/// feature, which should only be enabled for development builds.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Router>
+///             <RouterDevtools/>
+///             <Routes>
+///                 // ...
+/// #               <Route path="" view=|cx| view! { cx, <p>"Home"</p> }/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all,)
+)]
+#[component]
+pub fn RouterDevtools(
+    cx: Scope,
+    /// The key combination that shows or hides the overlay, as a [Hotkey],
+    /// which also works while typing in an input. Defaults to
+    /// `"ctrl+shift+d"`.
+    #[prop(optional)]
+    hotkey: Option<&'static str>,
+    /// Whether the overlay is shown when the page loads.
+    #[prop(optional)]
+    open: bool,
+) -> impl IntoView {
+    let location = use_location(cx);
+    let is_routing = use_is_routing(cx);
+    let matched = use_matched(cx);
+
+    let (open, set_open) = create_signal(cx, open);
+    use_hotkeys_with_options(
+        cx,
+        hotkey.unwrap_or("ctrl+shift+d"),
+        HotkeyOptions {
+            in_inputs: true,
+            ..Default::default()
+        },
+        move |_| set_open.update(|open| *open = !*open),
+    );
+
+    // a navigation starts when the location changes, and finishes once the
+    // router is no longer routing, which may be right away
+    let (timings, set_timings) = create_signal(cx, Vec::<(String, f64)>::new());
+    let started = store_value(cx, None::<(String, f64)>);
+    create_effect(cx, move |prev: Option<String>| {
+        let path = current_path(&location, false);
+        if prev.is_some() && prev.as_ref() != Some(&path) {
+            started.set_value(Some((path.clone(), js_sys::Date::now())));
+        }
+        path
+    });
+    create_effect(cx, move |_| {
+        if !is_routing.get() {
+            if let Some((path, start)) =
+                started.try_update_value(Option::take).flatten()
+            {
+                set_timings.update(|timings| {
+                    timings.insert(0, (path, js_sys::Date::now() - start));
+                    timings.truncate(MAX_TIMINGS);
+                });
+            }
+        }
+    });
+
+    let matched_routes = move || {
+        matched
+            .get()
+            .into_iter()
+            .map(|route| {
+                let params = route
+                    .params
+                    .0
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let meta = match &route.meta {
+                    Some(meta) => format!("{meta:?}"),
+                    None => String::new(),
+                };
+                view! { cx,
+                    <tr>
+                        <td>{route.pattern}</td>
+                        <td>{route.path}</td>
+                        <td>{params}</td>
+                        <td>{meta}</td>
+                    </tr>
+                }
+            })
+            .collect_view(cx)
+    };
+
+    let navigation_timings = move || {
+        timings
+            .get()
+            .into_iter()
+            .map(|(path, ms)| {
+                view! { cx, <li>{format!("{path} — {ms:.0} ms")}</li> }
+            })
+            .collect_view(cx)
+    };
+
+    view! { cx,
+        <aside
+            class="leptos-router-devtools"
+            style=STYLE
+            hidden=move || !open.get()
+        >
+            <strong>"Router"</strong>
+            <p>
+                "Location: "
+                <code>{move || current_path(&location, true)}</code>
+            </p>
+            <p>
+                "Pending navigation: "
+                {move || if is_routing.get() { "loading" } else { "none" }}
+            </p>
+            <table>
+                <thead>
+                    <tr>
+                        <th>"Pattern"</th>
+                        <th>"Path"</th>
+                        <th>"Params"</th>
+                        <th>"Meta"</th>
+                    </tr>
+                </thead>
+                <tbody>{matched_routes}</tbody>
+            </table>
+            <p>"Navigation timings:"</p>
+            <ol>{navigation_timings}</ol>
+        </aside>
+    }
+}
+
+fn current_path(location: &Location, with_hash: bool) -> String {
+    let mut path = location.pathname.get();
+    // the query string only starts with `?` in the browser
+    let search = location.search.get();
+    let search = search.trim_start_matches('?');
+    if !search.is_empty() {
+        path.push('?');
+        path.push_str(search);
+    }
+    if with_hash {
+        path.push_str(&location.hash.get());
+    }
+    path
+}
@@ -1,4 +1,4 @@
-use crate::{use_navigate, use_resolved_path, ToHref, Url};
+use crate::{revalidate_on, use_navigate, use_resolved_path, ToHref, Url};
 use leptos::{html::form, *};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{error::Error, rc::Rc};
@@ -176,7 +176,7 @@ where
             form = form.node_ref(node_ref)
         };
         if let Some(attributes) = attributes {
-            let attributes = attributes.get();
+            let attributes = attributes.get_untracked();
             for (attr_name, attr_value) in attributes.into_iter() {
                 let attr_name = attr_name.to_owned();
                 let attr_value = attr_value.to_owned();
@@ -236,6 +236,11 @@ pub fn ActionForm<I, O>(
     /// Arbitrary attributes to add to the `<form>`
     #[prop(optional, into)]
     attributes: Option<MaybeSignal<AdditionalAttributes>>,
+    /// Whether to refetch the resources of the current routes, created with
+    /// [create_route_resource](crate::create_route_resource), each time the
+    /// action completes. `true` by default.
+    #[prop(default = true)]
+    revalidate: bool,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -255,6 +260,9 @@ where
     let version = action.version();
     let value = action.value();
     let input = action.input();
+    if revalidate {
+        revalidate_on(cx, version);
+    }
 
     let on_form_data = Rc::new(move |form_data: &web_sys::FormData| {
         let data = I::from_form_data(form_data);
@@ -283,7 +291,7 @@ where
                         let json = json
                             .as_string()
                             .expect("couldn't get String from JsString");
-                        if (500..=599).contains(&status) {
+                        if (400..=599).contains(&status) {
                             match serde_json::from_str::<ServerFnError>(&json) {
                                 Ok(res) => {
                                     value.try_set(Some(Err(res)));
@@ -340,6 +348,7 @@ where
         });
     });
     let class = class.map(|bx| bx.into_attribute_boxed(cx));
+    let children = with_csrf_field(cx, children);
     let mut props = FormProps::builder()
         .action(action_url)
         .version(version)
@@ -381,6 +390,11 @@ pub fn MultiActionForm<I, O>(
     /// Arbitrary attributes to add to the `<form>`
     #[prop(optional, into)]
     attributes: Option<MaybeSignal<AdditionalAttributes>>,
+    /// Whether to refetch the resources of the current routes, created with
+    /// [create_route_resource](crate::create_route_resource), each time a
+    /// submission completes. `true` by default.
+    #[prop(default = true)]
+    revalidate: bool,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -389,6 +403,9 @@ where
     O: Clone + Serializable + 'static,
 {
     let multi_action = action;
+    if revalidate {
+        revalidate_on(cx, multi_action.version());
+    }
     let action = if let Some(url) = multi_action.url() {
         url
     } else {
@@ -423,6 +440,7 @@ where
     };
 
     let class = class.map(|bx| bx.into_attribute_boxed(cx));
+    let children = with_csrf_field(cx, children);
     let mut form = form(cx)
         .attr("method", "POST")
         .attr("action", action)
@@ -433,7 +451,7 @@ where
         form = form.node_ref(node_ref)
     };
     if let Some(attributes) = attributes {
-        let attributes = attributes.get();
+        let attributes = attributes.get_untracked();
         for (attr_name, attr_value) in attributes.into_iter() {
             let attr_name = attr_name.to_owned();
             let attr_value = attr_value.to_owned();
@@ -442,6 +460,22 @@ where
     }
     form
 }
+
+// Adds a hidden field with the CSRF token, if there is one, so that the form
+// can also be submitted before the WASM has loaded.
+fn with_csrf_field(cx: Scope, children: Children) -> Children {
+    match use_csrf_token(cx) {
+        Some(token) => Box::new(move |cx| {
+            let field = html::input(cx)
+                .attr("type", "hidden")
+                .attr("name", CSRF_FIELD)
+                .attr("value", token);
+            Fragment::new(vec![field.into_view(cx), children(cx).into_view(cx)])
+        }),
+        None => children,
+    }
+}
+
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
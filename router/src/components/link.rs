@@ -1,5 +1,8 @@
 use crate::{use_location, use_resolved_path, State};
 use leptos::{leptos_dom::IntoView, *};
+use std::{borrow::Cow, rc::Rc};
+
+type ActiveMatcher = Rc<dyn Fn(&str, &str) -> bool>;
 
 /// Describes a value that is either a static or a reactive URL, i.e.,
 /// a [String], a [&str], or a reactive `Fn() -> String`.
@@ -42,6 +45,34 @@ where
 /// 2) Sets the `aria-current` attribute if this link is the active link (i.e., it’s a link to the page you’re on).
 ///    This is helpful for accessibility and for styling. For example, maybe you want to set the link a
 ///    different color if it’s a link to the page you’re currently on.
+///
+/// By default, a link is active when the current path is its path, or is nested under it: a link to
+/// `/users` is active at `/users` and `/users/42`, but not at `/users-online`. Set `exact` to only
+/// match its own path, or pass a `matcher` to decide for yourself, e.g., by comparing a param.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # use std::rc::Rc;
+/// #[component]
+/// fn Nav(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///       <nav>
+///         <A href="/" exact=true active_class="active">"Home"</A>
+///         <A href="/users" active_class="active">"Users"</A>
+///         // active for any page of the same project, e.g., `/projects/7/settings`
+///         <A
+///           href="/projects/7"
+///           matcher=Rc::new(|href: &str, path: &str| {
+///               href.split('/').nth(2) == path.split('/').nth(2)
+///           })
+///         >
+///           "Project 7"
+///         </A>
+///       </nav>
+///     }
+/// }
+/// ```
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "info", skip_all,)
@@ -56,7 +87,17 @@ pub fn A<H>(
     /// if false, link is marked active if the current route starts with it.
     #[prop(optional)]
     exact: bool,
-    /// An object of any type that will be pushed to router state
+    /// A class that is added to the `<a>` while the link is active. It must be a
+    /// single class name.
+    #[prop(optional, into)]
+    active_class: Option<Cow<'static, str>>,
+    /// Decides whether the link is active instead of `exact`, given the path the
+    /// link points to and the current path. Signals read by it are tracked.
+    #[prop(optional)]
+    matcher: Option<ActiveMatcher>,
+    /// An object of any type that will be pushed to router state. Use
+    /// [State::new] to store a serializable value, which can be read back
+    /// with [use_location_state](crate::use_location_state).
     #[prop(optional)]
     state: Option<State>,
     /// If `true`, the link will not add to the browser's history (so, pressing `Back`
@@ -79,10 +120,12 @@ where
         any(debug_assertions, feature = "ssr"),
         tracing::instrument(level = "trace", skip_all,)
     )]
+    #[allow(clippy::too_many_arguments)]
     fn inner(
         cx: Scope,
         href: Memo<Option<String>>,
-        exact: bool,
+        is_active: Memo<bool>,
+        active_class: Option<Cow<'static, str>>,
         state: Option<State>,
         replace: bool,
         class: Option<AttributeValue>,
@@ -95,26 +138,7 @@ where
         #[cfg(not(any(feature = "hydrate", feature = "csr")))]
         _ = replace;
 
-        let location = use_location(cx);
-        let is_active = create_memo(cx, move |_| match href.get() {
-            None => false,
-
-            Some(to) => {
-                let path = to
-                    .split(['?', '#'])
-                    .next()
-                    .unwrap_or_default()
-                    .to_lowercase();
-                let loc = location.pathname.get().to_lowercase();
-                if exact {
-                    loc == path
-                } else {
-                    loc.starts_with(&path)
-                }
-            }
-        });
-
-        view! { cx,
+        let link = view! { cx,
             <a
                 href=move || href.get().unwrap_or_default()
                 prop:state={state.map(|s| s.to_js_value())}
@@ -125,9 +149,82 @@ where
             >
                 {children(cx)}
             </a>
+        };
+        match active_class {
+            Some(active_class) => {
+                link.class(active_class, move || is_active.get())
+            }
+            None => link,
         }
     }
 
     let href = use_resolved_path(cx, move || href.to_href()());
-    inner(cx, href, exact, state, replace, class, id, children)
+    let location = use_location(cx);
+    let is_active = create_memo(cx, move |_| match href.get() {
+        None => false,
+
+        Some(to) => {
+            let path = to.split(['?', '#']).next().unwrap_or_default();
+            let loc = location.pathname.get();
+            if let Some(matcher) = &matcher {
+                return matcher(path, &loc);
+            }
+            is_active_path(path, &loc, exact)
+        }
+    });
+    inner(
+        cx,
+        href,
+        is_active,
+        active_class,
+        state,
+        replace,
+        class,
+        id,
+        children,
+    )
+}
+
+// Whether a link to `href` is active at `path`, ignoring case.
+fn is_active_path(href: &str, path: &str, exact: bool) -> bool {
+    let href = href.to_lowercase();
+    let path = path.to_lowercase();
+    if exact {
+        path == href
+    } else {
+        // only match whole segments, so that `/users` is not active at
+        // `/users-online`
+        path.starts_with(&href)
+            && (path.len() == href.len()
+                || href.ends_with('/')
+                || path[href.len()..].starts_with('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_is_active_under_its_path() {
+        assert!(is_active_path("/users", "/users", false));
+        assert!(is_active_path("/users", "/users/42", false));
+        assert!(is_active_path("/users", "/Users/42", false));
+        assert!(!is_active_path("/users", "/users-online", false));
+        assert!(!is_active_path("/users", "/", false));
+    }
+
+    #[test]
+    fn root_link_is_active_everywhere_unless_exact() {
+        assert!(is_active_path("/", "/", false));
+        assert!(is_active_path("/", "/users", false));
+        assert!(is_active_path("/", "/", true));
+        assert!(!is_active_path("/", "/users", true));
+    }
+
+    #[test]
+    fn exact_link_is_only_active_at_its_path() {
+        assert!(is_active_path("/users", "/users", true));
+        assert!(!is_active_path("/users", "/users/42", true));
+    }
 }
@@ -1,14 +1,22 @@
+#[cfg(feature = "devtools")]
+mod devtools;
 mod form;
 mod link;
 mod outlet;
+mod progress;
+mod protected;
 mod redirect;
 mod route;
 mod router;
 mod routes;
 
+#[cfg(feature = "devtools")]
+pub use devtools::*;
 pub use form::*;
 pub use link::*;
 pub use outlet::*;
+pub use progress::*;
+pub use protected::*;
 pub use redirect::*;
 pub use route::*;
 pub use router::*;
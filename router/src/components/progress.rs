@@ -0,0 +1,117 @@
+use crate::use_is_routing;
+use leptos::{leptos_dom::helpers::IntervalHandle, *};
+use std::time::Duration;
+
+// How often the progress bar moves forward while routing.
+const TICK: Duration = Duration::from_millis(200);
+
+// How long the full progress bar stays on screen once routing has finished.
+const HIDE_AFTER: Duration = Duration::from_millis(250);
+
+/// A progress bar that is shown at the top of the page while the router is
+/// navigating to a new route and waiting for its data to load, like
+/// [nprogress](https://ricostacruz.com/nprogress/).
+///
+/// Because there is no way to know how long loading will take, the bar moves
+/// steadily towards 90% over `max_time`, then fills up once routing has
+/// finished, and is hidden shortly afterwards.
+///
+/// The bar is a [`<progress>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/progress)
+/// element, so it is announced by screen readers, and can be styled with the
+/// `::-webkit-progress-value` and `::-moz-progress-bar` pseudo-elements. It
+/// has the `hidden` attribute while nothing is loading.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Router>
+///             <RoutingProgress class="routing-progress"/>
+///             <Routes>
+///                 // ...
+/// #               <Route path="" view=|cx| view! { cx, <p>"Home"</p> }/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all,)
+)]
+#[component]
+pub fn RoutingProgress(
+    cx: Scope,
+    /// Whether the progress bar should be shown. Defaults to
+    /// [use_is_routing], but can be any other signal, such as one derived
+    /// from [use_global_loading](leptos::use_global_loading).
+    #[prop(optional, into)]
+    is_routing: Option<Signal<bool>>,
+    /// How long it takes the bar to reach 90%. Defaults to one second.
+    #[prop(optional)]
+    max_time: Option<Duration>,
+    /// Sets the `class` attribute on the `<progress>` element.
+    #[prop(optional, into)]
+    class: Option<AttributeValue>,
+    /// The accessible name of the progress bar. Defaults to `"Loading"`.
+    #[prop(optional, into)]
+    label: Option<String>,
+) -> impl IntoView {
+    let is_routing = is_routing.unwrap_or_else(|| use_is_routing(cx).into());
+    let max_time = max_time.unwrap_or(Duration::from_secs(1));
+    let step = 90.0 * TICK.as_secs_f64() / max_time.as_secs_f64().max(0.001);
+
+    let (percent, set_percent) = create_signal(cx, 0.0_f64);
+    let (visible, set_visible) = create_signal(cx, false);
+    let interval = store_value(cx, None::<IntervalHandle>);
+
+    create_effect(cx, move |_| {
+        let routing = is_routing.get();
+        let running = interval.with_value(Option::is_some);
+        if routing && !running {
+            set_percent.set(0.0);
+            set_visible.set(true);
+            let handle = set_interval_with_handle(
+                move || {
+                    _ = set_percent.try_update(|n| *n = (*n + step).min(90.0));
+                },
+                TICK,
+            );
+            interval.set_value(handle.ok());
+        } else if !routing && running {
+            if let Some(handle) =
+                interval.try_update_value(Option::take).flatten()
+            {
+                handle.clear();
+            }
+            set_percent.set(100.0);
+            set_timeout(
+                move || {
+                    // routing may have started again in the meantime
+                    if !is_routing.try_get_untracked().unwrap_or(true) {
+                        _ = set_visible.try_set(false);
+                    }
+                },
+                HIDE_AFTER,
+            );
+        }
+    });
+
+    on_cleanup(cx, move || {
+        if let Some(Some(handle)) = interval.try_get_value() {
+            handle.clear();
+        }
+    });
+
+    view! { cx,
+        <progress
+            class=class
+            max="100"
+            value=move || percent.get().to_string()
+            aria-label=label.unwrap_or_else(|| "Loading".to_string())
+            hidden=move || !visible.get()
+        />
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Redirect;
+use leptos::*;
+use std::future::Future;
+
+/// Renders its children only if `check` resolves to `true`, and otherwise
+/// redirects to `redirect`. This is useful for pages that only logged-in
+/// users, or only some users, may see.
+///
+/// The check is run in a [blocking resource](leptos::create_blocking_resource),
+/// so during server-side rendering the response waits for it: the children
+/// are sent if it passes, and a `302` with a `Location` header if it fails.
+/// After hydration, or when navigating on the client, the check runs in the
+/// browser and a failure is handled with client-side navigation. `fallback`
+/// is shown while the check is running.
+///
+/// The check usually calls a server function that reads the user's session.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # async fn is_admin() -> Result<bool, ServerFnError> { Ok(true) }
+/// #[component]
+/// fn AdminPage(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///       <Protected
+///         check=|| async { is_admin().await.unwrap_or(false) }
+///         redirect="/login"
+///         fallback=|cx| view! { cx, <p>"Checking permissions..."</p> }
+///       >
+///         <h1>"Admin"</h1>
+///       </Protected>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "info", skip_all,)
+)]
+#[component]
+pub fn Protected<F, Fut, FB, W>(
+    cx: Scope,
+    /// Returns whether the children may be shown.
+    check: F,
+    /// The path to redirect to if the check fails. Like [Redirect], it is
+    /// resolved relative to the current route.
+    #[prop(into)]
+    redirect: String,
+    /// Shown while the check is running.
+    fallback: FB,
+    /// Shown once the check has passed.
+    children: ChildrenFn,
+) -> impl IntoView
+where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = bool> + 'static,
+    FB: Fn(Scope) -> W + 'static,
+    W: IntoView,
+{
+    let allowed = create_blocking_resource(cx, || (), move |_| check());
+    let redirect = store_value(cx, redirect);
+    let children = store_value(cx, children);
+
+    view! { cx,
+        <Suspense fallback=move || fallback(cx)>
+            {move || {
+                allowed.read(cx).map(|allowed| {
+                    if allowed {
+                        children.with_value(|children| children(cx)).into_view(cx)
+                    } else {
+                        view! { cx, <Redirect path=redirect.get_value()/> }
+                            .into_view(cx)
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
@@ -1,13 +1,12 @@
 use crate::{
-    matching::{resolve_path, PathMatch, RouteDefinition, RouteMatch},
+    matching::{
+        next_route_id, resolve_path, PathMatch, RouteDefinition, RouteMatch,
+        RouteMeta,
+    },
     ParamsMap, RouterContext, SsrMode,
 };
 use leptos::{leptos_dom::Transparent, *};
-use std::{cell::Cell, rc::Rc};
-
-thread_local! {
-    static ROUTE_ID: Cell<usize> = Cell::new(0);
-}
+use std::rc::Rc;
 
 /// Represents an HTTP method that can be handled by this route.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -54,6 +53,10 @@ pub fn Route<E, F, P>(
     /// The HTTP methods that this route can handle (defaults to only `GET`).
     #[prop(default = &[Method::Get])]
     methods: &'static [Method],
+    /// Data attached to the route, like a title for breadcrumbs, which is
+    /// available from [use_matched](crate::use_matched).
+    #[prop(optional, into)]
+    meta: Option<RouteMeta>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -70,6 +73,7 @@ where
         Rc::new(move |cx| view(cx).into_view(cx)),
         ssr,
         methods,
+        meta,
     )
 }
 
@@ -99,6 +103,10 @@ pub fn ProtectedRoute<P, E, F, C>(
     /// The HTTP methods that this route can handle (defaults to only `GET`).
     #[prop(default = &[Method::Get])]
     methods: &'static [Method],
+    /// Data attached to the route, like a title for breadcrumbs, which is
+    /// available from [use_matched](crate::use_matched).
+    #[prop(optional, into)]
+    meta: Option<RouteMeta>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -126,6 +134,7 @@ where
         }),
         ssr,
         methods,
+        meta,
     )
 }
 #[cfg_attr(
@@ -139,6 +148,7 @@ pub(crate) fn define_route(
     view: Rc<dyn Fn(Scope) -> View>,
     ssr_mode: SsrMode,
     methods: &'static [Method],
+    meta: Option<RouteMeta>,
 ) -> RouteDefinition {
     let children = children
         .map(|children| {
@@ -155,19 +165,14 @@ pub(crate) fn define_route(
         })
         .unwrap_or_default();
 
-    let id = ROUTE_ID.with(|id| {
-        let next = id.get() + 1;
-        id.set(next);
-        next
-    });
-
     RouteDefinition {
-        id,
+        id: next_route_id(),
         path,
         children,
         view,
         ssr_mode,
         methods,
+        meta,
     }
 }
 
@@ -1,6 +1,8 @@
 use crate::{
-    create_location, matching::resolve_path, Branch, History, Location,
-    LocationChange, RouteContext, RouterIntegrationContext, State,
+    create_location,
+    matching::{resolve_path, RouteMatch},
+    Branch, History, Location, LocationChange, RouteContext,
+    RouterIntegrationContext, State,
 };
 #[cfg(not(feature = "ssr"))]
 use crate::{unescape, Url};
@@ -24,13 +26,17 @@ pub fn Router(
     /// A fallback that should be shown if no route is matched.
     #[prop(optional)]
     fallback: Option<fn(Scope) -> View>,
+    /// If `true`, links to an element on the current page, like
+    /// `<A href="#comments">`, scroll smoothly to it rather than jumping.
+    #[prop(optional)]
+    smooth_scroll: bool,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [Routes](crate::Routes) component somewhere
     /// to define and display [Route](crate::Route)s.
     children: Children,
 ) -> impl IntoView {
     // create a new RouterContext and provide it to every component beneath the router
-    let router = RouterContext::new(cx, base, fallback);
+    let router = RouterContext::new(cx, base, fallback, smooth_scroll);
     provide_context(cx, router);
 
     children(cx)
@@ -56,6 +62,18 @@ pub(crate) struct RouterContextInner {
     set_state: WriteSignal<State>,
     pub(crate) is_back: RwSignal<bool>,
     pub(crate) path_stack: StoredValue<Vec<String>>,
+    is_routing: RwSignal<bool>,
+    global_loading: ReadSignal<usize>,
+    // incremented to refetch the resources of the current routes
+    pub(crate) revalidation: RwSignal<usize>,
+    // the routes matched by <Routes/>, once it has been rendered
+    pub(crate) matches: RwSignal<Option<Memo<Rc<Vec<RouteMatch>>>>>,
+    #[allow(unused)] // used in CSR/hydrate
+    smooth_scroll: bool,
+    // the hash to scroll to once loading has finished, if its element was
+    // not found when navigating
+    #[allow(unused)] // used in CSR/hydrate
+    pending_hash: StoredValue<Option<String>>,
 }
 
 impl std::fmt::Debug for RouterContextInner {
@@ -70,6 +88,7 @@ impl std::fmt::Debug for RouterContextInner {
             .field("state", &self.state)
             .field("set_state", &self.set_state)
             .field("path_stack", &self.path_stack)
+            .field("is_routing", &self.is_routing)
             .finish()
     }
 }
@@ -83,6 +102,7 @@ impl RouterContext {
         cx: Scope,
         base: Option<&'static str>,
         fallback: Option<fn(Scope) -> View>,
+        smooth_scroll: bool,
     ) -> Self {
         cfg_if! {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
@@ -142,6 +162,26 @@ impl RouterContext {
         let base_path = base_path.unwrap_or_default();
         let base = RouteContext::base(cx, &base_path, fallback);
 
+        // The router is routing from when the location changes until the
+        // resources loaded by the new route have resolved
+        let is_routing = create_rw_signal(cx, false);
+        let global_loading = use_global_loading(cx);
+        let pending_hash = store_value(cx, None::<String>);
+        create_effect(cx, move |_| {
+            if global_loading.get() == 0 {
+                is_routing.set(false);
+                // the element may have been rendered inside a <Suspense/>
+                #[cfg(any(feature = "csr", feature = "hydrate"))]
+                if let Some(hash) =
+                    pending_hash.try_update_value(Option::take).flatten()
+                {
+                    request_animation_frame(move || {
+                        scroll_to_hash(&hash, smooth_scroll);
+                    });
+                }
+            }
+        });
+
         // Every time the History gives us a new location,
         // 1) start a transition
         // 2) update the reference (URL)
@@ -149,11 +189,19 @@ impl RouterContext {
         // this will trigger the new route match below
 
         create_render_effect(cx, move |_| {
-            let LocationChange { value, state, .. } = source.get();
+            let LocationChange {
+                value,
+                state: next_state,
+                ..
+            } = source.get();
             cx.untrack(move || {
                 if value != reference.get() {
                     set_reference.update(move |r| *r = value);
-                    set_state.update(move |s| *s = state);
+                    set_state.update(move |s| *s = next_state);
+                    is_routing.set(global_loading.get_untracked() > 0);
+                } else if next_state != state.get() {
+                    // moving between history entries with the same URL
+                    set_state.set(next_state);
                 }
             });
         });
@@ -175,6 +223,12 @@ impl RouterContext {
             set_state,
             possible_routes: Default::default(),
             is_back: create_rw_signal(cx, false),
+            is_routing,
+            global_loading,
+            revalidation: create_rw_signal(cx, 0),
+            matches: create_rw_signal(cx, None),
+            smooth_scroll,
+            pending_hash,
         });
 
         // handle all click events on anchor tags
@@ -185,6 +239,19 @@ impl RouterContext {
         });
         // TODO on_cleanup remove event listener
 
+        // scroll to the element in the initial URL once it has loaded
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        {
+            let hash = inner.location.hash.get_untracked();
+            if !hash.is_empty() {
+                request_animation_frame(move || {
+                    if !scroll_to_hash(&hash, false) {
+                        pending_hash.set_value(Some(hash));
+                    }
+                });
+            }
+        }
+
         Self { inner }
     }
 
@@ -198,6 +265,22 @@ impl RouterContext {
         self.inner.base.clone()
     }
 
+    /// Whether the router is navigating to a new route: `true` from when the
+    /// location changes until any resources that are loading (including
+    /// those the new route has started to load) have resolved. This is
+    /// always `false` on the server.
+    pub fn is_routing(&self) -> ReadSignal<bool> {
+        self.inner.is_routing.read_only()
+    }
+
+    /// Refetches every resource created with
+    /// [create_route_resource](crate::create_route_resource) for the routes
+    /// that are currently matched, e.g., after a mutation that has changed
+    /// their data.
+    pub fn revalidate(&self) {
+        self.inner.revalidation.update(|n| *n += 1);
+    }
+
     /// A list of all possible routes this router can match.
     pub fn possible_branches(&self) -> Vec<Branch> {
         self.inner
@@ -266,6 +349,11 @@ impl RouterContextInner {
                             stack.push(resolved_to.clone())
                         });
 
+                        // matching the new route has started loading its
+                        // data by now, if it needs any
+                        self.is_routing
+                            .set(self.global_loading.get_untracked() > 0);
+
                         if referrers.borrow().len() == len {
                             this.navigate_end(LocationChange {
                                 value: resolved_to,
@@ -282,6 +370,49 @@ impl RouterContextInner {
         })
     }
 
+    pub(crate) fn set_hash(&self, hash: &str) {
+        let hash = hash.trim_start_matches('#');
+        let mut value = self.location.pathname.get_untracked()
+            + &self.location.search.get_untracked();
+        if !hash.is_empty() {
+            value.push('#');
+            value.push_str(hash);
+        }
+        if value != self.reference.get_untracked() {
+            // the path does not change, so no route is matched again
+            self.set_reference.set(value.clone());
+            self.path_stack
+                .update_value(|stack| stack.push(value.clone()));
+            self.history.navigate(&LocationChange {
+                value,
+                replace: false,
+                scroll: false,
+                state: State(None),
+            });
+        }
+        // scroll even if the hash has not changed, as the user may have
+        // scrolled away since
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        self.scroll_to_location_hash(false);
+    }
+
+    // Scrolls to the element with the ID in the current hash, if any, or to
+    // the top if there is no such element and `scroll_to_top` is `true`.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn scroll_to_location_hash(&self, scroll_to_top: bool) {
+        let hash = self.location.hash.get_untracked();
+        if hash.is_empty() {
+            return;
+        }
+        if !scroll_to_hash(&hash, self.smooth_scroll) {
+            // the element may be rendered once the route has loaded its data
+            self.pending_hash.set_value(Some(hash));
+            if scroll_to_top {
+                leptos_dom::window().scroll_to_with_x_and_y(0.0, 0.0);
+            }
+        }
+    }
+
     pub(crate) fn navigate_end(self: Rc<Self>, mut next: LocationChange) {
         let first = self.referrers.borrow().get(0).cloned();
         if let Some(first) = first {
@@ -289,6 +420,8 @@ impl RouterContextInner {
                 next.replace = first.replace;
                 next.scroll = first.scroll;
                 self.history.navigate(&next);
+                #[cfg(any(feature = "csr", feature = "hydrate"))]
+                self.scroll_to_location_hash(next.scroll);
             }
             self.referrers.borrow_mut().clear();
         }
@@ -356,6 +489,17 @@ impl RouterContextInner {
                 return;
             }
 
+            // a link to an element on the current page only changes the hash
+            if !url.hash.is_empty()
+                && !a.has_attribute("state")
+                && path_name == self.location.pathname.get_untracked()
+                && unescape(&url.search) == self.location.search.get_untracked()
+            {
+                ev.prevent_default();
+                self.set_hash(&unescape(&url.hash));
+                return;
+            }
+
             let to = path_name + &unescape(&url.search) + &unescape(&url.hash);
             let state =
                 leptos_dom::helpers::get_property(a.unchecked_ref(), "state")
@@ -427,3 +571,27 @@ impl Default for NavigateOptions {
         }
     }
 }
+
+// Scrolls to the element with the ID in the hash, returning `false` if there
+// is no such element (yet).
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn scroll_to_hash(hash: &str, smooth: bool) -> bool {
+    let id = hash.trim_start_matches('#');
+    let id = js_sys::decode_uri(id)
+        .ok()
+        .and_then(|decoded| decoded.as_string())
+        .unwrap_or_else(|| id.to_string());
+    match leptos_dom::document().get_element_by_id(&id) {
+        Some(el) => {
+            if smooth {
+                let mut options = web_sys::ScrollIntoViewOptions::new();
+                options.behavior(web_sys::ScrollBehavior::Smooth);
+                el.scroll_into_view_with_scroll_into_view_options(&options);
+            } else {
+                el.scroll_into_view();
+            }
+            true
+        }
+        None => false,
+    }
+}
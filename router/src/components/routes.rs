@@ -287,6 +287,7 @@ fn route_states(
     // whenever path changes, update matches
     let matches =
         create_memo(cx, move |_| get_route_matches(&base, current_route.get()));
+    router.inner.matches.set(Some(matches));
 
     // iterate over the new matches, reusing old routes when they are the same
     // and replacing them with new routes when they differ
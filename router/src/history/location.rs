@@ -10,7 +10,17 @@ pub fn create_location(
 ) -> Location {
     let url = create_memo(cx, move |prev: Option<&Url>| {
         path.with(|path| match Url::try_from(path.as_str()) {
-            Ok(url) => url,
+            #[allow(unused_mut)]
+            Ok(mut url) => {
+                // paths are parsed relative to a placeholder host
+                #[cfg(any(feature = "csr", feature = "hydrate"))]
+                {
+                    url.origin = leptos_dom::helpers::location()
+                        .origin()
+                        .unwrap_or_default();
+                }
+                url
+            }
             Err(e) => {
                 leptos::error!("[Leptos Router] Invalid path {path}\n\n{e:?}");
                 prev.cloned().unwrap()
@@ -18,6 +28,7 @@ pub fn create_location(
         })
     });
 
+    let origin = create_memo(cx, move |_| url.with(|url| url.origin.clone()));
     let pathname =
         create_memo(cx, move |_| url.with(|url| url.pathname.clone()));
     let search = create_memo(cx, move |_| url.with(|url| url.search.clone()));
@@ -26,6 +37,8 @@ pub fn create_location(
         create_memo(cx, move |_| url.with(|url| url.search_params.clone()));
 
     Location {
+        url,
+        origin,
         pathname,
         search,
         hash,
@@ -38,13 +51,21 @@ pub fn create_location(
 /// the browser's [`Location`](https://developer.mozilla.org/en-US/docs/Web/API/Location).
 #[derive(Debug, Clone, PartialEq)]
 pub struct Location {
+    /// The whole URL, parsed into its parts.
+    pub url: Memo<Url>,
+    /// The scheme, host, and port of the URL, like `https://leptos.dev`. On
+    /// the server, this is only known if the integration provides the full
+    /// URL.
+    pub origin: Memo<String>,
     /// The path of the URL, not containing the query string or hash fragment.
     pub pathname: Memo<String>,
     /// The raw query string.
     pub search: Memo<String>,
     /// The query string parsed into its key-value pairs.
     pub query: Memo<ParamsMap>,
-    /// The hash fragment.
+    /// The hash fragment, including the leading `#`, or an empty string.
+    /// Change it without navigating to a new route with
+    /// [use_set_hash](crate::use_set_hash).
     pub hash: Memo<String>,
     /// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) at the top of the history stack.
     pub state: ReadSignal<State>,
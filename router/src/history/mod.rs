@@ -37,13 +37,18 @@ pub struct BrowserIntegration {}
 impl BrowserIntegration {
     fn current() -> LocationChange {
         let loc = leptos_dom::helpers::location();
+        let state = leptos_dom::window()
+            .history()
+            .and_then(|history| history.state())
+            .ok()
+            .filter(|state| !state.is_undefined() && !state.is_null());
         LocationChange {
             value: loc.pathname().unwrap_or_default()
                 + &loc.search().unwrap_or_default()
                 + &loc.hash().unwrap_or_default(),
             replace: true,
             scroll: true,
-            state: State(None),
+            state: State(state),
         }
     }
 }
@@ -115,21 +120,7 @@ impl History for BrowserIntegration {
                 .push_state_with_url(state, "", Some(&loc.value))
                 .unwrap_throw();
         }
-        // scroll to el
-        if let Ok(hash) = leptos_dom::helpers::location().hash() {
-            if !hash.is_empty() {
-                let hash = js_sys::decode_uri(&hash[1..])
-                    .ok()
-                    .and_then(|decoded| decoded.as_string())
-                    .unwrap_or(hash);
-                let el = leptos_dom::document().get_element_by_id(&hash);
-                if let Some(el) = el {
-                    el.scroll_into_view()
-                } else if loc.scroll {
-                    leptos_dom::window().scroll_to_with_x_and_y(0.0, 0.0);
-                }
-            }
-        }
+        // the router scrolls to the element in the hash, if any
     }
 }
 
@@ -206,3 +197,121 @@ impl History for ServerIntegration {
 
     fn navigate(&self, _loc: &LocationChange) {}
 }
+
+/// A router integration that keeps the history in memory, rather than in the
+/// browser's address bar. Use it where the URL of the page can't be changed
+/// or reloaded, like in a webview that loads the app from a custom protocol
+/// (e.g., in a [Tauri](https://tauri.app/) app), or in tests.
+///
+/// Navigation works as it does in the browser, but there is no back button,
+/// so [back](MemoryIntegration::back) goes back to the previous location.
+/// Clones of the integration share the same history.
+///
+/// ```
+/// # use leptos_router::*;
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let history = MemoryIntegration::new("/");
+/// provide_context(cx, RouterIntegrationContext::new(history.clone()));
+/// // later, e.g., in a "Back" button
+/// history.back();
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct MemoryIntegration {
+    initial: String,
+    inner: Rc<std::cell::RefCell<MemoryHistory>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryHistory {
+    entries: Vec<LocationChange>,
+    router: Option<(Scope, WriteSignal<LocationChange>)>,
+}
+
+impl MemoryIntegration {
+    /// Creates a history that starts at the given path, like `/`.
+    pub fn new(initial: impl Into<String>) -> Self {
+        Self {
+            initial: initial.into(),
+            inner: Default::default(),
+        }
+    }
+
+    /// The locations in the history, from the oldest to the current one.
+    pub fn entries(&self) -> Vec<String> {
+        self.inner
+            .borrow()
+            .entries
+            .iter()
+            .map(|entry| entry.value.clone())
+            .collect()
+    }
+
+    /// Goes back to the previous location, returning `false` if there is
+    /// none.
+    pub fn back(&self) -> bool {
+        use crate::{NavigateOptions, RouterContext};
+
+        let (previous, (cx, set_location)) = {
+            let mut inner = self.inner.borrow_mut();
+            // the history is only changed once a router is using it
+            let Some(router) = inner.router else {
+                return false;
+            };
+            if inner.entries.len() < 2 {
+                return false;
+            }
+            inner.entries.pop();
+            (inner.entries.last().cloned().unwrap_or_default(), router)
+        };
+        let Some(router) = use_context::<RouterContext>(cx) else {
+            leptos::warn!("RouterContext not found");
+            return false;
+        };
+
+        let is_back = router.inner.is_back;
+        router.inner.path_stack.update_value(|stack| {
+            stack.pop();
+        });
+        is_back.set(true);
+        request_animation_frame(move || is_back.set(false));
+        if let Err(e) = router.inner.navigate_from_route(
+            &previous.value,
+            &NavigateOptions {
+                resolve: false,
+                replace: true,
+                scroll: previous.scroll,
+                state: previous.state.clone(),
+            },
+        ) {
+            leptos::error!("{e:#?}");
+        }
+        set_location.set(previous);
+        true
+    }
+}
+
+impl History for MemoryIntegration {
+    fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
+        let mut inner = self.inner.borrow_mut();
+        if inner.entries.is_empty() {
+            inner.entries.push(LocationChange {
+                value: self.initial.clone(),
+                ..Default::default()
+            });
+        }
+        let current = inner.entries.last().cloned().unwrap_or_default();
+        let (location, set_location) = create_signal(cx, current);
+        inner.router = Some((cx, set_location));
+        location
+    }
+
+    fn navigate(&self, loc: &LocationChange) {
+        let mut inner = self.inner.borrow_mut();
+        if loc.replace {
+            inner.entries.pop();
+        }
+        inner.entries.push(loc.clone());
+    }
+}
@@ -1,9 +1,45 @@
+use leptos::{Serializable, SerializationError};
 use wasm_bindgen::JsValue;
 
+/// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state)
+/// stored with an entry of the browser's history. It is restored when the user
+/// navigates back or forward to that entry, so it can be used to keep
+/// transient UI state, like a scroll offset or a selection.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct State(pub Option<JsValue>);
 
 impl State {
+    /// Creates a state holding a value encoded with the same [Serializable]
+    /// implementation as resources. Read it back with [State::get], or with
+    /// [use_location_state](crate::use_location_state).
+    ///
+    /// ```
+    /// # use leptos::*;
+    /// # use leptos_router::*;
+    /// # fn example(cx: Scope) {
+    /// let navigate = use_navigate(cx);
+    /// _ = navigate(
+    ///     "/search",
+    ///     NavigateOptions {
+    ///         state: State::new(&42_usize).unwrap_or_default(),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// # }
+    /// ```
+    pub fn new<T: Serializable>(value: &T) -> Result<Self, SerializationError> {
+        Ok(State(Some(JsValue::from_str(&value.ser()?))))
+    }
+
+    /// Decodes a value stored with [State::new]. Returns `None` if there is
+    /// no state, or if it does not hold a `T`.
+    pub fn get<T: Serializable>(&self) -> Option<T> {
+        self.0
+            .as_ref()
+            .and_then(JsValue::as_string)
+            .and_then(|text| T::de(&text).ok())
+    }
+
     pub fn to_js_value(&self) -> JsValue {
         match &self.0 {
             Some(v) => v.clone(),
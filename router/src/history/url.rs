@@ -6,12 +6,20 @@ use wasm_bindgen::JsCast;
 #[cfg(not(feature = "ssr"))]
 use wasm_bindgen::JsValue;
 
+/// A URL parsed into its parts, like the browser's
+/// [`URL`](https://developer.mozilla.org/en-US/docs/Web/API/URL). The current
+/// one is [Location::url](crate::Location::url).
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Url {
+    /// The scheme, host, and port, like `https://leptos.dev`.
     pub origin: String,
+    /// The path, not containing the query string or hash fragment.
     pub pathname: String,
+    /// The raw query string.
     pub search: String,
+    /// The query string parsed into its key-value pairs.
     pub search_params: ParamsMap,
+    /// The hash fragment, including the leading `#`, or an empty string.
     pub hash: String,
 }
 
@@ -91,7 +99,10 @@ impl TryFrom<&str> for Url {
                     .map(|(key, value)| (key.to_string(), value.to_string()))
                     .collect::<linear_map::LinearMap<String, String>>(),
             ),
-            hash: Default::default(),
+            hash: url
+                .fragment()
+                .map(|hash| format!("#{hash}"))
+                .unwrap_or_default(),
         })
     }
 }
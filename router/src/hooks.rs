@@ -1,8 +1,11 @@
 use crate::{
-    Location, NavigateOptions, NavigationError, Params, ParamsError, ParamsMap,
-    RouteContext, RouterContext,
+    Location, MatchedRoute, NavigateOptions, NavigationError, Params,
+    ParamsError, ParamsMap, RouteContext, RouterContext, State,
+};
+use leptos::{
+    create_memo, signal_prelude::*, use_context, Memo, ReadSignal, Scope,
+    Serializable,
 };
-use leptos::{create_memo, signal_prelude::*, use_context, Memo, Scope};
 use std::rc::Rc;
 
 /// Returns the current [RouterContext], containing information about the router's state.
@@ -27,6 +30,71 @@ pub fn use_location(cx: Scope) -> Location {
     use_router(cx).inner.location.clone()
 }
 
+/// Returns whether the router is navigating to a new route. See
+/// [RouterContext::is_routing].
+pub fn use_is_routing(cx: Scope) -> ReadSignal<bool> {
+    use_router(cx).is_routing()
+}
+
+/// Returns the chain of nested routes matched by the current location, from
+/// the outermost to the innermost, with their path patterns, params and
+/// `meta` data. This can be used to build breadcrumbs.
+///
+/// The list is empty until the [`<Routes/>`](crate::Routes) component has
+/// been created, so while rendering on the server it should be read within
+/// `<Routes/>`, e.g., in the view of a parent route.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Breadcrumbs(cx: Scope) -> impl IntoView {
+///     let matched = use_matched(cx);
+///
+///     view! { cx,
+///         <nav>
+///             {move || matched.get()
+///                 .into_iter()
+///                 .filter_map(|route| {
+///                     let title = route.meta.as_ref()?.as_str()?.to_string();
+///                     Some(view! { cx, <A href=route.path>{title}</A> })
+///                 })
+///                 .collect_view(cx)}
+///         </nav>
+///     }
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Router>
+///             <Routes>
+///                 <Route
+///                     path="users"
+///                     meta="Users"
+///                     view=|cx| view! { cx, <Breadcrumbs/><Outlet/> }
+///                 >
+///                     <Route
+///                         path=":id"
+///                         meta="Profile"
+///                         view=|cx| view! { cx, <p>"Profile"</p> }
+///                     />
+///                 </Route>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+/// ```
+pub fn use_matched(cx: Scope) -> Memo<Vec<MatchedRoute>> {
+    let matches = use_router(cx).inner.matches;
+    create_memo(cx, move |_| {
+        matches
+            .get()
+            .map(|matches| matches.get().iter().map(Into::into).collect())
+            .unwrap_or_default()
+    })
+}
+
 /// Returns a raw key-value map of route params.
 pub fn use_params_map(cx: Scope) -> Memo<ParamsMap> {
     let route = use_route(cx);
@@ -58,17 +126,54 @@ where
     })
 }
 
+/// Returns the [State] of the current history entry, decoded as a `T`, or
+/// `None` if there is none or it does not hold a `T`. The state can be set
+/// with [State::new] when navigating, with [use_navigate] or the `state` prop
+/// of [`<A/>`](crate::A), and is restored when the user navigates back or
+/// forward to the entry.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Gallery(cx: Scope) -> impl IntoView {
+///     // the photo that was open when the user navigated away
+///     let selected = use_location_state::<usize>(cx);
+///     view! { cx, <p>{move || format!("{:?}", selected.get())}</p> }
+/// }
+/// ```
+pub fn use_location_state<T>(cx: Scope) -> Memo<Option<T>>
+where
+    T: Serializable + PartialEq + 'static,
+{
+    let router = use_router(cx);
+    create_memo(cx, move |_| {
+        router.inner.location.state.with(State::get::<T>)
+    })
+}
+
 /// Resolves the given path relative to the current route.
 pub fn use_resolved_path(
     cx: Scope,
     path: impl Fn() -> String + 'static,
 ) -> Memo<Option<String>> {
     let route = use_route(cx);
+    let location = use_location(cx);
 
     create_memo(cx, move |_| {
         let path = path();
         if path.starts_with('/') {
             Some(path)
+        } else if path.starts_with('#') {
+            // a link to an element on the current page
+            let search = location.search.with(|search| {
+                if search.is_empty() || search.starts_with('?') {
+                    search.clone()
+                } else {
+                    format!("?{search}")
+                }
+            });
+            Some(location.pathname.get() + &search + &path)
         } else {
             route.resolve_path_tracked(&path).map(String::from)
         }
@@ -85,6 +190,30 @@ pub fn use_navigate(
     }
 }
 
+/// Returns a function that changes the hash fragment of the URL, adding an
+/// entry to the browser's history, and scrolls to the element with that ID,
+/// if there is one. Because the path stays the same, no route is matched
+/// again and no data is loaded, which makes this useful for tabs or sections
+/// within a page. An empty hash removes it.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Tabs(cx: Scope) -> impl IntoView {
+///     let hash = use_location(cx).hash;
+///     let set_hash = use_set_hash(cx);
+///     view! { cx,
+///         <button on:click=move |_| set_hash("comments")>"Comments"</button>
+///         <p>{move || format!("showing {}", hash.get())}</p>
+///     }
+/// }
+/// ```
+pub fn use_set_hash(cx: Scope) -> impl Fn(&str) {
+    let router = use_router(cx);
+    move |hash| router.inner.set_hash(hash)
+}
+
 /// Returns a signal that tells you whether you are currently navigating backwards.
 pub(crate) fn use_is_back_navigation(cx: Scope) -> ReadSignal<bool> {
     let router = use_router(cx);
@@ -177,6 +177,8 @@
 //! - `csr` Client-side rendering: Generate DOM nodes in the browser
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
 //! - `hydrate` Hydration: use this to add interactivity to an SSRed Leptos app
+//! - `devtools` Adds the [`<RouterDevtools/>`](crate::RouterDevtools) overlay, which shows
+//!   the matched routes and navigation timings. Only enable it for development builds.
 //! - `stable` By default, Leptos requires `nightly` Rust, which is what allows the ergonomics
 //!   of calling signals as functions. Enable this feature to support `stable` Rust.
 //!
@@ -188,6 +190,8 @@
 #![cfg_attr(not(feature = "stable"), feature(type_name_of_val))]
 
 mod animation;
+#[cfg(feature = "ssr")]
+mod audit;
 mod components;
 #[cfg(any(feature = "ssr", doc))]
 mod extract_routes;
@@ -196,6 +200,9 @@ mod hooks;
 #[doc(hidden)]
 pub mod matching;
 mod render_mode;
+mod revalidation;
+#[cfg(feature = "ssr")]
+pub use audit::*;
 pub use components::*;
 #[cfg(any(feature = "ssr", doc))]
 pub use extract_routes::*;
@@ -203,4 +210,5 @@ pub use history::*;
 pub use hooks::*;
 pub use matching::{RouteDefinition, *};
 pub use render_mode::*;
+pub use revalidation::*;
 extern crate tracing;
@@ -3,7 +3,7 @@ mod matcher;
 mod resolve_path;
 mod route;
 
-use crate::{Branches, RouteData};
+use crate::{Branches, ParamsMap, RouteData};
 pub use expand_optionals::*;
 pub use matcher::*;
 pub use resolve_path::*;
@@ -16,6 +16,32 @@ pub(crate) struct RouteMatch {
     pub route: RouteData,
 }
 
+/// One of the nested routes matched by the current location, as returned by
+/// [use_matched](crate::use_matched).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedRoute {
+    /// The full path pattern of the route, including those of its parents,
+    /// like `/users/:id`.
+    pub pattern: String,
+    /// The part of the current path that the route matched, like `/users/42`.
+    pub path: String,
+    /// The params matched by the route and its parents.
+    pub params: ParamsMap,
+    /// The data attached to the route with its `meta` prop.
+    pub meta: Option<RouteMeta>,
+}
+
+impl From<&RouteMatch> for MatchedRoute {
+    fn from(route_match: &RouteMatch) -> Self {
+        Self {
+            pattern: route_match.route.pattern.clone(),
+            path: route_match.path_match.path.clone(),
+            params: route_match.path_match.params.clone(),
+            meta: route_match.route.key.meta.clone(),
+        }
+    }
+}
+
 pub(crate) fn get_route_matches(
     base: &str,
     location: String,
@@ -1,10 +1,36 @@
 use crate::{Method, SsrMode};
 use leptos::{leptos_dom::View, *};
-use std::rc::Rc;
+use std::{any::Any, cell::Cell, rc::Rc};
+
+thread_local! {
+    static ROUTE_ID: Cell<usize> = Cell::new(0);
+}
+
+pub(crate) fn next_route_id() -> usize {
+    ROUTE_ID.with(|id| {
+        let next = id.get() + 1;
+        id.set(next);
+        next
+    })
+}
 
 /// Defines a single route in a nested route tree. This is the return
 /// type of the [`<Route/>`](crate::Route) component, but can also be
 /// used to build your own configuration-based or filesystem-based routing.
+///
+/// Create one with [RouteDefinition::new] and set the other fields on it,
+/// rather than with a struct literal, which stops compiling whenever a field
+/// is added (as it did when [meta](RouteDefinition::meta) was).
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// let mut users = RouteDefinition::new("users", |cx| view! { cx, <Outlet/> });
+/// users.meta = Some("Users".into());
+/// users.children = vec![RouteDefinition::new(":id", |cx| {
+///     view! { cx, <p>"Profile"</p> }
+/// })];
+/// ```
 #[derive(Clone)]
 pub struct RouteDefinition {
     /// A unique ID for each route.
@@ -19,6 +45,33 @@ pub struct RouteDefinition {
     pub ssr_mode: SsrMode,
     /// The HTTP request methods this route is able to handle.
     pub methods: &'static [Method],
+    /// Data attached to the route, like a title for breadcrumbs, which is
+    /// available from [use_matched](crate::use_matched).
+    pub meta: Option<RouteMeta>,
+}
+
+impl RouteDefinition {
+    /// Creates a route that shows `view` at `path`, with a new ID, no
+    /// children and no meta, which prefers the default [SsrMode] and only
+    /// handles `GET` requests, like a [`<Route/>`](crate::Route) without
+    /// other props.
+    pub fn new<V>(
+        path: impl Into<String>,
+        view: impl Fn(Scope) -> V + 'static,
+    ) -> Self
+    where
+        V: IntoView,
+    {
+        Self {
+            id: next_route_id(),
+            path: path.into(),
+            children: Vec::new(),
+            view: Rc::new(move |cx| view(cx).into_view(cx)),
+            ssr_mode: SsrMode::default(),
+            methods: &[Method::Get],
+            meta: None,
+        }
+    }
 }
 
 impl std::fmt::Debug for RouteDefinition {
@@ -27,6 +80,7 @@ impl std::fmt::Debug for RouteDefinition {
             .field("path", &self.path)
             .field("children", &self.children)
             .field("ssr_mode", &self.ssr_mode)
+            .field("meta", &self.meta)
             .finish()
     }
 }
@@ -36,3 +90,57 @@ impl PartialEq for RouteDefinition {
         self.path == other.path && self.children == other.children
     }
 }
+
+/// Arbitrary data attached to a route with the `meta` prop of
+/// [`<Route/>`](crate::Route), like a title for breadcrumbs or a flag for
+/// the layout. Strings can be passed directly, while any other type is
+/// wrapped with [RouteMeta::new].
+#[derive(Clone)]
+pub struct RouteMeta(Rc<dyn Any>);
+
+impl RouteMeta {
+    /// Wraps the data for a route.
+    pub fn new<T: 'static>(value: T) -> Self {
+        Self(Rc::new(value))
+    }
+
+    /// Returns a reference to the data if it is a `T`, or `None` if it isn't.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns the data as a string slice, if it was created from a
+    /// `&'static str` or a [String].
+    pub fn as_str(&self) -> Option<&str> {
+        self.downcast_ref::<&'static str>()
+            .copied()
+            .or_else(|| self.downcast_ref::<String>().map(String::as_str))
+    }
+}
+
+impl From<&'static str> for RouteMeta {
+    fn from(value: &'static str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for RouteMeta {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl std::fmt::Debug for RouteMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.as_str() {
+            Some(s) => f.debug_tuple("RouteMeta").field(&s).finish(),
+            None => f.debug_tuple("RouteMeta").finish(),
+        }
+    }
+}
+
+impl PartialEq for RouteMeta {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
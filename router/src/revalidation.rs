@@ -0,0 +1,87 @@
+use crate::RouterContext;
+use leptos::{
+    create_effect, create_resource, signal_prelude::*, use_context, Resource,
+    Scope, Serializable,
+};
+use std::future::Future;
+
+/// Creates a [Resource] that loads data for the current route, like a Remix
+/// loader. It works like [create_resource], but it is also refetched whenever
+/// the router revalidates: after an [ActionForm](crate::ActionForm) or
+/// [MultiActionForm](crate::MultiActionForm) completes, after an action
+/// passed to [revalidate_on] completes, or when
+/// [RouterContext::revalidate] is called.
+///
+/// Only the resources of the routes that are currently matched exist, so
+/// those are the only ones that are refetched.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # #[server(AddTodo, "/api")]
+/// # pub async fn add_todo(title: String) -> Result<(), ServerFnError> { todo!() }
+/// # async fn load_todos(_: ()) -> Vec<String> { vec![] }
+/// #[component]
+/// fn Todos(cx: Scope) -> impl IntoView {
+///     let todos = create_route_resource(cx, || (), load_todos);
+///     let add_todo = create_server_action::<AddTodo>(cx);
+///
+///     view! { cx,
+///         // once the todo has been added, `todos` is loaded again
+///         <ActionForm action=add_todo>
+///             <input type="text" name="title"/>
+///             <input type="submit" value="Add"/>
+///         </ActionForm>
+///         <ul>
+///             {move || todos.read(cx).map(|todos| {
+///                 todos.into_iter()
+///                     .map(|todo| view! { cx, <li>{todo}</li> })
+///                     .collect_view(cx)
+///             })}
+///         </ul>
+///     }
+/// }
+/// ```
+pub fn create_route_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let resource = create_resource(cx, source, fetcher);
+    if let Some(router) = use_context::<RouterContext>(cx) {
+        let revalidation = router.inner.revalidation;
+        create_effect(cx, move |prev: Option<usize>| {
+            let version = revalidation.get();
+            if prev.is_some() {
+                resource.refetch();
+            }
+            version
+        });
+    }
+    resource
+}
+
+/// Revalidates the router, refetching every resource created with
+/// [create_route_resource], each time `version` changes. Pass the
+/// [version](leptos::Action::version) of an action, so that the data for the
+/// current routes is loaded again once the action completes.
+///
+/// [ActionForm](crate::ActionForm) and [MultiActionForm](crate::MultiActionForm)
+/// already do this for their action, unless `revalidate` is `false`.
+pub fn revalidate_on(cx: Scope, version: impl Into<Signal<usize>>) {
+    let version = version.into();
+    if let Some(router) = use_context::<RouterContext>(cx) {
+        create_effect(cx, move |prev: Option<usize>| {
+            let version = version.get();
+            if prev.is_some() {
+                router.revalidate();
+            }
+            version
+        });
+    }
+}
@@ -0,0 +1,64 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos_router::{audit_html, EnhancementIssueKind};
+
+        fn kinds(html: &str) -> Vec<EnhancementIssueKind> {
+            audit_html(html).into_iter().map(|issue| issue.kind).collect()
+        }
+
+        #[test]
+        fn audit_accepts_working_html() {
+            assert_eq!(
+                kinds(
+                    r#"<a href="/about">About</a><!-- <button> -->
+                    <form action="/api/add" method="POST">
+                        <input name="x"/><button>Add</button>
+                    </form>"#
+                ),
+                vec![]
+            );
+        }
+
+        #[test]
+        fn audit_finds_script_only_forms() {
+            assert_eq!(
+                kinds(r#"<form><button type="submit">Go</button></form>"#),
+                vec![EnhancementIssueKind::FormWithoutAction]
+            );
+            assert_eq!(
+                kinds(r#"<form action="/x" method="delete"></form>"#),
+                vec![EnhancementIssueKind::FormInvalidMethod("delete".into())]
+            );
+        }
+
+        #[test]
+        fn audit_finds_script_only_links() {
+            assert_eq!(
+                kinds(r##"<a>One</a><a href="#">Two</a><a href='javascript:void(0)'>Three</a>"##),
+                vec![
+                    EnhancementIssueKind::LinkWithoutHref,
+                    EnhancementIssueKind::LinkWithScriptHref("#".into()),
+                    EnhancementIssueKind::LinkWithScriptHref(
+                        "javascript:void(0)".into()
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn audit_finds_script_only_buttons() {
+            assert_eq!(
+                kinds(
+                    r#"<button id="_0-1">+1</button>
+                    <form action="/x"><button type="button">Clear</button></form>"#
+                ),
+                vec![
+                    EnhancementIssueKind::ButtonOutsideForm,
+                    EnhancementIssueKind::ButtonOutsideForm,
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(not(feature = "ssr"))]
+
+use leptos::*;
+use leptos_router::*;
+
+// Renders the routes at `path`, and returns the routes that are matched.
+fn matched_at(path: &'static str) -> Vec<MatchedRoute> {
+    run_scope(create_runtime(), move |cx| {
+        provide_context(
+            cx,
+            RouterIntegrationContext::new(MemoryIntegration::new(path)),
+        );
+        let matched = store_value(cx, None);
+        _ = Router(
+            cx,
+            RouterProps::builder()
+                .children(Box::new(move |cx| {
+                    let routes = view! { cx,
+                        <Routes>
+                            <Route
+                                path="users"
+                                meta="Users"
+                                view=|cx| view! { cx, <Outlet/> }
+                            >
+                                <Route
+                                    path=":id"
+                                    meta=RouteMeta::new(42_u32)
+                                    view=|cx| view! { cx, <p>"Profile"</p> }
+                                />
+                                <Route
+                                    path=""
+                                    view=|cx| view! { cx, <p>"Users"</p> }
+                                />
+                            </Route>
+                        </Routes>
+                    };
+                    matched.set_value(Some(use_matched(cx)));
+                    Fragment::new(vec![routes.into_view(cx)])
+                }))
+                .build(),
+        );
+        matched.get_value().unwrap().get_untracked()
+    })
+}
+
+#[test]
+fn use_matched_lists_nested_routes_with_their_meta() {
+    let matched = matched_at("/users/42");
+    assert_eq!(matched.len(), 2);
+
+    assert_eq!(matched[0].pattern, "/users");
+    assert_eq!(matched[0].path, "/users");
+    assert_eq!(
+        matched[0].meta.as_ref().and_then(RouteMeta::as_str),
+        Some("Users")
+    );
+
+    assert_eq!(matched[1].pattern, "/users/:id");
+    assert_eq!(matched[1].path, "/users/42");
+    assert_eq!(matched[1].params.get("id"), Some(&"42".to_string()));
+    assert_eq!(
+        matched[1]
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.downcast_ref::<u32>()),
+        Some(&42)
+    );
+}
+
+#[test]
+fn routes_without_meta_are_matched_too() {
+    let matched = matched_at("/users");
+    assert_eq!(matched.len(), 2);
+    assert_eq!(matched[1].pattern, "/users");
+    assert_eq!(matched[1].meta, None);
+    assert_eq!(matched[1].meta.as_ref().and_then(RouteMeta::as_str), None);
+}
+
+#[test]
+fn nothing_is_matched_outside_the_routes() {
+    assert!(matched_at("/about").is_empty());
+}
+
+#[test]
+fn route_definition_new_sets_defaults() {
+    let route = RouteDefinition::new("users", |cx| view! { cx, <Outlet/> });
+    assert_eq!(route.path, "users");
+    assert!(route.children.is_empty());
+    assert_eq!(route.ssr_mode, SsrMode::default());
+    assert_eq!(route.methods, &[Method::Get]);
+    assert_eq!(route.meta, None);
+    assert_ne!(RouteDefinition::new("users", |_| ()).id, route.id);
+}
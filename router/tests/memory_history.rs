@@ -0,0 +1,55 @@
+use leptos::{create_runtime, run_scope, SignalGetUntracked};
+use leptos_router::{History, LocationChange, MemoryIntegration};
+
+fn change(value: &str, replace: bool) -> LocationChange {
+    LocationChange {
+        value: value.to_string(),
+        replace,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn memory_history_starts_at_initial_path() {
+    run_scope(create_runtime(), |cx| {
+        let history = MemoryIntegration::new("/todos");
+        assert!(history.entries().is_empty());
+
+        let location = history.location(cx);
+        assert_eq!(location.get_untracked().value, "/todos");
+        assert_eq!(history.entries(), vec!["/todos".to_string()]);
+    });
+}
+
+#[test]
+fn memory_history_pushes_and_replaces() {
+    run_scope(create_runtime(), |cx| {
+        let history = MemoryIntegration::new("/");
+        _ = history.location(cx);
+
+        history.navigate(&change("/todos", false));
+        history.navigate(&change("/todos/1", false));
+        history.navigate(&change("/todos/2", true));
+        assert_eq!(history.entries(), vec!["/", "/todos", "/todos/2"]);
+
+        // clones share the same history
+        history.clone().navigate(&change("/about", false));
+        assert_eq!(history.entries().last().unwrap(), "/about");
+    });
+}
+
+#[test]
+fn memory_history_back_needs_a_router_and_a_previous_entry() {
+    run_scope(create_runtime(), |cx| {
+        let history = MemoryIntegration::new("/");
+        history.navigate(&change("/todos", false));
+        // not used by a router yet
+        assert!(!history.back());
+        assert_eq!(history.entries(), vec!["/todos"]);
+
+        let history = MemoryIntegration::new("/");
+        _ = history.location(cx);
+        assert!(!history.back());
+        assert_eq!(history.entries(), vec!["/"]);
+    });
+}
@@ -0,0 +1,106 @@
+#![cfg(not(feature = "ssr"))]
+
+use leptos::*;
+use leptos_router::*;
+use std::{cell::Cell, rc::Rc};
+
+#[server(AddTodo, "/api")]
+pub async fn add_todo(title: String) -> Result<(), ServerFnError> {
+    _ = title;
+    Ok(())
+}
+
+type AddTodoAction = Action<AddTodo, Result<(), ServerFnError>>;
+
+// Renders a router with a route resource, calls `setup` beneath it, and
+// dispatches the action once. Returns how many times the resource was
+// fetched.
+fn fetches_after_action(
+    setup: impl FnOnce(Scope, AddTodoAction) + 'static,
+) -> usize {
+    let fetches = Rc::new(Cell::new(0));
+    run_scope(create_runtime(), {
+        let fetches = Rc::clone(&fetches);
+        move |cx| {
+            let executor = TestExecutor::new();
+            provide_context(
+                cx,
+                RouterIntegrationContext::new(MemoryIntegration::new("/todos")),
+            );
+            let action: AddTodoAction =
+                create_action(cx, |_: &AddTodo| async { Ok(()) });
+
+            _ = Router(
+                cx,
+                RouterProps::builder()
+                    .children(Box::new(move |cx| {
+                        _ = create_route_resource(
+                            cx,
+                            || (),
+                            move |_| {
+                                fetches.set(fetches.get() + 1);
+                                async {}
+                            },
+                        );
+                        setup(cx, action);
+                        Fragment::new(vec![])
+                    }))
+                    .build(),
+            );
+            executor.run_until_stalled();
+
+            action.dispatch(AddTodo {
+                title: "Write tests".into(),
+            });
+            executor.run_until_stalled();
+            assert_eq!(action.version().get_untracked(), 1);
+        }
+    });
+    fetches.get()
+}
+
+#[test]
+fn route_resource_is_not_refetched_without_revalidation() {
+    assert_eq!(fetches_after_action(|_, _| {}), 1);
+}
+
+#[test]
+fn completed_action_refetches_route_resource() {
+    assert_eq!(
+        fetches_after_action(|cx, action| revalidate_on(cx, action.version())),
+        2
+    );
+}
+
+#[test]
+fn action_form_revalidates_by_default() {
+    assert_eq!(
+        fetches_after_action(|cx, action| {
+            _ = ActionForm(
+                cx,
+                ActionFormProps::builder()
+                    .action(action)
+                    .children(Box::new(|_| Fragment::new(vec![])))
+                    .build(),
+            );
+        }),
+        2
+    );
+}
+
+#[test]
+fn action_form_without_revalidate_does_not_refetch() {
+    assert_eq!(
+        fetches_after_action(|cx, action| {
+            _ = ActionForm(
+                cx,
+                ActionFormProps::builder()
+                    .action(action)
+                    .revalidate(false)
+                    .children(Box::new(|_| Fragment::new(vec![])))
+                    .build(),
+            );
+        }),
+        1
+    );
+}
@@ -0,0 +1,56 @@
+#![cfg(not(feature = "ssr"))]
+
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+
+type Navigate =
+    Box<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+#[test]
+fn is_routing_until_the_new_route_has_loaded() {
+    run_scope(create_runtime(), |cx| {
+        let executor = TestExecutor::new();
+        provide_context(
+            cx,
+            RouterIntegrationContext::new(MemoryIntegration::new("/")),
+        );
+        let router =
+            Rc::new(RefCell::new(None::<(ReadSignal<bool>, Navigate)>));
+        _ = Router(
+            cx,
+            RouterProps::builder()
+                .children(Box::new({
+                    let router = Rc::clone(&router);
+                    move |cx| {
+                        let routes = view! { cx,
+                            <Routes>
+                                <Route path="" view=|_| ()/>
+                                <Route path="loading" view=|cx| {
+                                    _ = create_resource(cx, || (), |_| async {});
+                                }/>
+                                <Route path="static" view=|_| ()/>
+                            </Routes>
+                        };
+                        *router.borrow_mut() = Some((
+                            use_is_routing(cx),
+                            Box::new(use_navigate(cx)) as Navigate,
+                        ));
+                        Fragment::new(vec![routes.into_view(cx)])
+                    }
+                }))
+                .build(),
+        );
+        let router = router.borrow();
+        let (is_routing, navigate) = router.as_ref().unwrap();
+        assert!(!is_routing.get_untracked());
+
+        navigate("/loading", Default::default()).unwrap();
+        assert!(is_routing.get_untracked());
+        executor.run_until_stalled();
+        assert!(!is_routing.get_untracked());
+
+        navigate("/static", Default::default()).unwrap();
+        assert!(!is_routing.get_untracked());
+    });
+}
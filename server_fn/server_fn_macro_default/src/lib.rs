@@ -10,7 +10,7 @@ use syn::__private::ToTokens;
 /// This means that its body will only run on the server, i.e., when the `ssr`
 /// feature is enabled.
 ///
-/// You can specify one, two, or three arguments to the server function:
+/// You can specify up to four arguments to the server function:
 /// 1. **Required**: A type name that will be used to identify and register the server function
 ///   (e.g., `MyServerFn`).
 /// 2. *Optional*: A URL prefix at which the function will be mounted when it’s registered
@@ -20,6 +20,10 @@ use syn::__private::ToTokens;
 ///   Defaults to `"Url"`. If you want to use this server function to power a `<form>` that will
 ///   work without WebAssembly, the encoding must be `"Url"`. If you want to use this server function
 ///   using Get instead of Post methods, the encoding must be `"GetCbor"` or `"GetJson"`.
+/// 4. *Optional*: `dedupe` or `no_dedupe`. Concurrent calls with the same arguments from the
+///   client share one request if the function uses the `"GetJson"` or `"GetCbor"` encoding.
+///   `dedupe` does the same for the `POST` encodings, and `no_dedupe` sends a new request for
+///   every call.
 ///
 /// The server function itself can take any number of arguments, each of which should be serializable
 /// and deserializable with `serde`.
@@ -0,0 +1,103 @@
+//! Authorization for server functions.
+//!
+//! When a hook is set with [set_server_fn_auth], the server runs it for every
+//! request to a server function, before its body is read, and so before the
+//! [CSRF](crate::csrf) check and the arguments are decoded. If it returns an error, the server
+//! function is not called, and the error is sent back instead: with the
+//! status `401 Unauthorized` for [ServerFnError::Unauthorized], `403
+//! Forbidden` for [ServerFnError::Forbidden], and
+//! [its usual status](ServerFnError::status) otherwise.
+//!
+//! ```rust,ignore
+//! set_server_fn_auth(|req: AuthRequest| async move {
+//!     // anyone may log in
+//!     if req.fn_name.starts_with("login") {
+//!         return Ok(());
+//!     }
+//!     match req.header("authorization") {
+//!         Some(token) if verify(token).await => Ok(()),
+//!         Some(_) => Err(ServerFnError::Forbidden("invalid token".into())),
+//!         None => Err(ServerFnError::Unauthorized("log in first".into())),
+//!     }
+//! });
+//! ```
+
+use crate::{csrf::cookie_value, Encoding, ServerFnError};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+/// The parts of a request to a server function that an authorization hook
+/// can check.
+#[derive(Clone, Debug)]
+pub struct AuthRequest {
+    /// The URL of the server function, without its prefix, e.g.,
+    /// `add_todo` followed by a hash.
+    pub fn_name: String,
+    /// The encoding of the server function.
+    pub encoding: Encoding,
+    /// The HTTP method of the request.
+    pub method: String,
+    /// The URI of the request.
+    pub uri: String,
+    /// The headers of the request, with lowercase names.
+    pub headers: Vec<(String, String)>,
+}
+
+impl AuthRequest {
+    /// The value of the first header with the given name, if there is one.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The value of the cookie with the given name, if there is one.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")
+            .and_then(|cookies| cookie_value(cookies, name))
+    }
+}
+
+type AuthHook = Arc<
+    dyn Fn(
+            AuthRequest,
+        ) -> Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>>
+        + Send
+        + Sync,
+>;
+
+static HOOK: RwLock<Option<AuthHook>> = RwLock::new(None);
+
+/// Sets the hook that decides whether a request may call a server function.
+/// Call this in your `main` function, before the server starts.
+pub fn set_server_fn_auth<F, Fut>(hook: F)
+where
+    F: Fn(AuthRequest) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), ServerFnError>> + 'static,
+{
+    if let Ok(mut current) = HOOK.write() {
+        *current = Some(Arc::new(move |req| Box::pin(hook(req))));
+    }
+}
+
+/// Removes the hook set with [set_server_fn_auth], so that every request is
+/// allowed again.
+pub fn clear_server_fn_auth() {
+    if let Ok(mut current) = HOOK.write() {
+        *current = None;
+    }
+}
+
+/// Runs the hook set with [set_server_fn_auth] for the request, if there is
+/// one. Used by the server integrations.
+pub async fn authorize(request: AuthRequest) -> Result<(), ServerFnError> {
+    let hook = HOOK.read().ok().and_then(|hook| hook.clone());
+    match hook {
+        Some(hook) => hook(request).await,
+        None => Ok(()),
+    }
+}
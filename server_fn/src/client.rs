@@ -0,0 +1,502 @@
+//! The HTTP client used to call server functions, which can be replaced to
+//! configure every request in one place, or to mock the server in tests.
+//!
+//! [call_server_fn](crate::call_server_fn) sends its requests with the client
+//! set by [set_http_client]. By default, this is [DefaultHttpClient], which
+//! uses the browser's `fetch` in WebAssembly and `reqwest` everywhere else.
+//! The same client can be used to load other data, like the fetcher of a
+//! resource, by calling [http_client]:
+//!
+//! ```
+//! # use server_fn::{client::*, ServerFnError};
+//! async fn load_user(id: u32) -> Result<String, ServerFnError> {
+//!     let response = http_client()
+//!         .send(HttpRequest::get(format!("/api/users/{id}")))
+//!         .await?;
+//!     Ok(response.text()?.to_string())
+//! }
+//! ```
+//!
+//! The client is stored per thread, since the futures of the browser's
+//! `fetch` can't be sent to another thread.
+
+use crate::ServerFnError;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+/// The HTTP method of an [HttpRequest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HttpMethod {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+}
+
+/// Whether the browser sends cookies and HTTP authentication with a request,
+/// like the [`credentials`](https://developer.mozilla.org/en-US/docs/Web/API/Request/credentials)
+/// option of `fetch`. Outside the browser, requests never include cookies.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Credentials {
+    /// Never send credentials.
+    Omit,
+    /// Only send credentials to the origin of the page.
+    #[default]
+    SameOrigin,
+    /// Also send credentials to other origins. The server must allow this in
+    /// its CORS headers.
+    Include,
+}
+
+/// A request sent by a [LeptosHttpClient].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+    /// The method of the request.
+    pub method: HttpMethod,
+    /// The URL the request is sent to.
+    pub url: String,
+    /// The headers of the request, in the order they were added.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request, if any.
+    pub body: Option<Vec<u8>>,
+    /// Whether the browser sends cookies with the request.
+    pub credentials: Credentials,
+}
+
+impl HttpRequest {
+    /// Creates a `GET` request without headers.
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: HttpMethod::Get,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            credentials: Credentials::default(),
+        }
+    }
+
+    /// Creates a `POST` request with the given body, without headers.
+    pub fn post(url: impl Into<String>, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            method: HttpMethod::Post,
+            url: url.into(),
+            headers: Vec::new(),
+            body: Some(body.into()),
+            credentials: Credentials::default(),
+        }
+    }
+
+    /// Adds a header to the request.
+    pub fn header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets whether the browser sends cookies with the request.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+}
+
+/// A response received by a [LeptosHttpClient].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The status text of the response, like `Not Found`.
+    pub status_text: String,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// The value of the header with the given name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The body of the response as UTF-8 text.
+    pub fn text(&self) -> Result<&str, ServerFnError> {
+        std::str::from_utf8(&self.body)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+}
+
+/// The future returned by [LeptosHttpClient::send].
+pub type HttpFuture =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, ServerFnError>>>>;
+
+/// Sends HTTP requests for server functions, and for anything else that
+/// loads data with [http_client].
+///
+/// Implement it to add a base URL or headers (like an auth token) to every
+/// request by wrapping another client, or to answer requests without a
+/// server in tests:
+///
+/// ```
+/// # use server_fn::{client::*, ServerFnError};
+/// struct MockServer;
+///
+/// impl LeptosHttpClient for MockServer {
+///     fn send(&self, request: HttpRequest) -> HttpFuture {
+///         Box::pin(async move {
+///             Ok(HttpResponse {
+///                 status: 200,
+///                 status_text: "OK".into(),
+///                 headers: Vec::new(),
+///                 body: format!("\"{}\"", request.url).into_bytes(),
+///             })
+///         })
+///     }
+/// }
+///
+/// set_http_client(MockServer);
+/// ```
+pub trait LeptosHttpClient {
+    /// Sends the request, returning a [ServerFnError::Request] if the
+    /// server can't be reached. Responses with an error status are returned
+    /// as they are.
+    fn send(&self, request: HttpRequest) -> HttpFuture;
+}
+
+thread_local! {
+    static CLIENT: RefCell<Rc<dyn LeptosHttpClient>> =
+        RefCell::new(Rc::new(DefaultHttpClient::default()));
+}
+
+/// Replaces the client used on this thread by server functions and
+/// [http_client].
+pub fn set_http_client(client: impl LeptosHttpClient + 'static) {
+    CLIENT.with(|current| *current.borrow_mut() = Rc::new(client));
+}
+
+/// The client set by [set_http_client], or [DefaultHttpClient].
+pub fn http_client() -> Rc<dyn LeptosHttpClient> {
+    CLIENT.with(|current| Rc::clone(&current.borrow()))
+}
+
+/// Wraps another [LeptosHttpClient], resolving relative URLs against a base
+/// URL and adding headers to every request.
+///
+/// ```
+/// # use server_fn::client::*;
+/// set_http_client(
+///     ConfiguredHttpClient::new(DefaultHttpClient::default())
+///         .base_url("https://api.example.com")
+///         .header("Authorization", "Bearer token")
+///         .credentials(Credentials::Include),
+/// );
+/// ```
+pub struct ConfiguredHttpClient<C> {
+    inner: C,
+    defaults: RequestDefaults,
+}
+
+impl<C> ConfiguredHttpClient<C>
+where
+    C: LeptosHttpClient,
+{
+    /// Wraps the client without changing any request.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            defaults: RequestDefaults::new(),
+        }
+    }
+
+    /// Prefixes every URL that starts with `/` with `base_url`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.defaults.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Adds a header to every request that does not already have it.
+    pub fn header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.defaults.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets whether the browser sends cookies with every request.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.defaults.credentials = Some(credentials);
+        self
+    }
+}
+
+impl<C> LeptosHttpClient for ConfiguredHttpClient<C>
+where
+    C: LeptosHttpClient,
+{
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        self.inner.send(self.defaults.apply(request))
+    }
+}
+
+/// The changes a [ConfiguredHttpClient] makes to each request. The same are
+/// made to every server function call, as configured by
+/// [set_server_url](crate::set_server_url),
+/// [set_server_credentials](crate::set_server_credentials) and
+/// [add_server_header](crate::add_server_header).
+#[derive(Debug, Clone)]
+pub(crate) struct RequestDefaults {
+    pub(crate) base_url: Option<String>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) credentials: Option<Credentials>,
+}
+
+impl RequestDefaults {
+    pub(crate) const fn new() -> Self {
+        Self {
+            base_url: None,
+            headers: Vec::new(),
+            credentials: None,
+        }
+    }
+
+    pub(crate) fn apply(&self, mut request: HttpRequest) -> HttpRequest {
+        if let Some(base_url) = &self.base_url {
+            if request.url.starts_with('/') {
+                request.url = format!(
+                    "{}{}",
+                    base_url.trim_end_matches('/'),
+                    request.url
+                );
+            }
+        }
+        for (name, value) in &self.headers {
+            if !request
+                .headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case(name))
+            {
+                request.headers.push((name.clone(), value.clone()));
+            }
+        }
+        if let Some(credentials) = self.credentials {
+            request.credentials = credentials;
+        }
+        request
+    }
+}
+
+/// Sends requests with the browser's `fetch` in WebAssembly, and with
+/// `reqwest` everywhere else.
+#[derive(Default, Clone)]
+pub struct DefaultHttpClient {
+    #[cfg(not(target_arch = "wasm32"))]
+    client: reqwest::Client,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LeptosHttpClient for DefaultHttpClient {
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        use gloo_net::http::Request;
+
+        Box::pin(async move {
+            let mut builder = match request.method {
+                HttpMethod::Get => Request::get(&request.url),
+                HttpMethod::Post => Request::post(&request.url),
+            };
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+            builder = builder.credentials(match request.credentials {
+                Credentials::Omit => web_sys::RequestCredentials::Omit,
+                Credentials::SameOrigin => {
+                    web_sys::RequestCredentials::SameOrigin
+                }
+                Credentials::Include => web_sys::RequestCredentials::Include,
+            });
+            if let Some(body) = request.body {
+                builder =
+                    builder.body(js_sys::Uint8Array::from(&body[..]).buffer());
+            }
+            let response = builder
+                .send()
+                .await
+                .map_err(|e| ServerFnError::Request(e.to_string()))?;
+            Ok(HttpResponse {
+                status: response.status(),
+                status_text: response.status_text(),
+                headers: response.headers().entries().collect(),
+                body: response
+                    .binary()
+                    .await
+                    .map_err(|e| ServerFnError::Request(e.to_string()))?,
+            })
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LeptosHttpClient for DefaultHttpClient {
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        let method = match request.method {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+        };
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        Box::pin(async move {
+            let response = builder
+                .send()
+                .await
+                .map_err(|e| ServerFnError::Request(e.to_string()))?;
+            let status = response.status();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            Ok(HttpResponse {
+                status: status.as_u16(),
+                status_text: status
+                    .canonical_reason()
+                    .unwrap_or_default()
+                    .to_string(),
+                headers,
+                body: response
+                    .bytes()
+                    .await
+                    .map_err(|e| ServerFnError::Request(e.to_string()))?
+                    .to_vec(),
+            })
+        })
+    }
+}
+
+/// Sends every request to a command of the [Tauri](https://tauri.app/) app
+/// the page runs in, over its IPC channel rather than HTTP, so that server
+/// functions run in the Rust process of a desktop app.
+///
+/// The command receives the [HttpRequest] as its `request` argument, and
+/// returns an [HttpResponse]. With the `leptos_tauri` integration, it can be
+/// written as
+///
+/// ```ignore
+/// #[tauri::command]
+/// async fn leptos_server_fn(request: HttpRequest) -> HttpResponse {
+///     leptos_tauri::handle_server_fn(request).await
+/// }
+/// ```
+///
+/// The global Tauri API must be enabled with `withGlobalTauri` in
+/// `tauri.conf.json`. Outside of a Tauri webview, every request fails with
+/// a [ServerFnError::Request].
+///
+/// ```
+/// # use server_fn::client::*;
+/// set_http_client(TauriIpcClient::default());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TauriIpcClient {
+    command: String,
+}
+
+impl Default for TauriIpcClient {
+    fn default() -> Self {
+        Self {
+            command: "leptos_server_fn".into(),
+        }
+    }
+}
+
+impl TauriIpcClient {
+    /// Sends the requests to the command with the given name, rather than
+    /// `leptos_server_fn`.
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.command = command.into();
+        self
+    }
+}
+
+impl LeptosHttpClient for TauriIpcClient {
+    #[cfg(target_arch = "wasm32")]
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        use wasm_bindgen::{JsCast, JsValue};
+
+        let command = self.command.clone();
+        Box::pin(async move {
+            let ipc_error = |e: JsValue| {
+                ServerFnError::Request(
+                    e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                )
+            };
+            // Tauri 1 exposes `invoke` in `__TAURI__.tauri`, Tauri 2 in
+            // `__TAURI__.core`
+            let tauri = js_sys::Reflect::get(
+                &js_sys::global(),
+                &JsValue::from_str("__TAURI__"),
+            )
+            .map_err(ipc_error)?;
+            let invoke = ["tauri", "core"]
+                .into_iter()
+                .filter_map(|module| {
+                    js_sys::Reflect::get(&tauri, &JsValue::from_str(module))
+                        .ok()
+                })
+                .filter_map(|module| {
+                    js_sys::Reflect::get(&module, &JsValue::from_str("invoke"))
+                        .ok()
+                })
+                .find_map(|invoke| invoke.dyn_into::<js_sys::Function>().ok())
+                .ok_or_else(|| {
+                    ServerFnError::Request(
+                        "the Tauri API is not available; enable \
+                         `withGlobalTauri` in `tauri.conf.json`"
+                            .into(),
+                    )
+                })?;
+
+            let args = serde_json::json!({ "request": request }).to_string();
+            let args = js_sys::JSON::parse(&args).map_err(ipc_error)?;
+            let promise = invoke
+                .call2(&JsValue::NULL, &JsValue::from_str(&command), &args)
+                .map_err(ipc_error)?;
+            let response = wasm_bindgen_futures::JsFuture::from(
+                js_sys::Promise::resolve(&promise),
+            )
+            .await
+            .map_err(ipc_error)?;
+            let response = js_sys::JSON::stringify(&response)
+                .map_err(ipc_error)?
+                .as_string()
+                .unwrap_or_default();
+            serde_json::from_str(&response)
+                .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn send(&self, _request: HttpRequest) -> HttpFuture {
+        Box::pin(async move {
+            Err(ServerFnError::Request(
+                "Tauri IPC is only available in a Tauri webview".into(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Protection against [cross-site request forgery](https://owasp.org/www-community/attacks/csrf)
+//! for server functions.
+//!
+//! When it is turned on with [set_csrf_policy], the server gives each browser
+//! a random token in the [CSRF_COOKIE] cookie. A request that calls a server
+//! function must send the same token back, either in the [CSRF_HEADER] header
+//! (which [call_server_fn](crate::call_server_fn) does in the browser) or in
+//! the [CSRF_FIELD] form field (which `<ActionForm/>` includes). Another site
+//! can make the browser send the cookie, but it cannot read it, so it cannot
+//! send the matching token.
+//!
+//! Server functions with the `GetJson` or `GetCbor` encoding are not checked,
+//! since `GET` requests should not change anything on the server.
+
+use crate::{Encoding, ServerFnError};
+use std::sync::{Arc, RwLock};
+
+/// The name of the cookie that holds the CSRF token.
+pub const CSRF_COOKIE: &str = "leptos_csrf";
+
+/// The name of the request header that holds the CSRF token.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// The name of the form field that holds the CSRF token.
+pub const CSRF_FIELD: &str = "csrf_token";
+
+/// Decides whether a request may call a server function.
+#[derive(Clone, Default)]
+pub enum CsrfPolicy {
+    /// Requests are not checked.
+    #[default]
+    Disabled,
+    /// The token in the [CSRF_COOKIE] cookie must be sent back in the
+    /// [CSRF_HEADER] header or the [CSRF_FIELD] form field.
+    DoubleSubmitCookie,
+    /// Requests are checked by the given function, e.g., to compare the token
+    /// with one stored in the user's session.
+    Custom(Arc<dyn Fn(&CsrfCheck) -> bool + Send + Sync>),
+}
+
+impl std::fmt::Debug for CsrfPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "Disabled"),
+            Self::DoubleSubmitCookie => write!(f, "DoubleSubmitCookie"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl CsrfPolicy {
+    /// Whether the server should give each browser a CSRF token.
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+
+    /// Whether the request described by `check` may call the server function.
+    pub fn allows(&self, check: &CsrfCheck) -> bool {
+        if matches!(check.encoding, Encoding::GetJSON | Encoding::GetCBOR) {
+            return true;
+        }
+        match self {
+            Self::Disabled => true,
+            Self::DoubleSubmitCookie => match check.cookie_token() {
+                Some(expected) => check
+                    .submitted_token()
+                    .map(|token| constant_time_eq(&token, expected))
+                    .unwrap_or(false),
+                None => false,
+            },
+            Self::Custom(f) => f(check),
+        }
+    }
+}
+
+/// The parts of a request to a server function that a [CsrfPolicy] checks.
+#[derive(Debug)]
+pub struct CsrfCheck<'a> {
+    /// The encoding of the server function.
+    pub encoding: &'a Encoding,
+    /// The value of the `Cookie` header.
+    pub cookies: Option<&'a str>,
+    /// The value of the [CSRF_HEADER] header.
+    pub header: Option<&'a str>,
+    /// The body of the request.
+    pub body: &'a [u8],
+}
+
+impl<'a> CsrfCheck<'a> {
+    /// The token in the [CSRF_COOKIE] cookie.
+    pub fn cookie_token(&self) -> Option<&'a str> {
+        self.cookies
+            .and_then(|cookies| cookie_value(cookies, CSRF_COOKIE))
+    }
+
+    /// The token sent in the [CSRF_HEADER] header or, for a form that was
+    /// submitted without JS, in the [CSRF_FIELD] form field.
+    pub fn submitted_token(&self) -> Option<String> {
+        if let Some(header) = self.header {
+            return Some(header.to_string());
+        }
+        if *self.encoding != Encoding::Url {
+            return None;
+        }
+        let body = std::str::from_utf8(self.body).ok()?;
+        body.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == CSRF_FIELD).then(|| value.to_string())
+        })
+    }
+}
+
+/// The error sent back when a [CsrfPolicy] does not allow a request.
+pub fn csrf_error() -> ServerFnError {
+    ServerFnError::Forbidden("missing or invalid CSRF token".to_string())
+}
+
+static POLICY: RwLock<CsrfPolicy> = RwLock::new(CsrfPolicy::Disabled);
+
+/// Sets the [CsrfPolicy] used for every server function. Call this in your
+/// `main` function, before the server starts.
+pub fn set_csrf_policy(policy: CsrfPolicy) {
+    if let Ok(mut current) = POLICY.write() {
+        *current = policy;
+    }
+}
+
+/// Returns the current [CsrfPolicy].
+pub fn csrf_policy() -> CsrfPolicy {
+    POLICY
+        .read()
+        .map(|policy| policy.clone())
+        .unwrap_or_default()
+}
+
+/// Finds the value of the cookie with the given name in a `Cookie` header.
+pub fn cookie_value<'a>(cookies: &'a str, name: &str) -> Option<&'a str> {
+    cookies.split(';').find_map(|cookie| {
+        let (key, value) = cookie.trim().split_once('=')?;
+        (key == name).then_some(value)
+    })
+}
+
+/// The CSRF token the server gave this browser, read from the
+/// [CSRF_COOKIE] cookie. Always `None` outside the browser.
+pub fn browser_csrf_token() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        let document =
+            Reflect::get(&js_sys::global(), &JsValue::from_str("document"))
+                .ok()?;
+        let cookies =
+            Reflect::get(&document, &JsValue::from_str("cookie")).ok()?;
+        cookie_value(&cookies.as_string()?, CSRF_COOKIE).map(String::from)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        None
+    }
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
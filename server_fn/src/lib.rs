@@ -78,6 +78,13 @@
 //!   form data using [`serde_qs`](https://docs.rs/serde_qs/latest/serde_qs/) or as `application/cbor`
 //!   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
 
+pub mod auth;
+pub mod client;
+pub mod csrf;
+pub mod limits;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+
 // used by the macro
 #[doc(hidden)]
 pub use const_format;
@@ -89,6 +96,10 @@ use std::{future::Future, pin::Pin, str::FromStr, sync::Arc};
 use syn::parse_quote;
 use thiserror::Error;
 // used by the macro
+#[cfg(feature = "openapi")]
+#[doc(hidden)]
+pub use schemars;
+// used by the macro
 #[doc(hidden)]
 pub use xxhash_rust;
 
@@ -139,6 +150,188 @@ pub enum Payload {
     Url(String),
     ///Encodes Data using Json
     Json(String),
+    /// A file, which is sent as it is rather than being encoded
+    File(FileResponse),
+}
+
+/// A file returned by a server function, which is sent to the browser as a
+/// download with the given file name and MIME type, rather than being
+/// encoded like other return values.
+///
+/// Because the browser handles the response itself, a server function that
+/// returns a file can be used as the `href` of a plain link, which works even
+/// before (or without) the WASM loading. Use the `GetJson` or `GetCbor`
+/// encoding, so that the arguments are part of the URL, and get that URL
+/// from [ServerFn::download_url].
+///
+/// ```rust,ignore
+/// #[server(ExportTodos, "/api", "GetJson")]
+/// async fn export_todos(user: u32) -> Result<FileResponse, ServerFnError> {
+///     let csv = todos_as_csv(user).await?;
+///     Ok(FileResponse::new(csv, "todos.csv", "text/csv"))
+/// }
+///
+/// // on the client
+/// let href = ExportTodos { user: 1 }.download_url()?;
+/// ```
+///
+/// Large files can be sent in chunks as they are read with
+/// [FileResponse::stream], rather than loaded into memory first.
+///
+/// When it is called like any other server function, it resolves to a
+/// `FileResponse` with the contents of the file.
+///
+/// The `server` macro recognizes the file by the name of the type, so the
+/// return type must be written as `Result<FileResponse, ServerFnError>`
+/// rather than with an alias.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct FileResponse {
+    /// The contents of the file.
+    pub body: FileBody,
+    /// The name the browser will suggest when saving the file.
+    pub filename: String,
+    /// The MIME type of the file, sent as its `Content-Type`.
+    pub mime: String,
+}
+
+/// The contents of a [FileResponse].
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum FileBody {
+    /// The whole file.
+    Bytes(Vec<u8>),
+    /// A file that is sent in chunks as they are produced. A file is only
+    /// streamed from the server, and always arrives on the client as
+    /// [FileBody::Bytes].
+    #[serde(skip)]
+    Stream(FileStream),
+}
+
+/// The chunks of a streamed [FileResponse].
+pub type FileStream =
+    Pin<Box<dyn futures::Stream<Item = Result<Vec<u8>, ServerFnError>> + Send>>;
+
+impl std::fmt::Debug for FileBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            Self::Stream(_) => f.write_str("Stream(..)"),
+        }
+    }
+}
+
+impl FileResponse {
+    /// Creates a file with the given contents, file name and MIME type.
+    pub fn new(
+        bytes: impl Into<Vec<u8>>,
+        filename: impl Into<String>,
+        mime: impl Into<String>,
+    ) -> Self {
+        Self {
+            body: FileBody::Bytes(bytes.into()),
+            filename: filename.into(),
+            mime: mime.into(),
+        }
+    }
+
+    /// Creates a file that is sent in chunks as the stream yields them, with
+    /// the given file name and MIME type. If the stream fails, the download is
+    /// cut short.
+    pub fn stream(
+        stream: impl futures::Stream<Item = Result<Vec<u8>, ServerFnError>>
+            + Send
+            + 'static,
+        filename: impl Into<String>,
+        mime: impl Into<String>,
+    ) -> Self {
+        Self {
+            body: FileBody::Stream(Box::pin(stream)),
+            filename: filename.into(),
+            mime: mime.into(),
+        }
+    }
+
+    /// The contents of the file, unless it is streamed.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.body {
+            FileBody::Bytes(bytes) => Some(bytes),
+            FileBody::Stream(_) => None,
+        }
+    }
+
+    /// Reads the whole file, waiting for the rest of the stream if it is
+    /// streamed.
+    pub async fn into_bytes(self) -> Result<Vec<u8>, ServerFnError> {
+        use futures::TryStreamExt;
+
+        match self.body {
+            FileBody::Bytes(bytes) => Ok(bytes),
+            FileBody::Stream(stream) => stream.try_concat().await,
+        }
+    }
+
+    /// The value of the `Content-Disposition` header that makes the browser
+    /// download the file rather than show it.
+    pub fn content_disposition(&self) -> String {
+        // the plain `filename` is for older browsers, and `filename*` allows
+        // names that aren't ASCII
+        let fallback: String = self
+            .filename
+            .chars()
+            .map(|c| match c {
+                '"' | '\\' => '_',
+                c if c.is_ascii_graphic() || c == ' ' => c,
+                _ => '_',
+            })
+            .collect();
+        let mut encoded = String::with_capacity(self.filename.len());
+        for byte in self.filename.bytes() {
+            match byte {
+                b'A'..=b'Z'
+                | b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'-'
+                | b'.'
+                | b'_'
+                | b'~' => encoded.push(byte as char),
+                _ => encoded.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        format!(
+            "attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}"
+        )
+    }
+
+    /// Reads the file name from a `Content-Disposition` header, if it has
+    /// one.
+    pub fn filename_from_content_disposition(header: &str) -> Option<String> {
+        let mut plain = None;
+        for part in header.split(';').map(str::trim) {
+            if let Some(encoded) = part.strip_prefix("filename*=") {
+                let encoded = encoded.split_once("''")?.1;
+                return percent_decode(encoded);
+            } else if let Some(name) = part.strip_prefix("filename=") {
+                plain = Some(name.trim_matches('"').to_string());
+            }
+        }
+        plain
+    }
+}
+
+fn percent_decode(encoded: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
 }
 
 /// Attempts to find a server function registered at the given path.
@@ -214,6 +407,49 @@ pub fn server_fns_by_path<T: 'static, R: ServerFunctionRegistry<T>>(
     R::paths_registered()
 }
 
+/// Describes a server function that has been registered, e.g., to list the
+/// endpoints of an app or to generate documentation for them.
+#[derive(Clone, Debug)]
+pub struct ServerFnInfo {
+    /// The name of the type that describes the server function's arguments.
+    pub type_name: &'static str,
+    /// The URL prefix of the server function.
+    pub prefix: &'static str,
+    /// The URL of the server function, relative to its prefix.
+    pub url: &'static str,
+    /// How the arguments and the return value are encoded.
+    pub encoding: Encoding,
+    #[cfg(feature = "openapi")]
+    pub(crate) schemas: fn(
+        &mut schemars::gen::SchemaGenerator,
+    ) -> Option<openapi::ServerFnSchemas>,
+}
+
+impl ServerFnInfo {
+    /// The path at which the server function is called, i.e., its prefix
+    /// followed by its URL.
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.prefix, self.url)
+    }
+}
+
+#[cfg(any(feature = "ssr", doc))]
+static REGISTERED_INFO: once_cell::sync::Lazy<
+    std::sync::RwLock<Vec<ServerFnInfo>>,
+> = once_cell::sync::Lazy::new(Default::default);
+
+/// Returns a description of every server function that has been registered,
+/// in any registry, sorted by path.
+#[cfg(any(feature = "ssr", doc))]
+pub fn registered_server_fns() -> Vec<ServerFnInfo> {
+    let mut fns = REGISTERED_INFO
+        .read()
+        .map(|fns| fns.clone())
+        .unwrap_or_default();
+    fns.sort_by_key(ServerFnInfo::path);
+    fns
+}
+
 /// Holds the current options for encoding types.
 /// More could be added, but they need to be serde
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -283,6 +519,53 @@ where
     /// The path at which the server function can be reached on the server.
     fn encoding() -> Encoding;
 
+    /// Whether concurrent calls with the same arguments share one request
+    /// when they are made from the client. See [call_server_fn].
+    ///
+    /// By default, this is `true` only for the `GetJson` and `GetCbor`
+    /// encodings, since a `POST` is usually a mutation that is meant to happen
+    /// once per call. The `dedupe` and `no_dedupe` flags override this, e.g.,
+    /// `#[server(GetTodos, "/api", "Cbor", dedupe)]`.
+    fn deduplicate() -> bool {
+        matches!(Self::encoding(), Encoding::GetJSON | Encoding::GetCBOR)
+    }
+
+    /// The URL that runs the server function with these arguments when it is
+    /// fetched with a `GET` request, e.g., by following a link. This is
+    /// mostly useful for server functions that return a [FileResponse].
+    ///
+    /// Returns an error if the server function doesn't use the `GetJson` or
+    /// `GetCbor` encoding, since other encodings send their arguments in the
+    /// body of a `POST` request.
+    fn download_url(&self) -> Result<String, ServerFnError> {
+        match Self::encoding() {
+            Encoding::GetJSON | Encoding::GetCBOR => {}
+            Encoding::Url | Encoding::Cbor => {
+                return Err(ServerFnError::Serialization(format!(
+                    "the server function at {} must use the GetJson or \
+                     GetCbor encoding to be called from a URL",
+                    Self::url()
+                )))
+            }
+        }
+        let query = serde_qs::to_string(self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        let prefix = Self::prefix();
+        Ok(if query.is_empty() {
+            format!("{prefix}/{}", Self::url())
+        } else {
+            format!("{prefix}/{}?{query}", Self::url())
+        })
+    }
+
+    /// Returns the output of the function as a [FileResponse], if that is its
+    /// type, so that it is sent as a file rather than encoded. This is
+    /// implemented by the `server` macro.
+    #[cfg(any(feature = "ssr", doc))]
+    fn into_file(output: Self::Output) -> Result<FileResponse, Self::Output> {
+        Err(output)
+    }
+
     /// Runs the function on the server.
     #[cfg(any(feature = "ssr", doc))]
     fn call_fn(
@@ -297,6 +580,39 @@ where
         cx: T,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Output, ServerFnError>>>>;
 
+    /// Calls the server function with these arguments, wherever the code is
+    /// running. On the server, this runs the body of the function directly;
+    /// on the client, it sends a request to the server, like calling the
+    /// function itself.
+    ///
+    /// This is the stable way to call a server function outside of an action
+    /// or a resource, e.g., from an event handler or from other server code,
+    /// when all you have is the type that describes its arguments.
+    ///
+    /// ```ignore
+    /// #[server(AddTodo, "/api")]
+    /// pub async fn add_todo(title: String) -> Result<(), ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// let add = AddTodo { title: "Write docs".to_string() };
+    /// add.call(()).await?;
+    /// ```
+    fn call(
+        self,
+        cx: T,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, ServerFnError>>>>
+    {
+        #[cfg(feature = "ssr")]
+        {
+            self.call_fn(cx)
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            self.call_fn_client(cx)
+        }
+    }
+
     /// Registers the server function, allowing the server to query it by URL.
     #[cfg(any(feature = "ssr", doc,))]
     fn register_in<R: ServerFunctionRegistry<T>>() -> Result<(), ServerFnError>
@@ -327,6 +643,12 @@ where
                     Err(e) => return Err(e),
                 };
 
+                // files are sent as they are
+                let result = match Self::into_file(result) {
+                    Ok(file) => return Ok(Payload::File(file)),
+                    Err(result) => result,
+                };
+
                 // serialize the output
                 let result = match Self::encoding() {
                     Encoding::Url | Encoding::GetJSON => {
@@ -356,7 +678,39 @@ where
 
         // store it in the hashmap
         R::register(Self::url(), run_server_fn, Self::encoding())
-            .map_err(|e| ServerFnError::Registration(e.to_string()))
+            .map_err(|e| ServerFnError::Registration(e.to_string()))?;
+
+        // and describe it for introspection
+        if let Ok(mut registered) = REGISTERED_INFO.write() {
+            let info = ServerFnInfo {
+                type_name: std::any::type_name::<Self>(),
+                prefix: Self::prefix(),
+                url: Self::url(),
+                encoding: Self::encoding(),
+                #[cfg(feature = "openapi")]
+                schemas: Self::schemas,
+            };
+            // registering it again, e.g., in another registry, replaces it
+            match registered.iter_mut().find(|f| f.path() == info.path()) {
+                Some(existing) => *existing = info,
+                None => registered.push(info),
+            }
+        }
+        Ok(())
+    }
+
+    /// Describes the arguments and the return value of the server function
+    /// as JSON schemas, for [openapi::openapi_document].
+    ///
+    /// This is implemented by the `server` macro when the `openapi` feature
+    /// is enabled, which requires the arguments and the return type to
+    /// implement [schemars::JsonSchema].
+    #[cfg(feature = "openapi")]
+    fn schemas(
+        gen: &mut schemars::gen::SchemaGenerator,
+    ) -> Option<openapi::ServerFnSchemas> {
+        _ = gen;
+        None
     }
 }
 
@@ -384,9 +738,72 @@ pub enum ServerFnError {
     /// Occurs on the server if there's a missing argument.
     #[error("missing argument {0}")]
     MissingArg(String),
+    /// The request did not say who is making it, or its credentials are not
+    /// valid. Sent with the status `401 Unauthorized`.
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    /// The user making the request may not call the server function. Sent
+    /// with the status `403 Forbidden`.
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    /// The body of the request is larger than the server allows. Sent with
+    /// the status `413 Payload Too Large`.
+    #[error("payload too large: {0}")]
+    PayloadTooLarge(String),
+    /// The client has made more requests than the server allows. Sent with
+    /// the status `429 Too Many Requests`.
+    #[error("too many requests: {0}")]
+    TooManyRequests(String),
+    /// The server function took longer to run than the server allows. Sent
+    /// with the status `504 Gateway Timeout`.
+    #[error("timed out: {0}")]
+    Timeout(String),
+}
+
+impl ServerFnError {
+    /// The HTTP status code the server responds with when a server function
+    /// fails with this error.
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::Unauthorized(_) => 401,
+            Self::Forbidden(_) => 403,
+            Self::PayloadTooLarge(_) => 413,
+            Self::TooManyRequests(_) => 429,
+            Self::Timeout(_) => 504,
+            _ => 500,
+        }
+    }
+
+    /// The error for a response with the given status code that doesn't
+    /// describe the error itself, e.g., one sent by a proxy.
+    pub fn from_status(status: u16, status_text: String) -> Self {
+        match status {
+            401 => Self::Unauthorized(status_text),
+            403 => Self::Forbidden(status_text),
+            413 => Self::PayloadTooLarge(status_text),
+            429 => Self::TooManyRequests(status_text),
+            408 | 504 => Self::Timeout(status_text),
+            _ => Self::ServerError(status_text),
+        }
+    }
 }
 
 /// Executes the HTTP call to call a server function from the client, given its URL and argument type.
+///
+/// The request is sent with the client set by [client::set_http_client].
+///
+/// If [ServerFn::deduplicate] is `true` and the same server function is
+/// already being called with the same arguments and headers on this thread, no
+/// new request is sent. Instead, the response to the request in flight is
+/// shared by every caller, so that several components loading the same data at
+/// once only cause one request.
+///
+/// Any `4xx` or `5xx` response is returned as the [ServerFnError] in its body,
+/// like the [ServerFnError::Unauthorized] a rejected [authorization](auth)
+/// check sends, or as the error for its status from
+/// [ServerFnError::from_status] if the body isn't one. Note that this changed
+/// along with authorization: a `4xx` response used to be decoded as the return
+/// value, as only `5xx` responses were errors.
 #[cfg(not(feature = "ssr"))]
 pub async fn call_server_fn<T, C: 'static>(
     url: &str,
@@ -397,9 +814,14 @@ where
     T: serde::Serialize + serde::de::DeserializeOwned + Sized,
 {
     use ciborium::ser::into_writer;
+    use client::{http_client, HttpRequest};
     use serde_json::Deserializer as JSONDeserializer;
+
+    // on WASM, a relative URL is sent to the page's origin
     #[cfg(not(target_arch = "wasm32"))]
-    let url = format!("{}{}", get_server_url(), url);
+    if SERVER_DEFAULTS.read().unwrap().base_url.is_none() {
+        panic!("Call set_server_url before calling a server function.");
+    }
 
     #[derive(Debug)]
     enum Payload {
@@ -433,146 +855,179 @@ where
         Encoding::Cbor | Encoding::GetCBOR => "application/cbor",
     };
 
-    #[cfg(target_arch = "wasm32")]
-    let resp = match &enc {
-        Encoding::Url | Encoding::Cbor => match args_encoded {
-            Payload::Binary(b) => {
-                let slice_ref: &[u8] = &b;
-                let js_array = js_sys::Uint8Array::from(slice_ref).buffer();
-                gloo_net::http::Request::post(url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .body(js_array)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
-            }
-            Payload::Url(s) => gloo_net::http::Request::post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(s)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        },
-        Encoding::GetCBOR | Encoding::GetJSON => match args_encoded {
-            Payload::Binary(_) => panic!(
+    let request = match args_encoded {
+        Payload::Binary(body) => match &enc {
+            Encoding::Url | Encoding::Cbor => HttpRequest::post(url, body),
+            Encoding::GetJSON | Encoding::GetCBOR => panic!(
                 "Binary data cannot be transferred via GET request in a query \
                  string. Please try using the CBOR encoding."
             ),
-            Payload::Url(s) => {
-                let full_url = format!("{url}?{s}");
-                gloo_net::http::Request::get(&full_url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
-            }
-        },
-    };
-    #[cfg(not(target_arch = "wasm32"))]
-    let resp = match &enc {
-        Encoding::Url | Encoding::Cbor => match args_encoded {
-            Payload::Binary(b) => CLIENT
-                .post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(b)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-            Payload::Url(s) => CLIENT
-                .post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(s)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
         },
-        Encoding::GetJSON | Encoding::GetCBOR => match args_encoded {
-            Payload::Binary(_) => panic!(
-                "Binary data cannot be transferred via GET request in a query \
-                 string. Please try using the CBOR encoding."
-            ),
-
-            Payload::Url(s) => {
-                let full_url = format!("{url}?{s}");
-                CLIENT
-                    .get(full_url)
-                    .header("Content-Type", content_type_header)
-                    .header("Accept", accept_header)
-                    .send()
-                    .await
-                    .map_err(|e| ServerFnError::Request(e.to_string()))?
+        Payload::Url(s) => match &enc {
+            Encoding::Url | Encoding::Cbor => HttpRequest::post(url, s),
+            Encoding::GetJSON | Encoding::GetCBOR => {
+                HttpRequest::get(format!("{url}?{s}"))
             }
         },
     };
+    let request = SERVER_DEFAULTS.read().unwrap().apply(
+        request
+            .header("Content-Type", content_type_header)
+            .header("Accept", accept_header),
+    );
+    // sent with every `POST`, in case the server checks it
+    #[cfg(target_arch = "wasm32")]
+    let request = if request.method == client::HttpMethod::Post {
+        let csrf_token = csrf::browser_csrf_token().unwrap_or_default();
+        request.header(csrf::CSRF_HEADER, csrf_token)
+    } else {
+        request
+    };
+    let resp = if should_deduplicate(&args) {
+        send_deduplicated(request).await?
+    } else {
+        http_client().send(request).await?
+    };
 
     // check for error status
-    let status = resp.status();
-    #[cfg(not(target_arch = "wasm32"))]
-    let status = status.as_u16();
-    if (500..=599).contains(&status) {
-        let text = resp.text().await.unwrap_or_default();
-        #[cfg(target_arch = "wasm32")]
-        let status_text = resp.status_text();
-        #[cfg(not(target_arch = "wasm32"))]
-        let status_text = status.to_string();
-        return Err(serde_json::from_str(&text)
-            .unwrap_or(ServerFnError::ServerError(status_text)));
+    if (400..=599).contains(&resp.status) {
+        let text = String::from_utf8_lossy(&resp.body);
+        return Err(serde_json::from_str(&text).unwrap_or_else(|_| {
+            ServerFnError::from_status(resp.status, resp.status_text)
+        }));
     }
 
-    // Decoding the body of the request
-    if (enc == Encoding::Cbor) || (enc == Encoding::GetCBOR) {
-        #[cfg(target_arch = "wasm32")]
-        let binary = resp
-            .binary()
-            .await
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
-        #[cfg(target_arch = "wasm32")]
-        let binary = binary.as_slice();
-        #[cfg(not(target_arch = "wasm32"))]
-        let binary = resp
-            .bytes()
-            .await
+    // files are sent as they are, rather than being encoded
+    if let Some(disposition) = resp
+        .header("Content-Disposition")
+        .filter(|value| value.starts_with("attachment"))
+    {
+        let filename =
+            FileResponse::filename_from_content_disposition(disposition)
+                .unwrap_or_default();
+        let mime = resp.header("Content-Type").unwrap_or_default().to_string();
+        // `T` is only known to be deserializable, so the file is passed
+        // through CBOR to turn it into a `T`
+        let mut buffer = Vec::new();
+        into_writer(&FileResponse::new(resp.body, filename, mime), &mut buffer)
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
-        #[cfg(not(target_arch = "wasm32"))]
-        let binary = binary.as_ref();
+        return ciborium::de::from_reader(buffer.as_slice()).map_err(|_| {
+            ServerFnError::Deserialization(
+                "the server sent a file, but the server function does not \
+                 return a FileResponse"
+                    .to_string(),
+            )
+        });
+    }
 
-        ciborium::de::from_reader(binary)
+    // Decoding the body of the request
+    if (enc == Encoding::Cbor) || (enc == Encoding::GetCBOR) {
+        ciborium::de::from_reader(resp.body.as_slice())
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))
     } else {
-        let text = resp
-            .text()
-            .await
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
-
-        let mut deserializer = JSONDeserializer::from_str(&text);
+        let mut deserializer = JSONDeserializer::from_str(resp.text()?);
         T::deserialize(&mut deserializer)
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))
     }
 }
 
-// Lazily initialize the client to be reused for all server function calls.
-#[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
-static CLIENT: once_cell::sync::Lazy<reqwest::Client> =
-    once_cell::sync::Lazy::new(|| reqwest::Client::new());
+#[cfg(not(feature = "ssr"))]
+fn should_deduplicate<C: 'static, A: ServerFn<C>>(_args: &A) -> bool {
+    A::deduplicate()
+}
 
-#[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
-static ROOT_URL: once_cell::sync::OnceCell<&'static str> =
-    once_cell::sync::OnceCell::new();
+#[cfg(not(feature = "ssr"))]
+type SharedResponse = futures::future::Shared<client::HttpFuture>;
 
-#[cfg(any(all(not(feature = "ssr"), not(target_arch = "wasm32")), doc))]
-/// Set the root server url that all server function paths are relative to for the client. On WASM this will default to the origin.
+#[cfg(not(feature = "ssr"))]
+thread_local! {
+    // requests that have been sent but not yet answered, keyed by
+    // their method, URL, headers and body
+    static IN_FLIGHT: std::cell::RefCell<
+        std::collections::HashMap<RequestKey, SharedResponse>,
+    > = Default::default();
+}
+
+#[cfg(not(feature = "ssr"))]
+type RequestKey = (
+    client::HttpMethod,
+    String,
+    Vec<(String, String)>,
+    Option<Vec<u8>>,
+);
+
+/// Sends the request, or waits for the response to an identical request that
+/// has already been sent.
+#[cfg(not(feature = "ssr"))]
+async fn send_deduplicated(
+    request: client::HttpRequest,
+) -> Result<client::HttpResponse, ServerFnError> {
+    use futures::FutureExt;
+
+    let mut headers = request.headers.clone();
+    headers.sort();
+    let key = (
+        request.method,
+        request.url.clone(),
+        headers,
+        request.body.clone(),
+    );
+    let response = IN_FLIGHT.with(|in_flight| {
+        in_flight
+            .borrow_mut()
+            .entry(key.clone())
+            .or_insert_with(|| client::http_client().send(request).shared())
+            .clone()
+    });
+    let result = response.clone().await;
+    // the first caller to finish removes the entry, unless it has already
+    // been replaced by a later request
+    IN_FLIGHT.with(|in_flight| {
+        let mut in_flight = in_flight.borrow_mut();
+        if in_flight
+            .get(&key)
+            .map(|current| current.ptr_eq(&response))
+            .unwrap_or(false)
+        {
+            in_flight.remove(&key);
+        }
+    });
+    result
+}
+
+// The base URL, headers and credentials of every server function call, like
+// those of a `ConfiguredHttpClient`, but shared by every thread.
+#[cfg(any(not(feature = "ssr"), doc))]
+static SERVER_DEFAULTS: std::sync::RwLock<client::RequestDefaults> =
+    std::sync::RwLock::new(client::RequestDefaults::new());
+
+#[cfg(any(not(feature = "ssr"), doc))]
+/// Set the root server url that all server function paths are relative to for the client.
+///
+/// On WASM this will default to the origin, and only needs to be set if the
+/// server functions are served from another origin, e.g., when the app is
+/// hosted on a CDN or runs in a mobile webview. The server must then allow
+/// requests from the app's origin in its CORS headers.
 pub fn set_server_url(url: &'static str) {
-    ROOT_URL.set(url).unwrap();
+    SERVER_DEFAULTS.write().unwrap().base_url = Some(url.to_string());
+}
+
+#[cfg(any(not(feature = "ssr"), doc))]
+/// Sets whether the browser sends cookies and HTTP authentication with
+/// server function calls. Defaults to
+/// [`SameOrigin`](client::Credentials::SameOrigin), so
+/// [`Include`](client::Credentials::Include) is needed to send cookies to a
+/// server set with [set_server_url].
+pub fn set_server_credentials(credentials: client::Credentials) {
+    SERVER_DEFAULTS.write().unwrap().credentials = Some(credentials);
 }
 
-#[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32")))]
-fn get_server_url() -> &'static str {
-    ROOT_URL
-        .get()
-        .expect("Call set_root_url before calling a server function.")
+#[cfg(any(not(feature = "ssr"), doc))]
+/// Adds a header, like an API key, to every server function call.
+pub fn add_server_header(name: impl Into<String>, value: impl Into<String>) {
+    SERVER_DEFAULTS
+        .write()
+        .unwrap()
+        .headers
+        .push((name.into(), value.into()));
 }
@@ -0,0 +1,275 @@
+//! Limits on the requests that call server functions.
+//!
+//! The server integrations check the [ServerFnLimits] set with
+//! [set_server_fn_limits] before a server function is called: the rate
+//! limit before the body of the request is read, and the body size while it
+//! is read. A request that goes over one of them is answered with an error,
+//! which the client receives as the matching [ServerFnError] variant:
+//!
+//! | Limit | Error | Status |
+//! |---|---|---|
+//! | [max_body_size](ServerFnLimits::max_body_size) | [ServerFnError::PayloadTooLarge] | `413 Payload Too Large` |
+//! | [rate_limit](ServerFnLimits::rate_limit) | [ServerFnError::TooManyRequests] | `429 Too Many Requests` |
+//! | [timeout](ServerFnLimits::timeout) | [ServerFnError::Timeout] | `504 Gateway Timeout` |
+//!
+//! ```rust,ignore
+//! set_server_fn_limits(
+//!     ServerFnLimits::default()
+//!         .max_body_size(64 * 1024)
+//!         .rate_limit(RateLimit::per_ip(100, Duration::from_secs(60)))
+//!         .timeout(Duration::from_secs(10)),
+//! );
+//! ```
+
+use crate::ServerFnError;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+/// The limits on requests to server functions. By default, there are none.
+#[derive(Clone, Debug, Default)]
+pub struct ServerFnLimits {
+    max_body_size: Option<usize>,
+    rate_limit: Option<RateLimit>,
+    timeout: Option<Duration>,
+}
+
+impl ServerFnLimits {
+    /// Rejects requests whose body is longer than `bytes`. A request whose
+    /// `Content-Length` is too large is rejected without reading its body,
+    /// and otherwise reading stops as soon as the limit is passed.
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = Some(bytes);
+        self
+    }
+
+    /// Rejects requests from clients that have made too many of them.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Stops running a server function that takes longer than `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The timeout for running a server function, if there is one.
+    pub fn timeout_duration(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The largest body that is allowed, in bytes, if there is a limit.
+    pub fn body_size_limit(&self) -> Option<usize> {
+        self.max_body_size
+    }
+
+    /// Whether there is a [rate limit](Self::rate_limit). Requests whose
+    /// client IP address is not known are limited as its
+    /// [UnknownClient] option says.
+    pub fn has_rate_limit(&self) -> bool {
+        self.rate_limit.is_some()
+    }
+
+    /// Checks that a body of `body_len` bytes, or the part of it read so far,
+    /// is allowed. Used by the server integrations while they read the body.
+    pub fn check_body_size(
+        &self,
+        body_len: usize,
+    ) -> Result<(), ServerFnError> {
+        match self.max_body_size {
+            Some(max) if body_len > max => {
+                Err(ServerFnError::PayloadTooLarge(format!(
+                    "the request body is longer than the {max} bytes that are \
+                     allowed"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a request from the client with the given IP address and
+    /// checks that it is allowed by the [rate limit](Self::rate_limit). Used
+    /// by the server integrations before they read the body.
+    pub fn check_rate_limit(
+        &self,
+        client_ip: Option<&str>,
+    ) -> Result<(), ServerFnError> {
+        let Some(rate_limit) = &self.rate_limit else {
+            return Ok(());
+        };
+        let key = match (client_ip, rate_limit.unknown_client) {
+            (Some(ip), _) => ip,
+            (None, UnknownClient::SharedBucket) => UNKNOWN_CLIENT_KEY,
+            (None, UnknownClient::Reject) => {
+                return Err(ServerFnError::TooManyRequests(
+                    "requests from an unknown address are not allowed"
+                        .to_string(),
+                ));
+            }
+        };
+        if !rate_limit
+            .store
+            .hit(key, rate_limit.requests, rate_limit.per)
+        {
+            return Err(ServerFnError::TooManyRequests(format!(
+                "at most {} requests are allowed every {:?}",
+                rate_limit.requests, rate_limit.per
+            )));
+        }
+        Ok(())
+    }
+
+    /// The error returned when a server function runs for longer than the
+    /// [timeout](Self::timeout).
+    pub fn timeout_error(&self) -> ServerFnError {
+        ServerFnError::Timeout(format!(
+            "the server function did not finish within {:?}",
+            self.timeout.unwrap_or_default()
+        ))
+    }
+}
+
+/// Allows each client, identified by its IP address, to make a number of
+/// requests in each window of time.
+#[derive(Clone)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+    store: Arc<dyn RateLimitStore>,
+    unknown_client: UnknownClient,
+}
+
+// IP addresses are never empty, so this can't be confused with a client's
+const UNKNOWN_CLIENT_KEY: &str = "";
+
+impl RateLimit {
+    /// Allows each IP address `requests` requests every `per`, counted in
+    /// this server's memory.
+    ///
+    /// The integrations can't always tell which address a request came from,
+    /// e.g., when an axum app is not served with `ConnectInfo`. By default,
+    /// all of those requests share a single count, as if they came from one
+    /// client; use [unknown_client](Self::unknown_client) to reject them
+    /// instead.
+    pub fn per_ip(requests: u32, per: Duration) -> Self {
+        Self {
+            requests,
+            per,
+            store: Arc::new(MemoryRateLimitStore::default()),
+            unknown_client: UnknownClient::default(),
+        }
+    }
+
+    /// Sets how requests whose client IP address is not known are limited.
+    pub fn unknown_client(mut self, unknown_client: UnknownClient) -> Self {
+        self.unknown_client = unknown_client;
+        self
+    }
+
+    /// Counts the requests in the given store, e.g., one that is shared by
+    /// several servers.
+    pub fn with_store(mut self, store: impl RateLimitStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+}
+
+impl fmt::Debug for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("requests", &self.requests)
+            .field("per", &self.per)
+            .field("unknown_client", &self.unknown_client)
+            .finish()
+    }
+}
+
+/// How a [RateLimit] treats requests whose client IP address is not known.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownClient {
+    /// Counts them all together, as if they came from a single client.
+    #[default]
+    SharedBucket,
+    /// Rejects them with [ServerFnError::TooManyRequests].
+    Reject,
+}
+
+/// Counts the requests made by each client for a [RateLimit].
+pub trait RateLimitStore: Send + Sync {
+    /// Records a request from `key`, returning `false` if it has already made
+    /// `limit` requests in the current window of length `window`.
+    fn hit(&self, key: &str, limit: u32, window: Duration) -> bool;
+}
+
+/// A [RateLimitStore] that counts requests in memory, in fixed windows.
+#[derive(Debug, Default)]
+pub struct MemoryRateLimitStore {
+    windows: Mutex<Windows>,
+}
+
+#[derive(Debug, Default)]
+struct Windows {
+    clients: HashMap<String, (Instant, u32)>,
+    pruned_at: Option<Instant>,
+}
+
+impl RateLimitStore for MemoryRateLimitStore {
+    fn hit(&self, key: &str, limit: u32, window: Duration) -> bool {
+        // the counts are still usable if another thread panicked while it
+        // held the lock, so keep limiting rather than letting every request
+        // through
+        let mut windows = self
+            .windows
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        // forget the clients whose window is over, so the map doesn't grow,
+        // but only once per window rather than on every request
+        if windows
+            .pruned_at
+            .map(|pruned_at| now.duration_since(pruned_at) >= window)
+            .unwrap_or(true)
+        {
+            windows
+                .clients
+                .retain(|_, (start, _)| now.duration_since(*start) < window);
+            windows.pruned_at = Some(now);
+        }
+        let (start, count) =
+            windows.clients.entry(key.to_string()).or_insert((now, 0));
+        if now.duration_since(*start) >= window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            false
+        } else {
+            *count += 1;
+            true
+        }
+    }
+}
+
+static LIMITS: RwLock<Option<ServerFnLimits>> = RwLock::new(None);
+
+/// Sets the [ServerFnLimits] used for every server function. Call this in
+/// your `main` function, before the server starts.
+pub fn set_server_fn_limits(limits: ServerFnLimits) {
+    if let Ok(mut current) = LIMITS.write() {
+        *current = Some(limits);
+    }
+}
+
+/// Returns the current [ServerFnLimits].
+pub fn server_fn_limits() -> ServerFnLimits {
+    LIMITS
+        .read()
+        .ok()
+        .and_then(|limits| limits.clone())
+        .unwrap_or_default()
+}
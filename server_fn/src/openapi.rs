@@ -0,0 +1,121 @@
+//! Describes the registered server functions as an
+//! [OpenAPI](https://www.openapis.org/) document, so that they can be called
+//! by clients that aren't written with `server_fn`, or exposed through an API
+//! gateway.
+//!
+//! This module is only available with the `openapi` feature, which makes the
+//! `server` macro derive [JsonSchema](schemars::JsonSchema) for the arguments
+//! of every server function. Their arguments and return types must implement
+//! `JsonSchema` as well.
+//!
+//! ```rust,ignore
+//! // after registering the server functions
+//! let document = openapi_document("Todo App", "1.0.0");
+//! std::fs::write("openapi.json", document.to_string())?;
+//! ```
+
+#[cfg(any(feature = "ssr", doc))]
+use crate::{Encoding, ServerFnInfo};
+use schemars::schema::Schema;
+#[cfg(any(feature = "ssr", doc))]
+use serde_json::{json, Map, Value};
+
+/// The JSON schemas of the arguments and the return value of a server
+/// function.
+#[derive(Clone, Debug)]
+pub struct ServerFnSchemas {
+    /// The schema of the type that describes the arguments.
+    pub args: Schema,
+    /// The schema of the value returned when the call succeeds.
+    pub output: Schema,
+}
+
+/// Generates an OpenAPI 3.0 document describing every server function that
+/// has been registered, with the given title and version for the API.
+///
+/// Server functions that use the `Url` or `Cbor` encoding are described as
+/// `POST` endpoints with a request body; the `GetJson` and `GetCbor`
+/// encodings as `GET` endpoints with their arguments in the query string.
+#[cfg(any(feature = "ssr", doc))]
+pub fn openapi_document(title: &str, version: &str) -> Value {
+    let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+    let mut paths = Map::new();
+
+    for info in crate::registered_server_fns() {
+        let operation = operation(&info, &mut gen);
+        let method = match info.encoding {
+            Encoding::Url | Encoding::Cbor => "post",
+            Encoding::GetJSON | Encoding::GetCBOR => "get",
+        };
+        paths.insert(info.path(), json!({ method: operation }));
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": title,
+            "version": version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+        },
+    })
+}
+
+#[cfg(any(feature = "ssr", doc))]
+fn operation(
+    info: &ServerFnInfo,
+    gen: &mut schemars::gen::SchemaGenerator,
+) -> Value {
+    // server functions that were not compiled with the feature are listed
+    // without describing their types
+    let (args, output) = match (info.schemas)(gen) {
+        Some(schemas) => (json!(schemas.args), json!(schemas.output)),
+        None => (json!({}), json!({})),
+    };
+    let response_type = match info.encoding {
+        Encoding::Url | Encoding::GetJSON => "application/json",
+        Encoding::Cbor | Encoding::GetCBOR => "application/cbor",
+    };
+
+    let mut operation = json!({
+        "operationId": info.url,
+        "summary": info.type_name,
+        "responses": {
+            "200": {
+                "description": "The value returned by the server function.",
+                "content": { response_type: { "schema": output } },
+            },
+            "500": {
+                "description": "The server function returned an error.",
+            },
+        },
+    });
+    match info.encoding {
+        Encoding::Url => {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/x-www-form-urlencoded": { "schema": args },
+                },
+            });
+        }
+        Encoding::Cbor => {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/cbor": { "schema": args } },
+            });
+        }
+        Encoding::GetJSON | Encoding::GetCBOR => {
+            operation["parameters"] = json!([{
+                "name": "args",
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "schema": args,
+            }]);
+        }
+    }
+    operation
+}
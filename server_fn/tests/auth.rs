@@ -0,0 +1,98 @@
+use futures::executor::block_on;
+use server_fn::{
+    auth::{authorize, clear_server_fn_auth, set_server_fn_auth, AuthRequest},
+    Encoding, ServerFnError,
+};
+use std::sync::Mutex;
+
+// the hook is global, so the tests that set it can't run at the same time
+static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+fn request(fn_name: &str, headers: &[(&str, &str)]) -> AuthRequest {
+    AuthRequest {
+        fn_name: fn_name.to_string(),
+        encoding: Encoding::Url,
+        method: "POST".to_string(),
+        uri: format!("/api/{fn_name}"),
+        headers: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    }
+}
+
+async fn check_token(req: AuthRequest) -> Result<(), ServerFnError> {
+    if req.fn_name.starts_with("login") {
+        return Ok(());
+    }
+    match req.header("authorization") {
+        Some("secret") => Ok(()),
+        Some(_) => Err(ServerFnError::Forbidden("invalid token".into())),
+        None => Err(ServerFnError::Unauthorized("log in first".into())),
+    }
+}
+
+#[test]
+fn every_request_is_allowed_without_hook() {
+    let _lock = HOOK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    clear_server_fn_auth();
+    assert!(block_on(authorize(request("add_todo", &[]))).is_ok());
+}
+
+#[test]
+fn hook_decides_whether_request_is_allowed() {
+    let _lock = HOOK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    set_server_fn_auth(check_token);
+
+    assert!(block_on(authorize(request("login", &[]))).is_ok());
+    assert!(block_on(authorize(request(
+        "add_todo",
+        &[("authorization", "secret")]
+    )))
+    .is_ok());
+    assert!(matches!(
+        block_on(authorize(request("add_todo", &[]))),
+        Err(ServerFnError::Unauthorized(_))
+    ));
+    assert!(matches!(
+        block_on(authorize(request(
+            "add_todo",
+            &[("authorization", "guess")]
+        ))),
+        Err(ServerFnError::Forbidden(_))
+    ));
+
+    clear_server_fn_auth();
+    assert!(block_on(authorize(request("add_todo", &[]))).is_ok());
+}
+
+#[test]
+fn header_and_cookie_are_read_from_request() {
+    let req = request(
+        "add_todo",
+        &[("authorization", "secret"), ("cookie", "theme=dark; id=42")],
+    );
+    assert_eq!(req.header("Authorization"), Some("secret"));
+    assert_eq!(req.header("x-missing"), None);
+    assert_eq!(req.cookie("id"), Some("42"));
+    assert_eq!(req.cookie("session"), None);
+}
+
+#[test]
+fn auth_errors_are_sent_as_401_and_403() {
+    let unauthorized = ServerFnError::Unauthorized("log in first".into());
+    let forbidden = ServerFnError::Forbidden("invalid token".into());
+    assert_eq!(unauthorized.status(), 401);
+    assert_eq!(forbidden.status(), 403);
+    assert_eq!(ServerFnError::ServerError("oops".into()).status(), 500);
+
+    // and a client turns the statuses back into the same errors
+    assert!(matches!(
+        ServerFnError::from_status(401, "Unauthorized".into()),
+        ServerFnError::Unauthorized(_)
+    ));
+    assert!(matches!(
+        ServerFnError::from_status(403, "Forbidden".into()),
+        ServerFnError::Forbidden(_)
+    ));
+}
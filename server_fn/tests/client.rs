@@ -0,0 +1,125 @@
+#![cfg(not(feature = "ssr"))]
+
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    add_server_header, call_server_fn,
+    client::{
+        set_http_client, ConfiguredHttpClient, Credentials, HttpFuture,
+        HttpRequest, HttpResponse, LeptosHttpClient,
+    },
+    set_server_credentials, set_server_url, Encoding, ServerFn, ServerFnError,
+};
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+#[derive(Clone, Default)]
+struct RecordingServer {
+    sent: Rc<RefCell<Vec<HttpRequest>>>,
+}
+
+impl LeptosHttpClient for RecordingServer {
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        self.sent.borrow_mut().push(request);
+        Box::pin(async move {
+            Ok(HttpResponse {
+                status: 200,
+                status_text: "OK".into(),
+                headers: Vec::new(),
+                body: b"\"ok\"".to_vec(),
+            })
+        })
+    }
+}
+
+fn send(client: &impl LeptosHttpClient, request: HttpRequest) {
+    futures::executor::block_on(client.send(request)).unwrap();
+}
+
+#[test]
+fn configured_client_resolves_relative_urls() {
+    let server = RecordingServer::default();
+    let client = ConfiguredHttpClient::new(server.clone())
+        .base_url("https://api.example.com/");
+
+    send(&client, HttpRequest::get("/api/posts"));
+    send(&client, HttpRequest::get("https://other.example.com/posts"));
+
+    let sent = server.sent.borrow();
+    assert_eq!(sent[0].url, "https://api.example.com/api/posts");
+    assert_eq!(sent[1].url, "https://other.example.com/posts");
+}
+
+#[test]
+fn configured_client_adds_missing_headers_and_credentials() {
+    let server = RecordingServer::default();
+    let client = ConfiguredHttpClient::new(server.clone())
+        .header("Authorization", "Bearer token")
+        .header("X-Api-Key", "key")
+        .credentials(Credentials::Include);
+
+    send(
+        &client,
+        HttpRequest::get("/api/posts").header("x-api-key", "other"),
+    );
+
+    let sent = server.sent.borrow();
+    assert_eq!(
+        sent[0].headers,
+        vec![
+            ("x-api-key".to_string(), "other".to_string()),
+            ("Authorization".to_string(), "Bearer token".to_string()),
+        ]
+    );
+    assert_eq!(sent[0].credentials, Credentials::Include);
+}
+
+#[derive(Serialize, Deserialize)]
+struct GetPost {
+    id: u32,
+}
+
+impl ServerFn<()> for GetPost {
+    type Output = String;
+
+    fn prefix() -> &'static str {
+        "/api"
+    }
+
+    fn url() -> &'static str {
+        "get_post"
+    }
+
+    fn encoding() -> Encoding {
+        Encoding::Url
+    }
+
+    fn call_fn_client(
+        self,
+        _cx: (),
+    ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>> {
+        Box::pin(call_server_fn("/api/get_post", self, Self::encoding()))
+    }
+}
+
+// the server fn config is global, so it is checked in one test
+#[test]
+fn server_fn_calls_use_the_server_config() {
+    let server = RecordingServer::default();
+    set_http_client(server.clone());
+    set_server_url("http://localhost:3000/");
+    set_server_credentials(Credentials::Include);
+    add_server_header("X-Api-Key", "key");
+
+    let response = futures::executor::block_on(GetPost { id: 1 }.call(()));
+    assert_eq!(response.unwrap(), "ok");
+
+    let sent = server.sent.borrow();
+    assert_eq!(sent[0].url, "http://localhost:3000/api/get_post");
+    assert_eq!(sent[0].credentials, Credentials::Include);
+    assert!(sent[0]
+        .headers
+        .contains(&("X-Api-Key".to_string(), "key".to_string())));
+    assert!(sent[0]
+        .headers
+        .iter()
+        .any(|(name, _)| name == "Content-Type"));
+}
@@ -0,0 +1,105 @@
+use server_fn::{
+    csrf::{cookie_value, CsrfCheck, CsrfPolicy, CSRF_COOKIE},
+    Encoding,
+};
+use std::sync::Arc;
+
+fn check<'a>(
+    encoding: &'a Encoding,
+    cookies: Option<&'a str>,
+    header: Option<&'a str>,
+    body: &'a [u8],
+) -> CsrfCheck<'a> {
+    CsrfCheck {
+        encoding,
+        cookies,
+        header,
+        body,
+    }
+}
+
+const COOKIES: &str = "theme=dark; leptos_csrf=abc123";
+
+#[test]
+fn token_in_header_is_accepted() {
+    let policy = CsrfPolicy::DoubleSubmitCookie;
+    assert!(policy.allows(&check(
+        &Encoding::Cbor,
+        Some(COOKIES),
+        Some("abc123"),
+        b""
+    )));
+    assert!(!policy.allows(&check(
+        &Encoding::Cbor,
+        Some(COOKIES),
+        Some("abc124"),
+        b""
+    )));
+}
+
+#[test]
+fn token_in_form_field_is_accepted() {
+    let policy = CsrfPolicy::DoubleSubmitCookie;
+    assert!(policy.allows(&check(
+        &Encoding::Url,
+        Some(COOKIES),
+        None,
+        b"title=Write+docs&csrf_token=abc123"
+    )));
+    assert!(!policy.allows(&check(
+        &Encoding::Url,
+        Some(COOKIES),
+        None,
+        b"title=Write+docs"
+    )));
+    // only URL-encoded bodies are read as forms
+    assert!(!policy.allows(&check(
+        &Encoding::Cbor,
+        Some(COOKIES),
+        None,
+        b"csrf_token=abc123"
+    )));
+}
+
+#[test]
+fn request_without_cookie_is_rejected() {
+    let policy = CsrfPolicy::DoubleSubmitCookie;
+    assert!(!policy.allows(&check(&Encoding::Url, None, Some("abc123"), b"")));
+    assert!(!policy.allows(&check(
+        &Encoding::Url,
+        Some("theme=dark"),
+        Some(""),
+        b""
+    )));
+}
+
+#[test]
+fn get_requests_are_not_checked() {
+    let policy = CsrfPolicy::DoubleSubmitCookie;
+    assert!(policy.allows(&check(&Encoding::GetJSON, None, None, b"")));
+    assert!(policy.allows(&check(&Encoding::GetCBOR, None, None, b"")));
+}
+
+#[test]
+fn disabled_and_custom_policies() {
+    assert!(!CsrfPolicy::Disabled.is_enabled());
+    assert!(CsrfPolicy::Disabled.allows(&check(
+        &Encoding::Url,
+        None,
+        None,
+        b""
+    )));
+
+    let policy =
+        CsrfPolicy::Custom(Arc::new(|check| check.header == Some("session")));
+    assert!(policy.is_enabled());
+    assert!(policy.allows(&check(&Encoding::Url, None, Some("session"), b"")));
+    assert!(!policy.allows(&check(&Encoding::Url, None, None, b"")));
+}
+
+#[test]
+fn reads_cookie_values() {
+    assert_eq!(cookie_value(COOKIES, CSRF_COOKIE), Some("abc123"));
+    assert_eq!(cookie_value(COOKIES, "theme"), Some("dark"));
+    assert_eq!(cookie_value(COOKIES, "leptos"), None);
+}
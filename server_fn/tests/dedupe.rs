@@ -0,0 +1,176 @@
+#![cfg(not(feature = "ssr"))]
+
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    call_server_fn,
+    client::{
+        set_http_client, HttpFuture, HttpRequest, HttpResponse,
+        LeptosHttpClient,
+    },
+    set_server_url, Encoding, ServerFn, ServerFnError,
+};
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Once,
+    task::{Context, Poll},
+};
+
+static SERVER_URL: Once = Once::new();
+
+#[derive(Clone, Default)]
+struct CountingServer {
+    sent: Rc<Cell<usize>>,
+}
+
+impl LeptosHttpClient for CountingServer {
+    fn send(&self, request: HttpRequest) -> HttpFuture {
+        self.sent.set(self.sent.get() + 1);
+        let body = if request
+            .headers
+            .contains(&("Accept".into(), "application/cbor".into()))
+        {
+            let mut body = Vec::new();
+            ciborium::ser::into_writer("ok", &mut body).unwrap();
+            body
+        } else {
+            b"\"ok\"".to_vec()
+        };
+        Box::pin(async move {
+            // stay in flight until the other call has been made
+            YieldOnce(false).await;
+            Ok(HttpResponse {
+                status: 200,
+                status_text: "OK".into(),
+                headers: Vec::new(),
+                body,
+            })
+        })
+    }
+}
+
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn counting_server() -> Rc<Cell<usize>> {
+    SERVER_URL.call_once(|| set_server_url("http://localhost:3000"));
+    let server = CountingServer::default();
+    let sent = Rc::clone(&server.sent);
+    set_http_client(server);
+    sent
+}
+
+macro_rules! test_server_fn {
+    ($name:ident, $encoding:expr $(, $deduplicate:expr)?) => {
+        #[derive(Serialize, Deserialize)]
+        struct $name {
+            id: u32,
+        }
+
+        impl ServerFn<()> for $name {
+            type Output = String;
+
+            fn prefix() -> &'static str {
+                "/api"
+            }
+
+            fn url() -> &'static str {
+                stringify!($name)
+            }
+
+            fn encoding() -> Encoding {
+                $encoding
+            }
+
+            $(
+                fn deduplicate() -> bool {
+                    $deduplicate
+                }
+            )?
+
+            fn call_fn_client(
+                self,
+                _cx: (),
+            ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>>
+            {
+                Box::pin(call_server_fn(
+                    concat!("/api/", stringify!($name)),
+                    self,
+                    Self::encoding(),
+                ))
+            }
+        }
+    };
+}
+
+test_server_fn!(GetUser, Encoding::GetJSON);
+test_server_fn!(AddUser, Encoding::Url);
+test_server_fn!(SearchUsers, Encoding::Cbor, true);
+test_server_fn!(GetUncached, Encoding::GetJSON, false);
+
+#[test]
+fn get_calls_share_one_request() {
+    let sent = counting_server();
+    let (a, b) = futures::executor::block_on(futures::future::join(
+        GetUser { id: 1 }.call(()),
+        GetUser { id: 1 }.call(()),
+    ));
+    assert_eq!(a.unwrap(), "ok");
+    assert_eq!(b.unwrap(), "ok");
+    assert_eq!(sent.get(), 1);
+}
+
+#[test]
+fn calls_with_different_arguments_are_sent() {
+    let sent = counting_server();
+    let (a, b) = futures::executor::block_on(futures::future::join(
+        GetUser { id: 1 }.call(()),
+        GetUser { id: 2 }.call(()),
+    ));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(sent.get(), 2);
+}
+
+#[test]
+fn post_calls_are_not_deduplicated_by_default() {
+    let sent = counting_server();
+    let (a, b) = futures::executor::block_on(futures::future::join(
+        AddUser { id: 1 }.call(()),
+        AddUser { id: 1 }.call(()),
+    ));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(sent.get(), 2);
+}
+
+#[test]
+fn deduplicate_overrides_encoding() {
+    let sent = counting_server();
+    let (a, b) = futures::executor::block_on(futures::future::join(
+        SearchUsers { id: 1 }.call(()),
+        SearchUsers { id: 1 }.call(()),
+    ));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(sent.get(), 1);
+
+    let (a, b) = futures::executor::block_on(futures::future::join(
+        GetUncached { id: 1 }.call(()),
+        GetUncached { id: 1 }.call(()),
+    ));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(sent.get(), 3);
+}
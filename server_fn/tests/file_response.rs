@@ -0,0 +1,189 @@
+use server_fn::{FileBody, FileResponse, ServerFnError};
+
+#[test]
+fn content_disposition_of_ascii_name() {
+    let file = FileResponse::new(vec![], "report 2023.csv", "text/csv");
+    assert_eq!(
+        file.content_disposition(),
+        "attachment; filename=\"report 2023.csv\"; \
+         filename*=UTF-8''report%202023.csv"
+    );
+}
+
+#[test]
+fn content_disposition_escapes_name() {
+    let file =
+        FileResponse::new(vec![], "résumé \"v2\".pdf", "application/pdf");
+    assert_eq!(
+        file.content_disposition(),
+        "attachment; filename=\"r_sum_ _v2_.pdf\"; \
+         filename*=UTF-8''r%C3%A9sum%C3%A9%20%22v2%22.pdf"
+    );
+}
+
+#[test]
+fn filename_from_content_disposition() {
+    assert_eq!(
+        FileResponse::filename_from_content_disposition(
+            "attachment; filename=\"todos.csv\""
+        ),
+        Some("todos.csv".to_string())
+    );
+    // the encoded name is preferred, wherever it is
+    assert_eq!(
+        FileResponse::filename_from_content_disposition(
+            "attachment; filename*=UTF-8''na%C3%AFve.txt; \
+             filename=\"na_ve.txt\""
+        ),
+        Some("naïve.txt".to_string())
+    );
+    assert_eq!(
+        FileResponse::filename_from_content_disposition("attachment"),
+        None
+    );
+    assert_eq!(
+        FileResponse::filename_from_content_disposition(
+            "attachment; filename*=UTF-8''bad%ZZ"
+        ),
+        None
+    );
+}
+
+#[test]
+fn content_disposition_round_trips() {
+    for name in ["todos.csv", "résumé \"v2\".pdf", "日本語.txt", ""] {
+        let file = FileResponse::new(vec![], name, "text/plain");
+        assert_eq!(
+            FileResponse::filename_from_content_disposition(
+                &file.content_disposition()
+            ),
+            Some(name.to_string())
+        );
+    }
+}
+
+#[test]
+fn streamed_file_is_read_in_order() {
+    let chunks: Vec<Result<Vec<u8>, ServerFnError>> =
+        vec![Ok(b"id,title\n".to_vec()), Ok(b"1,Write docs\n".to_vec())];
+    let file = FileResponse::stream(
+        futures::stream::iter(chunks),
+        "todos.csv",
+        "text/csv",
+    );
+    assert!(file.bytes().is_none());
+    assert_eq!(
+        futures::executor::block_on(file.into_bytes()).unwrap(),
+        b"id,title\n1,Write docs\n"
+    );
+}
+
+#[test]
+fn failed_stream_is_an_error() {
+    let chunks: Vec<Result<Vec<u8>, ServerFnError>> = vec![
+        Ok(b"id,title\n".to_vec()),
+        Err(ServerFnError::ServerError("disk error".into())),
+    ];
+    let file = FileResponse::stream(
+        futures::stream::iter(chunks),
+        "todos.csv",
+        "text/csv",
+    );
+    assert!(futures::executor::block_on(file.into_bytes()).is_err());
+}
+
+#[test]
+fn streamed_file_cannot_be_serialized() {
+    let file =
+        FileResponse::stream(futures::stream::empty(), "todos.csv", "text/csv");
+    assert!(serde_json::to_string(&file).is_err());
+    assert!(matches!(file.body, FileBody::Stream(_)));
+}
+
+#[cfg(not(feature = "ssr"))]
+mod client {
+    use serde::{Deserialize, Serialize};
+    use server_fn::{
+        call_server_fn,
+        client::{
+            set_http_client, HttpFuture, HttpRequest, HttpResponse,
+            LeptosHttpClient,
+        },
+        set_server_url, Encoding, FileResponse, ServerFn, ServerFnError,
+    };
+    use std::{future::Future, pin::Pin};
+
+    struct FileServer;
+
+    impl LeptosHttpClient for FileServer {
+        fn send(&self, _request: HttpRequest) -> HttpFuture {
+            Box::pin(async move {
+                Ok(HttpResponse {
+                    status: 200,
+                    status_text: "OK".into(),
+                    headers: vec![
+                        ("Content-Type".into(), "text/csv".into()),
+                        (
+                            "Content-Disposition".into(),
+                            FileResponse::new(vec![], "todos.csv", "text/csv")
+                                .content_disposition(),
+                        ),
+                    ],
+                    body: b"id,title\n".to_vec(),
+                })
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct ExportTodos {}
+
+    impl ServerFn<()> for ExportTodos {
+        type Output = FileResponse;
+
+        fn prefix() -> &'static str {
+            "/api"
+        }
+
+        fn url() -> &'static str {
+            "export_todos"
+        }
+
+        fn encoding() -> Encoding {
+            Encoding::GetJSON
+        }
+
+        fn call_fn_client(
+            self,
+            _cx: (),
+        ) -> Pin<Box<dyn Future<Output = Result<FileResponse, ServerFnError>>>>
+        {
+            Box::pin(call_server_fn(
+                "/api/export_todos",
+                self,
+                Self::encoding(),
+            ))
+        }
+    }
+
+    #[test]
+    fn client_receives_file() {
+        set_server_url("http://localhost:3000");
+        set_http_client(FileServer);
+
+        let file =
+            futures::executor::block_on(ExportTodos {}.call(())).unwrap();
+        assert_eq!(file.filename, "todos.csv");
+        assert_eq!(file.mime, "text/csv");
+        assert_eq!(file.bytes(), Some(&b"id,title\n"[..]));
+
+        // a server function that doesn't return a file can't receive one
+        let not_a_file: Result<String, _> =
+            futures::executor::block_on(call_server_fn(
+                "/api/export_todos",
+                ExportTodos {},
+                Encoding::GetJSON,
+            ));
+        assert!(matches!(not_a_file, Err(ServerFnError::Deserialization(_))));
+    }
+}
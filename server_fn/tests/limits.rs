@@ -0,0 +1,112 @@
+use server_fn::{
+    limits::{
+        MemoryRateLimitStore, RateLimit, RateLimitStore, ServerFnLimits,
+        UnknownClient,
+    },
+    ServerFnError,
+};
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn body_size_is_checked() {
+    let limits = ServerFnLimits::default().max_body_size(10);
+    assert!(limits.check_body_size(0).is_ok());
+    assert!(limits.check_body_size(10).is_ok());
+    assert!(matches!(
+        limits.check_body_size(11),
+        Err(ServerFnError::PayloadTooLarge(_))
+    ));
+    assert!(ServerFnLimits::default()
+        .check_body_size(usize::MAX)
+        .is_ok());
+}
+
+#[test]
+fn rate_limit_counts_each_client() {
+    let limits = ServerFnLimits::default()
+        .rate_limit(RateLimit::per_ip(2, Duration::from_secs(60)));
+    assert!(limits.has_rate_limit());
+    assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+    assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+    assert!(matches!(
+        limits.check_rate_limit(Some("10.0.0.1")),
+        Err(ServerFnError::TooManyRequests(_))
+    ));
+    // other clients have their own count
+    assert!(limits.check_rate_limit(Some("10.0.0.2")).is_ok());
+}
+
+#[test]
+fn unknown_clients_share_a_count_by_default() {
+    let limits = ServerFnLimits::default()
+        .rate_limit(RateLimit::per_ip(2, Duration::from_secs(60)));
+    assert!(limits.check_rate_limit(None).is_ok());
+    assert!(limits.check_rate_limit(None).is_ok());
+    assert!(matches!(
+        limits.check_rate_limit(None),
+        Err(ServerFnError::TooManyRequests(_))
+    ));
+    // known clients are still counted separately
+    assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+}
+
+#[test]
+fn unknown_clients_can_be_rejected() {
+    let limits = ServerFnLimits::default().rate_limit(
+        RateLimit::per_ip(2, Duration::from_secs(60))
+            .unknown_client(UnknownClient::Reject),
+    );
+    assert!(matches!(
+        limits.check_rate_limit(None),
+        Err(ServerFnError::TooManyRequests(_))
+    ));
+    assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+}
+
+#[test]
+fn rate_limit_resets_after_window() {
+    let store = MemoryRateLimitStore::default();
+    let window = Duration::from_millis(50);
+    assert!(store.hit("10.0.0.1", 1, window));
+    assert!(!store.hit("10.0.0.1", 1, window));
+    thread::sleep(window);
+    assert!(store.hit("10.0.0.1", 1, window));
+    assert!(!store.hit("10.0.0.1", 1, window));
+}
+
+#[test]
+fn no_rate_limit_by_default() {
+    let limits = ServerFnLimits::default();
+    assert!(!limits.has_rate_limit());
+    for _ in 0..100 {
+        assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+    }
+}
+
+#[derive(Clone, Default)]
+struct CountingStore(Arc<AtomicU32>);
+
+impl RateLimitStore for CountingStore {
+    fn hit(&self, _key: &str, limit: u32, _window: Duration) -> bool {
+        self.0.fetch_add(1, Ordering::SeqCst) < limit
+    }
+}
+
+#[test]
+fn rate_limit_uses_custom_store() {
+    let store = CountingStore::default();
+    let limits = ServerFnLimits::default().rate_limit(
+        RateLimit::per_ip(1, Duration::from_secs(60)).with_store(store.clone()),
+    );
+    // the store counts every client together
+    assert!(limits.check_rate_limit(Some("10.0.0.1")).is_ok());
+    assert!(limits.check_rate_limit(Some("10.0.0.2")).is_err());
+    assert_eq!(store.0.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "ssr")]
+
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    registered_server_fns, Encoding, ServerFn, ServerFnError, ServerFnTraitObj,
+    ServerFunction, ServerFunctionRegistry,
+};
+use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc};
+
+struct TestRegistry;
+
+impl ServerFunctionRegistry<()> for TestRegistry {
+    type Error = Infallible;
+
+    fn register(
+        _url: &'static str,
+        _server_function: Arc<ServerFnTraitObj<()>>,
+        _encoding: Encoding,
+    ) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn get(_url: &str) -> Option<ServerFunction<()>> {
+        None
+    }
+
+    fn get_trait_obj(_url: &str) -> Option<Arc<ServerFnTraitObj<()>>> {
+        None
+    }
+
+    fn get_encoding(_url: &str) -> Option<Encoding> {
+        None
+    }
+
+    fn paths_registered() -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+macro_rules! test_server_fn {
+    ($name:ident, $url:literal, $encoding:expr) => {
+        #[derive(Serialize, Deserialize)]
+        #[cfg_attr(
+            feature = "openapi",
+            derive(server_fn::schemars::JsonSchema),
+            schemars(crate = "server_fn::schemars")
+        )]
+        struct $name {
+            id: u32,
+        }
+
+        impl ServerFn<()> for $name {
+            type Output = String;
+
+            fn prefix() -> &'static str {
+                "/api"
+            }
+
+            fn url() -> &'static str {
+                $url
+            }
+
+            fn encoding() -> Encoding {
+                $encoding
+            }
+
+            fn call_fn(
+                self,
+                _cx: (),
+            ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>>
+            {
+                Box::pin(async move { Ok(self.id.to_string()) })
+            }
+
+            #[cfg(feature = "openapi")]
+            fn schemas(
+                gen: &mut server_fn::schemars::gen::SchemaGenerator,
+            ) -> Option<server_fn::openapi::ServerFnSchemas> {
+                Some(server_fn::openapi::ServerFnSchemas {
+                    args: gen.subschema_for::<Self>(),
+                    output: gen.subschema_for::<String>(),
+                })
+            }
+        }
+    };
+}
+
+test_server_fn!(GetTodo, "get_todo", Encoding::GetJSON);
+test_server_fn!(AddTodo, "add_todo", Encoding::Url);
+
+fn register() {
+    GetTodo::register_in::<TestRegistry>().unwrap();
+    AddTodo::register_in::<TestRegistry>().unwrap();
+}
+
+#[test]
+fn registered_server_fns_are_listed_once_by_path() {
+    register();
+    // registering again doesn't list them twice
+    register();
+
+    let fns = registered_server_fns();
+    let paths = fns.iter().map(|info| info.path()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/api/add_todo", "/api/get_todo"]);
+    assert_eq!(fns[0].encoding, Encoding::Url);
+    assert!(fns[0].type_name.ends_with("AddTodo"));
+    assert_eq!(fns[1].encoding, Encoding::GetJSON);
+}
+
+#[cfg(feature = "openapi")]
+#[test]
+fn openapi_document_describes_registered_server_fns() {
+    register();
+
+    let document = server_fn::openapi::openapi_document("Todos", "1.0.0");
+    assert_eq!(document["openapi"], "3.0.3");
+    assert_eq!(document["info"]["title"], "Todos");
+    assert_eq!(document["info"]["version"], "1.0.0");
+
+    let add = &document["paths"]["/api/add_todo"]["post"];
+    assert_eq!(add["operationId"], "add_todo");
+    assert!(
+        add["requestBody"]["content"]["application/x-www-form-urlencoded"]
+            ["schema"]
+            .is_object()
+    );
+    assert_eq!(
+        add["responses"]["200"]["content"]["application/json"]["schema"]
+            ["type"],
+        "string"
+    );
+
+    let get = &document["paths"]["/api/get_todo"]["get"];
+    assert_eq!(get["parameters"][0]["in"], "query");
+    assert!(document["paths"]["/api/get_todo"]["post"].is_null());
+
+    // the argument types are shared as components
+    assert!(document["components"]["schemas"]["AddTodo"].is_object());
+}
@@ -75,9 +75,17 @@ pub fn server_macro_impl(
         struct_name,
         prefix,
         encoding,
+        deduplicate,
         ..
     } = syn::parse2::<ServerFnName>(args)?;
     let prefix = prefix.unwrap_or_else(|| Literal::string(""));
+    let deduplicate_fn = deduplicate.map(|deduplicate| {
+        quote! {
+            fn deduplicate() -> bool {
+                #deduplicate
+            }
+        }
+    });
     let encoding = quote!(#server_fn_path::#encoding);
 
     let body = syn::parse::<ServerFnBody>(body.into())?;
@@ -119,6 +127,13 @@ pub fn server_macro_impl(
     } else {
         quote! {}
     };
+    let cx_param = match cx_arg {
+        Some(FnArg::Typed(t)) => {
+            let ty = &t.ty;
+            quote! { cx: #ty }
+        }
+        _ => quote! {},
+    };
 
     let fn_args = body.inputs.iter().map(|f| {
         let typed_arg = match f {
@@ -162,6 +177,8 @@ pub fn server_macro_impl(
     let field_names_3 = field_names.clone();
     let field_names_4 = field_names.clone();
     let field_names_5 = field_names.clone();
+    let field_names_6 = field_names.clone();
+    let field_names_7 = field_names.clone();
 
     let output_arrow = body.output_arrow;
     let return_ty = body.return_ty;
@@ -183,6 +200,17 @@ pub fn server_macro_impl(
         );
     };
 
+    // a `FileResponse` is sent as a file rather than encoded
+    let returns_file = match output_ty {
+        GenericArgument::Type(syn::Type::Path(ty)) => ty
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "FileResponse")
+            .unwrap_or(false),
+        _ => false,
+    };
+
     let server_ctx_path = if let Some(ctx) = &server_context {
         let path = &ctx.path;
         quote!(#path)
@@ -194,6 +222,40 @@ pub fn server_macro_impl(
         .map(|path| quote!(#path))
         .unwrap_or_else(|| quote! { server_fn });
 
+    // describe the types for OpenAPI documents, if enabled
+    let (schema_derive, schemas_fn) = if cfg!(feature = "openapi") {
+        let schemars_path = format!("{server_fn_path}::schemars");
+        (
+            quote! {
+                #[derive(#server_fn_path::schemars::JsonSchema)]
+                #[schemars(crate = #schemars_path)]
+            },
+            quote! {
+                fn schemas(
+                    gen: &mut #server_fn_path::schemars::gen::SchemaGenerator,
+                ) -> Option<#server_fn_path::openapi::ServerFnSchemas> {
+                    Some(#server_fn_path::openapi::ServerFnSchemas {
+                        args: gen.subschema_for::<Self>(),
+                        output: gen.subschema_for::<#output_ty>(),
+                    })
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let into_file_fn = returns_file.then(|| {
+        quote! {
+            #[cfg(feature = "ssr")]
+            fn into_file(
+                output: Self::Output,
+            ) -> Result<#server_fn_path::FileResponse, Self::Output> {
+                Ok(output)
+            }
+        }
+    });
+
     let key_env_var = match option_env!("SERVER_FN_OVERRIDE_KEY") {
         Some(_) => "SERVER_FN_OVERRIDE_KEY",
         None => "CARGO_MANIFEST_DIR",
@@ -201,6 +263,7 @@ pub fn server_macro_impl(
 
     Ok(quote::quote! {
         #[derive(Clone, Debug, ::serde::Serialize, ::serde::Deserialize)]
+        #schema_derive
         pub struct #struct_name {
             #(#fields),*
         }
@@ -220,6 +283,12 @@ pub fn server_macro_impl(
                 #encoding
             }
 
+            #deduplicate_fn
+
+            #into_file_fn
+
+            #schemas_fn
+
             #[cfg(feature = "ssr")]
             fn call_fn(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, server_fn::ServerFnError>>>> {
                 let #struct_name { #(#field_names),* } = self;
@@ -233,6 +302,16 @@ pub fn server_macro_impl(
             }
         }
 
+        #[cfg(feature = "ssr")]
+        impl #struct_name {
+            /// Runs the body of the server function with these arguments on
+            /// the server, without going through its HTTP endpoint.
+            pub async fn run_body(self, #cx_param) #output_arrow #return_ty {
+                let #struct_name { #(#field_names_6),* } = self;
+                #fn_name( #cx_fn_arg #(#field_names_7),*).await
+            }
+        }
+
         #[cfg(feature = "ssr")]
         #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
             #block
@@ -260,6 +339,8 @@ struct ServerFnName {
     prefix: Option<Literal>,
     _comma2: Option<Token![,]>,
     encoding: Path,
+    _comma3: Option<Token![,]>,
+    deduplicate: Option<bool>,
 }
 
 impl Parse for ServerFnName {
@@ -280,6 +361,18 @@ impl Parse for ServerFnName {
                 }
             })
             .unwrap_or_else(|_| syn::parse_quote!(Encoding::Url));
+        let _comma3 = input.parse()?;
+        let deduplicate = match input.parse::<Option<Ident>>()? {
+            None => None,
+            Some(flag) if flag == "dedupe" => Some(true),
+            Some(flag) if flag == "no_dedupe" => Some(false),
+            Some(flag) => {
+                return Err(syn::Error::new(
+                    flag.span(),
+                    "expected `dedupe` or `no_dedupe`",
+                ))
+            }
+        };
 
         Ok(Self {
             struct_name,
@@ -287,6 +380,8 @@ impl Parse for ServerFnName {
             prefix,
             _comma2,
             encoding,
+            _comma3,
+            deduplicate,
         })
     }
 }
@@ -343,3 +438,35 @@ impl Parse for ServerFnBody {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ServerFnName;
+
+    fn deduplicate(args: &str) -> syn::Result<Option<bool>> {
+        syn::parse_str::<ServerFnName>(args).map(|name| name.deduplicate)
+    }
+
+    #[test]
+    fn dedupe_flag_defaults_to_encoding() {
+        assert_eq!(deduplicate("AddTodo").unwrap(), None);
+        assert_eq!(deduplicate(r#"AddTodo, "/api", "Url""#).unwrap(), None);
+    }
+
+    #[test]
+    fn parses_dedupe_flags() {
+        assert_eq!(
+            deduplicate(r#"AddTodo, "/api", "Url", no_dedupe"#).unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            deduplicate(r#"GetTodos, "/api", "Cbor", dedupe"#).unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert!(deduplicate(r#"AddTodo, "/api", "Url", dedup"#).is_err());
+    }
+}